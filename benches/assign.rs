@@ -0,0 +1,76 @@
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use irrops::aircraft::Aircraft;
+use irrops::airport::Airport;
+use irrops::flight::{Flight, FlightKey};
+use irrops::schedule::schedule::Schedule;
+use irrops::time::Time;
+use std::collections::HashMap;
+use std::hint::black_box;
+
+const AIRPORTS: usize = 40;
+const AIRCRAFT: usize = 200;
+const LEGS: usize = 8;
+
+/// A day of `AIRCRAFT` rotations of `LEGS` legs around `AIRPORTS` airports; every other
+/// rotation is left unassigned for `assign` to fill.
+fn build() -> Schedule {
+    let airport = |n: usize| format!("AP{}", n % AIRPORTS);
+    let airports = (0..AIRPORTS)
+        .map(|n| Airport::new(&airport(n), 30 + (n as u64 % 4) * 10))
+        .map(|ap| (ap.id.clone(), ap))
+        .collect::<HashMap<_, _>>();
+    let aircraft = (0..AIRCRAFT)
+        .map(|a| Aircraft::new(&format!("AC{}", a), &airport(a)))
+        .map(|ac| (ac.id.clone(), ac))
+        .collect::<HashMap<_, _>>();
+    let mut flights = vec![];
+    for a in 0..AIRCRAFT {
+        for leg in 0..LEGS {
+            let departure = Time(300 + leg as u64 * 130);
+            let flight = Flight::new(
+                &format!("FL{}-{}", a, leg),
+                &airport(a + leg),
+                &airport(a + leg + 1),
+                departure,
+                departure + 90,
+            );
+            flights.push(match a % 2 {
+                0 => flight.assigned(&format!("AC{}", a)),
+                _ => flight,
+            });
+        }
+    }
+    Schedule::new(aircraft, airports, flights)
+}
+
+fn assign(c: &mut Criterion) {
+    let schedule = build();
+    c.bench_function("assign", |b| {
+        b.iter_batched(
+            || schedule.fork(),
+            |mut schedule| {
+                schedule.assign();
+                black_box(schedule)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn apply_delay(c: &mut Criterion) {
+    let schedule = build();
+    let first_leg = FlightKey::new("FL0-0", 1);
+    c.bench_function("apply_delay", |b| {
+        b.iter_batched(
+            || schedule.fork(),
+            |mut schedule| {
+                schedule.apply_delay(first_leg.clone(), 45).unwrap();
+                black_box(schedule)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, assign, apply_delay);
+criterion_main!(benches);
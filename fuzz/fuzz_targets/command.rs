@@ -0,0 +1,39 @@
+#![no_main]
+
+use irrops::cli::commands::Command;
+use irrops::config::Config;
+use irrops::filter::FlightFilter;
+use irrops::schedule::schedule::Schedule;
+use irrops::script;
+use irrops::time::{Time, parse_duration};
+use libfuzzer_sys::fuzz_target;
+
+thread_local! {
+    static SCHEDULE: Schedule = {
+        let mut schedule = Schedule::load_from_slice(include_bytes!("../../data/default.json"))
+            .expect("the default scenario loads");
+        schedule.assign();
+        schedule
+    };
+}
+
+// The parsers behind REPL arguments: commands, times and durations, `where` expressions,
+// flight ids, `foreach` and config values.
+fuzz_target!(|line: &str| SCHEDULE.with(|schedule| {
+    let _ = Command::parse(line);
+    let _ = line.parse::<Time>();
+    let _ = parse_duration(line);
+    if let Ok(filter) = FlightFilter::parse(line) {
+        schedule.flights.iter().for_each(|f| {
+            filter.matches(f);
+        });
+    }
+    let mut words = line.split_whitespace();
+    if let Some(filter) = words.next() {
+        let _ = script::foreach(schedule, filter, &words.collect::<Vec<_>>().join(" "));
+    }
+    line.split_whitespace().for_each(|word| {
+        let _ = schedule.find_flight(word);
+    });
+    let _ = Config::parse(line);
+}));
@@ -0,0 +1,8 @@
+#![no_main]
+
+use irrops::schedule::schedule::Schedule;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Schedule::load_from_slice(data);
+});
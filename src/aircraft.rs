@@ -1,9 +1,10 @@
 use crate::airport::AirportId;
 use crate::time::Time;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Availability {
     pub from: Time,
     pub to: Time,
@@ -12,9 +13,155 @@ pub struct Availability {
 
 pub type AircraftId = Arc<str>;
 
-#[derive(Serialize, Deserialize)]
+/// Aircraft type or family, e.g. "A320" or "B738", that airports may restrict.
+pub type FleetType = Arc<str>;
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Aircraft {
     pub id: AircraftId,
     pub disruptions: Vec<Availability>,
     pub initial_location_id: AirportId,
+    #[serde(default)]
+    pub maintenance: Option<MaintenanceCheck>,
+    #[serde(default)]
+    pub fleet_type: Option<FleetType>,
+    /// Certified for ETOPS sectors.
+    #[serde(default)]
+    pub etops: bool,
+}
+
+impl Aircraft {
+    /// An aircraft available all along, without a maintenance check, fleet type or ETOPS
+    /// certification.
+    pub fn new(id: &str, initial_location_id: &str) -> Aircraft {
+        Aircraft {
+            id: Arc::from(id),
+            disruptions: vec![],
+            initial_location_id: Arc::from(initial_location_id),
+            maintenance: None,
+            fleet_type: None,
+            etops: false,
+        }
+    }
+}
+
+/// Sorts `windows` by start and merges the ones overlapping or touching at the same
+/// location. Windows at different locations never overlap in a valid scenario, so the
+/// result can be binary-searched by either end.
+pub fn normalize_windows(windows: &mut Vec<Availability>) {
+    windows.sort_by_key(|w| (w.from, w.to));
+    let mut merged: Vec<Availability> = Vec::with_capacity(windows.len());
+    for window in windows.drain(..) {
+        match merged.last_mut() {
+            Some(last) if last.location_id == window.location_id && window.from <= last.to => {
+                last.to = last.to.max(window.to);
+            }
+            _ => merged.push(window),
+        }
+    }
+    *windows = merged;
+}
+
+/// Utilisation limits until the next maintenance check, which can only be done at one of
+/// `bases`. The check is assumed to happen on the ground at the first base reached, after
+/// which the counters start over and `due_by` no longer applies.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct MaintenanceCheck {
+    /// Block minutes flown since the last check.
+    #[serde(default)]
+    pub flown_minutes: u64,
+    /// Cycles (take-offs) flown since the last check.
+    #[serde(default)]
+    pub flown_cycles: u32,
+    pub max_minutes: u64,
+    pub max_cycles: u32,
+    /// Latest arrival at a base, if the check is also due at a fixed time.
+    #[serde(default)]
+    pub due_by: Option<Time>,
+    pub bases: Vec<AirportId>,
+}
+
+/// Running counters of an aircraft against its `MaintenanceCheck`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MaintenanceUsage {
+    pub minutes: u64,
+    pub cycles: u32,
+    pub checked: bool,
+}
+
+impl MaintenanceCheck {
+    pub fn usage(&self) -> MaintenanceUsage {
+        MaintenanceUsage {
+            minutes: self.flown_minutes,
+            cycles: self.flown_cycles,
+            checked: false,
+        }
+    }
+
+    /// Counters after flying `dep..arr` to `destination`, or `None` when the leg takes the
+    /// aircraft past a limit before it reaches a base.
+    pub fn fly(
+        &self,
+        usage: MaintenanceUsage,
+        dep: Time,
+        arr: Time,
+        destination: &AirportId,
+    ) -> Option<MaintenanceUsage> {
+        let minutes = usage.minutes + (arr - dep).0;
+        let cycles = usage.cycles + 1;
+        if minutes > self.max_minutes
+            || cycles > self.max_cycles
+            || (!usage.checked && self.due_by.is_some_and(|due| arr > due))
+        {
+            return None;
+        }
+        Some(if self.bases.contains(destination) {
+            MaintenanceUsage {
+                minutes: 0,
+                cycles: 0,
+                checked: true,
+            }
+        } else {
+            MaintenanceUsage {
+                minutes,
+                cycles,
+                checked: usage.checked,
+            }
+        })
+    }
+
+    /// Counters at the end of a rotation given as `(dep, arr, destination)` legs in
+    /// departure order, or the index of the first leg that busts a limit.
+    pub fn walk<'a>(
+        &self,
+        legs: impl IntoIterator<Item = (Time, Time, &'a AirportId)>,
+    ) -> Result<MaintenanceUsage, usize> {
+        legs.into_iter()
+            .enumerate()
+            .try_fold(self.usage(), |usage, (i, (dep, arr, destination))| {
+                self.fly(usage, dep, arr, destination).ok_or(i)
+            })
+    }
+}
+
+/// External wet-lease capacity that cost-based recovery may hire to cover flights that
+/// would otherwise be cancelled.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Charter {
+    pub id: AircraftId,
+    pub base_id: AirportId,
+    pub from: Time,
+    pub to: Time,
+    pub cost_per_block_hour: u64,
+    #[serde(default)]
+    pub fleet_type: Option<FleetType>,
+    /// Certified for ETOPS sectors.
+    #[serde(default)]
+    pub etops: bool,
+}
+
+impl Charter {
+    pub fn block_cost(&self, block_minutes: u64) -> u64 {
+        block_minutes * self.cost_per_block_hour / 60
+    }
 }
@@ -1,4 +1,6 @@
+use crate::aircraft::FleetType;
 use crate::time::Time;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Formatter;
@@ -6,19 +8,326 @@ use std::sync::Arc;
 use tabled::Tabled;
 
 pub type AirportId = Arc<str>;
+pub type RunwayId = Arc<str>;
 
-#[derive(Serialize, Deserialize, Tabled, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, JsonSchema, Tabled, Clone, Debug, PartialEq)]
 pub struct Curfew {
     pub from: Time,
     pub to: Time,
+    /// Minutes after the curfew starts during which late arrivals are still accepted.
+    #[serde(default)]
+    #[tabled(skip)]
+    pub arrival_tolerance: u64,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Tabled)]
+const DAY: u64 = 1440;
+
+impl Curfew {
+    /// A curfew with `from` after `to` wraps midnight, e.g. 23:30-05:30, and repeats every
+    /// day. Only the time of day of both ends is used then.
+    pub fn is_wrapping(&self) -> bool {
+        self.from > self.to
+    }
+
+    /// Concrete closed windows of this curfew overlapping `span`.
+    pub fn windows(&self, span: (Time, Time)) -> Vec<(Time, Time)> {
+        if !self.is_wrapping() {
+            return Some((self.from, self.to))
+                .filter(|w| Time::is_overlapping(&span, w))
+                .into_iter()
+                .collect();
+        }
+        daily_windows(self.from.0, self.to.0, span)
+    }
+
+    /// Closed windows overlapping `span` for a flight landing at the airport: each one
+    /// starts `arrival_tolerance` minutes late, so marginal late arrivals get through.
+    pub fn arrival_windows(&self, span: (Time, Time)) -> Vec<(Time, Time)> {
+        self.windows(span)
+            .into_iter()
+            .map(|(from, to)| (from + self.arrival_tolerance, to))
+            .filter(|w| w.0 < w.1 && Time::is_overlapping(&span, w))
+            .collect()
+    }
+}
+
+/// Regular opening hours, in minutes after local midnight. A `close` earlier than `open`
+/// keeps the airport open past midnight.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
+pub struct OperatingHours {
+    pub open: u64,
+    pub close: u64,
+}
+
+impl OperatingHours {
+    /// Closed windows, from closing to the next opening, overlapping `span`.
+    pub fn closures(&self, span: (Time, Time)) -> Vec<(Time, Time)> {
+        daily_windows(self.close, self.open, span)
+    }
+}
+
+impl fmt::Display for OperatingHours {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", hhmm(self.open), hhmm(self.close))
+    }
+}
+
+fn hhmm(minutes: u64) -> String {
+    format!("{:02}:{:02}", minutes % DAY / 60, minutes % 60)
+}
+
+/// Windows repeating every day from the time of day of `from` to that of `to`, wrapping
+/// midnight when needed, that overlap `span`.
+fn daily_windows(from: u64, to: u64, span: (Time, Time)) -> Vec<(Time, Time)> {
+    let start = from % DAY;
+    let length = (to % DAY + DAY - start) % DAY;
+    // the window opened on the day before covers the early hours of the first day
+    let first_day = (span.0.0 / DAY) as i64 - 1;
+    let last_day = (span.1.0 / DAY) as i64;
+    (first_day..=last_day)
+        .map(|day| {
+            let from = day * DAY as i64 + start as i64;
+            (
+                Time(from.max(0) as u64),
+                Time((from + length as i64).max(0) as u64),
+            )
+        })
+        .filter(|w| Time::is_overlapping(&span, w))
+        .collect()
+}
+
+/// A band of the day, in minutes after local midnight, during which departures need
+/// `extra` block minutes, e.g. for evening taxi and airway congestion.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
+pub struct BlockBand {
+    #[serde(flatten)]
+    pub hours: OperatingHours,
+    pub extra: u64,
+}
+
+/// A runway and the movements it handles per hour on its own.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct Runway {
+    pub id: RunwayId,
+    pub movements_per_hour: u32,
+}
+
+/// A cut in an airport's movement rate between `from` and `to`, as metered by
+/// `apply_capacity_reduction` or `close_runway`.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct CapacityReduction {
+    pub from: Time,
+    pub to: Time,
+    /// Movements per hour the airport handles while the reduction is in force.
+    pub rate: u32,
+}
+
+impl CapacityReduction {
+    /// Minutes between two movements at the reduced rate.
+    pub fn spacing(&self) -> f64 {
+        60.0 / self.rate.max(1) as f64
+    }
+
+    pub fn covers(&self, at: Time) -> bool {
+        self.from <= at && at < self.to
+    }
+}
+
+/// A connection bank at a hub: passengers on flights arriving inside `arrivals` connect to
+/// flights departing inside `departures`, both in minutes after local midnight of the
+/// scheduled times, on the same day.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
+pub struct Bank {
+    pub arrivals: OperatingHours,
+    pub departures: OperatingHours,
+    /// Shortest passenger connection between an arrival and a departure of the bank.
+    #[serde(default = "default_min_connection")]
+    pub min_connection: u64,
+}
+
+fn default_min_connection() -> u64 {
+    45
+}
+
+impl Bank {
+    pub fn is_arrival(&self, arrival: Time) -> bool {
+        within(arrival.0 % DAY, self.arrivals)
+    }
+
+    pub fn is_departure(&self, departure: Time) -> bool {
+        within(departure.0 % DAY, self.departures)
+    }
+}
+
+impl fmt::Display for Bank {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "in {} out {}", self.arrivals, self.departures)
+    }
+}
+
+fn within(minute: u64, window: OperatingHours) -> bool {
+    minute >= window.open && minute < window.close
+}
+
+impl fmt::Display for Curfew {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_wrapping() {
+            write!(f, "daily {}-{}", hhmm(self.from.0), hhmm(self.to.0))?;
+        } else {
+            write!(f, "{}-{}", self.from, self.to)?;
+        }
+        if self.arrival_tolerance > 0 {
+            write!(f, " (arrivals +{}m)", self.arrival_tolerance)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema, Tabled)]
 pub struct Airport {
     pub id: Arc<str>,
     pub mtt: u64,
     #[tabled(display = "format_disruptions")]
     pub disruptions: Vec<Curfew>,
+    #[serde(default)]
+    #[tabled(display = "format_hours")]
+    pub operating_hours: Option<OperatingHours>,
+    /// Number of aircraft that can be on the ground at once, unlimited when absent.
+    #[serde(default)]
+    #[tabled(display = "format_stands")]
+    pub stands: Option<u32>,
+    /// Fleet types the airport cannot accept, e.g. for runway length or noise category.
+    #[serde(default)]
+    #[tabled(display = "format_restrictions")]
+    pub restricted_types: Vec<FleetType>,
+    /// Connection banks; an airport with banks is a hub.
+    #[serde(default)]
+    #[tabled(skip)]
+    pub banks: Vec<Bank>,
+    /// Extra block time for flights departing in congested bands of the day.
+    #[serde(default)]
+    #[tabled(skip)]
+    pub block_bands: Vec<BlockBand>,
+    /// Minutes a movement may drift from its slot; set only at coordinated airports.
+    #[serde(default)]
+    #[tabled(skip)]
+    pub slot_tolerance: Option<u64>,
+    /// Movements the coordinator can slot per clock hour, for granting new slots.
+    #[serde(default)]
+    #[tabled(skip)]
+    pub slots_per_hour: Option<u32>,
+    /// Turnarounds the ground handlers can service at once, unlimited when absent.
+    #[serde(default)]
+    #[tabled(skip)]
+    pub handling: Option<u32>,
+    /// Runways that can be closed one at a time; their rates add up to the airport's.
+    #[serde(default)]
+    #[tabled(skip)]
+    pub runways: Vec<Runway>,
+    /// Movement rate cuts applied so far, enforced on later assignments and delays.
+    #[serde(default)]
+    #[tabled(skip)]
+    pub reductions: Vec<CapacityReduction>,
+}
+
+impl Airport {
+    /// An airport open around the clock, without curfews or any capacity limits.
+    pub fn new(id: &str, mtt: u64) -> Airport {
+        Airport {
+            id: Arc::from(id),
+            mtt,
+            disruptions: vec![],
+            operating_hours: None,
+            stands: None,
+            restricted_types: vec![],
+            banks: vec![],
+            block_bands: vec![],
+            slot_tolerance: None,
+            slots_per_hour: None,
+            handling: None,
+            runways: vec![],
+            reductions: vec![],
+        }
+    }
+
+    /// Movements the airport handles per hour: its runways together, or else the
+    /// coordinator's `slots_per_hour`.
+    pub fn movement_rate(&self) -> Option<u32> {
+        match self.runways.as_slice() {
+            [] => self.slots_per_hour,
+            runways => Some(runways.iter().map(|r| r.movements_per_hour).sum()),
+        }
+    }
+
+    /// Extra block minutes of a flight departing here at `departure`.
+    pub fn extra_block(&self, departure: Time) -> u64 {
+        self.block_bands
+            .iter()
+            .filter(|band| within(departure.0 % DAY, band.hours))
+            .map(|band| band.extra)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Aircraft without a fleet type are accepted everywhere.
+    pub fn accepts(&self, fleet_type: Option<&FleetType>) -> bool {
+        fleet_type.is_none_or(|t| !self.restricted_types.contains(t))
+    }
+
+    /// Every window overlapping `span` during which the airport cannot handle movements,
+    /// whether outside its operating hours or under a curfew.
+    pub fn closed_windows(&self, span: (Time, Time)) -> Vec<(Time, Time)> {
+        self.operating_hours
+            .iter()
+            .flat_map(|h| h.closures(span))
+            .chain(self.disruptions.iter().flat_map(|c| c.windows(span)))
+            .collect()
+    }
+
+    /// Like `closed_windows`, for a flight landing here: curfews honour their arrival
+    /// tolerance, operating hours do not.
+    pub fn arrival_closed_windows(&self, span: (Time, Time)) -> Vec<(Time, Time)> {
+        self.operating_hours
+            .iter()
+            .flat_map(|h| h.closures(span))
+            .chain(
+                self.disruptions
+                    .iter()
+                    .flat_map(|c| c.arrival_windows(span)),
+            )
+            .collect()
+    }
+}
+
+/// Sorts `curfews`, fixed ones first and by arrival tolerance, and merges fixed ones
+/// overlapping or touching with the same tolerance; a daily curfew only drops out when it
+/// repeats another.
+pub fn normalize_curfews(curfews: &mut Vec<Curfew>) {
+    curfews.sort_by_key(|c| (c.is_wrapping(), c.arrival_tolerance, c.from, c.to));
+    let mut merged: Vec<Curfew> = Vec::with_capacity(curfews.len());
+    for curfew in curfews.drain(..) {
+        match merged.last_mut() {
+            Some(last) if *last == curfew => {}
+            Some(last)
+                if !last.is_wrapping()
+                    && !curfew.is_wrapping()
+                    && last.arrival_tolerance == curfew.arrival_tolerance
+                    && curfew.from <= last.to =>
+            {
+                last.to = last.to.max(curfew.to);
+            }
+            _ => merged.push(curfew),
+        }
+    }
+    *curfews = merged;
+}
+
+impl Airport {
+    /// Closes the airport for `curfew` too, keeping the curfews normalized.
+    pub fn add_curfew(&mut self, curfew: Curfew) {
+        self.disruptions.push(curfew);
+        normalize_curfews(&mut self.disruptions);
+    }
 }
 
 impl fmt::Display for Airport {
@@ -27,18 +336,28 @@ impl fmt::Display for Airport {
     }
 }
 
-fn format_disruptions(disruptions: &Vec<Curfew>) -> String {
+fn format_disruptions(disruptions: &[Curfew]) -> String {
     if disruptions.is_empty() {
         return "None".to_string();
     }
     disruptions
         .iter()
-        .map(
-            |Curfew {
-                 from: start,
-                 to: end,
-             }| format!("{}-{}", start, end),
-        )
+        .map(|c| c.to_string())
         .collect::<Vec<_>>()
         .join(", ")
 }
+
+fn format_restrictions(types: &[FleetType]) -> String {
+    if types.is_empty() {
+        return "None".to_string();
+    }
+    types.join(", ")
+}
+
+fn format_stands(stands: &Option<u32>) -> String {
+    stands.map_or("-".to_string(), |s| s.to_string())
+}
+
+fn format_hours(hours: &Option<OperatingHours>) -> String {
+    hours.map_or("24h".to_string(), |h| h.to_string())
+}
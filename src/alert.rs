@@ -0,0 +1,94 @@
+use crate::flight::FlightStatus::Delayed;
+use crate::metrics;
+use crate::schedule::schedule::Schedule;
+use serde::Deserialize;
+use std::fmt;
+
+/// Health thresholds from the `[alerts]` table of the config file; a rule left out is
+/// never checked.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertRules {
+    pub max_unscheduled: Option<usize>,
+    pub max_delayed: Option<usize>,
+    /// Longest delay any single flight may carry, in minutes.
+    pub max_flight_delay: Option<u64>,
+    pub max_delay_minutes: Option<u64>,
+    /// Passenger compensation exposure, in EUR.
+    pub max_compensation: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Alert {
+    pub rule: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.rule, self.message)
+    }
+}
+
+impl AlertRules {
+    pub fn is_empty(&self) -> bool {
+        *self == AlertRules::default()
+    }
+
+    /// Rules the schedule currently breaks, in declaration order.
+    pub fn evaluate(&self, schedule: &Schedule) -> Vec<Alert> {
+        let summary = metrics::summarize(&schedule.flights);
+        let longest = self.max_flight_delay.and_then(|limit| {
+            schedule
+                .flights
+                .iter()
+                .filter_map(|f| match f.status {
+                    Delayed { minutes } if minutes > limit => Some((minutes, f.key())),
+                    _ => None,
+                })
+                // the earliest of equally late flights, usually where the delay started
+                .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+                .map(|(minutes, key)| Alert {
+                    rule: "max_flight_delay",
+                    message: format!("{} delayed by {} min (limit {})", key, minutes, limit),
+                })
+        });
+        [
+            exceeded(
+                "max_unscheduled",
+                self.max_unscheduled.map(|n| n as u64),
+                summary.unscheduled() as u64,
+                "flights unscheduled",
+            ),
+            exceeded(
+                "max_delayed",
+                self.max_delayed.map(|n| n as u64),
+                summary.delayed as u64,
+                "flights delayed",
+            ),
+            longest,
+            exceeded(
+                "max_delay_minutes",
+                self.max_delay_minutes,
+                summary.delay_minutes,
+                "delay minutes in total",
+            ),
+            exceeded(
+                "max_compensation",
+                self.max_compensation,
+                schedule.compensation.exposure(&schedule.flights),
+                "EUR compensation exposure",
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+fn exceeded(rule: &'static str, limit: Option<u64>, value: u64, what: &str) -> Option<Alert> {
+    limit.filter(|limit| value > *limit).map(|limit| Alert {
+        rule,
+        message: format!("{} {} (limit {})", value, what, limit),
+    })
+}
@@ -0,0 +1,131 @@
+use crate::metrics;
+use crate::schedule::schedule::Schedule;
+use std::collections::BTreeMap;
+use std::{fmt, mem};
+
+/// Name of the branch a session starts on.
+pub const MAIN: &str = "main";
+
+/// One what-if line. The live schedule of the current branch sits with the caller; every
+/// other branch keeps a snapshot of its schedule, history included, from when it was
+/// last left.
+struct Branch {
+    parent: Option<String>,
+    /// Disruptions in the parent's history when the branch was forked.
+    forked_at: usize,
+    snapshot: Option<Schedule>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BranchError {
+    Exists(String),
+    Unknown(String),
+    AlreadyOn(String),
+}
+
+impl fmt::Display for BranchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BranchError::Exists(name) => write!(f, "branch {} already exists", name),
+            BranchError::Unknown(name) => write!(f, "unknown branch {}", name),
+            BranchError::AlreadyOn(name) => write!(f, "already on branch {}", name),
+        }
+    }
+}
+
+impl std::error::Error for BranchError {}
+
+/// A branch as shown by `branch list`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BranchInfo {
+    pub name: String,
+    pub current: bool,
+    pub parent: Option<String>,
+    pub forked_at: usize,
+    pub disruptions: usize,
+    pub unscheduled: usize,
+    pub cost: u64,
+}
+
+/// What-if lines forked from a common state, each with its own schedule and history,
+/// switched between like git branches.
+pub struct Branches {
+    current: String,
+    branches: BTreeMap<String, Branch>,
+}
+
+impl Default for Branches {
+    fn default() -> Self {
+        Branches {
+            current: MAIN.to_string(),
+            branches: BTreeMap::from([(
+                MAIN.to_string(),
+                Branch {
+                    parent: None,
+                    forked_at: 0,
+                    snapshot: None,
+                },
+            )]),
+        }
+    }
+}
+
+impl Branches {
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    /// Forks `name` off the current branch at the state of `live`; the session stays on
+    /// the current branch.
+    pub fn create(&mut self, name: &str, live: &Schedule) -> Result<(), BranchError> {
+        if self.branches.contains_key(name) {
+            return Err(BranchError::Exists(name.to_string()));
+        }
+        self.branches.insert(
+            name.to_string(),
+            Branch {
+                parent: Some(self.current.clone()),
+                forked_at: live.history.len(),
+                snapshot: Some(live.fork()),
+            },
+        );
+        Ok(())
+    }
+
+    /// Parks `live` on the current branch and swaps in the schedule of `name`.
+    pub fn switch(&mut self, name: &str, live: &mut Schedule) -> Result<(), BranchError> {
+        if name == self.current {
+            return Err(BranchError::AlreadyOn(name.to_string()));
+        }
+        let target = self
+            .branches
+            .get_mut(name)
+            .and_then(|b| b.snapshot.take())
+            .ok_or_else(|| BranchError::Unknown(name.to_string()))?;
+        let parked = mem::replace(live, target);
+        if let Some(branch) = self.branches.get_mut(&self.current) {
+            branch.snapshot = Some(parked);
+        }
+        self.current = name.to_string();
+        Ok(())
+    }
+
+    /// Every branch in name order, the current one read from `live`.
+    pub fn list(&self, live: &Schedule) -> Vec<BranchInfo> {
+        self.branches
+            .iter()
+            .map(|(name, branch)| {
+                let schedule = branch.snapshot.as_ref().unwrap_or(live);
+                BranchInfo {
+                    name: name.clone(),
+                    current: *name == self.current,
+                    parent: branch.parent.clone(),
+                    forked_at: branch.forked_at,
+                    disruptions: schedule.history.len(),
+                    unscheduled: metrics::summarize(&schedule.flights).unscheduled(),
+                    cost: schedule.total_cost(),
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,949 @@
+use crate::airport::Curfew;
+use crate::config::TableStyle;
+use crate::export::Date;
+use crate::feed::FeedFormat;
+use crate::filter::FlightFilter;
+use crate::flight::FlightStatus;
+use crate::metrics::GroupBy;
+use crate::report::Format;
+use crate::schedule::anneal::AnnealConfig;
+use crate::schedule::atfm::DelayDistribution;
+use crate::schedule::monte_carlo::MonteCarloConfig;
+use crate::schedule::schedule::Objective;
+use crate::script;
+use crate::time::{Time, TimeFormat, parse_duration};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Flights `ls` narrows the listing to.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StatusFilter {
+    Unscheduled,
+    Scheduled,
+    Delayed,
+}
+
+impl StatusFilter {
+    pub fn matches(&self, status: &FlightStatus) -> bool {
+        match self {
+            StatusFilter::Unscheduled => matches!(status, FlightStatus::Unscheduled(_)),
+            StatusFilter::Scheduled => matches!(status, FlightStatus::Scheduled),
+            StatusFilter::Delayed => matches!(status, FlightStatus::Delayed { .. }),
+        }
+    }
+}
+
+/// What `atfm` regulates, as typed: whether an id names an airport is only known against
+/// the schedule.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AtfmScope {
+    /// An airport id followed by a departure window.
+    Window {
+        airport: String,
+        from: Time,
+        to: Time,
+    },
+    /// Comma-separated flight ids.
+    Flights(Vec<String>),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CsvExport {
+    Flights,
+    Stats,
+    History,
+}
+
+impl fmt::Display for CsvExport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            CsvExport::Flights => "flights",
+            CsvExport::Stats => "stats",
+            CsvExport::History => "history",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Columnar file formats for `export parquet|ipc flights`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FrameExport {
+    Parquet,
+    Ipc,
+}
+
+impl fmt::Display for FrameExport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            FrameExport::Parquet => "Parquet",
+            FrameExport::Ipc => "Arrow IPC",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BranchAction {
+    List,
+    New(String),
+    Switch(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WebhookAction {
+    List,
+    Add(String),
+    Remove(String),
+}
+
+/// A session setting changed with `set`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Setting {
+    /// How times are displayed; `start` moves DAY1 for ISO timestamps.
+    TimeFormat {
+        format: TimeFormat,
+        start: Option<Date>,
+    },
+    /// Borders of every table.
+    TableStyle(TableStyle),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StatsView {
+    Summary,
+    By(GroupBy),
+    Delays,
+    Banks,
+}
+
+/// A REPL command with its arguments checked. Ids of flights, aircraft and airports are
+/// kept as typed; they are looked up when the command runs.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Ls {
+        day: Option<u64>,
+        status: Option<StatusFilter>,
+    },
+    LsWhere(FlightFilter),
+    Clock(Option<Time>),
+    At {
+        at: Time,
+        command: String,
+    },
+    Events,
+    CancelEvent(usize),
+    Play {
+        speed: Option<u32>,
+    },
+    Pause,
+    Stop,
+    Freeze(Option<u64>),
+    Delay {
+        flight: String,
+        minutes: u64,
+    },
+    DelayWhere {
+        filter: FlightFilter,
+        minutes: u64,
+    },
+    Curfew {
+        airport: String,
+        curfew: Curfew,
+        push: bool,
+    },
+    PreviewCurfew {
+        airport: String,
+        curfew: Curfew,
+    },
+    Atfm {
+        scope: AtfmScope,
+        delays: DelayDistribution,
+    },
+    /// Without `from`, departures from the clock on are held.
+    DelayAirport {
+        airport: String,
+        minutes: u64,
+        from: Option<Time>,
+        to: Option<Time>,
+    },
+    Capacity {
+        airport: String,
+        percent: u32,
+        from: Time,
+        to: Time,
+    },
+    CloseRunway {
+        airport: String,
+        runway: String,
+        from: Time,
+        to: Time,
+    },
+    CloneDay {
+        day: u64,
+        copies: u64,
+    },
+    Retime {
+        flight: String,
+        minutes: i64,
+    },
+    Rollover {
+        days: u64,
+    },
+    Explain {
+        full: bool,
+    },
+    ExportCsv {
+        what: CsvExport,
+        path: String,
+    },
+    ExportIcs {
+        path: String,
+        start: Date,
+    },
+    ExportJson {
+        path: String,
+    },
+    /// Needs the `dataframe` feature.
+    ExportFrame {
+        format: FrameExport,
+        path: String,
+    },
+    History {
+        by_severity: bool,
+    },
+    GroundOccupancy {
+        airport: String,
+    },
+    Slack {
+        aircraft: Option<String>,
+    },
+    Candidates {
+        flight: String,
+    },
+    Impact {
+        delay: u64,
+        top: usize,
+    },
+    Feed {
+        source: String,
+        format: FeedFormat,
+        /// Keep polling at this interval until a line is typed.
+        every: Option<Duration>,
+    },
+    ScriptRun {
+        path: String,
+    },
+    Foreach {
+        filter: String,
+        command: String,
+    },
+    Merge {
+        path: String,
+    },
+    Audit,
+    Baseline,
+    CompareBaseline,
+    Branch(BranchAction),
+    Webhook(WebhookAction),
+    /// `strategy` is a built-in one or the name of a registered plugin.
+    Recover {
+        strategy: String,
+        objective: Objective,
+        anneal: AnnealConfig,
+    },
+    Report {
+        path: String,
+        format: Format,
+    },
+    ExperimentAddAircraft {
+        fleet_type: String,
+        base: String,
+        count: usize,
+    },
+    /// Monte Carlo runs of random delays on copies of the schedule.
+    Simulate(MonteCarloConfig),
+    Forecast {
+        aircraft: String,
+    },
+    Strandings,
+    Utilization,
+    /// Flight numbers, routes and aircraft resembling the text.
+    Find(String),
+    Stats(StatsView),
+    /// Re-render an `ls` or `stats` view whenever the schedule or clock changes, or stop
+    /// with `None`.
+    Watch(Option<Box<Command>>),
+    /// The current settings, or a change to one.
+    Set(Option<Setting>),
+    /// The list of commands, or the full help of one.
+    Help(Option<String>),
+    Exit,
+    /// Any other name, for commands registered by plugins.
+    Plugin {
+        name: String,
+        args: Vec<String>,
+    },
+}
+
+/// Arguments a command cannot run with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    /// What was wrong, when more can be said than the usage.
+    pub problem: Option<String>,
+    pub usage: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(problem) = &self.problem {
+            writeln!(f, "{}", problem)?;
+        }
+        write!(f, "Usage: {}", self.usage)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn usage(usage: &'static str) -> ParseError {
+    ParseError {
+        problem: None,
+        usage,
+    }
+}
+
+fn problem(usage: &'static str, problem: String) -> ParseError {
+    ParseError {
+        problem: Some(problem),
+        usage,
+    }
+}
+
+/// For commands whose usage is just their name.
+fn no_arguments(name: &'static str) -> ParseError {
+    problem(name, format!("{} takes no arguments", name))
+}
+
+/// Parses `value` as the `what` argument of a command.
+fn number<T: FromStr>(value: &str, what: &str, usage: &'static str) -> Result<T, ParseError> {
+    value
+        .parse()
+        .map_err(|_| problem(usage, format!("Invalid {} `{}`", what, value)))
+}
+
+/// Parses `value` as the `what` argument of a command taking a time.
+fn time(value: &str, what: &str, usage: &'static str) -> Result<Time, ParseError> {
+    value
+        .parse()
+        .map_err(|e| problem(usage, format!("Invalid {}: {}", what, e)))
+}
+
+/// Parses `value` as the `what` argument of a command taking a duration.
+fn duration(value: &str, what: &str, usage: &'static str) -> Result<u64, ParseError> {
+    parse_duration(value).map_err(|e| problem(usage, format!("Invalid {}: {}", what, e)))
+}
+
+/// A duration with an optional sign, negative for earlier.
+fn signed_duration(value: &str, what: &str, usage: &'static str) -> Result<i64, ParseError> {
+    let (sign, unsigned) = match value.strip_prefix('-') {
+        Some(unsigned) => (-1, unsigned),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+    i64::try_from(duration(unsigned, what, usage)?)
+        .map(|minutes| sign * minutes)
+        .map_err(|_| problem(usage, format!("Invalid {}: `{}` is too long", what, value)))
+}
+
+/// The words of `line`, with a `DAYn` and the time of day after it kept together as one
+/// time, e.g. `DAY2 06:30`. Words are slices of `line`.
+fn words(line: &str) -> Vec<&str> {
+    let offset = |word: &str| word.as_ptr() as usize - line.as_ptr() as usize;
+    let mut words: Vec<&str> = Vec::new();
+    for word in line.split_whitespace() {
+        match words.last_mut() {
+            Some(day) if is_day(day) && word.contains(':') => {
+                *day = &line[offset(day)..offset(word) + word.len()];
+            }
+            _ => words.push(word),
+        }
+    }
+    words
+}
+
+fn is_day(word: &str) -> bool {
+    word.get(..3).is_some_and(|p| p.eq_ignore_ascii_case("day"))
+        && word.len() > 3
+        && word[3..].bytes().all(|b| b.is_ascii_digit())
+}
+
+/// What follows `word`, a word of `line`, spacing kept.
+fn after<'a>(line: &'a str, word: &str) -> &'a str {
+    line[word.as_ptr() as usize - line.as_ptr() as usize + word.len()..].trim()
+}
+
+/// Start and end of a window that has to end after it starts.
+fn window(from: &str, to: &str, usage: &'static str) -> Result<(Time, Time), ParseError> {
+    let (from, to) = (
+        time(from, "start time", usage)?,
+        time(to, "end time", usage)?,
+    );
+    match from < to {
+        true => Ok((from, to)),
+        false => Err(problem(
+            usage,
+            format!(
+                "The window has to end after it starts at {}, not at {}",
+                from, to
+            ),
+        )),
+    }
+}
+
+/// A curfew ending before it starts wraps midnight and repeats every day, so it is given
+/// as times of day then.
+fn curfew(from: &str, to: &str, rest: &[&str], usage: &'static str) -> Result<Curfew, ParseError> {
+    let curfew = Curfew {
+        from: time(from, "start time", usage)?,
+        to: time(to, "end time", usage)?,
+        arrival_tolerance: arrival_tolerance(rest, usage)?,
+    };
+    if curfew.from == curfew.to {
+        return Err(problem(
+            usage,
+            format!("The curfew starts and ends at {}", curfew.from),
+        ));
+    }
+    if curfew.is_wrapping() && curfew.from.0 >= 1440 {
+        return Err(problem(
+            usage,
+            format!(
+                "The curfew ends at {} before it starts at {}; an overnight curfew takes times of day, e.g. 23:30 05:30",
+                curfew.to, curfew.from
+            ),
+        ));
+    }
+    Ok(curfew)
+}
+
+fn arrival_tolerance(rest: &[&str], usage: &'static str) -> Result<u64, ParseError> {
+    match rest {
+        [] => Ok(0),
+        ["--arrival-tolerance", m] => duration(m, "arrival tolerance", usage),
+        _ => Err(self::usage(usage)),
+    }
+}
+
+/// The delay distribution of `atfm`: `--fixed <m>`, `--uniform <min> <max> [--seed <n>]`
+/// or `--rate <n>` departures per hour.
+fn atfm_delays(rest: &[&str], usage: &'static str) -> Result<DelayDistribution, ParseError> {
+    match rest {
+        ["--fixed", m] => Ok(DelayDistribution::Fixed(duration(m, "delay", usage)?)),
+        ["--uniform", min, max, seed @ ..] => Ok(DelayDistribution::Uniform {
+            min: duration(min, "minimum delay", usage)?,
+            max: duration(max, "maximum delay", usage)?,
+            seed: match seed {
+                [] => 0,
+                ["--seed", n] => number(n, "seed", usage)?,
+                _ => return Err(self::usage(usage)),
+            },
+        }),
+        ["--rate", n] => Ok(DelayDistribution::Rate(number(n, "rate", usage)?)),
+        _ => Err(self::usage(usage)),
+    }
+}
+
+pub(crate) const LS: &str = "ls [<day>] [u|s|d]";
+pub(crate) const LS_WHERE: &str = "ls where <conditions>";
+pub(crate) const CLOCK: &str = "clock [<time>]";
+pub(crate) const AT: &str = "at <time> <command>";
+pub(crate) const EVENTS: &str = "events [cancel <n>]";
+pub(crate) const PLAY: &str = "play [--speed <n>]";
+pub(crate) const FREEZE: &str = "freeze [<minutes>]";
+pub(crate) const DELAY: &str = "delay <flight_id> <minutes>";
+pub(crate) const DELAY_WHERE: &str = "delay where <conditions> by <minutes>";
+pub(crate) const CURFEW: &str =
+    "curfew <airport_id> <from> <to> [--arrival-tolerance <minutes>] [--push]";
+pub(crate) const CURFEW_PREVIEW: &str =
+    "curfew --preview <airport_id> <from> <to> [--arrival-tolerance <minutes>]";
+pub(crate) const ATFM: &str = "atfm <airport_id> <from> <to> | <flight_id>[,<flight_id>...] then --fixed <minutes> | --uniform <minutes> <minutes> [--seed <n>] | --rate <n>";
+pub(crate) const DELAY_AIRPORT: &str = "delay-airport <airport_id> <minutes> [<from> [<to>]]";
+pub(crate) const CAPACITY: &str = "capacity <airport_id> <percent> <from> <to>";
+pub(crate) const CLOSE_RUNWAY: &str = "close-runway <airport_id> <runway_id> <from> <to>";
+pub(crate) const CLONE_DAY: &str = "clone-day <day> <copies>";
+pub(crate) const RETIME: &str = "retime <flight_id> <+/-minutes>";
+pub(crate) const ROLLOVER: &str = "rollover [<days>]";
+pub(crate) const EXPLAIN: &str = "explain [full]";
+pub(crate) const EXPORT: &str = "export csv <flights|stats|history> <file>";
+pub(crate) const EXPORT_ICS: &str = "export ics <file> [--start <YYYY-MM-DD>]";
+pub(crate) const EXPORT_JSON: &str = "export json flights <file>";
+pub(crate) const EXPORT_FRAME: &str = "export parquet|ipc flights <file>";
+pub(crate) const HISTORY: &str = "history [--by-severity]";
+pub(crate) const GROUND_OCCUPANCY: &str = "ground-occupancy <airport_id>";
+pub(crate) const SLACK: &str = "slack [<aircraft_id>]";
+pub(crate) const CANDIDATES: &str = "candidates <flight_id>";
+pub(crate) const IMPACT: &str = "impact [--delay <minutes>] [<top>]";
+pub(crate) const FEED: &str = "feed <file|url> [--format observations|opensky] [--every <secs>s]";
+pub(crate) const SCRIPT: &str = "script run <file>";
+pub(crate) const FOREACH: &str =
+    "foreach <all|scheduled|delayed|unscheduled> <command with {flight}>";
+pub(crate) const MERGE: &str = "merge <file>";
+pub(crate) const COMPARE: &str = "compare baseline";
+pub(crate) const BRANCH: &str = "branch [list | new <name> | switch <name>]";
+pub(crate) const WEBHOOK: &str = "webhook [add <url> | rm <url>]";
+pub(crate) const RECOVER: &str = "recover [--strategy greedy|flow|anneal] [--objective first-fit|robust|balanced] [--time <secs>s] [--seed <n>]";
+pub(crate) const REPORT: &str = "report <file.md|file.html>";
+pub(crate) const EXPERIMENT: &str = "experiment add-aircraft <type> <base> <count>";
+pub(crate) const SIMULATE: &str = "simulate <runs> [--probability <percent>] [--delay <minutes> <minutes>] [--seed <n>] [--recover]";
+pub(crate) const FORECAST: &str = "forecast <aircraft_id>";
+pub(crate) const STATS: &str = "stats [by=day|by=airport|by=aircraft|delays|banks]";
+pub(crate) const PAUSE: &str = "pause";
+pub(crate) const STOP: &str = "stop";
+pub(crate) const AUDIT: &str = "audit";
+pub(crate) const BASELINE: &str = "baseline";
+pub(crate) const STRANDINGS: &str = "strandings";
+pub(crate) const UTILIZATION: &str = "utilization";
+pub(crate) const FIND: &str = "find <text>";
+pub(crate) const WATCH: &str = "watch <ls ...|stats ...> | watch off";
+pub(crate) const SET: &str =
+    "set [timefmt minutes|dayhhmm|iso [--start <YYYY-MM-DD>] | style rounded|ascii|markdown|psql]";
+pub(crate) const HELP: &str = "help [<command>]";
+pub(crate) const EXIT: &str = "exit";
+
+impl Command {
+    /// Parses one line of REPL input; words are separated by whitespace.
+    pub fn parse(line: &str) -> Result<Command, ParseError> {
+        let parts = words(line);
+        let Some(name) = parts.first() else {
+            return Err(usage("help"));
+        };
+        let args = &parts[1..];
+        let owned = |s: &str| s.to_string();
+        Ok(match (*name, args) {
+            ("ls", ["where", conditions @ ..]) => Command::LsWhere(
+                FlightFilter::parse(&conditions.join(" "))
+                    .map_err(|e| problem(LS_WHERE, format!("Cannot filter flights: {}", e)))?,
+            ),
+            ("ls", args) => {
+                let (mut day, mut status) = (None, None);
+                for arg in args {
+                    if let Ok(d) = arg.parse::<u64>() {
+                        if d == 0 {
+                            return Err(problem(LS, "Days start at 1".to_string()));
+                        }
+                        day = Some(d);
+                        continue;
+                    }
+                    status = Some(match *arg {
+                        "u" | "unscheduled" => StatusFilter::Unscheduled,
+                        "s" | "scheduled" => StatusFilter::Scheduled,
+                        "d" | "delayed" => StatusFilter::Delayed,
+                        _ => return Err(problem(LS, format!("Unknown status `{}`", arg))),
+                    });
+                }
+                Command::Ls { day, status }
+            }
+            ("clock", []) => Command::Clock(None),
+            ("clock", [m]) => Command::Clock(Some(time(m, "time", CLOCK)?)),
+            ("clock", _) => return Err(usage(CLOCK)),
+            ("at", [m, _, ..]) => Command::At {
+                at: time(m, "time", AT)?,
+                command: owned(after(line, m)),
+            },
+            ("at", _) => return Err(usage(AT)),
+            ("events", []) => Command::Events,
+            ("events", ["cancel", n]) => Command::CancelEvent(number(n, "event", EVENTS)?),
+            ("events", _) => return Err(usage(EVENTS)),
+            ("play", []) => Command::Play { speed: None },
+            ("play", ["--speed", n]) => match number(n, "speed", PLAY)? {
+                0 => return Err(problem(PLAY, "The speed must be at least 1".to_string())),
+                speed => Command::Play { speed: Some(speed) },
+            },
+            ("play", _) => return Err(usage(PLAY)),
+            ("pause", []) => Command::Pause,
+            ("stop", []) => Command::Stop,
+            ("freeze", []) => Command::Freeze(None),
+            ("freeze", [m]) => Command::Freeze(Some(duration(m, "horizon", FREEZE)?)),
+            ("freeze", _) => return Err(usage(FREEZE)),
+            ("delay", ["where", ..]) => {
+                let Some(by) = args.iter().rposition(|p| *p == "by") else {
+                    return Err(usage(DELAY_WHERE));
+                };
+                let [m] = args[by + 1..] else {
+                    return Err(usage(DELAY_WHERE));
+                };
+                Command::DelayWhere {
+                    filter: FlightFilter::parse(&args[1..by].join(" ")).map_err(|e| {
+                        problem(DELAY_WHERE, format!("Cannot filter flights: {}", e))
+                    })?,
+                    minutes: duration(m, "delay", DELAY_WHERE)?,
+                }
+            }
+            ("delay", [flight, m]) => Command::Delay {
+                flight: owned(flight),
+                minutes: duration(m, "delay", DELAY)?,
+            },
+            ("delay", _) => return Err(usage(DELAY)),
+            ("curfew", ["--preview", airport, from, to, rest @ ..]) => Command::PreviewCurfew {
+                airport: owned(airport),
+                curfew: curfew(from, to, rest, CURFEW_PREVIEW)?,
+            },
+            ("curfew", ["--preview", ..]) => return Err(usage(CURFEW_PREVIEW)),
+            ("curfew", [airport, from, to, rest @ ..]) => {
+                let push = rest.contains(&"--push");
+                let rest = rest
+                    .iter()
+                    .copied()
+                    .filter(|p| *p != "--push")
+                    .collect::<Vec<_>>();
+                Command::Curfew {
+                    airport: owned(airport),
+                    curfew: curfew(from, to, &rest, CURFEW)?,
+                    push,
+                }
+            }
+            ("curfew", _) => return Err(usage(CURFEW)),
+            ("atfm", [id, rest @ ..]) => {
+                let (scope, rest) = match rest {
+                    [from, to, rest @ ..]
+                        if from.parse::<Time>().is_ok() && to.parse::<Time>().is_ok() =>
+                    {
+                        let (from, to) = window(from, to, ATFM)?;
+                        let scope = AtfmScope::Window {
+                            airport: owned(id),
+                            from,
+                            to,
+                        };
+                        (scope, rest)
+                    }
+                    _ => (AtfmScope::Flights(id.split(',').map(owned).collect()), rest),
+                };
+                Command::Atfm {
+                    scope,
+                    delays: atfm_delays(rest, ATFM)?,
+                }
+            }
+            ("atfm", _) => return Err(usage(ATFM)),
+            ("delay-airport", [airport, m, times @ ..]) if times.len() <= 2 => {
+                let (from, to) = match times {
+                    [] => (None, None),
+                    [from] => (Some(time(from, "start time", DELAY_AIRPORT)?), None),
+                    [from, to, ..] => {
+                        let (from, to) = window(from, to, DELAY_AIRPORT)?;
+                        (Some(from), Some(to))
+                    }
+                };
+                Command::DelayAirport {
+                    airport: owned(airport),
+                    minutes: duration(m, "delay", DELAY_AIRPORT)?,
+                    from,
+                    to,
+                }
+            }
+            ("delay-airport", _) => return Err(usage(DELAY_AIRPORT)),
+            ("capacity", [airport, percent, from, to]) => {
+                let (from, to) = window(from, to, CAPACITY)?;
+                Command::Capacity {
+                    airport: owned(airport),
+                    percent: number(percent.trim_end_matches('%'), "percentage", CAPACITY)?,
+                    from,
+                    to,
+                }
+            }
+            ("capacity", _) => return Err(usage(CAPACITY)),
+            ("close-runway", [airport, runway, from, to]) => {
+                let (from, to) = window(from, to, CLOSE_RUNWAY)?;
+                Command::CloseRunway {
+                    airport: owned(airport),
+                    runway: owned(runway),
+                    from,
+                    to,
+                }
+            }
+            ("close-runway", _) => return Err(usage(CLOSE_RUNWAY)),
+            ("clone-day", [day, copies]) => Command::CloneDay {
+                day: number(day, "day", CLONE_DAY)?,
+                copies: number(copies, "number of copies", CLONE_DAY)?,
+            },
+            ("clone-day", _) => return Err(usage(CLONE_DAY)),
+            ("retime", [flight, m]) => Command::Retime {
+                flight: owned(flight),
+                minutes: signed_duration(m, "shift", RETIME)?,
+            },
+            ("retime", _) => return Err(usage(RETIME)),
+            ("rollover", []) => Command::Rollover { days: 1 },
+            ("rollover", [days]) => Command::Rollover {
+                days: number(days, "number of days", ROLLOVER)?,
+            },
+            ("rollover", _) => return Err(usage(ROLLOVER)),
+            ("explain", []) => Command::Explain { full: false },
+            ("explain", ["full"]) => Command::Explain { full: true },
+            ("explain", _) => return Err(usage(EXPLAIN)),
+            ("export", ["ics", path, rest @ ..]) => Command::ExportIcs {
+                path: owned(path),
+                start: match rest {
+                    [] => Date::default(),
+                    ["--start", date] => date.parse().map_err(|e| problem(EXPORT_ICS, e))?,
+                    _ => return Err(usage(EXPORT_ICS)),
+                },
+            },
+            ("export", ["ics", ..]) => return Err(usage(EXPORT_ICS)),
+            ("export", ["json", "flights", path]) => Command::ExportJson { path: owned(path) },
+            ("export", ["json", ..]) => return Err(usage(EXPORT_JSON)),
+            ("export", ["parquet", "flights", path]) => Command::ExportFrame {
+                format: FrameExport::Parquet,
+                path: owned(path),
+            },
+            ("export", ["ipc", "flights", path]) => Command::ExportFrame {
+                format: FrameExport::Ipc,
+                path: owned(path),
+            },
+            ("export", ["parquet" | "ipc", ..]) => return Err(usage(EXPORT_FRAME)),
+            ("export", ["csv", what, path]) => Command::ExportCsv {
+                what: match *what {
+                    "flights" => CsvExport::Flights,
+                    "stats" => CsvExport::Stats,
+                    "history" => CsvExport::History,
+                    _ => return Err(problem(EXPORT, format!("Unknown export `{}`", what))),
+                },
+                path: owned(path),
+            },
+            ("export", _) => return Err(usage(EXPORT)),
+            ("history", []) => Command::History { by_severity: false },
+            ("history", ["--by-severity"]) => Command::History { by_severity: true },
+            ("history", _) => return Err(usage(HISTORY)),
+            ("ground-occupancy", [airport]) => Command::GroundOccupancy {
+                airport: owned(airport),
+            },
+            ("ground-occupancy", _) => return Err(usage(GROUND_OCCUPANCY)),
+            ("slack", []) => Command::Slack { aircraft: None },
+            ("slack", [aircraft]) => Command::Slack {
+                aircraft: Some(owned(aircraft)),
+            },
+            ("slack", _) => return Err(usage(SLACK)),
+            ("candidates", [flight]) => Command::Candidates {
+                flight: owned(flight),
+            },
+            ("candidates", _) => return Err(usage(CANDIDATES)),
+            ("impact", args) => {
+                let (mut delay, mut top) = (60, 20);
+                let mut args = args.iter();
+                while let Some(arg) = args.next() {
+                    match *arg {
+                        "--delay" => {
+                            let Some(m) = args.next() else {
+                                return Err(usage(IMPACT));
+                            };
+                            delay = duration(m, "delay", IMPACT)?;
+                        }
+                        n => top = number(n, "number of flights", IMPACT)?,
+                    }
+                }
+                if delay == 0 {
+                    return Err(problem(IMPACT, "The delay must be at least 1".to_string()));
+                }
+                Command::Impact { delay, top }
+            }
+            ("feed", [source, args @ ..]) => {
+                let mut format = FeedFormat::Observations;
+                let mut every = None;
+                for pair in args.chunks(2) {
+                    match pair {
+                        ["--format", "observations"] => format = FeedFormat::Observations,
+                        ["--format", "opensky"] => format = FeedFormat::OpenSky,
+                        ["--format", f] => {
+                            return Err(problem(FEED, format!("Unknown feed format `{}`", f)));
+                        }
+                        ["--every", t] => {
+                            let secs = number(t.trim_end_matches('s'), "interval", FEED)?;
+                            if secs == 0 {
+                                return Err(problem(
+                                    FEED,
+                                    "The interval must be at least 1s".to_string(),
+                                ));
+                            }
+                            every = Some(Duration::from_secs(secs));
+                        }
+                        _ => return Err(usage(FEED)),
+                    }
+                }
+                Command::Feed {
+                    source: owned(source),
+                    format,
+                    every,
+                }
+            }
+            ("feed", _) => return Err(usage(FEED)),
+            ("script", ["run", path]) => Command::ScriptRun { path: owned(path) },
+            ("script", _) => return Err(usage(SCRIPT)),
+            ("foreach", [filter, _, ..]) => Command::Foreach {
+                filter: owned(filter),
+                command: owned(after(line, filter)),
+            },
+            ("foreach", _) => return Err(usage(FOREACH)),
+            ("merge", [path]) => Command::Merge { path: owned(path) },
+            ("merge", _) => return Err(usage(MERGE)),
+            ("audit", []) => Command::Audit,
+            ("baseline", []) => Command::Baseline,
+            ("compare", ["baseline"]) => Command::CompareBaseline,
+            ("compare", _) => return Err(usage(COMPARE)),
+            ("branch", [] | ["list"]) => Command::Branch(BranchAction::List),
+            ("branch", ["new", name]) => Command::Branch(BranchAction::New(owned(name))),
+            ("branch", ["switch", name]) => Command::Branch(BranchAction::Switch(owned(name))),
+            ("branch", _) => return Err(usage(BRANCH)),
+            ("webhook", []) => Command::Webhook(WebhookAction::List),
+            ("webhook", ["add", url]) => Command::Webhook(WebhookAction::Add(owned(url))),
+            ("webhook", ["rm", url]) => Command::Webhook(WebhookAction::Remove(owned(url))),
+            ("webhook", _) => return Err(usage(WEBHOOK)),
+            ("recover", args) => {
+                let mut strategy = "greedy";
+                let mut objective = Objective::FirstFit;
+                let mut anneal = AnnealConfig::default();
+                for pair in args.chunks(2) {
+                    match pair {
+                        ["--strategy", s] => strategy = s,
+                        ["--objective", "first-fit"] => objective = Objective::FirstFit,
+                        ["--objective", "robust"] => objective = Objective::Robust,
+                        ["--objective", "balanced"] => objective = Objective::Balanced,
+                        ["--objective", o] => {
+                            return Err(problem(RECOVER, format!("Unknown objective `{}`", o)));
+                        }
+                        ["--time", t] => {
+                            let secs = number(t.trim_end_matches('s'), "time limit", RECOVER)?;
+                            anneal.time_limit = Duration::from_secs(secs);
+                        }
+                        ["--seed", n] => anneal.seed = number(n, "seed", RECOVER)?,
+                        _ => return Err(usage(RECOVER)),
+                    }
+                }
+                Command::Recover {
+                    strategy: owned(strategy),
+                    objective,
+                    anneal,
+                }
+            }
+            ("report", [path]) => Command::Report {
+                path: owned(path),
+                format: Format::from_path(path).ok_or_else(|| usage(REPORT))?,
+            },
+            ("report", _) => return Err(usage(REPORT)),
+            ("experiment", ["add-aircraft", fleet_type, base, count]) => {
+                Command::ExperimentAddAircraft {
+                    fleet_type: owned(fleet_type),
+                    base: owned(base),
+                    count: number(count, "number of aircraft", EXPERIMENT)?,
+                }
+            }
+            ("experiment", _) => return Err(usage(EXPERIMENT)),
+            ("simulate", [runs, rest @ ..]) => {
+                let mut config = MonteCarloConfig {
+                    runs: number(runs, "number of runs", SIMULATE)?,
+                    ..MonteCarloConfig::default()
+                };
+                let mut rest = rest;
+                loop {
+                    rest = match rest {
+                        [] => break,
+                        ["--recover", tail @ ..] => {
+                            config.recover = true;
+                            tail
+                        }
+                        ["--probability", p, tail @ ..] => {
+                            config.probability =
+                                number(p.trim_end_matches('%'), "probability", SIMULATE)?;
+                            tail
+                        }
+                        ["--delay", min, max, tail @ ..] => {
+                            config.min = duration(min, "minimum delay", SIMULATE)?;
+                            config.max = duration(max, "maximum delay", SIMULATE)?;
+                            tail
+                        }
+                        ["--seed", n, tail @ ..] => {
+                            config.seed = number(n, "seed", SIMULATE)?;
+                            tail
+                        }
+                        _ => return Err(usage(SIMULATE)),
+                    };
+                }
+                Command::Simulate(config)
+            }
+            ("simulate", _) => return Err(usage(SIMULATE)),
+            ("forecast", [aircraft]) => Command::Forecast {
+                aircraft: owned(aircraft),
+            },
+            ("forecast", _) => return Err(usage(FORECAST)),
+            ("strandings", []) => Command::Strandings,
+            ("utilization", []) => Command::Utilization,
+            ("stats", []) => Command::Stats(StatsView::Summary),
+            ("stats", ["delays"]) => Command::Stats(StatsView::Delays),
+            ("stats", ["banks"]) => Command::Stats(StatsView::Banks),
+            ("stats", ["by=day"]) => Command::Stats(StatsView::By(GroupBy::Day)),
+            ("stats", ["by=airport"]) => Command::Stats(StatsView::By(GroupBy::Airport)),
+            ("stats", ["by=aircraft"]) => Command::Stats(StatsView::By(GroupBy::Aircraft)),
+            ("stats", _) => return Err(usage(STATS)),
+            ("find", [_, ..]) => Command::Find(owned(after(line, name))),
+            ("find", []) => return Err(usage(FIND)),
+            ("watch", ["off"]) => Command::Watch(None),
+            ("watch", [_, ..]) => match Command::parse(after(line, name))? {
+                view @ (Command::Ls { .. } | Command::LsWhere(_) | Command::Stats(_)) => {
+                    Command::Watch(Some(Box::new(view)))
+                }
+                _ => {
+                    return Err(problem(
+                        WATCH,
+                        "Only ls and stats can be watched".to_string(),
+                    ));
+                }
+            },
+            ("watch", []) => return Err(usage(WATCH)),
+            ("set", []) => Command::Set(None),
+            ("set", ["timefmt", format, rest @ ..]) => Command::Set(Some(Setting::TimeFormat {
+                format: format.parse().map_err(|e| problem(SET, e))?,
+                start: match rest {
+                    [] => None,
+                    ["--start", date] => Some(date.parse().map_err(|e| problem(SET, e))?),
+                    _ => return Err(usage(SET)),
+                },
+            })),
+            ("set", ["style", style]) => Command::Set(Some(Setting::TableStyle(
+                style.parse().map_err(|e| problem(SET, e))?,
+            ))),
+            ("set", _) => return Err(usage(SET)),
+            ("help" | "?", []) => Command::Help(None),
+            ("help" | "?", [name]) => Command::Help(Some(owned(name))),
+            ("exit" | "quit", []) => Command::Exit,
+            ("pause", _) => return Err(no_arguments(PAUSE)),
+            ("stop", _) => return Err(no_arguments(STOP)),
+            ("audit", _) => return Err(no_arguments(AUDIT)),
+            ("baseline", _) => return Err(no_arguments(BASELINE)),
+            ("strandings", _) => return Err(no_arguments(STRANDINGS)),
+            ("utilization", _) => return Err(no_arguments(UTILIZATION)),
+            ("help" | "?", _) => return Err(usage(HELP)),
+            ("exit" | "quit", _) => return Err(no_arguments(EXIT)),
+            (name, args) => Command::Plugin {
+                name: owned(name),
+                args: args.iter().copied().map(owned).collect(),
+            },
+        })
+    }
+
+    /// Whether running the command changes flights, which is when alerts are checked.
+    /// Commands of plugins are not known here; a Rhai script counts as changing flights.
+    pub fn mutates(&self) -> bool {
+        matches!(
+            self,
+            Command::Delay { .. }
+                | Command::DelayWhere { .. }
+                | Command::Curfew { .. }
+                | Command::Atfm { .. }
+                | Command::DelayAirport { .. }
+                | Command::Capacity { .. }
+                | Command::CloseRunway { .. }
+                | Command::Recover { .. }
+                | Command::Retime { .. }
+                | Command::CloneDay { .. }
+                | Command::Rollover { .. }
+                | Command::Merge { .. }
+                | Command::Feed { .. }
+        ) || matches!(self, Command::ScriptRun { path } if script::is_rhai(path))
+    }
+}
@@ -0,0 +1,793 @@
+use crate::cli::commands::*;
+use std::fmt::Write;
+
+/// Help for one form of a command; a command like `delay` or `export` has several.
+pub struct CommandHelp {
+    /// What the form is looked up by with `help <name>`.
+    pub name: &'static str,
+    /// Short form shown in the command list.
+    pub synopsis: &'static str,
+    pub summary: &'static str,
+    /// The usage the parser reports for bad arguments.
+    pub usage: &'static str,
+    pub arguments: &'static [(&'static str, &'static str)],
+    pub examples: &'static [&'static str],
+    pub related: &'static [&'static str],
+}
+
+const TIME: (&str, &str) = (
+    "<minutes>",
+    "a time: minutes since DAY1 00:00 (1830), a day and time of day (DAY2 06:30) or a time of day and the days after DAY1 (06:30+1)",
+);
+const FLIGHT: (&str, &str) = (
+    "<flight_id>",
+    "flight number, or <number>/<day> when it repeats on several days, e.g. FL-101/2",
+);
+const CONDITIONS: (&str, &str) = (
+    "<conditions>",
+    "route, origin, destination, aircraft, status, day or delay compared with =, !=, <, >, <= or >=, joined by and",
+);
+
+/// Every built-in command, in the order `help` lists them.
+pub const COMMANDS: &[CommandHelp] = &[
+    CommandHelp {
+        name: "ls",
+        synopsis: "ls [status]",
+        summary: "List all flights in a table or filter by status: u - unscheduled, s - scheduled, d - delayed",
+        usage: LS,
+        arguments: &[
+            ("<day>", "operating day, starting at 1"),
+            ("u|s|d", "unscheduled, scheduled or delayed flights only"),
+        ],
+        examples: &["ls", "ls 2 d"],
+        related: &["stats", "history"],
+    },
+    CommandHelp {
+        name: "ls",
+        synopsis: "ls where <cond>",
+        summary: "List flights matching conditions on route, origin, destination, aircraft, status, day or delay joined by and, e.g. ls where delay>60",
+        usage: LS_WHERE,
+        arguments: &[CONDITIONS],
+        examples: &["ls where delay>60", "ls where route=KRK-WAW and day=2"],
+        related: &["delay", "foreach"],
+    },
+    CommandHelp {
+        name: "audit",
+        synopsis: "audit",
+        summary: "Check the schedule's invariants (assignments, continuity, turn times, flight lookup), e.g. after a plugin strategy",
+        usage: AUDIT,
+        arguments: &[],
+        examples: &["audit"],
+        related: &["recover"],
+    },
+    CommandHelp {
+        name: "baseline",
+        synopsis: "baseline",
+        summary: "Capture the flights as they stand now as the baseline (taken automatically after the first assignment)",
+        usage: BASELINE,
+        arguments: &[],
+        examples: &["baseline"],
+        related: &["compare", "branch"],
+    },
+    CommandHelp {
+        name: "compare",
+        synopsis: "compare baseline",
+        summary: "Report drift from the baseline: flights moved, average drift, aircraft changed, flights lost",
+        usage: COMPARE,
+        arguments: &[],
+        examples: &["compare baseline"],
+        related: &["baseline"],
+    },
+    CommandHelp {
+        name: "branch",
+        synopsis: "branch [list|new <name>|switch <name>]",
+        summary: "Fork what-if lines from the current state and switch between them, each with its own history",
+        usage: BRANCH,
+        arguments: &[("<name>", "name of the branch, unique in the session")],
+        examples: &["branch new no-charter", "branch switch main", "branch list"],
+        related: &["baseline", "history"],
+    },
+    CommandHelp {
+        name: "clock",
+        synopsis: "clock [<m>]",
+        summary: "Show the simulation clock or move it forward to minute <m>, firing queued events on the way",
+        usage: CLOCK,
+        arguments: &[TIME],
+        examples: &["clock", "clock 600", "clock DAY2 06:30"],
+        related: &["at", "events", "play", "freeze"],
+    },
+    CommandHelp {
+        name: "at",
+        synopsis: "at <m> <command>",
+        summary: "Queue <command> to run when the clock reaches minute <m>, e.g. at 600 delay FLIGHT_5 30",
+        usage: AT,
+        arguments: &[TIME, ("<command>", "any command, run as typed")],
+        examples: &["at 600 delay FL-101 30", "at 22:00 curfew KRK 23:30 05:30"],
+        related: &["events", "clock", "play"],
+    },
+    CommandHelp {
+        name: "events",
+        synopsis: "events [cancel <n>]",
+        summary: "List the queued events, or cancel event <n>",
+        usage: EVENTS,
+        arguments: &[("<n>", "number of the event, as listed")],
+        examples: &["events", "events cancel 2"],
+        related: &["at", "clock"],
+    },
+    CommandHelp {
+        name: "play",
+        synopsis: "play [--speed <n>]",
+        summary: "Run the clock in real time, <n> simulated seconds per second (default 60), firing events and echoing departures and landings; pause or stop",
+        usage: PLAY,
+        arguments: &[("<n>", "simulated seconds per second, at least 1")],
+        examples: &["play", "play --speed 600"],
+        related: &["pause", "stop", "clock", "at"],
+    },
+    CommandHelp {
+        name: "pause",
+        synopsis: "pause",
+        summary: "While playing, stop the clock and keep the speed for the next play",
+        usage: PAUSE,
+        arguments: &[],
+        examples: &["pause"],
+        related: &["play", "stop"],
+    },
+    CommandHelp {
+        name: "stop",
+        synopsis: "stop",
+        summary: "While playing, stop the clock and go back to the default speed",
+        usage: STOP,
+        arguments: &[],
+        examples: &["stop"],
+        related: &["play", "pause"],
+    },
+    CommandHelp {
+        name: "clone-day",
+        synopsis: "clone-day <d> <n>",
+        summary: "Copy the flights of day <d> into the next <n> days, unassigned",
+        usage: CLONE_DAY,
+        arguments: &[
+            ("<day>", "operating day to copy, starting at 1"),
+            ("<copies>", "number of following days to fill"),
+        ],
+        examples: &["clone-day 1 6"],
+        related: &["recover", "rollover"],
+    },
+    CommandHelp {
+        name: "delay",
+        synopsis: "delay <id> <m>",
+        summary: "Inject <m> minutes of delay into flight <id>; write <number>/<day> when the number repeats on several days",
+        usage: DELAY,
+        arguments: &[
+            FLIGHT,
+            (
+                "<minutes>",
+                "length of the delay: minutes (90), 90m or 1h30",
+            ),
+        ],
+        examples: &["delay FL-101 30", "delay FL-101/2 45"],
+        related: &["explain", "retime", "delay-airport", "recover"],
+    },
+    CommandHelp {
+        name: "delay",
+        synopsis: "delay where <cond> by <m>",
+        summary: "Delay every flight matching the conditions by <m> minutes, e.g. delay where route=KRK-WAW and day=2 by 30",
+        usage: DELAY_WHERE,
+        arguments: &[
+            CONDITIONS,
+            (
+                "<minutes>",
+                "length of each delay: minutes (90), 90m or 1h30",
+            ),
+        ],
+        examples: &["delay where route=KRK-WAW and day=2 by 30"],
+        related: &["ls", "foreach"],
+    },
+    CommandHelp {
+        name: "delay-airport",
+        synopsis: "delay-airport <id> <m> [from] [to]",
+        summary: "Delay every departure from airport <id> from minute [from] (default the clock) until [to] by <m> minutes, in one report",
+        usage: DELAY_AIRPORT,
+        arguments: &[
+            ("<airport_id>", "airport the departures leave from"),
+            (
+                "<minutes>",
+                "length of each delay: minutes (90), 90m or 1h30",
+            ),
+            (
+                "<from> <to>",
+                "window of departures, as times like DAY2 06:30 ending after it starts; open-ended without <to>",
+            ),
+        ],
+        examples: &[
+            "delay-airport WAW 40",
+            "delay-airport WAW 1h DAY2 06:00 DAY2 12:00",
+        ],
+        related: &["atfm", "curfew", "delay"],
+    },
+    CommandHelp {
+        name: "capacity",
+        synopsis: "capacity <id> <%> <from> <to>",
+        summary: "Cut the movement rate of airport <id> by <%> in a window, metering its departures and arrivals with delays instead of cancelling them",
+        usage: CAPACITY,
+        arguments: &[
+            ("<airport_id>", "airport losing capacity"),
+            (
+                "<percent>",
+                "share of the hourly rate lost, 1 to 99; the rate is that of the runways, slots_per_hour or the busiest scheduled hour",
+            ),
+            (
+                "<from> <to>",
+                "window of the reduction, as times like DAY2 06:30 ending after it starts",
+            ),
+        ],
+        examples: &[
+            "capacity WAW 50 06:00 10:00",
+            "capacity KRK 30% DAY2 14:00 DAY2 18:00",
+        ],
+        related: &["atfm", "close-runway", "delay-airport", "curfew"],
+    },
+    CommandHelp {
+        name: "close-runway",
+        synopsis: "close-runway <id> <runway> <from> <to>",
+        summary: "Close one runway of airport <id> in a window: movements are metered to the other runways, and closing the last one closes the airport like a curfew",
+        usage: CLOSE_RUNWAY,
+        arguments: &[
+            ("<airport_id>", "airport the runway belongs to"),
+            (
+                "<runway_id>",
+                "runway from the airport's runways in the scenario",
+            ),
+            (
+                "<from> <to>",
+                "window of the closure, as times like DAY2 06:30 ending after it starts",
+            ),
+        ],
+        examples: &["close-runway WAW 11/29 06:00 10:00"],
+        related: &["capacity", "curfew"],
+    },
+    CommandHelp {
+        name: "curfew",
+        synopsis: "curfew <id> <m> <m> [--arrival-tolerance <m>] [--push]",
+        summary: "Inject a curfew from <m> to <m> minutes into airport <id>; from > to repeats nightly across midnight, late arrivals within the tolerance still land, --push delays departures inside the window until it reopens",
+        usage: CURFEW,
+        arguments: &[
+            ("<airport_id>", "airport to close"),
+            (
+                "<from> <to>",
+                "start and end of the closure, as times like DAY2 06:30 or 06:30+1; a start after the end, e.g. 23:30 05:30, closes nightly between those times of day",
+            ),
+            (
+                "--arrival-tolerance <minutes>",
+                "how late into the closure arrivals may still land, e.g. 15m",
+            ),
+            (
+                "--push",
+                "delay departures inside the closure until it reopens instead of unscheduling them",
+            ),
+        ],
+        examples: &[
+            "curfew KRK 600 720",
+            "curfew KRK 23:30 05:30 --arrival-tolerance 15m",
+        ],
+        related: &["explain", "recover", "atfm"],
+    },
+    CommandHelp {
+        name: "curfew",
+        synopsis: "curfew --preview <id> <m> <m> [--arrival-tolerance <m>]",
+        summary: "List flights and rotations a curfew would hit, without applying it",
+        usage: CURFEW_PREVIEW,
+        arguments: &[
+            ("<airport_id>", "airport to close"),
+            ("<from> <to>", "start and end of the closure, as for curfew"),
+            (
+                "--arrival-tolerance <minutes>",
+                "how late into the closure arrivals may still land, e.g. 15m",
+            ),
+        ],
+        examples: &["curfew --preview KRK 1410 330"],
+        related: &["candidates", "forecast"],
+    },
+    CommandHelp {
+        name: "atfm",
+        synopsis: "atfm <id> <m> <m> | <ids> --fixed <m> | --uniform <m> <m> [--seed <n>] | --rate <n>",
+        summary: "Delay departures from airport <id> between two minutes, or the listed flights, under one ATFM regulation with a fixed, uniform or hourly-rate delay",
+        usage: ATFM,
+        arguments: &[
+            (
+                "<airport_id> <from> <to>",
+                "departures from the airport in a window, as times like DAY2 06:30 ending after it starts",
+            ),
+            (
+                "<flight_id>[,<flight_id>...]",
+                "the listed flights, e.g. FL-101,FL-102/2",
+            ),
+            (
+                "--fixed <minutes>",
+                "the same delay for each flight, e.g. 45m or 1h",
+            ),
+            (
+                "--uniform <minutes> <minutes>",
+                "delays drawn between the two, repeatably for a --seed",
+            ),
+            (
+                "--rate <n>",
+                "departures let through per hour, the rest held for the next free slot",
+            ),
+        ],
+        examples: &[
+            "atfm WAW 600 720 --rate 10",
+            "atfm FL-101,FL-102 --uniform 10 40 --seed 7",
+        ],
+        related: &["delay-airport", "explain"],
+    },
+    CommandHelp {
+        name: "candidates",
+        synopsis: "candidates <id>",
+        summary: "Check every aircraft against flight <id>: location, busy, maintenance, curfew, fleet type, range and ETOPS",
+        usage: CANDIDATES,
+        arguments: &[FLIGHT],
+        examples: &["candidates FL-102"],
+        related: &["slack", "recover"],
+    },
+    CommandHelp {
+        name: "explain",
+        synopsis: "explain [full]",
+        summary: "Explain the most recent disruption (use 'full' for full causal trace with old and new times as -/+ lines)",
+        usage: EXPLAIN,
+        arguments: &[(
+            "full",
+            "list every delayed or unscheduled flight with its old and new times",
+        )],
+        examples: &["explain", "explain full"],
+        related: &["history", "report"],
+    },
+    CommandHelp {
+        name: "export",
+        synopsis: "export csv <w> <f>",
+        summary: "Export flights, stats or history as CSV into file <f>",
+        usage: EXPORT,
+        arguments: &[("<file>", "path to write, replaced if it exists")],
+        examples: &["export csv flights flights.csv"],
+        related: &["report"],
+    },
+    CommandHelp {
+        name: "export",
+        synopsis: "export ics <f> [--start <date>]",
+        summary: "Export flights and maintenance per aircraft as iCalendar events into file <f>, day 1 on <date>",
+        usage: EXPORT_ICS,
+        arguments: &[
+            ("<file>", "path to write, replaced if it exists"),
+            ("<YYYY-MM-DD>", "calendar date of DAY1"),
+        ],
+        examples: &["export ics timeline.ics --start 2024-07-01"],
+        related: &["report"],
+    },
+    CommandHelp {
+        name: "export",
+        synopsis: "export json flights <f>",
+        summary: "Export each flight's status, aircraft and times as JSON into file <f>, as compared by irrops test",
+        usage: EXPORT_JSON,
+        arguments: &[("<file>", "path to write, replaced if it exists")],
+        examples: &["export json flights flights.json"],
+        related: &["script"],
+    },
+    CommandHelp {
+        name: "export",
+        synopsis: "export parquet|ipc flights <f>",
+        summary: "Export flights with statuses and plan deltas as a Parquet or Arrow IPC file <f>; needs a build with the dataframe feature",
+        usage: EXPORT_FRAME,
+        arguments: &[("<file>", "path to write, replaced if it exists")],
+        examples: &[
+            "export parquet flights run.parquet",
+            "export ipc flights run.arrow",
+        ],
+        related: &["report"],
+    },
+    CommandHelp {
+        name: "feed",
+        synopsis: "feed <file|url> [--format <f>] [--every <n>s]",
+        summary: "Apply the delays seen in observed departures from a file or http(s):// feed, once or every <n> seconds until a line is typed",
+        usage: FEED,
+        arguments: &[
+            (
+                "<file|url>",
+                "JSON list of {\"flight\": ..., \"departure\": <minutes>}, from disk or http(s)://",
+            ),
+            (
+                "--format opensky",
+                "read an OpenSky Network flight list instead, matched by callsign and timed from DAY1 (set timefmt --start)",
+            ),
+            ("--every <secs>s", "poll again at this interval"),
+        ],
+        examples: &[
+            "feed observed.json",
+            "feed http://localhost:8080/departures --every 30s",
+            "feed https://opensky-network.org/api/flights/departure?airport=EPWA&begin=1717372800&end=1717459200 --format opensky --every 300s",
+        ],
+        related: &["delay", "webhook"],
+    },
+    CommandHelp {
+        name: "foreach",
+        synopsis: "foreach <filter> <cmd>",
+        summary: "Run <cmd> once per all/scheduled/delayed/unscheduled flight, with {flight} replaced by its id",
+        usage: FOREACH,
+        arguments: &[(
+            "<command>",
+            "any command, with {flight} standing for each flight",
+        )],
+        examples: &["foreach scheduled delay {flight} 15"],
+        related: &["script", "delay"],
+    },
+    CommandHelp {
+        name: "freeze",
+        synopsis: "freeze [<m>]",
+        summary: "Show or set the freeze horizon; recovery never touches flights departing within <m> minutes of the clock",
+        usage: FREEZE,
+        arguments: &[(
+            "<minutes>",
+            "length of the horizon from the clock, e.g. 120 or 2h",
+        )],
+        examples: &["freeze", "freeze 120"],
+        related: &["clock", "recover"],
+    },
+    CommandHelp {
+        name: "ground-occupancy",
+        synopsis: "ground-occupancy <id>",
+        summary: "Show aircraft on the ground at airport <id> against its stand count",
+        usage: GROUND_OCCUPANCY,
+        arguments: &[("<airport_id>", "airport to show")],
+        examples: &["ground-occupancy WAW"],
+        related: &["slack", "strandings"],
+    },
+    CommandHelp {
+        name: "history",
+        synopsis: "history [--by-severity]",
+        summary: "List past disruptions with their severity, optionally worst first",
+        usage: HISTORY,
+        arguments: &[],
+        examples: &["history", "history --by-severity"],
+        related: &["explain", "report"],
+    },
+    CommandHelp {
+        name: "impact",
+        synopsis: "impact [--delay <m>] [<n>]",
+        summary: "Rank the <n> most fragile flights by simulated delay impact",
+        usage: IMPACT,
+        arguments: &[
+            (
+                "--delay <minutes>",
+                "delay simulated on each flight, 60 by default, e.g. 30m",
+            ),
+            ("<top>", "number of flights to rank, 20 by default"),
+        ],
+        examples: &["impact", "impact --delay 30 10"],
+        related: &["slack", "forecast"],
+    },
+    CommandHelp {
+        name: "merge",
+        synopsis: "merge <file>",
+        summary: "Add another scenario's aircraft, airports and flights; fails on conflicting ids",
+        usage: MERGE,
+        arguments: &[("<file>", "scenario JSON, as given with --scenario")],
+        examples: &["merge data/charters.json"],
+        related: &["recover", "clone-day"],
+    },
+    CommandHelp {
+        name: "recover",
+        synopsis: "recover [--strategy <s>] [--objective <o>] [--time <t>] [--seed <n>]",
+        summary: "Re-run assignment to repair unscheduled flights (greedy, flow or anneal; first-fit, robust or balanced)",
+        usage: RECOVER,
+        arguments: &[
+            (
+                "--strategy",
+                "greedy (default), flow, anneal or a plugin strategy",
+            ),
+            (
+                "--objective",
+                "how greedy picks an aircraft: first-fit (default), robust or balanced",
+            ),
+            ("--time <secs>s", "time limit of anneal, e.g. 5s"),
+            ("--seed <n>", "seed of anneal, for repeatable runs"),
+        ],
+        examples: &["recover", "recover --strategy anneal --time 5s --seed 1"],
+        related: &["audit", "explain", "experiment", "freeze"],
+    },
+    CommandHelp {
+        name: "report",
+        synopsis: "report <file>",
+        summary: "Write a Markdown (.md) or HTML (.html) post-mortem of the session: scenario, disruptions, recoveries and final KPIs",
+        usage: REPORT,
+        arguments: &[(
+            "<file>",
+            "path ending in .md or .html, replaced if it exists",
+        )],
+        examples: &["report post-mortem.md"],
+        related: &["history", "export"],
+    },
+    CommandHelp {
+        name: "retime",
+        synopsis: "retime <id> <+/-m>",
+        summary: "Move flight <id> later (as a delay) or earlier, if the aircraft and airports allow it",
+        usage: RETIME,
+        arguments: &[
+            FLIGHT,
+            (
+                "<+/-minutes>",
+                "how far to move the flight, negative for earlier, e.g. -20 or +1h15",
+            ),
+        ],
+        examples: &["retime FL-102 -20", "retime FL-102 +1h15"],
+        related: &["delay", "candidates"],
+    },
+    CommandHelp {
+        name: "rollover",
+        synopsis: "rollover [<d>]",
+        summary: "Drop the first <d> completed days (default 1), rebase times to the next day and load flights the horizon now reaches",
+        usage: ROLLOVER,
+        arguments: &[("<days>", "number of days to drop")],
+        examples: &["rollover", "rollover 2"],
+        related: &["clock", "clone-day"],
+    },
+    CommandHelp {
+        name: "script",
+        synopsis: "script run <file>",
+        summary: "Run the commands in <file>, one per line; # starts a comment. A .rhai file runs as a Rhai script with clock(), cost(), flights(), flight(key), delay(key, minutes), recover(strategy) and repl(line)",
+        usage: SCRIPT,
+        arguments: &[("<file>", "text file of commands, or a .rhai script")],
+        examples: &[
+            "script run drills/snow.script",
+            "script run drills/worst_first.rhai",
+        ],
+        related: &["foreach", "at"],
+    },
+    CommandHelp {
+        name: "slack",
+        synopsis: "slack [aircraft]",
+        summary: "Show turn buffers and absorbable delay per rotation",
+        usage: SLACK,
+        arguments: &[("<aircraft_id>", "show the connections of one rotation")],
+        examples: &["slack", "slack ALPHA"],
+        related: &["impact", "forecast"],
+    },
+    CommandHelp {
+        name: "stats",
+        synopsis: "stats [by=<group>]",
+        summary: "Display summary statistics, optionally grouped by day, airport or aircraft",
+        usage: STATS,
+        arguments: &[("by=day|by=airport|by=aircraft", "one row per group")],
+        examples: &["stats", "stats by=airport"],
+        related: &["utilization", "history"],
+    },
+    CommandHelp {
+        name: "stats",
+        synopsis: "stats delays",
+        summary: "Show a histogram of delay minutes with P50/P90/P99",
+        usage: STATS,
+        arguments: &[],
+        examples: &["stats delays"],
+        related: &["impact"],
+    },
+    CommandHelp {
+        name: "stats",
+        synopsis: "stats banks",
+        summary: "Show planned and protected connections of every hub bank",
+        usage: STATS,
+        arguments: &[],
+        examples: &["stats banks"],
+        related: &["strandings"],
+    },
+    CommandHelp {
+        name: "experiment",
+        synopsis: "experiment add-aircraft <type> <base> <count>",
+        summary: "Rerun recovery on a copy with spare tails added and report flights saved per aircraft",
+        usage: EXPERIMENT,
+        arguments: &[
+            ("<type>", "fleet type of the spare tails"),
+            ("<base>", "airport the spare tails start at"),
+            ("<count>", "number of spare tails"),
+        ],
+        examples: &["experiment add-aircraft A320 WAW 2"],
+        related: &["recover"],
+    },
+    CommandHelp {
+        name: "simulate",
+        synopsis: "simulate <runs> [--probability <%>] [--delay <m> <m>] [--seed <n>] [--recover]",
+        summary: "Run random delays on copies of the schedule across threads and report delay and cancellation percentiles",
+        usage: SIMULATE,
+        arguments: &[
+            ("<runs>", "number of independent runs"),
+            (
+                "--probability <%>",
+                "chance of each operating flight being delayed, 10 by default",
+            ),
+            (
+                "--delay <m> <m>",
+                "range the delays are drawn from, 10 to 60 minutes by default",
+            ),
+            (
+                "--seed <n>",
+                "master seed the run seeds are derived from, for repeatable results",
+            ),
+            (
+                "--recover",
+                "rerun greedy recovery after the delays of each run",
+            ),
+        ],
+        examples: &[
+            "simulate 1000",
+            "simulate 500 --probability 20 --delay 15 2h --seed 7 --recover",
+        ],
+        related: &["experiment", "impact"],
+    },
+    CommandHelp {
+        name: "forecast",
+        synopsis: "forecast <aircraft>",
+        summary: "Predict where the aircraft's remaining rotation breaks if nothing is done, against maintenance, curfews and current delays",
+        usage: FORECAST,
+        arguments: &[("<aircraft_id>", "aircraft to follow")],
+        examples: &["forecast ALPHA"],
+        related: &["slack", "candidates"],
+    },
+    CommandHelp {
+        name: "strandings",
+        synopsis: "strandings",
+        summary: "List passengers without a same-day rebooking, per airport and day",
+        usage: STRANDINGS,
+        arguments: &[],
+        examples: &["strandings"],
+        related: &["stats"],
+    },
+    CommandHelp {
+        name: "utilization",
+        synopsis: "utilization",
+        summary: "Show block hours and cycles flown by each aircraft",
+        usage: UTILIZATION,
+        arguments: &[],
+        examples: &["utilization"],
+        related: &["stats", "recover"],
+    },
+    CommandHelp {
+        name: "webhook",
+        synopsis: "webhook [add|rm <url>]",
+        summary: "List, register or remove http(s):// endpoints notified of every disruption and recovery",
+        usage: WEBHOOK,
+        arguments: &[("<url>", "http:// or https:// endpoint")],
+        examples: &["webhook add http://localhost:9000/irrops"],
+        related: &["feed"],
+    },
+    CommandHelp {
+        name: "find",
+        synopsis: "find <text>",
+        summary: "Fuzzy-search flight numbers, routes and aircraft, ignoring case and punctuation",
+        usage: FIND,
+        arguments: &[(
+            "<text>",
+            "part of a name, the letters of one in order, or one with a typo",
+        )],
+        examples: &["find fl101", "find waw krk", "find ALHPA"],
+        related: &["ls", "explain"],
+    },
+    CommandHelp {
+        name: "watch",
+        synopsis: "watch <ls|stats ...>",
+        summary: "Clear the screen and redraw an ls or stats view whenever flights or the clock change, also while playing",
+        usage: WATCH,
+        arguments: &[(
+            "<ls|stats ...>",
+            "any form of ls or stats, with its arguments",
+        )],
+        examples: &["watch ls u", "watch stats by=airport"],
+        related: &["ls", "stats", "play"],
+    },
+    CommandHelp {
+        name: "watch",
+        synopsis: "watch off",
+        summary: "Stop redrawing the watched view",
+        usage: WATCH,
+        arguments: &[],
+        examples: &["watch off"],
+        related: &[],
+    },
+    CommandHelp {
+        name: "set",
+        synopsis: "set [timefmt <fmt>]",
+        summary: "Show the settings, or choose how times display in tables, reports and CSV exports",
+        usage: SET,
+        arguments: &[
+            (
+                "minutes|dayhhmm|iso",
+                "minutes since DAY1 00:00, DAY2 06:30 (the default) or 2024-01-02T06:30",
+            ),
+            (
+                "--start <YYYY-MM-DD>",
+                "date DAY1 falls on in ISO timestamps",
+            ),
+        ],
+        examples: &[
+            "set",
+            "set timefmt minutes",
+            "set timefmt iso --start 2024-06-03",
+        ],
+        related: &["ls", "export", "report"],
+    },
+    CommandHelp {
+        name: "set",
+        synopsis: "set style <style>",
+        summary: "Choose the borders of every table; settings carry over to the next session",
+        usage: SET,
+        arguments: &[(
+            "rounded|ascii|markdown|psql",
+            "table style, rounded by default",
+        )],
+        examples: &["set style markdown"],
+        related: &["ls", "stats"],
+    },
+    CommandHelp {
+        name: "help",
+        synopsis: "help [<command>] / ?",
+        summary: "Show this help menu, or the usage, arguments and examples of one command",
+        usage: HELP,
+        arguments: &[],
+        examples: &["help", "help curfew"],
+        related: &[],
+    },
+    CommandHelp {
+        name: "exit",
+        synopsis: "exit / quit",
+        summary: "Exit the simulator",
+        usage: EXIT,
+        arguments: &[],
+        examples: &[],
+        related: &[],
+    },
+];
+
+/// One line per form, for the list of commands.
+pub fn overview() -> Vec<String> {
+    COMMANDS
+        .iter()
+        .map(|c| format!("{:<19} - {}", c.synopsis, c.summary))
+        .collect()
+}
+
+/// Usage, arguments, examples and related commands of every form of `name`, or `None`
+/// for a name that is not a built-in command.
+pub fn detail(name: &str) -> Option<String> {
+    let name = match name {
+        "?" => "help",
+        "quit" => "exit",
+        name => name,
+    };
+    let forms = COMMANDS
+        .iter()
+        .filter(|c| c.name == name)
+        .collect::<Vec<_>>();
+    if forms.is_empty() {
+        return None;
+    }
+    let mut out = String::new();
+    let mut related: Vec<&str> = Vec::new();
+    for form in forms {
+        writeln!(out, "\nUsage: {}\n  {}", form.usage, form.summary).unwrap();
+        if !form.arguments.is_empty() {
+            writeln!(out, "\nArguments:").unwrap();
+            for (argument, description) in form.arguments {
+                writeln!(out, "  {}\n      {}", argument, description).unwrap();
+            }
+        }
+        if !form.examples.is_empty() {
+            writeln!(out, "\nExamples:").unwrap();
+            for example in form.examples {
+                writeln!(out, "  {}", example).unwrap();
+            }
+        }
+        related.extend(form.related.iter().filter(|r| **r != name));
+    }
+    related.sort();
+    related.dedup();
+    if !related.is_empty() {
+        writeln!(out, "\nSee also: {}", related.join(", ")).unwrap();
+    }
+    Some(out)
+}
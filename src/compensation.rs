@@ -0,0 +1,64 @@
+use crate::flight::{Flight, FlightStatus, UnscheduledReason};
+use serde::{Deserialize, Serialize};
+
+/// EU261-style compensation bands, in EUR per passenger.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompensationModel {
+    pub delay_threshold: u64,
+    pub short_haul_km: u64,
+    pub medium_haul_km: u64,
+    pub short_haul: u64,
+    pub medium_haul: u64,
+    pub long_haul: u64,
+}
+
+impl Default for CompensationModel {
+    fn default() -> Self {
+        CompensationModel {
+            delay_threshold: 180,
+            short_haul_km: 1500,
+            medium_haul_km: 3500,
+            short_haul: 250,
+            medium_haul: 400,
+            long_haul: 600,
+        }
+    }
+}
+
+impl CompensationModel {
+    /// Per-passenger amount for a flight; unknown distances fall into the short-haul band.
+    pub fn rate(&self, flight: &Flight) -> u64 {
+        match flight.distance {
+            Some(d) if d > self.medium_haul_km => self.long_haul,
+            Some(d) if d > self.short_haul_km => self.medium_haul,
+            _ => self.short_haul,
+        }
+    }
+
+    /// Exposure of a single flight in its current state: cancellations and delays
+    /// at or above the threshold pay the full band amount for every passenger.
+    /// Flights that were never assigned (`Waiting`) are not counted as cancellations.
+    pub fn flight_exposure(&self, flight: &Flight) -> u64 {
+        self.exposure_in(flight, &flight.status)
+    }
+
+    /// Exposure of a flight were it in `status`, such as the one it had before a change.
+    pub fn exposure_in(&self, flight: &Flight, status: &FlightStatus) -> u64 {
+        let eligible = match status {
+            FlightStatus::Unscheduled(UnscheduledReason::Waiting) => false,
+            FlightStatus::Unscheduled(_) => true,
+            FlightStatus::Delayed { minutes } => *minutes >= self.delay_threshold,
+            FlightStatus::Scheduled => false,
+        };
+        if eligible {
+            self.rate(flight) * flight.passengers as u64
+        } else {
+            0
+        }
+    }
+
+    pub fn exposure<'a>(&self, flights: impl IntoIterator<Item = &'a Flight>) -> u64 {
+        flights.into_iter().map(|f| self.flight_exposure(f)).sum()
+    }
+}
@@ -0,0 +1,127 @@
+use crate::alert::AlertRules;
+use crate::compensation::CompensationModel;
+use crate::cost::CostModel;
+use crate::severity::SeverityModel;
+use crate::time::TimeFormat;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableStyle {
+    #[default]
+    Rounded,
+    Ascii,
+    Markdown,
+    Psql,
+}
+
+impl std::str::FromStr for TableStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rounded" => Ok(TableStyle::Rounded),
+            "ascii" => Ok(TableStyle::Ascii),
+            "markdown" => Ok(TableStyle::Markdown),
+            "psql" => Ok(TableStyle::Psql),
+            _ => Err(format!(
+                "unknown table style `{}`, expected rounded, ascii, markdown or psql",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TableStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            TableStyle::Rounded => "rounded",
+            TableStyle::Ascii => "ascii",
+            TableStyle::Markdown => "markdown",
+            TableStyle::Psql => "psql",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Startup defaults from `config.toml`; command line flags take precedence.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub scenario: Option<PathBuf>,
+    pub log_level: Option<String>,
+    pub max_delay: Option<u64>,
+    pub table_style: TableStyle,
+    pub color: ColorMode,
+    pub time_format: TimeFormat,
+    /// `YYYY-MM-DD` that DAY1 falls on in ISO timestamps.
+    pub start_date: Option<String>,
+    pub cost: Option<CostModel>,
+    pub compensation: Option<CompensationModel>,
+    pub severity: Option<SeverityModel>,
+    pub alerts: AlertRules,
+    /// `http://` or `https://` endpoints notified of every disruption and recovery.
+    pub webhooks: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    /// Not TOML, or not a config; the error points at the offending line.
+    Invalid(toml::de::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "cannot read {}: {}", path.display(), e),
+            ConfigError::Invalid(e) => write!(f, "invalid config: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/irrops/config.toml`, falling back to `~/.config/irrops/config.toml`.
+    pub fn default_path() -> Option<PathBuf> {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .map(|dir| dir.join("irrops").join("config.toml"))
+    }
+
+    /// Reads `path`, or the default location when `None`; a missing default file simply
+    /// yields the defaults, while an explicitly given one has to exist.
+    pub fn load(path: Option<&Path>) -> Result<Config, ConfigError> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match Self::default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Config::default()),
+            },
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if !required && e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(ConfigError::Io(path, e)),
+        }
+    }
+
+    /// Parses the TOML text of a config file.
+    pub fn parse(text: &str) -> Result<Config, ConfigError> {
+        toml::from_str(text).map_err(ConfigError::Invalid)
+    }
+}
@@ -0,0 +1,67 @@
+use crate::compensation::CompensationModel;
+use crate::flight::Flight;
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use serde::{Deserialize, Serialize};
+
+/// Weights used by cost-based recovery strategies, in EUR.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CostModel {
+    pub cancellation: u64,
+    pub delay_minute: u64,
+    /// Rebooking a passenger who does not fit the aircraft a flight was swapped to.
+    pub spilled_passenger: u64,
+    /// Calling out one reserve crew.
+    pub reserve_callout: u64,
+    /// Each planned connection of a hub bank that no longer holds.
+    pub broken_connection: u64,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        CostModel {
+            cancellation: 10_000,
+            delay_minute: 50,
+            spilled_passenger: 250,
+            reserve_callout: 1_500,
+            broken_connection: 300,
+        }
+    }
+}
+
+impl CostModel {
+    pub fn delay_cost(
+        &self,
+        compensation: &CompensationModel,
+        flight: &Flight,
+        minutes: u64,
+    ) -> u64 {
+        let exposure = if minutes >= compensation.delay_threshold {
+            compensation.rate(flight) * flight.passengers as u64
+        } else {
+            0
+        };
+        minutes * self.delay_minute + exposure
+    }
+
+    pub fn cancellation_cost(&self, compensation: &CompensationModel, flight: &Flight) -> u64 {
+        self.cancellation + compensation.rate(flight) * flight.passengers as u64
+    }
+
+    /// Cost of the flights in their current state; every flight without an aircraft counts
+    /// as cancelled.
+    pub fn evaluate<'a>(
+        &self,
+        compensation: &CompensationModel,
+        flights: impl IntoIterator<Item = &'a Flight>,
+    ) -> u64 {
+        flights
+            .into_iter()
+            .map(|f| match f.status {
+                Scheduled => 0,
+                Delayed { minutes } => self.delay_cost(compensation, f, minutes),
+                Unscheduled(_) => self.cancellation_cost(compensation, f),
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,65 @@
+use crate::airport::AirportId;
+use crate::time::Time;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub type CrewId = Arc<str>;
+
+/// A crew flying the flights that name it in `crew_id`, as one duty.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Crew {
+    pub id: CrewId,
+    pub base_id: AirportId,
+    /// Longest duty allowed, from the first departure to the last arrival, in minutes.
+    pub max_duty: u64,
+    /// Shortest rest between two duties, in minutes.
+    #[serde(default = "default_min_rest")]
+    pub min_rest: u64,
+    /// Minutes added to the rest when it is taken in a hotel away from base, for the
+    /// transfer to and from the airport.
+    #[serde(default = "default_hotel_transfer")]
+    pub hotel_transfer: u64,
+}
+
+fn default_min_rest() -> u64 {
+    600
+}
+
+fn default_hotel_transfer() -> u64 {
+    60
+}
+
+impl Crew {
+    /// Rest the crew needs after a duty ending at `airport_id`.
+    pub fn required_rest(&self, airport_id: &AirportId) -> u64 {
+        if *airport_id == self.base_id {
+            self.min_rest
+        } else {
+            self.min_rest + self.hotel_transfer
+        }
+    }
+}
+
+/// Why a crew cannot start its next duty as planned.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RestBreach {
+    /// Less time off between duties than the crew needs where it rests.
+    Short { required: u64, available: u64 },
+    /// The next duty starts away from where the crew rested, e.g. the outstation hotel
+    /// it was left at by a disruption.
+    Positioning {
+        rested_at: AirportId,
+        origin: AirportId,
+    },
+}
+
+/// A standby crew that recovery may call out at its base while it is available.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ReserveCrew {
+    pub id: CrewId,
+    pub base_id: AirportId,
+    pub from: Time,
+    pub to: Time,
+    pub max_duty: u64,
+}
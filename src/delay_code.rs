@@ -0,0 +1,84 @@
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::{
+    AircraftMaintenance, AirportCurfew, BrokenChain, MaintenanceDue, MaxDelayExceeded,
+    MovementCapacity, OutOfPosition, OutsideOperatingHours, StandCapacity, TurnTimeViolation,
+};
+use crate::flight::{Flight, UnscheduledReason};
+use crate::schedule::schedule::{DisruptionReport, DisruptionType};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// IATA delay codes reported for each cause the engine can tell apart, configurable
+/// through `delay_codes` in the scenario file. A `reasons` map given there replaces the
+/// defaults as a whole.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DelayCodes {
+    pub reasons: HashMap<UnscheduledReason, u8>,
+    /// Delay propagated from an earlier leg of the same aircraft.
+    pub rotational: Option<u8>,
+    /// Delay injected into a flight directly, whose real cause the engine does not know.
+    pub injected: Option<u8>,
+    /// Aircraft not where a maintenance window needs it. Kept out of `reasons` because
+    /// that reason carries the airports involved.
+    pub out_of_position: Option<u8>,
+    /// Turn shorter than the minimum turn time, kept apart for the same reason.
+    pub turn_time: Option<u8>,
+}
+
+impl Default for DelayCodes {
+    fn default() -> Self {
+        DelayCodes {
+            reasons: HashMap::from([
+                (MaxDelayExceeded, 93),
+                (AirportCurfew, 87),
+                (OutsideOperatingHours, 89),
+                (AircraftMaintenance, 41),
+                (MaintenanceDue, 42),
+                (StandCapacity, 87),
+                (MovementCapacity, 89),
+                (BrokenChain, 93),
+            ]),
+            rotational: Some(93),
+            injected: None,
+            out_of_position: Some(93),
+            turn_time: Some(93),
+        }
+    }
+}
+
+impl DelayCodes {
+    pub fn reason(&self, reason: &UnscheduledReason) -> Option<u8> {
+        match reason {
+            OutOfPosition { .. } => self.out_of_position,
+            TurnTimeViolation { .. } => self.turn_time,
+            _ => self.reasons.get(reason).copied(),
+        }
+    }
+
+    /// Code for a flight in its current state. A delay counts as rotational when the
+    /// latest disruption touching the flight propagated into it rather than started there.
+    pub fn flight(&self, flight: &Flight, history: &[DisruptionReport]) -> Option<u8> {
+        match &flight.status {
+            Scheduled => None,
+            Unscheduled(reason) => self.reason(reason),
+            Delayed { .. } => history
+                .iter()
+                .rev()
+                .find_map(|report| match &report.kind {
+                    DisruptionType::Delay {
+                        flight: trigger, ..
+                    } if *trigger == flight.key() => Some(self.injected),
+                    _ if report.affected.contains(&flight.key()) => Some(self.rotational),
+                    _ => None,
+                })
+                .unwrap_or(self.injected),
+        }
+    }
+}
+
+/// Formats a code for display, empty when there is none.
+pub fn format_code(code: Option<u8>) -> String {
+    code.map_or(String::new(), |c| format!("{:02}", c))
+}
@@ -0,0 +1,50 @@
+use crate::airport::{AirportId, RunwayId};
+use crate::flight::FlightKey;
+use std::fmt;
+
+/// Why a disruption could not be applied to the schedule. A disruption that applies but
+/// changes nothing is not an error; its report is simply empty.
+#[derive(Debug, PartialEq)]
+pub enum IrropsError {
+    UnknownFlight(FlightKey),
+    UnknownAirport(AirportId),
+    /// A delay of zero minutes.
+    ZeroDelay(FlightKey),
+    /// A curfew starting and ending at the same time.
+    EmptyCurfew(AirportId),
+    /// A capacity reduction of no percent, or of all of it, which is a curfew.
+    InvalidReduction(u32),
+    /// A capacity reduction or runway closure not ending after it starts.
+    EmptyReduction(AirportId),
+    UnknownRunway(AirportId, RunwayId),
+}
+
+impl fmt::Display for IrropsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IrropsError::UnknownFlight(id) => write!(f, "unknown flight {}", id),
+            IrropsError::UnknownAirport(id) => write!(f, "unknown airport {}", id),
+            IrropsError::ZeroDelay(id) => write!(f, "nothing to delay {} by", id),
+            IrropsError::EmptyCurfew(id) => {
+                write!(f, "the curfew at {} starts and ends at the same time", id)
+            }
+            IrropsError::InvalidReduction(percent) => write!(
+                f,
+                "capacity can be reduced by 1 to 99 percent, not {}; use a curfew to close the airport",
+                percent
+            ),
+            IrropsError::EmptyReduction(id) => {
+                write!(
+                    f,
+                    "the capacity reduction at {} does not end after it starts",
+                    id
+                )
+            }
+            IrropsError::UnknownRunway(airport, runway) => {
+                write!(f, "unknown runway {} at {}", runway, airport)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IrropsError {}
@@ -0,0 +1,536 @@
+use crate::delay_code::format_code;
+use crate::flight::Flight;
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::metrics;
+use crate::schedule::atfm::AtfmTarget;
+use crate::schedule::schedule::{DisruptionType, Schedule};
+use crate::time::{self, Time, TimeFormat};
+#[cfg(feature = "dataframe")]
+use polars::prelude::*;
+use std::io;
+use std::io::Write;
+
+/// Quotes a CSV field when it contains a separator, quote or line break.
+fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// A time in a CSV cell: raw minutes since DAY1 00:00, which spreadsheets can do
+/// arithmetic on, unless ISO timestamps were chosen with `set timefmt iso`.
+fn csv_time(time: Time) -> String {
+    match time::display() {
+        (TimeFormat::Iso, day_one) => time.display_with(TimeFormat::Iso, day_one),
+        _ => time.0.to_string(),
+    }
+}
+
+fn row<W: Write>(w: &mut W, values: &[String]) -> io::Result<()> {
+    writeln!(
+        w,
+        "{}",
+        values
+            .iter()
+            .map(|v| field(v))
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
+
+pub fn flights_csv<W: Write>(w: &mut W, schedule: &Schedule) -> io::Result<()> {
+    row(
+        w,
+        &[
+            "id",
+            "day",
+            "aircraft_id",
+            "origin_id",
+            "destination_id",
+            "departure_time",
+            "arrival_time",
+            "status",
+            "reason",
+            "delay_minutes",
+            "delay_code",
+            "passengers",
+            "plan_delta",
+        ]
+        .map(String::from),
+    )?;
+    for f in &schedule.flights {
+        let (status, reason, delay) = match &f.status {
+            Scheduled => ("Scheduled", String::new(), 0),
+            Delayed { minutes } => ("Delayed", String::new(), *minutes),
+            Unscheduled(reason) => ("Unscheduled", format!("{:?}", reason), 0),
+        };
+        row(
+            w,
+            &[
+                f.id.to_string(),
+                f.day.to_string(),
+                f.aircraft_id.as_deref().unwrap_or_default().to_string(),
+                f.origin_id.to_string(),
+                f.destination_id.to_string(),
+                csv_time(f.departure_time),
+                csv_time(f.arrival_time),
+                status.to_string(),
+                reason,
+                delay.to_string(),
+                format_code(schedule.delay_codes.flight(f, &schedule.history)),
+                f.passengers.to_string(),
+                schedule
+                    .plan_delta(f)
+                    .map_or(String::new(), |d| d.to_string()),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Flights as equally long columns, one entry per flight, ready to hand over to a
+/// columnar analytics library without reshaping.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FlightColumns {
+    pub id: Vec<String>,
+    pub day: Vec<u64>,
+    pub aircraft_id: Vec<Option<String>>,
+    pub origin_id: Vec<String>,
+    pub destination_id: Vec<String>,
+    pub departure_time: Vec<u64>,
+    pub arrival_time: Vec<u64>,
+    pub status: Vec<&'static str>,
+    pub reason: Vec<Option<String>>,
+    pub delay_minutes: Vec<u64>,
+    pub delay_code: Vec<Option<u8>>,
+    pub passengers: Vec<u32>,
+    /// Minutes from the departure the flight entered the schedule with.
+    pub plan_delta: Vec<Option<i64>>,
+}
+
+pub fn flight_columns(schedule: &Schedule) -> FlightColumns {
+    let mut columns = FlightColumns::default();
+    for f in &schedule.flights {
+        let (status, reason, delay) = match &f.status {
+            Scheduled => ("Scheduled", None, 0),
+            Delayed { minutes } => ("Delayed", None, *minutes),
+            Unscheduled(reason) => ("Unscheduled", Some(format!("{:?}", reason)), 0),
+        };
+        columns.id.push(f.id.to_string());
+        columns.day.push(f.day);
+        columns
+            .aircraft_id
+            .push(f.aircraft_id.as_ref().map(|id| id.to_string()));
+        columns.origin_id.push(f.origin_id.to_string());
+        columns.destination_id.push(f.destination_id.to_string());
+        columns.departure_time.push(f.departure_time.0);
+        columns.arrival_time.push(f.arrival_time.0);
+        columns.status.push(status);
+        columns.reason.push(reason);
+        columns.delay_minutes.push(delay);
+        columns
+            .delay_code
+            .push(schedule.delay_codes.flight(f, &schedule.history));
+        columns.passengers.push(f.passengers);
+        columns.plan_delta.push(schedule.plan_delta(f));
+    }
+    columns
+}
+
+#[cfg(feature = "dataframe")]
+impl Schedule {
+    /// Flights as a polars DataFrame with the columns of `flight_columns`.
+    pub fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        let c = flight_columns(self);
+        df!(
+            "id" => c.id,
+            "day" => c.day,
+            "aircraft_id" => c.aircraft_id,
+            "origin_id" => c.origin_id,
+            "destination_id" => c.destination_id,
+            "departure_time" => c.departure_time,
+            "arrival_time" => c.arrival_time,
+            "status" => c.status,
+            "reason" => c.reason,
+            "delay_minutes" => c.delay_minutes,
+            "delay_code" => c.delay_code.into_iter().map(|code| code.map(u32::from)).collect::<Vec<_>>(),
+            "passengers" => c.passengers,
+            "plan_delta" => c.plan_delta,
+        )
+    }
+}
+
+/// Writes `Schedule::to_dataframe` as Parquet.
+#[cfg(feature = "dataframe")]
+pub fn flights_parquet<W: Write>(w: W, schedule: &Schedule) -> PolarsResult<()> {
+    ParquetWriter::new(w).finish(&mut schedule.to_dataframe()?)?;
+    Ok(())
+}
+
+/// Writes `Schedule::to_dataframe` as an Arrow IPC file.
+#[cfg(feature = "dataframe")]
+pub fn flights_ipc<W: Write>(w: W, schedule: &Schedule) -> PolarsResult<()> {
+    IpcWriter::new(w).finish(&mut schedule.to_dataframe()?)
+}
+
+pub fn stats_csv<W: Write>(w: &mut W, flights: &[Flight]) -> io::Result<()> {
+    let summary = metrics::summarize(flights);
+    row(w, &["metric", "count", "pct"].map(String::from))?;
+    for (label, count) in [
+        ("scheduled", summary.scheduled),
+        ("delayed", summary.delayed),
+        ("unscheduled_waiting", summary.waiting),
+        ("unscheduled_max_delay_exceeded", summary.max_delay_exceeded),
+        ("unscheduled_airport_curfew", summary.airport_curfew),
+        (
+            "unscheduled_outside_operating_hours",
+            summary.outside_operating_hours,
+        ),
+        (
+            "unscheduled_aircraft_maintenance",
+            summary.aircraft_maintenance,
+        ),
+        ("unscheduled_out_of_position", summary.out_of_position),
+        (
+            "unscheduled_turn_time_violation",
+            summary.turn_time_violation,
+        ),
+        ("unscheduled_maintenance_due", summary.maintenance_due),
+        ("unscheduled_stand_capacity", summary.stand_capacity),
+        ("unscheduled_movement_capacity", summary.movement_capacity),
+        (
+            "unscheduled_constraint_violated",
+            summary.constraint_violated,
+        ),
+        ("unscheduled_broken_chain", summary.broken_chain),
+        ("total", summary.total),
+    ] {
+        row(
+            w,
+            &[
+                label.to_string(),
+                count.to_string(),
+                format!("{:.1}", summary.pct(count)),
+            ],
+        )?;
+    }
+    row(
+        w,
+        &[
+            "delay_minutes".to_string(),
+            summary.delay_minutes.to_string(),
+            String::new(),
+        ],
+    )
+}
+
+pub fn history_csv<W: Write>(w: &mut W, schedule: &Schedule) -> io::Result<()> {
+    row(
+        w,
+        &[
+            "seq",
+            "kind",
+            "target",
+            "from",
+            "to",
+            "delay_by",
+            "delayed",
+            "unscheduled",
+            "first_break",
+            "first_break_reason",
+            "first_break_code",
+            "compensation",
+            "severity",
+        ]
+        .map(String::from),
+    )?;
+    for (i, report) in schedule.history.iter().enumerate() {
+        let (kind, target, from, to, delay_by) = match &report.kind {
+            DisruptionType::Delay { flight, delay_by } => (
+                "delay",
+                flight.to_string(),
+                String::new(),
+                String::new(),
+                delay_by.to_string(),
+            ),
+            DisruptionType::Curfew {
+                airport, from, to, ..
+            } => (
+                "curfew",
+                airport.to_string(),
+                csv_time(*from),
+                csv_time(*to),
+                String::new(),
+            ),
+            DisruptionType::Atfm {
+                target: AtfmTarget::Airport { airport, from, to },
+                delays,
+            } => (
+                "atfm",
+                airport.to_string(),
+                csv_time(*from),
+                csv_time(*to),
+                delays.to_string(),
+            ),
+            DisruptionType::Atfm { target, delays } => (
+                "atfm",
+                target.to_string(),
+                String::new(),
+                String::new(),
+                delays.to_string(),
+            ),
+            DisruptionType::AirportDelay {
+                airport,
+                delay_by,
+                from,
+                to,
+            } => (
+                "delay-airport",
+                airport.to_string(),
+                csv_time(*from),
+                to.map_or(String::new(), csv_time),
+                delay_by.to_string(),
+            ),
+            DisruptionType::CapacityReduction {
+                airport,
+                runway: None,
+                from,
+                to,
+                percent,
+            } => (
+                "capacity",
+                airport.to_string(),
+                csv_time(*from),
+                csv_time(*to),
+                format!("-{}%", percent),
+            ),
+            DisruptionType::CapacityReduction {
+                airport,
+                runway: Some(runway),
+                from,
+                to,
+                percent,
+            } => (
+                "close-runway",
+                airport.to_string(),
+                csv_time(*from),
+                csv_time(*to),
+                format!("{} -{}%", runway, percent),
+            ),
+        };
+        let (first_break, first_break_reason, first_break_code) = match &report.first_break {
+            Some((id, reason)) => (
+                id.to_string(),
+                format!("{:?}", reason),
+                format_code(schedule.delay_codes.reason(reason)),
+            ),
+            None => (String::new(), String::new(), String::new()),
+        };
+        row(
+            w,
+            &[
+                (i + 1).to_string(),
+                kind.to_string(),
+                target,
+                from,
+                to,
+                delay_by,
+                report.affected.len().to_string(),
+                report.unscheduled.len().to_string(),
+                first_break,
+                first_break_reason,
+                first_break_code,
+                report.compensation.to_string(),
+                report.severity.to_string(),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Calendar date that day 1 of the scenario falls on in an iCalendar export.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Date {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Default for Date {
+    /// A Monday, like day 1 of every scenario.
+    fn default() -> Self {
+        Date {
+            year: 2024,
+            month: 1,
+            day: 1,
+        }
+    }
+}
+
+impl std::str::FromStr for Date {
+    type Err = String;
+
+    /// Parses `YYYY-MM-DD`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid date `{}`, expected YYYY-MM-DD", s);
+        let mut parts = s.splitn(3, '-');
+        let mut next = || parts.next().and_then(|p| p.parse::<i64>().ok());
+        let (Some(year), Some(month @ 1..=12), Some(day @ 1..=31)) = (next(), next(), next())
+        else {
+            return Err(invalid());
+        };
+        let date = Date {
+            year,
+            month: month as u32,
+            day: day as u32,
+        };
+        // reject days past the end of the month, e.g. 2024-02-30
+        match Date::from_days(date.days()) == date {
+            true => Ok(date),
+            false => Err(invalid()),
+        }
+    }
+}
+
+impl std::fmt::Display for Date {
+    /// Writes `YYYY-MM-DD`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Date {
+    /// Days since 1970-01-01 in the proleptic Gregorian calendar.
+    pub(crate) fn days(&self) -> i64 {
+        let year = if self.month <= 2 {
+            self.year - 1
+        } else {
+            self.year
+        };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = (self.month as i64 + 9) % 12;
+        let day_of_year = (153 * month + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    pub(crate) fn from_days(days: i64) -> Date {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Date { year, month, day }
+    }
+
+    /// Local date-time of a scenario time as iCalendar `YYYYMMDDTHHMMSS`, with day 1 on
+    /// this date.
+    fn stamp(&self, time: Time) -> String {
+        let date = Date::from_days(self.days() + (time.0 / 1440) as i64);
+        let minutes = time.0 % 1440;
+        format!(
+            "{:04}{:02}{:02}T{:02}{:02}00",
+            date.year,
+            date.month,
+            date.day,
+            minutes / 60,
+            minutes % 60
+        )
+    }
+}
+
+/// Escapes iCalendar TEXT values.
+fn ics_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn ics_event<W: Write>(w: &mut W, properties: &[(&str, String)]) -> io::Result<()> {
+    write!(w, "BEGIN:VEVENT\r\n")?;
+    for (name, value) in properties {
+        write!(w, "{}:{}\r\n", name, value)?;
+    }
+    write!(w, "END:VEVENT\r\n")
+}
+
+/// Flights at their current times and aircraft maintenance windows as an iCalendar
+/// timeline, grouped per aircraft: events are ordered by aircraft and carry it as their
+/// category, with flights no aircraft flies under `UNASSIGNED`. Times are floating local
+/// times with day 1 on `start`.
+pub fn ics<W: Write>(w: &mut W, schedule: &Schedule, start: Date) -> io::Result<()> {
+    let stamp = format!("{}Z", start.stamp(Time(0)));
+    write!(
+        w,
+        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//irrops//timeline//EN\r\n"
+    )?;
+
+    let mut aircraft = schedule.aircraft.values().collect::<Vec<_>>();
+    aircraft.sort_by(|a, b| a.id.cmp(&b.id));
+    let rotations = schedule.rotations();
+    for ac in aircraft {
+        for (i, window) in ac.disruptions.iter().enumerate() {
+            let mut properties = vec![
+                ("UID", format!("{}-maintenance-{}@irrops", ac.id, i + 1)),
+                ("DTSTAMP", stamp.clone()),
+                ("DTSTART", start.stamp(window.from)),
+                ("DTEND", start.stamp(window.to)),
+                ("SUMMARY", ics_text(&format!("{} maintenance", ac.id))),
+                ("CATEGORIES", ics_text(&ac.id)),
+            ];
+            if let Some(location) = &window.location_id {
+                properties.push(("LOCATION", ics_text(location)));
+            }
+            ics_event(w, &properties)?;
+        }
+        for f in rotations.get(&ac.id).into_iter().flatten() {
+            flight_event(w, f, &ac.id, &stamp, start)?;
+        }
+    }
+    for f in schedule.flights.iter().filter(|f| f.aircraft_id.is_none()) {
+        flight_event(w, f, "UNASSIGNED", &stamp, start)?;
+    }
+
+    write!(w, "END:VCALENDAR\r\n")
+}
+
+fn flight_event<W: Write>(
+    w: &mut W,
+    f: &Flight,
+    category: &str,
+    stamp: &str,
+    start: Date,
+) -> io::Result<()> {
+    let (status, description) = match &f.status {
+        Scheduled => ("CONFIRMED", "Scheduled".to_string()),
+        Delayed { minutes } => ("CONFIRMED", format!("Delayed +{} min", minutes)),
+        Unscheduled(reason) => ("CANCELLED", format!("Unscheduled ({:?})", reason)),
+    };
+    ics_event(
+        w,
+        &[
+            ("UID", format!("{}-{}@irrops", f.id, f.day)),
+            ("DTSTAMP", stamp.to_string()),
+            ("DTSTART", start.stamp(f.departure_time)),
+            ("DTEND", start.stamp(f.arrival_time)),
+            (
+                "SUMMARY",
+                ics_text(&format!("{} {}-{}", f.key(), f.origin_id, f.destination_id)),
+            ),
+            ("LOCATION", ics_text(&f.origin_id)),
+            ("DESCRIPTION", ics_text(&description)),
+            ("CATEGORIES", ics_text(category)),
+            ("STATUS", status.to_string()),
+        ],
+    )
+}
@@ -0,0 +1,164 @@
+use crate::export::Date;
+use crate::flight::FlightKey;
+use crate::flight::FlightStatus::Unscheduled;
+use crate::http::{Url, UrlError};
+use crate::schedule::schedule::{FlightLookupError, Schedule};
+use crate::time::Time;
+use serde::Deserialize;
+use std::{fmt, fs, io};
+
+/// A real departure seen by a flight-status source, such as an ADS-B take-off or an
+/// airline's estimated off-block time.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Observation {
+    /// Flight number or callsign, optionally with the operating day as `FL-101/2`.
+    pub flight: String,
+    pub departure: Time,
+}
+
+#[derive(Debug)]
+pub enum FeedError {
+    Io(io::Error),
+    Url(UrlError),
+    Status(u16),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for FeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeedError::Io(e) => write!(f, "{}", e),
+            FeedError::Url(e) => write!(f, "{}", e),
+            FeedError::Status(status) => write!(f, "feed answered {}", status),
+            FeedError::Json(e) => write!(f, "invalid observations: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FeedError {}
+
+impl From<io::Error> for FeedError {
+    fn from(e: io::Error) -> Self {
+        FeedError::Io(e)
+    }
+}
+
+/// What a feed serves.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum FeedFormat {
+    /// A JSON array of `Observation`s.
+    #[default]
+    Observations,
+    /// Flights from the OpenSky Network REST API, e.g. `/api/flights/departure`: each
+    /// one is matched by callsign and departs when the aircraft was first seen.
+    OpenSky,
+}
+
+/// A flight as listed by the OpenSky Network; other fields are ignored.
+#[derive(Deserialize)]
+struct OpenSkyFlight {
+    callsign: Option<String>,
+    /// Unix time in seconds.
+    #[serde(rename = "firstSeen")]
+    first_seen: i64,
+}
+
+/// Observations in an OpenSky flight list, with DAY1 00:00 UTC falling on `day_one`.
+/// Flights without a callsign or seen before DAY1 are left out.
+pub fn opensky_observations(
+    json: &str,
+    day_one: Date,
+) -> Result<Vec<Observation>, serde_json::Error> {
+    let epoch = day_one.days() * 86_400;
+    Ok(serde_json::from_str::<Vec<OpenSkyFlight>>(json)?
+        .into_iter()
+        .filter_map(|f| {
+            let callsign = f.callsign?.trim().to_string();
+            let minutes = u64::try_from(f.first_seen - epoch).ok()? / 60;
+            (!callsign.is_empty()).then_some(Observation {
+                flight: callsign,
+                departure: Time(minutes),
+            })
+        })
+        .collect())
+}
+
+/// Fetches the current observations, a JSON array, from an `http(s)://` endpoint or a file.
+pub fn poll(source: &str) -> Result<Vec<Observation>, FeedError> {
+    poll_as(source, FeedFormat::Observations, Date::default())
+}
+
+/// Fetches the current observations from a feed serving `format`, with DAY1 on `day_one`
+/// for feeds timed in calendar time.
+pub fn poll_as(
+    source: &str,
+    format: FeedFormat,
+    day_one: Date,
+) -> Result<Vec<Observation>, FeedError> {
+    let body = if source.contains("://") {
+        let url = Url::parse(source).map_err(FeedError::Url)?;
+        match url.get()? {
+            (200, body) => body,
+            (status, _) => return Err(FeedError::Status(status)),
+        }
+    } else {
+        fs::read_to_string(source)?
+    };
+    match format {
+        FeedFormat::Observations => serde_json::from_str(&body),
+        FeedFormat::OpenSky => opensky_observations(&body, day_one),
+    }
+    .map_err(FeedError::Json)
+}
+
+/// What one round of observations did to the schedule.
+#[derive(Debug, Default, PartialEq)]
+pub struct Ingested {
+    /// Flights delayed, with the minutes added to each.
+    pub delayed: Vec<(FlightKey, u64)>,
+    /// Observations no scenario flight could be matched to.
+    pub unmatched: Vec<String>,
+}
+
+impl Schedule {
+    /// Shadows a real operation: every observed departure later than the planned one is
+    /// applied as a delay of the difference. Departures on time or early, and flights
+    /// already unscheduled, leave the schedule as it is, so polling the same source
+    /// again is harmless.
+    pub fn ingest(&mut self, observations: &[Observation]) -> Ingested {
+        let mut ingested = Ingested::default();
+        for observation in observations {
+            let Some(key) = self.match_observation(observation) else {
+                ingested.unmatched.push(observation.flight.clone());
+                continue;
+            };
+            let flight = self.flight(&key).unwrap();
+            if matches!(flight.status, Unscheduled(_))
+                || observation.departure <= flight.departure_time
+            {
+                continue;
+            }
+            let delay = observation.departure.0 - flight.departure_time.0;
+            if self.apply_delay(key.clone(), delay).is_ok() {
+                ingested.delayed.push((key, delay));
+            }
+        }
+        ingested
+    }
+
+    /// A number flown on several days is matched to the one planned to depart closest to
+    /// the observed time, which keeps a flight delayed past midnight on its own day.
+    fn match_observation(&self, observation: &Observation) -> Option<FlightKey> {
+        match self.find_flight(observation.flight.trim()) {
+            Ok(key) => Some(key),
+            Err(FlightLookupError::Ambiguous(number, days)) => days
+                .into_iter()
+                .map(|day| FlightKey::new(&number, day))
+                .min_by_key(|key| {
+                    let departure = self.flight(key).unwrap().departure_time.0;
+                    departure.abs_diff(observation.departure.0)
+                }),
+            Err(FlightLookupError::Unknown(_)) => None,
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use crate::flight::Flight;
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use std::cmp::{Ordering, Reverse};
+use std::fmt;
+
+/// Flight attribute a selection condition looks at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Field {
+    /// `ORIGIN-DESTINATION`, e.g. `KRK-WAW`.
+    Route,
+    Origin,
+    Destination,
+    Aircraft,
+    /// `scheduled`, `delayed` or `unscheduled`, or their first letter.
+    Status,
+    Day,
+    /// Minutes of delay; 0 for flights that are not delayed.
+    Delay,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Field> {
+        match name {
+            "route" => Some(Field::Route),
+            "origin" => Some(Field::Origin),
+            "destination" | "dest" => Some(Field::Destination),
+            "aircraft" => Some(Field::Aircraft),
+            "status" => Some(Field::Status),
+            "day" => Some(Field::Day),
+            "delay" => Some(Field::Delay),
+            _ => None,
+        }
+    }
+
+    fn is_numeric(self) -> bool {
+        matches!(self, Field::Day | Field::Delay)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    const SYMBOLS: [(&'static str, Op); 6] = [
+        ("!=", Op::Ne),
+        ("<=", Op::Le),
+        (">=", Op::Ge),
+        ("=", Op::Eq),
+        ("<", Op::Lt),
+        (">", Op::Gt),
+    ];
+
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Op::Eq => ordering == Ordering::Equal,
+            Op::Ne => ordering != Ordering::Equal,
+            Op::Lt => ordering == Ordering::Less,
+            Op::Le => ordering != Ordering::Greater,
+            Op::Gt => ordering == Ordering::Greater,
+            Op::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Text(String),
+    Number(u64),
+}
+
+/// One `<field><op><value>` term, e.g. `delay>60`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Condition {
+    pub field: Field,
+    pub op: Op,
+    value: Value,
+}
+
+impl Condition {
+    fn parse(term: &str) -> Result<Condition, FilterError> {
+        let (at, symbol, op) = Op::SYMBOLS
+            .iter()
+            .filter_map(|(symbol, op)| term.find(symbol).map(|at| (at, *symbol, *op)))
+            // the first operator in the term, preferring `<=` over `<` at the same place
+            .min_by_key(|(at, symbol, _)| (*at, Reverse(symbol.len())))
+            .ok_or_else(|| FilterError::Condition(term.to_string()))?;
+        let (name, value) = (&term[..at], &term[at + symbol.len()..]);
+        let field =
+            Field::parse(name).ok_or_else(|| FilterError::UnknownField(name.to_string()))?;
+        let invalid = || FilterError::InvalidValue(term.to_string());
+        let value = if field.is_numeric() {
+            Value::Number(value.parse().map_err(|_| invalid())?)
+        } else if !matches!(op, Op::Eq | Op::Ne) {
+            return Err(invalid());
+        } else {
+            match (field, value) {
+                (Field::Route, route) if !route.contains('-') => return Err(invalid()),
+                (Field::Status, "s" | "scheduled") => Value::Text("scheduled".to_string()),
+                (Field::Status, "d" | "delayed") => Value::Text("delayed".to_string()),
+                (Field::Status, "u" | "unscheduled") => Value::Text("unscheduled".to_string()),
+                (Field::Status, _) => return Err(invalid()),
+                (_, "") => return Err(invalid()),
+                (_, text) => Value::Text(text.to_string()),
+            }
+        };
+        Ok(Condition { field, op, value })
+    }
+
+    pub fn matches(&self, flight: &Flight) -> bool {
+        let actual = match self.field {
+            Field::Route => Value::Text(format!("{}-{}", flight.origin_id, flight.destination_id)),
+            Field::Origin => Value::Text(flight.origin_id.to_string()),
+            Field::Destination => Value::Text(flight.destination_id.to_string()),
+            Field::Aircraft => Value::Text(
+                flight
+                    .aircraft_id
+                    .as_ref()
+                    .map_or(String::new(), |id| id.to_string()),
+            ),
+            Field::Status => Value::Text(
+                match flight.status {
+                    Scheduled => "scheduled",
+                    Delayed { .. } => "delayed",
+                    Unscheduled(_) => "unscheduled",
+                }
+                .to_string(),
+            ),
+            Field::Day => Value::Number(flight.day),
+            Field::Delay => Value::Number(match flight.status {
+                Delayed { minutes } => minutes,
+                _ => 0,
+            }),
+        };
+        match (&actual, &self.value) {
+            (Value::Number(actual), Value::Number(wanted)) => self.op.holds(actual.cmp(wanted)),
+            (Value::Text(actual), Value::Text(wanted)) => {
+                self.op.holds(match actual.eq_ignore_ascii_case(wanted) {
+                    true => Ordering::Equal,
+                    false => Ordering::Less,
+                })
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FilterError {
+    Empty,
+    /// A term without a comparison operator.
+    Condition(String),
+    UnknownField(String),
+    /// A value the field cannot take, or an ordering on a text field.
+    InvalidValue(String),
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterError::Empty => write!(f, "no conditions after where"),
+            FilterError::Condition(term) => {
+                write!(f, "{} is not a <field><op><value> condition", term)
+            }
+            FilterError::UnknownField(name) => write!(
+                f,
+                "unknown field {}; use route, origin, destination, aircraft, status, day or delay",
+                name
+            ),
+            FilterError::InvalidValue(term) => write!(f, "invalid condition {}", term),
+        }
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+/// Flights selected by a `where` expression: conditions joined by `and`, all of which
+/// must hold, e.g. `route=KRK-WAW and day=2` or `delay>60`. Spaces around operators are
+/// allowed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlightFilter {
+    pub conditions: Vec<Condition>,
+}
+
+impl FlightFilter {
+    pub fn parse(expression: &str) -> Result<FlightFilter, FilterError> {
+        let mut terms = vec![String::new()];
+        for word in expression.split_whitespace() {
+            match word {
+                "and" | "AND" => terms.push(String::new()),
+                _ => terms.last_mut().unwrap().push_str(word),
+            }
+        }
+        if terms.iter().all(|t| t.is_empty()) {
+            return Err(FilterError::Empty);
+        }
+        let conditions = terms
+            .iter()
+            .map(|term| Condition::parse(term))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(FlightFilter { conditions })
+    }
+
+    pub fn matches(&self, flight: &Flight) -> bool {
+        self.conditions.iter().all(|c| c.matches(flight))
+    }
+}
@@ -1,24 +1,68 @@
 use crate::aircraft::AircraftId;
 use crate::airport::AirportId;
+use crate::crew::CrewId;
 use crate::time::Time;
 use colored::*;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::sync::Arc;
 use tabled::Tabled;
 
+/// Flight number, repeated on every day the flight operates.
 pub type FlightId = Arc<str>;
 
-#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// A flight's identity: its number together with the operating day of its scheduled
+/// departure, written `FL-101/2`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FlightKey {
+    pub number: FlightId,
+    pub day: u64,
+}
+
+impl FlightKey {
+    pub fn new(number: &str, day: u64) -> FlightKey {
+        FlightKey {
+            number: number.into(),
+            day,
+        }
+    }
+}
+
+impl fmt::Display for FlightKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.number, self.day)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub enum UnscheduledReason {
     Waiting,
     MaxDelayExceeded,
     AirportCurfew,
+    OutsideOperatingHours,
     AircraftMaintenance,
+    /// The aircraft sits at `actual` when a maintenance window needs it at `expected`.
+    OutOfPosition {
+        expected: AirportId,
+        actual: AirportId,
+    },
+    /// The aircraft lands `available` minutes before departure, short of the `required`
+    /// minimum turn time.
+    TurnTimeViolation {
+        required: u64,
+        available: u64,
+    },
+    MaintenanceDue,
+    StandCapacity,
+    /// The airport is metered below its usual movement rate and has no room left for
+    /// the movement.
+    MovementCapacity,
+    ConstraintViolated,
     BrokenChain,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Tabled)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema, Tabled)]
 pub enum FlightStatus {
     Unscheduled(UnscheduledReason),
     Scheduled,
@@ -42,9 +86,13 @@ impl fmt::Display for FlightStatus {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Tabled)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, Tabled)]
 pub struct Flight {
     pub id: FlightId,
+    /// Operating day of the scheduled departure; derived from `departure_time` when left
+    /// out, and kept when the flight is later delayed or retimed.
+    #[serde(default)]
+    pub day: u64,
     #[tabled(display = "display_option")]
     pub aircraft_id: Option<AircraftId>,
     pub origin_id: AirportId,
@@ -52,6 +100,154 @@ pub struct Flight {
     pub departure_time: Time,
     pub arrival_time: Time,
     pub status: FlightStatus,
+    #[serde(default)]
+    #[tabled(skip)]
+    pub passengers: u32,
+    #[serde(default)]
+    #[tabled(skip)]
+    pub distance: Option<u64>,
+    /// An extended overwater sector that only ETOPS-certified aircraft may fly.
+    #[serde(default)]
+    #[tabled(skip)]
+    pub etops: bool,
+    /// Waived from curfews and operating hours, e.g. medevac or government flights, so it
+    /// may operate while an airport at either end is closed.
+    #[serde(default)]
+    #[tabled(skip)]
+    pub curfew_exempt: bool,
+    /// Crew operating the flight, checked against its duty limit.
+    #[serde(default)]
+    #[tabled(skip)]
+    pub crew_id: Option<CrewId>,
+    /// Weight of the flight against others when a curfew has to pick what to cancel,
+    /// multiplied by its passengers.
+    #[serde(default = "default_priority")]
+    #[tabled(skip)]
+    pub priority: u32,
+}
+
+fn default_priority() -> u32 {
+    1
+}
+
+impl Flight {
+    /// An unassigned flight waiting for an aircraft, operating on the day it departs, with
+    /// no passengers and the default priority.
+    pub fn new(
+        id: &str,
+        origin_id: &str,
+        destination_id: &str,
+        departure_time: Time,
+        arrival_time: Time,
+    ) -> Flight {
+        Flight {
+            id: Arc::from(id),
+            day: departure_time.day(),
+            aircraft_id: None,
+            origin_id: Arc::from(origin_id),
+            destination_id: Arc::from(destination_id),
+            departure_time,
+            arrival_time,
+            status: FlightStatus::Unscheduled(UnscheduledReason::Waiting),
+            passengers: 0,
+            distance: None,
+            etops: false,
+            curfew_exempt: false,
+            crew_id: None,
+            priority: default_priority(),
+        }
+    }
+
+    /// The flight scheduled on `aircraft_id`.
+    pub fn assigned(mut self, aircraft_id: &str) -> Flight {
+        self.aircraft_id = Some(Arc::from(aircraft_id));
+        self.status = FlightStatus::Scheduled;
+        self
+    }
+
+    /// What cancelling the flight costs when a curfew selects by priority.
+    pub fn weight(&self) -> u64 {
+        self.priority as u64 * self.passengers.max(1) as u64
+    }
+
+    pub fn key(&self) -> FlightKey {
+        FlightKey {
+            number: self.id.clone(),
+            day: self.day,
+        }
+    }
+
+    /// Departure and arrival before any delay.
+    pub fn scheduled_times(&self) -> (Time, Time) {
+        let minutes = match self.status {
+            FlightStatus::Delayed { minutes } => minutes,
+            _ => 0,
+        };
+        (
+            Time(self.departure_time.0.saturating_sub(minutes)),
+            Time(self.arrival_time.0.saturating_sub(minutes)),
+        )
+    }
+}
+
+/// Weekdays a flight operates on between two operating days, both inclusive. Day 1 of a
+/// scenario is a Monday.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Frequency {
+    /// Weekday digits from 1 (Monday) to 7 (Sunday), with dots as placeholders, e.g. `"1.3.5.."`.
+    pub days_of_week: String,
+    pub from_day: u64,
+    pub to_day: u64,
+}
+
+impl Frequency {
+    /// Most days one frequency may span: a leap year.
+    pub const MAX_DAYS: u64 = 366;
+
+    pub fn operating_days(&self) -> Result<Vec<u64>, String> {
+        let mut weekdays = [false; 7];
+        for c in self.days_of_week.chars().filter(|c| *c != '.') {
+            match c.to_digit(10) {
+                Some(d @ 1..=7) => weekdays[d as usize - 1] = true,
+                _ => return Err(format!("invalid weekday `{}` in days_of_week", c)),
+            }
+        }
+        if self.from_day == 0
+            || self.to_day < self.from_day
+            || self.to_day - self.from_day >= Self::MAX_DAYS
+        {
+            return Err(format!(
+                "invalid day range {}..{}",
+                self.from_day, self.to_day
+            ));
+        }
+        Ok((self.from_day..=self.to_day)
+            .filter(|day| weekdays[((day - 1) % 7) as usize])
+            .collect())
+    }
+
+    /// One flight per operating day, with the template's times taken as minutes into
+    /// that day.
+    pub fn expand(&self, template: &Flight) -> Result<Vec<Flight>, String> {
+        self.operating_days()?
+            .into_iter()
+            .map(|day| {
+                let at = |time: Time| {
+                    (day - 1)
+                        .checked_mul(1440)
+                        .and_then(|offset| time.0.checked_add(offset))
+                        .map(Time)
+                        .ok_or_else(|| format!("day {} is out of range", day))
+                };
+                Ok(Flight {
+                    day,
+                    departure_time: at(template.departure_time)?,
+                    arrival_time: at(template.arrival_time)?,
+                    ..template.clone()
+                })
+            })
+            .collect()
+    }
 }
 
 fn display_option(o: &Option<AircraftId>) -> String {
@@ -0,0 +1,112 @@
+use crate::schedule::schedule::{FlightState, LoadError, Schedule};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+/// Flight states at the end of a scripted run, by `number/day` key.
+pub type Outcomes = BTreeMap<String, FlightState>;
+
+/// A scenario, the script run against it and the outcomes it is expected to end with:
+/// `<name>.json`, `<name>.script` and `<name>.golden.json` side by side.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Case {
+    pub name: String,
+    pub scenario: PathBuf,
+    pub script: PathBuf,
+    pub golden: PathBuf,
+}
+
+/// Cases in `dir`, by name. Scenarios without a script next to them are not cases; the
+/// golden file may not exist yet.
+pub fn cases(dir: &Path) -> io::Result<Vec<Case>> {
+    let mut cases = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(name) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".json"))
+        else {
+            continue;
+        };
+        if name.ends_with(".golden") {
+            continue;
+        }
+        let script = dir.join(format!("{}.script", name));
+        if script.is_file() {
+            cases.push(Case {
+                name: name.to_string(),
+                golden: dir.join(format!("{}.golden.json", name)),
+                scenario: path.clone(),
+                script,
+            });
+        }
+    }
+    cases.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(cases)
+}
+
+pub fn outcomes(schedule: &Schedule) -> Outcomes {
+    schedule
+        .flights
+        .iter()
+        .map(|f| (f.key().to_string(), FlightState::of(f)))
+        .collect()
+}
+
+pub fn write(path: &Path, outcomes: &Outcomes) -> io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(&mut w, outcomes)?;
+    writeln!(w)?;
+    w.flush()
+}
+
+pub fn read(path: &Path) -> Result<Outcomes, LoadError> {
+    Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+}
+
+/// What differs between the expected and the actual outcomes, one line per flight field in
+/// flight order, followed by flights only the actual outcomes have. Empty when they match.
+pub fn diff(expected: &Outcomes, actual: &Outcomes) -> Vec<String> {
+    let mut lines = vec![];
+    for (flight, before) in expected {
+        let Some(after) = actual.get(flight) else {
+            lines.push(format!("{}: missing", flight));
+            continue;
+        };
+        let mut field = |name: &str, before: String, after: String| {
+            if before != after {
+                lines.push(format!("{} {}: {} -> {}", flight, name, before, after));
+            }
+        };
+        field(
+            "status",
+            format!("{:?}", before.status),
+            format!("{:?}", after.status),
+        );
+        field(
+            "aircraft",
+            before.aircraft_id.as_deref().unwrap_or("-").to_string(),
+            after.aircraft_id.as_deref().unwrap_or("-").to_string(),
+        );
+        field(
+            "departure",
+            before.departure_time.to_string(),
+            after.departure_time.to_string(),
+        );
+        field(
+            "arrival",
+            before.arrival_time.to_string(),
+            after.arrival_time.to_string(),
+        );
+    }
+    lines.extend(
+        actual
+            .keys()
+            .filter(|flight| !expected.contains_key(*flight))
+            .map(|flight| format!("{}: unexpected", flight)),
+    );
+    lines
+}
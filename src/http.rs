@@ -0,0 +1,84 @@
+use std::fmt;
+use std::io;
+use std::time::Duration;
+use ureq::Agent;
+use ureq::http::Uri;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, PartialEq)]
+pub enum UrlError {
+    /// Only `http://` and `https://` endpoints can be reached.
+    UnsupportedScheme(String),
+    Invalid(String),
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlError::UnsupportedScheme(url) => {
+                write!(f, "{}: only http:// and https:// URLs are supported", url)
+            }
+            UrlError::Invalid(url) => write!(f, "invalid URL {}", url),
+        }
+    }
+}
+
+impl std::error::Error for UrlError {}
+
+/// A checked `http://` or `https://` URL.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Url {
+    pub(crate) text: String,
+}
+
+impl Url {
+    pub(crate) fn parse(url: &str) -> Result<Url, UrlError> {
+        let invalid = || UrlError::Invalid(url.to_string());
+        let uri = url.parse::<Uri>().map_err(|_| invalid())?;
+        match uri.scheme_str() {
+            Some("http" | "https") => {}
+            Some(_) => return Err(UrlError::UnsupportedScheme(url.to_string())),
+            None => return Err(invalid()),
+        }
+        if uri.host().is_none_or(str::is_empty) {
+            return Err(invalid());
+        }
+        Ok(Url {
+            text: url.to_string(),
+        })
+    }
+
+    /// Fetches the URL and returns the status code and body of the response, whatever
+    /// the status.
+    pub(crate) fn get(&self) -> io::Result<(u16, String)> {
+        let mut response = agent()
+            .get(&self.text)
+            .call()
+            .map_err(ureq::Error::into_io)?;
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(ureq::Error::into_io)?;
+        Ok((response.status().as_u16(), body))
+    }
+
+    /// Posts a JSON `body` and returns the status code of the response.
+    pub(crate) fn post_json(&self, body: &str) -> io::Result<u16> {
+        agent()
+            .post(&self.text)
+            .header("Content-Type", "application/json")
+            .send(body)
+            .map(|response| response.status().as_u16())
+            .map_err(ureq::Error::into_io)
+    }
+}
+
+/// Times out after `TIMEOUT` and hands back error statuses rather than failing on them.
+fn agent() -> Agent {
+    Agent::config_builder()
+        .timeout_global(Some(TIMEOUT))
+        .http_status_as_error(false)
+        .build()
+        .into()
+}
@@ -0,0 +1,30 @@
+pub mod aircraft;
+pub mod airport;
+pub mod alert;
+pub mod branch;
+pub mod cli;
+pub mod compensation;
+pub mod config;
+pub mod cost;
+pub mod crew;
+pub mod delay_code;
+pub mod error;
+pub mod export;
+pub mod feed;
+pub mod filter;
+pub mod flight;
+pub mod golden;
+pub mod http;
+pub mod metrics;
+pub mod play;
+pub mod report;
+pub mod schedule;
+pub mod script;
+pub mod session;
+pub mod severity;
+/// Proptest strategies for property-testing recovery strategies against generated
+/// schedules.
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+pub mod time;
+pub mod webhook;
@@ -1,35 +1,327 @@
-use crate::flight::Flight;
-use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
-use crate::flight::UnscheduledReason::*;
-use crate::schedule::schedule::{DisruptionType, Schedule};
-use crate::time::Time;
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use colored::Colorize;
+use irrops::airport::Curfew;
+use irrops::alert::AlertRules;
+use irrops::branch::Branches;
+use irrops::cli::commands::{
+    AtfmScope, BranchAction, Command, CsvExport, Setting, StatsView, WebhookAction,
+};
+use irrops::cli::help;
+use irrops::config::{ColorMode, Config, TableStyle};
+use irrops::crew::RestBreach;
+use irrops::delay_code::DelayCodes;
+use irrops::error::IrropsError;
+use irrops::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use irrops::flight::{Flight, FlightKey, UnscheduledReason};
+use irrops::play::{self, Pace};
+use irrops::schedule::atfm::AtfmTarget;
+use irrops::schedule::schedule::{
+    DisruptionReport, DisruptionType, FlightLookupError, FlightState, Schedule, Strategy,
+};
+use irrops::schedule::search::{Hit, did_you_mean};
+use irrops::schedule::validate::{self, Severity};
+use irrops::session::SessionState;
+use irrops::time::{self, Time};
+use irrops::webhook::{self, Webhook};
+use irrops::{export, feed, golden, metrics, report, script};
 use rustyline::completion::{Completer, Pair};
 use rustyline::error::ReadlineError;
 use rustyline::{Context, Editor, Helper, Highlighter, Hinter, Validator};
-use std::io::Write;
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::collections::VecDeque;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Stdio};
 use std::sync::Arc;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use tabled::Tabled;
 use tabled::settings::Style;
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::prelude::*;
 
-mod aircraft;
-mod airport;
-mod flight;
-mod schedule;
-mod time;
+#[derive(Tabled)]
+struct FlightRow<'a> {
+    #[tabled(inline)]
+    flight: &'a Flight,
+    #[tabled(rename = "Δ plan")]
+    plan_delta: String,
+}
+
+#[derive(Tabled)]
+struct GroupRow {
+    group: String,
+    flights: usize,
+    scheduled: usize,
+    delayed: usize,
+    unscheduled: usize,
+    delay_minutes: u64,
+}
+
+#[derive(Tabled)]
+struct BankRow {
+    hub: String,
+    bank: String,
+    planned: usize,
+    protected: usize,
+    integrity: String,
+}
+
+#[derive(Tabled)]
+struct HitRow {
+    kind: &'static str,
+    name: String,
+    details: String,
+}
+
+#[derive(Tabled)]
+struct StrandingRow {
+    airport: String,
+    day: u64,
+    passengers: u32,
+    flights: String,
+}
+
+#[derive(Tabled)]
+struct SlackRow {
+    aircraft: String,
+    legs: usize,
+    min_buffer: String,
+    absorbable: u64,
+    limited_by: String,
+}
+
+#[derive(Tabled)]
+struct CandidateRow {
+    aircraft: String,
+    location: String,
+    busy: String,
+    maintenance: String,
+    curfew: String,
+    fleet_type: String,
+    spill: u32,
+    feasible: String,
+}
+
+#[derive(Tabled)]
+struct BranchRow {
+    #[tabled(rename = "")]
+    current: &'static str,
+    name: String,
+    parent: String,
+    forked_at: usize,
+    disruptions: usize,
+    unscheduled: usize,
+    cost: u64,
+}
 
-enum StatusFilter {
-    Unscheduled,
-    Scheduled,
-    Delayed,
+#[derive(Tabled)]
+struct HistoryRow {
+    seq: usize,
+    trigger: String,
+    delayed: usize,
+    unscheduled: usize,
+    delay_minutes: u64,
+    passengers: u64,
+    compensation: u64,
+    severity: u64,
+}
+
+#[derive(Tabled)]
+struct UtilizationRow {
+    aircraft: String,
+    block_hours: String,
+    cycles: usize,
+}
+
+#[derive(Tabled)]
+struct GroundRow {
+    aircraft: String,
+    from: Time,
+    to: String,
+}
+
+#[derive(Tabled)]
+struct ConnectionRow {
+    inbound: String,
+    outbound: String,
+    buffer: u64,
+}
+
+#[derive(Tabled)]
+struct CurfewHitRow {
+    flight: String,
+    aircraft: String,
+    departure: String,
+    reason: String,
+}
+
+#[derive(Tabled)]
+struct ImpactRow {
+    rank: usize,
+    flight: String,
+    delayed: usize,
+    unscheduled: usize,
+    delay_minutes: u64,
 }
 
 #[derive(Parser)]
 struct Args {
-    /// Path to the JSON scenario file
-    #[arg(short, long, value_name = "FILE", default_value = "data/default.json")]
-    scenario: PathBuf,
+    /// Path to the JSON scenario file [default: data/default.json]
+    #[arg(short, long, value_name = "FILE")]
+    scenario: Option<PathBuf>,
+    /// Log level for the scheduling core (off, error, warn, info, debug, trace), written to stderr [default: off]
+    #[arg(long, value_name = "LEVEL")]
+    log_level: Option<LevelFilter>,
+    /// Config file with startup defaults [default: ~/.config/irrops/config.toml]
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Neither restore nor save the command history, settings and scenario of the last
+    /// session, kept in ~/.local/state/irrops
+    #[arg(long)]
+    no_session: bool,
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+}
+
+#[derive(Subcommand)]
+enum CliCommand {
+    /// Check a scenario file's structure and references without starting a session
+    Validate {
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+    },
+    /// Print the JSON Schema of the scenario format
+    Schema,
+    /// Run every <name>.json scenario in a directory with its <name>.script and compare the
+    /// flights it ends with against <name>.golden.json, written on the first run
+    Test {
+        #[arg(value_name = "DIR")]
+        dir: PathBuf,
+        /// Overwrite the golden files with the current outcomes
+        #[arg(long)]
+        update: bool,
+    },
+}
+
+/// Runs a one-shot subcommand; the exit code is non-zero when validation finds errors.
+fn run_command(command: CliCommand) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        CliCommand::Validate { file } => {
+            let issues = match validate::validate_file(file.to_str().unwrap()) {
+                Ok(issues) => issues,
+                Err(e) => {
+                    eprintln!("{}: {}", file.display(), e);
+                    std::process::exit(1);
+                }
+            };
+            for issue in &issues {
+                println!("{}: {}", file.display(), issue);
+            }
+            let errors = issues
+                .iter()
+                .filter(|issue| issue.severity == Severity::Error)
+                .count();
+            println!(
+                "{}: {} error(s), {} warning(s)",
+                file.display(),
+                errors,
+                issues.len() - errors
+            );
+            if errors > 0 {
+                std::process::exit(1);
+            }
+        }
+        CliCommand::Schema => print!("{}", validate::scenario_schema()),
+        CliCommand::Test { dir, update } => {
+            let cases = golden::cases(&dir)?;
+            if cases.is_empty() {
+                eprintln!("{}: no scenario with a .script next to it", dir.display());
+                std::process::exit(1);
+            }
+            let mut failed = 0;
+            for case in &cases {
+                let actual = match run_case(&dir, case) {
+                    Ok(actual) => actual,
+                    Err(e) => {
+                        failed += 1;
+                        println!("{} {}: {}", "FAIL".red(), case.name, e);
+                        continue;
+                    }
+                };
+                if update || !case.golden.exists() {
+                    golden::write(&case.golden, &actual)?;
+                    println!("{} {}", "SAVED".yellow(), case.name);
+                    continue;
+                }
+                let lines = golden::diff(&golden::read(&case.golden)?, &actual);
+                if lines.is_empty() {
+                    println!("{} {}", "PASS".green(), case.name);
+                } else {
+                    failed += 1;
+                    println!("{} {}", "FAIL".red(), case.name);
+                    lines.iter().for_each(|line| println!("  {}", line));
+                }
+            }
+            println!("{} case(s), {} failed", cases.len(), failed);
+            if failed > 0 {
+                std::process::exit(1);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Runs a golden case through a session of its own, fed the script on stdin from the
+/// case's directory, and reads back the flights it exported on the way out. A
+/// `config.toml` in the directory is used instead of the user's config, and the user's
+/// last session is left alone.
+fn run_case(
+    dir: &Path,
+    case: &golden::Case,
+) -> Result<golden::Outcomes, Box<dyn std::error::Error>> {
+    let out = std::env::temp_dir().join(format!(
+        "irrops-golden-{}-{}.json",
+        std::process::id(),
+        case.name
+    ));
+    let mut lines = script::read(case.script.to_str().unwrap())?;
+    lines.push(format!("export json flights {}", out.display()));
+    lines.push("exit".to_string());
+
+    let mut session = process::Command::new(std::env::current_exe()?);
+    session
+        .current_dir(dir)
+        .arg("--scenario")
+        .arg(&case.scenario)
+        .arg("--no-session");
+    if dir.join("config.toml").is_file() {
+        session.args(["--config", "config.toml"]);
+    }
+    let mut child = session
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    stdin.write_all(format!("{}\n", lines.join("\n")).as_bytes())?;
+    drop(stdin);
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "session {}: {}",
+            output.status,
+            stderr
+                .lines()
+                .find(|line| !line.trim().is_empty())
+                .unwrap_or("")
+        )
+        .into());
+    }
+    let outcomes = golden::read(&out).map_err(|e| format!("no flights exported: {}", e))?;
+    std::fs::remove_file(&out)?;
+    Ok(outcomes)
 }
 
 #[derive(Helper, Hinter, Highlighter, Validator)]
@@ -62,12 +354,12 @@ impl Completer for CompleteHelper {
 }
 
 fn paginate(content: String) {
-    let mut pager = Command::new("less")
+    let mut pager = process::Command::new("less")
         .arg("-R")
         .stdin(Stdio::piped())
         .spawn()
         // Fallback to 'more' if 'less' isn't available
-        .or_else(|_| Command::new("more").stdin(Stdio::piped()).spawn())
+        .or_else(|_| process::Command::new("more").stdin(Stdio::piped()).spawn())
         .expect("Failed to spawn pager");
 
     let mut stdin = pager.stdin.take().expect("Failed to open stdin for pager");
@@ -83,38 +375,686 @@ fn paginate(content: String) {
     let _ = pager.wait();
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = Args::parse();
+fn print_charters(schedule: &Schedule) {
+    for usage in schedule.charter_usage() {
+        println!(
+            "Charter {}: {} flight{}, {} block min, EUR {} ({})",
+            usage.charter_id,
+            usage.flights.len(),
+            if usage.flights.len() == 1 { "" } else { "s" },
+            usage.block_minutes,
+            usage.cost,
+            usage
+                .flights
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
+
+fn print_callouts(schedule: &Schedule) {
+    let callouts = schedule.callouts();
+    if callouts.is_empty() {
+        return;
+    }
     println!(
-        "Tower online. Loaded flights from {}",
-        args.scenario.display()
+        "Reserve callouts: {}, EUR {}",
+        callouts.len(),
+        schedule.callout_cost()
     );
+    for callout in callouts {
+        println!(
+            "  {}: {}",
+            callout.crew_id,
+            callout
+                .flights
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
 
-    let mut schedule = Schedule::load_from_file(args.scenario.to_str().unwrap())?;
+fn print_rest_breaches(schedule: &Schedule) {
+    for (key, crew_id, breach) in schedule.rest_breaches() {
+        match breach {
+            RestBreach::Short {
+                required,
+                available,
+            } => println!(
+                "Crew {} short of rest before {}: {} of {} min",
+                crew_id, key, available, required
+            ),
+            RestBreach::Positioning { rested_at, origin } => println!(
+                "Crew {} out of position for {}: rests at {}, departs {}",
+                crew_id, key, rested_at, origin
+            ),
+        }
+    }
+}
+
+fn print_frozen(schedule: &Schedule) {
+    let Some(recovery) = schedule.recoveries.last() else {
+        return;
+    };
+    let frozen = &recovery.frozen;
+    if !frozen.is_empty() {
+        println!(
+            "Freeze window: {} unscheduled flight{} departing before {} left untouched: {}",
+            frozen.len(),
+            if frozen.len() == 1 { "" } else { "s" },
+            recovery.frozen_until,
+            frozen
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
+
+/// Flight and reason with its IATA code, naming the custom rule when one broke it.
+fn describe_unscheduled(
+    report: &DisruptionReport,
+    codes: &DelayCodes,
+    flight_id: &FlightKey,
+    reason: &UnscheduledReason,
+) -> String {
+    let cause = match report.violations.iter().find(|(id, _)| id == flight_id) {
+        Some((_, violation)) => violation.to_string(),
+        None => format!("{:?}", reason),
+    };
+    match codes.reason(reason) {
+        Some(code) => format!("{} ({}, IATA {:02})", flight_id, cause, code),
+        None => format!("{} ({})", flight_id, cause),
+    }
+}
+
+/// The most impactful break as its own section, when it is not the first break.
+fn worst_break(report: &DisruptionReport, codes: &DelayCodes) -> String {
+    match &report.worst_break {
+        Some((flight_id, reason)) if report.worst_break != report.first_break => format!(
+            "\n\nMost impactful break:\n  {}",
+            describe_unscheduled(report, codes, flight_id, reason)
+        ),
+        _ => String::new(),
+    }
+}
+
+/// Old and new times and status of a flight in the report as colored `-`/`+` lines,
+/// empty when the report did not record the flight changing.
+fn change_diff(report: &DisruptionReport, flight_id: &FlightKey) -> String {
+    let Some(change) = report.changes.iter().find(|c| c.flight == *flight_id) else {
+        return String::new();
+    };
+    let line = |state: &FlightState| {
+        let status = match &state.status {
+            Scheduled => "Scheduled".to_string(),
+            Delayed { minutes } => format!("Delayed (+{}m)", minutes),
+            Unscheduled(_) => "Unscheduled".to_string(),
+        };
+        format!(
+            "{} - {}  {}",
+            state.departure_time, state.arrival_time, status
+        )
+    };
+    format!(
+        "\n    {}\n    {}",
+        format!("- {}", line(&change.before)).red(),
+        format!("+ {}", line(&change.after)).green()
+    )
+}
+
+/// Curfew-exempt flights the report left operating inside a closed window, for auditors.
+fn print_exemptions(report: &DisruptionReport) {
+    if report.exempted.is_empty() {
+        return;
+    }
+    println!("Curfew exemptions ({}):", report.exempted.len());
+    for (flight_id, reason) in &report.exempted {
+        println!(
+            "  {} operates despite {:?} (curfew exempt)",
+            flight_id, reason
+        );
+    }
+    println!();
+}
+
+/// Movements the report pushed outside their slot at a coordinated airport.
+fn print_slot_violations(report: &DisruptionReport) {
+    if report.slot_violations.is_empty() {
+        return;
+    }
+    println!("Slot violations ({}):", report.slot_violations.len());
+    for v in &report.slot_violations {
+        println!(
+            "  {} at {}: slot {}, now {}{}",
+            v.flight,
+            v.airport_id,
+            v.slot,
+            v.actual,
+            if v.new_slot {
+                ", new slot available"
+            } else {
+                ", needs a new slot"
+            }
+        );
+    }
+    println!();
+}
+
+fn flight_error(schedule: &Schedule, e: FlightLookupError) -> String {
+    match &e {
+        FlightLookupError::Unknown(text) => {
+            did_you_mean(e.to_string(), &schedule.suggest_flights(text))
+        }
+        FlightLookupError::Ambiguous(..) => e.to_string(),
+    }
+}
+
+fn unknown_airport(schedule: &Schedule, id: &str) -> String {
+    did_you_mean(
+        format!("Unknown airport {}", id),
+        &schedule.suggest_airports(id),
+    )
+}
+
+/// Clears the screen and renders the watched view under a line saying how to stop.
+fn print_watch(schedule: &Schedule, view: &Command, style: TableStyle) {
+    if std::io::stdout().is_terminal() {
+        print!("\x1b[2J\x1b[H");
+    }
+    println!("Watching at {}; type watch off to stop\n", schedule.clock);
+    print_view(schedule, view, style, false);
+}
+
+/// The tables of `ls` and `stats`, which `watch` re-renders; long ones go through the
+/// pager unless `pager` is off.
+fn print_view(schedule: &Schedule, view: &Command, style: TableStyle, pager: bool) {
+    match view {
+        Command::LsWhere(filter) => {
+            let filtered_flights = schedule
+                .flights
+                .iter()
+                .filter(|f| filter.matches(f))
+                .collect::<Vec<_>>();
+            print_flights(schedule, &filtered_flights, style, pager);
+        }
+        Command::Ls { day, status } => {
+            let filtered_flights: Vec<&Flight> = schedule
+                .flights
+                .iter()
+                .filter(|f| day.is_none_or(|d| metrics::listing_day(f) == d))
+                .filter(|f| status.is_none_or(|s| s.matches(&f.status)))
+                .collect();
+            print_flights(schedule, &filtered_flights, style, pager);
+        }
+        Command::Stats(StatsView::Delays) => {
+            print_delay_distribution(&metrics::delay_distribution(&schedule.flights));
+        }
+        Command::Stats(StatsView::Banks) => {
+            let rows = schedule
+                .bank_integrity()
+                .into_iter()
+                .map(|b| BankRow {
+                    hub: b.hub.to_string(),
+                    bank: b.bank.to_string(),
+                    planned: b.planned,
+                    protected: b.protected,
+                    integrity: format!("{:.1}%", b.pct()),
+                })
+                .collect::<Vec<_>>();
+            if rows.is_empty() {
+                println!("No hub banks defined.");
+            } else {
+                let mut table = tabled::Table::new(&rows);
+                style_table(&mut table, style);
+                table.with(tabled::settings::Alignment::left());
+                println!("{}", table);
+            }
+        }
+        Command::Stats(StatsView::By(by)) => {
+            let rows = metrics::group(&schedule.flights, *by)
+                .into_iter()
+                .map(|(key, summary)| GroupRow {
+                    group: key.to_string(),
+                    flights: summary.total,
+                    scheduled: summary.scheduled,
+                    delayed: summary.delayed,
+                    unscheduled: summary.unscheduled(),
+                    delay_minutes: summary.delay_minutes,
+                })
+                .collect::<Vec<_>>();
+            let mut table = tabled::Table::new(&rows);
+            style_table(&mut table, style);
+            table.with(tabled::settings::Alignment::left());
+            if pager && rows.len() > 20 {
+                paginate(table.to_string());
+            } else {
+                println!("{}", table);
+            }
+        }
+        Command::Stats(StatsView::Summary) => {
+            let summary = metrics::summarize(&schedule.flights);
+            println!("\nFleet Utilization Summary:");
+            println!("---------------------------");
+            for (label, count) in [
+                ("Scheduled:                         ", summary.scheduled),
+                ("Delayed:                           ", summary.delayed),
+                ("Unscheduled (Waiting):             ", summary.waiting),
+                (
+                    "Unscheduled (Max Delay Exceeded):  ",
+                    summary.max_delay_exceeded,
+                ),
+                (
+                    "Unscheduled (Airport Curfew):      ",
+                    summary.airport_curfew,
+                ),
+                (
+                    "Unscheduled (Operating Hours):     ",
+                    summary.outside_operating_hours,
+                ),
+                (
+                    "Unscheduled (Aircraft Maintenance):",
+                    summary.aircraft_maintenance,
+                ),
+                (
+                    "Unscheduled (Out of Position):     ",
+                    summary.out_of_position,
+                ),
+                (
+                    "Unscheduled (Turn Time):           ",
+                    summary.turn_time_violation,
+                ),
+                (
+                    "Unscheduled (Maintenance Due):     ",
+                    summary.maintenance_due,
+                ),
+                (
+                    "Unscheduled (Stand Capacity):      ",
+                    summary.stand_capacity,
+                ),
+                (
+                    "Unscheduled (Movement Capacity):   ",
+                    summary.movement_capacity,
+                ),
+                (
+                    "Unscheduled (Custom Rule):         ",
+                    summary.constraint_violated,
+                ),
+                ("Unscheduled (Broken Chain):        ", summary.broken_chain),
+            ] {
+                println!("{} {} ({:.1}%)", label, count, summary.pct(count));
+            }
+            println!("---------------------------");
+            println!("Total Flights: {}", summary.total);
+            println!("Total Delay Minutes: {}", summary.delay_minutes);
+            let banks = schedule.bank_integrity();
+            let planned = banks.iter().map(|b| b.planned).sum::<usize>();
+            if planned > 0 {
+                let protected = banks.iter().map(|b| b.protected).sum::<usize>();
+                println!(
+                    "Bank Integrity: {}/{} connections ({:.1}%)",
+                    protected,
+                    planned,
+                    protected as f64 * 100.0 / planned as f64
+                );
+            }
+            println!(
+                "Compensation exposure: EUR {}\n",
+                schedule.compensation.exposure(&schedule.flights)
+            );
+        }
+        _ => {}
+    }
+}
+
+/// Width in characters of the longest bar in the `stats delays` histogram.
+const HISTOGRAM_WIDTH: usize = 40;
+
+fn print_delay_distribution(dist: &metrics::DelayDistribution) {
+    if dist.flights == 0 {
+        println!("No operating flights.");
+        return;
+    }
+    println!("\nDelay distribution ({} operating flights):", dist.flights);
+    let peak = dist.buckets.iter().copied().max().unwrap_or(0).max(1);
+    for (i, count) in dist.buckets.iter().enumerate() {
+        let from = i as u64 * dist.bucket_width;
+        println!(
+            "  {:>4}-{:<4} | {:<width$} {}",
+            from,
+            from + dist.bucket_width - 1,
+            "#".repeat(count * HISTOGRAM_WIDTH / peak),
+            count,
+            width = HISTOGRAM_WIDTH
+        );
+    }
+    println!(
+        "P50: {} min  P90: {} min  P99: {} min  Max: {} min\n",
+        dist.p50, dist.p90, dist.p99, dist.max
+    );
+}
+
+/// Longest list `print_recovery` prints before summing up the rest.
+const RECOVERY_LIST_LIMIT: usize = 20;
+/// Rows shown by `find`; the rest are only counted.
+const FIND_LIMIT: usize = 10;
+
+fn print_recovery(before: &[Flight], schedule: &Schedule) {
+    let outcome = metrics::compare_recovery(before, &schedule.flights);
+    let more = |n: usize| {
+        if n > RECOVERY_LIST_LIMIT {
+            println!("  ... and {} more", n - RECOVERY_LIST_LIMIT);
+        }
+    };
+    let plural = |n: usize| if n == 1 { "" } else { "s" };
+    println!(
+        "\nRecovered: {} flight{}",
+        outcome.fixed.len(),
+        plural(outcome.fixed.len())
+    );
+    for (key, aircraft_id) in outcome.fixed.iter().take(RECOVERY_LIST_LIMIT) {
+        match schedule
+            .flight(key)
+            .map(|f| schedule.spill_on(f, aircraft_id))
+        {
+            Some(spill) if spill > 0 => {
+                println!("  {} -> {} (spills {} pax)", key, aircraft_id, spill)
+            }
+            _ => println!("  {} -> {}", key, aircraft_id),
+        }
+    }
+    more(outcome.fixed.len());
+    let spilled = schedule.spills().iter().map(|(_, s)| *s).sum::<u32>();
+    if spilled > 0 {
+        println!("Spilled passengers: {}", spilled);
+    }
+    println!(
+        "Still unscheduled: {} flight{}",
+        outcome.broken.len(),
+        plural(outcome.broken.len())
+    );
+    for (key, reason) in outcome.broken.iter().take(RECOVERY_LIST_LIMIT) {
+        match schedule.delay_codes.reason(reason) {
+            Some(code) => println!("  {} ({:?}, IATA {:02})", key, reason, code),
+            None => println!("  {} ({:?})", key, reason),
+        }
+    }
+    more(outcome.broken.len());
+    let (b, a) = (&outcome.before, &outcome.after);
+    for (label, before, after) in [
+        ("Scheduled", b.scheduled as i64, a.scheduled as i64),
+        ("Delayed", b.delayed as i64, a.delayed as i64),
+        (
+            "Unscheduled",
+            b.unscheduled() as i64,
+            a.unscheduled() as i64,
+        ),
+        (
+            "Delay minutes",
+            b.delay_minutes as i64,
+            a.delay_minutes as i64,
+        ),
+    ] {
+        println!("{}: {} -> {} ({:+})", label, before, after, after - before);
+    }
+}
+
+fn print_ingested(observations: &[feed::Observation], ingested: &feed::Ingested) {
+    for (key, minutes) in &ingested.delayed {
+        println!("Flight {} delayed by {} min", key, minutes);
+    }
+    if !ingested.unmatched.is_empty() {
+        println!("Unmatched: {}", ingested.unmatched.join(", "));
+    }
+    println!(
+        "{} observations, {} delays applied",
+        observations.len(),
+        ingested.delayed.len()
+    );
+}
+
+/// Thresholds from the config the schedule now breaks, in red so they stand out.
+fn print_alerts(rules: &AlertRules, schedule: &Schedule) {
+    if rules.is_empty() {
+        return;
+    }
+    for alert in rules.evaluate(schedule) {
+        println!("{}", format!("ALERT {}", alert).red().bold());
+    }
+}
+
+fn print_stand_overflows(schedule: &Schedule) {
+    for o in schedule.stand_overflows() {
+        println!(
+            "Warning: {} aircraft on the ground at {} from {} to {}, with {} stand{}",
+            o.peak,
+            o.airport_id,
+            o.from,
+            o.to.map_or("end".to_string(), |t| t.to_string()),
+            o.stands,
+            if o.stands == 1 { "" } else { "s" }
+        );
+    }
+}
+
+/// One table per operating day, each under a header counting its flights.
+fn print_flights(schedule: &Schedule, flights: &[&Flight], style: TableStyle, pager: bool) {
+    if flights.is_empty() {
+        println!("No matching flights found.");
+        return;
+    }
+    let mut output = String::new();
+    for (day, flights) in metrics::by_day(flights.iter().copied()) {
+        let summary = metrics::summarize(flights.iter().copied());
+        let rows = flights.iter().map(|flight| FlightRow {
+            flight,
+            plan_delta: match schedule.plan_delta(flight) {
+                Some(0) | None => String::new(),
+                Some(delta) => format!("{:+}", delta),
+            },
+        });
+        let mut table = tabled::Table::new(rows);
+        style_table(&mut table, style);
+        table.with(tabled::settings::Alignment::left());
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&format!(
+            "DAY{} - {} flight{}, {} delayed, {} unscheduled\n{}\n",
+            day,
+            summary.total,
+            if summary.total == 1 { "" } else { "s" },
+            summary.delayed,
+            summary.unscheduled(),
+            table
+        ));
+    }
+    if pager && flights.len() > 20 {
+        paginate(output);
+    } else {
+        print!("{}", output);
+    }
+}
+
+fn style_table(table: &mut tabled::Table, style: TableStyle) {
+    match style {
+        TableStyle::Rounded => table.with(Style::rounded()),
+        TableStyle::Ascii => table.with(Style::ascii()),
+        TableStyle::Markdown => table.with(Style::markdown()),
+        TableStyle::Psql => table.with(Style::psql()),
+    };
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    if let Some(command) = args.command {
+        return run_command(command);
+    }
+    let mut config = Config::load(args.config.as_deref())?;
+    let session_dir = match args.no_session {
+        true => None,
+        false => SessionState::default_dir(),
+    };
+    let mut state = match session_dir.as_deref().map(SessionState::load) {
+        Some(Ok(state)) => state,
+        Some(Err(e)) => {
+            println!("Warning: ignoring the last session's state: {}", e);
+            SessionState::default()
+        }
+        None => SessionState::default(),
+    };
+
+    let log_level = match (args.log_level, &config.log_level) {
+        (Some(level), _) => level,
+        (None, Some(level)) => level.parse()?,
+        (None, None) => LevelFilter::OFF,
+    };
+    // the engine's events and spans, timed when they close, to stderr; dependencies are
+    // left out
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(io::stderr)
+                .with_ansi(io::stderr().is_terminal())
+                .with_span_events(FmtSpan::CLOSE)
+                .with_filter(Targets::new().with_target("irrops", log_level)),
+        )
+        .try_init()?;
+    match config.color {
+        ColorMode::Auto => {}
+        ColorMode::Always => colored::control::set_override(true),
+        ColorMode::Never => colored::control::set_override(false),
+    }
+    // settings changed in the last session win over the config file
+    if let Some(style) = state.table_style {
+        config.table_style = style;
+    }
+    let day_one = match state.start_date.as_ref().or(config.start_date.as_ref()) {
+        Some(date) => date.parse()?,
+        None => time::display().1,
+    };
+    time::set_display(state.time_format.unwrap_or(config.time_format), day_one);
+
+    let scenario = args
+        .scenario
+        .or(state.scenario.clone().filter(|path| path.is_file()))
+        .or(config.scenario.clone())
+        .unwrap_or_else(|| PathBuf::from("data/default.json"));
+    println!("Tower online. Loaded flights from {}", scenario.display());
+
+    let mut schedule = Schedule::load_from_file(scenario.to_str().unwrap())?;
+    state.scenario = Some(std::fs::canonicalize(&scenario).unwrap_or(scenario));
+    if let Some(max_delay) = config.max_delay {
+        schedule.max_delay = max_delay;
+    }
+    if let Some(cost) = &config.cost {
+        schedule.cost = cost.clone();
+    }
+    if let Some(compensation) = &config.compensation {
+        schedule.compensation = compensation.clone();
+    }
+    if let Some(severity) = &config.severity {
+        schedule.severity = severity.clone();
+    }
+    for issue in validate::load_warnings(&schedule) {
+        println!("Warning: {}", issue.message);
+    }
     schedule.assign();
+    print_alerts(&config.alerts, &schedule);
+    let mut webhooks = config
+        .webhooks
+        .iter()
+        .map(|url| Webhook::parse(url))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut branches = Branches::default();
+    // `play` keeps its speed across pause, and its stdin reader across queued events
+    let mut play_speed: Option<u32> = None;
+    let mut playing = false;
+    let mut reader: Option<Receiver<String>> = None;
+    // the `ls` or `stats` view kept on screen by `watch`, redrawn once queued commands ran
+    let mut watching: Option<Command> = None;
+    let mut redraw = false;
 
-    let config = rustyline::Config::builder()
+    let editor_config = rustyline::Config::builder()
         .history_ignore_space(true)
         .completion_type(rustyline::CompletionType::List)
         .build();
 
-    let helper = CompleteHelper {
+    let mut helper = CompleteHelper {
         commands: vec![
             "ls".to_string(),
+            "baseline".to_string(),
+            "branch".to_string(),
+            "compare".to_string(),
+            "at".to_string(),
+            "events".to_string(),
+            "play".to_string(),
+            "pause".to_string(),
+            "stop".to_string(),
+            "clock".to_string(),
+            "clone-day".to_string(),
             "delay".to_string(),
+            "delay-airport".to_string(),
             "curfew".to_string(),
+            "candidates".to_string(),
+            "capacity".to_string(),
+            "close-runway".to_string(),
+            "atfm".to_string(),
+            "audit".to_string(),
+            "experiment".to_string(),
             "explain".to_string(),
+            "export".to_string(),
+            "feed".to_string(),
+            "find".to_string(),
+            "forecast".to_string(),
+            "foreach".to_string(),
+            "freeze".to_string(),
+            "ground-occupancy".to_string(),
+            "history".to_string(),
+            "impact".to_string(),
+            "merge".to_string(),
             "recover".to_string(),
+            "report".to_string(),
+            "retime".to_string(),
+            "rollover".to_string(),
+            "script".to_string(),
+            "set".to_string(),
+            "simulate".to_string(),
+            "slack".to_string(),
+            "strandings".to_string(),
+            "utilization".to_string(),
+            "watch".to_string(),
+            "webhook".to_string(),
             "help".to_string(),
             "exit".to_string(),
         ],
     };
+    helper
+        .commands
+        .extend(schedule.commands().map(|c| c.name().to_string()));
 
-    let mut rl = Editor::with_config(config)?;
+    let mut rl = Editor::with_config(editor_config)?;
     rl.set_helper(Some(helper));
+    if let Some(dir) = &session_dir {
+        // there is no history before the first session
+        let _ = rl.load_history(&SessionState::history_path(dir));
+    }
 
+    // commands queued by `script run` and `foreach`, run before reading more input
+    let mut pending: VecDeque<String> = VecDeque::new();
     loop {
-        let readline = rl.readline(">> ");
+        let readline = match pending.pop_front() {
+            Some(line) => {
+                println!(">> {}", line);
+                Ok(line)
+            }
+            None => rl.readline(">> "),
+        };
         match readline {
             Ok(line) => {
                 let trimmed = line.trim();
@@ -124,149 +1064,643 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                 rl.add_history_entry(trimmed)?;
 
-                let parts: Vec<&str> = trimmed.split_whitespace().collect();
-                match parts[0] {
-                    "ls" => {
-                        let mut day = None;
-                        let mut status = None;
-                        for part in parts.iter().skip(1) {
-                            if let Ok(d) = part.parse::<u64>() {
-                                if d > 0 {
-                                    day = Some(d);
+                let command = match Command::parse(trimmed) {
+                    Ok(command) => command,
+                    Err(e) => {
+                        println!("{}", e);
+                        continue;
+                    }
+                };
+                let mutated = command.mutates()
+                    || matches!(&command, Command::Plugin { name, .. }
+                        if schedule.commands().any(|c| c.name() == name));
+                let mut reports_before = schedule.history.len();
+                let clock_before = schedule.clock;
+                let switched = matches!(&command, Command::Branch(BranchAction::Switch(_)));
+                match command {
+                    view @ (Command::Ls { .. } | Command::LsWhere(_) | Command::Stats(_)) => {
+                        print_view(&schedule, &view, config.table_style, true);
+                    }
+                    Command::Clock(at) => {
+                        if let Some(at) = at {
+                            if at < schedule.clock {
+                                println!("Clock cannot move backwards from {}", schedule.clock);
+                                continue;
+                            }
+                            let due = schedule.take_due_events(at);
+                            if !due.is_empty() {
+                                // fire each event with the clock at its time, then
+                                // carry on to where the clock was asked to go
+                                let mut lines = vec![];
+                                for event in due {
+                                    println!(
+                                        "Event {} at {}: {}",
+                                        event.id, event.at, event.command
+                                    );
+                                    lines.push(format!("clock {}", event.at.0));
+                                    lines.push(event.command);
                                 }
-                            } else {
-                                status = match *part {
-                                    "u" | "unscheduled" => Some(StatusFilter::Unscheduled),
-                                    "s" | "scheduled" => Some(StatusFilter::Scheduled),
-                                    "d" | "delayed" => Some(StatusFilter::Delayed),
-                                    _ => None,
+                                lines.push(format!("clock {}", at.0));
+                                lines
+                                    .into_iter()
+                                    .rev()
+                                    .for_each(|line| pending.push_front(line));
+                                continue;
+                            }
+                            schedule.clock = at
+                        }
+                        println!(
+                            "Clock: {} (frozen until {})",
+                            schedule.clock,
+                            schedule.frozen_until()
+                        );
+                    }
+                    Command::At { at, command } => match schedule.queue_event(at, &command) {
+                        Ok(id) => println!("Queued event {} at {}: {}", id, at, command),
+                        Err(e) => println!("Cannot queue event: {}", e),
+                    },
+                    Command::Events => {
+                        let events = schedule.events();
+                        if events.is_empty() {
+                            println!("No queued events.");
+                        }
+                        for event in events {
+                            println!("  {:>3}  {}  {}", event.id, event.at, event.command);
+                        }
+                    }
+                    Command::CancelEvent(id) => match schedule.cancel_event(id) {
+                        Ok(event) => println!("Cancelled event {}: {}", event.id, event.command),
+                        Err(e) => println!("Cannot cancel event: {}", e),
+                    },
+                    Command::Play { speed } => {
+                        let speed = speed.or(play_speed).unwrap_or(60);
+                        if !playing {
+                            println!(
+                                "Playing from {} at {}x; type pause or stop",
+                                schedule.clock, speed
+                            );
+                        }
+                        play_speed = Some(speed);
+                        playing = true;
+                        let pace = Pace::new(speed, schedule.clock);
+                        loop {
+                            let input = reader
+                                .get_or_insert_with(play::read_line)
+                                .recv_timeout(Duration::from_millis(200));
+                            let mut until = pace.clock();
+                            let next_event = schedule.events().first().map(|e| e.at);
+                            if let Some(at) = next_event
+                                && at <= until
+                            {
+                                until = at;
+                            }
+                            let from = schedule.clock;
+                            schedule.clock = until;
+                            if let Some(view) = &watching
+                                && until != from
+                            {
+                                print_watch(&schedule, view, config.table_style);
+                            }
+                            for movement in play::movements(&schedule, from, until) {
+                                println!("{}", movement);
+                            }
+                            match input {
+                                Ok(line) => {
+                                    reader = None;
+                                    match line.trim() {
+                                        "pause" => {
+                                            println!("Paused at {}", schedule.clock);
+                                            playing = false;
+                                        }
+                                        "stop" => {
+                                            println!("Stopped at {}", schedule.clock);
+                                            play_speed = None;
+                                            playing = false;
+                                        }
+                                        "" => {
+                                            pending.push_front("play".to_string());
+                                        }
+                                        command => {
+                                            pending.push_front("play".to_string());
+                                            pending.push_front(command.to_string());
+                                        }
+                                    }
+                                    break;
+                                }
+                                Err(RecvTimeoutError::Disconnected) => {
+                                    reader = None;
+                                    println!("Stopped at {}", schedule.clock);
+                                    play_speed = None;
+                                    playing = false;
+                                    break;
                                 }
+                                Err(RecvTimeoutError::Timeout) => {}
+                            }
+                            if next_event.is_some_and(|at| at <= until) {
+                                // let `clock` fire the events, then carry on playing
+                                pending.push_front("play".to_string());
+                                pending.push_front(format!("clock {}", until.0));
+                                break;
                             }
                         }
-                        let filtered_flights: Vec<&Flight> = schedule
+                    }
+                    Command::Pause | Command::Stop => {
+                        println!("Not playing; start the clock with play")
+                    }
+                    Command::Freeze(minutes) => match minutes {
+                        Some(minutes) => {
+                            schedule.freeze_horizon = minutes;
+                            println!(
+                                "Freeze horizon set to {} min (frozen until {})",
+                                minutes,
+                                schedule.frozen_until()
+                            );
+                        }
+                        None => println!(
+                            "Freeze horizon: {} min (frozen until {})",
+                            schedule.freeze_horizon,
+                            schedule.frozen_until()
+                        ),
+                    },
+                    Command::DelayWhere { filter, minutes } => {
+                        let keys = schedule
                             .flights
                             .iter()
-                            .filter(|f| {
-                                if let Some(d) = day {
-                                    f.departure_time / Time(1440) == Time(d - 1)
-                                } else {
-                                    true
-                                }
+                            .filter(|f| filter.matches(f))
+                            .map(|f| f.key())
+                            .collect::<Vec<_>>();
+                        if keys.is_empty() {
+                            println!("No matching flights found.");
+                        }
+                        keys.into_iter().rev().for_each(|key| {
+                            pending.push_front(format!("delay {} {}", key, minutes))
+                        });
+                    }
+                    Command::Delay { flight, minutes } => {
+                        let key = match schedule.find_flight(&flight) {
+                            Ok(key) => key,
+                            Err(e) => {
+                                println!("{}", flight_error(&schedule, e));
+                                continue;
+                            }
+                        };
+                        if let Err(e) = schedule.apply_delay(key.clone(), minutes) {
+                            println!("{}", e);
+                            continue;
+                        }
+                        let report = schedule.last_report().unwrap();
+                        println!(
+                            "\nFlight {} delayed by {} min\n\nImpact:\n  Delayed: {} flight{}\n  Unscheduled: {} flight{}\n\nFirst break:\n  {}\n",
+                            key,
+                            minutes,
+                            report.affected.len(),
+                            if report.affected.len() == 1 { "" } else { "s " },
+                            report.unscheduled.len(),
+                            if report.unscheduled.len() == 1 {
+                                ""
+                            } else {
+                                "s "
+                            },
+                            match &report.first_break {
+                                None => "None".to_string(),
+                                Some((flight_id, reason)) => describe_unscheduled(
+                                    report,
+                                    &schedule.delay_codes,
+                                    flight_id,
+                                    reason
+                                ),
+                            }
+                        );
+                        print_exemptions(report);
+                        print_slot_violations(report);
+                    }
+                    Command::PreviewCurfew { airport, curfew } => {
+                        if !schedule.airports.contains_key(airport.as_str()) {
+                            println!("{}", unknown_airport(&schedule, &airport));
+                            continue;
+                        }
+                        let report = schedule
+                            .preview_curfew_with(Arc::from(airport.as_str()), curfew.clone());
+                        println!("\nCurfew preview at {} ({})\n", airport, curfew);
+                        print_exemptions(&report);
+                        print_slot_violations(&report);
+                        if report.unscheduled.is_empty() {
+                            println!("No flights would be hit.\n");
+                            continue;
+                        }
+                        let rows = report
+                            .unscheduled
+                            .iter()
+                            .filter_map(|(flight_id, reason)| {
+                                schedule.flight(flight_id).map(|f| CurfewHitRow {
+                                    flight: flight_id.to_string(),
+                                    aircraft: f
+                                        .aircraft_id
+                                        .as_ref()
+                                        .map_or("-".to_string(), |a| a.to_string()),
+                                    departure: f.departure_time.to_string(),
+                                    reason: format!("{:?}", reason),
+                                })
                             })
-                            .filter(|f| {
-                                if let Some(s) = &status {
-                                    match s {
-                                        StatusFilter::Unscheduled => {
-                                            matches!(f.status, Unscheduled(_))
-                                        }
-                                        StatusFilter::Scheduled => matches!(f.status, Scheduled),
-                                        StatusFilter::Delayed => matches!(f.status, Delayed { .. }),
+                            .collect::<Vec<_>>();
+                        let rotations = rows
+                            .iter()
+                            .map(|r| &r.aircraft)
+                            .collect::<std::collections::BTreeSet<_>>()
+                            .len();
+                        let mut table = tabled::Table::new(&rows);
+                        style_table(&mut table, config.table_style);
+                        table.with(tabled::settings::Alignment::left());
+                        println!("{}", table);
+                        println!(
+                            "Would unschedule {} flight{} across {} rotation{}\nAdditional compensation exposure: EUR {}\n",
+                            rows.len(),
+                            if rows.len() == 1 { "" } else { "s" },
+                            rotations,
+                            if rotations == 1 { "" } else { "s" },
+                            report.compensation
+                        );
+                    }
+                    Command::Curfew {
+                        airport,
+                        curfew,
+                        push,
+                    } => {
+                        let airport_id = Arc::from(airport.as_str());
+                        let result = match push {
+                            true => schedule.push_curfew(airport_id, curfew.clone()),
+                            false => schedule.apply_curfew_with(airport_id, curfew.clone()),
+                        };
+                        match result.map(|_| ()) {
+                            Ok(()) => {}
+                            Err(IrropsError::UnknownAirport(_)) => {
+                                println!("{}", unknown_airport(&schedule, &airport));
+                                continue;
+                            }
+                            Err(e) => {
+                                println!("{}", e);
+                                continue;
+                            }
+                        }
+                        let report = schedule.last_report().unwrap();
+                        println!(
+                            "\nCurfew applied at {} ({})\n\nImpact:\n  Delayed: {} flight{}\n  Unscheduled: {} flight{}\n\nFirst break:\n  {}\n",
+                            airport,
+                            curfew,
+                            report.affected.len(),
+                            if report.affected.len() == 1 { "" } else { "s" },
+                            report.unscheduled.len(),
+                            if report.unscheduled.len() == 1 {
+                                ""
+                            } else {
+                                "s "
+                            },
+                            match &report.first_break {
+                                None => "None".to_string(),
+                                Some((flight_id, reason)) =>
+                                    format!("{} ({:?})", flight_id, reason),
+                            },
+                        );
+                        print_exemptions(report);
+                        print_slot_violations(report);
+                    }
+                    Command::Atfm { scope, delays } => {
+                        let target = match scope {
+                            AtfmScope::Window { airport, from, to } => {
+                                if !schedule.airports.contains_key(airport.as_str()) {
+                                    println!("{}", unknown_airport(&schedule, &airport));
+                                    continue;
+                                }
+                                AtfmTarget::Airport {
+                                    airport: Arc::from(airport),
+                                    from,
+                                    to,
+                                }
+                            }
+                            AtfmScope::Flights(ids) => {
+                                match ids
+                                    .iter()
+                                    .map(|f| schedule.find_flight(f))
+                                    .collect::<Result<Vec<_>, _>>()
+                                {
+                                    Ok(keys) => AtfmTarget::Flights(keys),
+                                    Err(e) => {
+                                        println!("{}", flight_error(&schedule, e));
+                                        continue;
                                     }
-                                } else {
-                                    true
                                 }
-                            })
-                            .collect();
-                        if filtered_flights.is_empty() {
-                            println!("No matching flights found.")
-                        } else {
-                            let mut table = tabled::Table::new(&filtered_flights);
-                            table.with(Style::rounded());
-                            table.with(tabled::settings::Alignment::left());
-                            if filtered_flights.len() > 20 {
-                                paginate(table.to_string());
-                            } else {
-                                println!("{}", table);
                             }
+                        };
+                        schedule.apply_atfm(target.clone(), delays.clone());
+                        let report = schedule.last_report().unwrap();
+                        println!(
+                            "\nATFM regulation on {} ({})\n\nImpact:\n  Delayed: {} flight{}\n  Unscheduled: {} flight{}\n  Delay minutes: {}\n\nFirst break:\n  {}\n",
+                            target,
+                            delays,
+                            report.affected.len(),
+                            if report.affected.len() == 1 { "" } else { "s" },
+                            report.unscheduled.len(),
+                            if report.unscheduled.len() == 1 {
+                                ""
+                            } else {
+                                "s"
+                            },
+                            report.delay_minutes,
+                            match &report.first_break {
+                                None => "None".to_string(),
+                                Some((flight_id, reason)) => describe_unscheduled(
+                                    report,
+                                    &schedule.delay_codes,
+                                    flight_id,
+                                    reason
+                                ),
+                            },
+                        );
+                        print_exemptions(report);
+                        print_slot_violations(report);
+                    }
+                    Command::DelayAirport {
+                        airport: id,
+                        minutes,
+                        from,
+                        to,
+                    } => {
+                        if !schedule.airports.contains_key(id.as_str()) {
+                            println!("{}", unknown_airport(&schedule, &id));
+                            continue;
                         }
+                        let from = from.unwrap_or(schedule.clock);
+                        schedule.delay_airport(Arc::from(id.as_str()), minutes, from, to);
+                        let report = schedule.last_report().unwrap();
+                        println!(
+                            "\nDepartures from {} delayed by {} min\n\nImpact:\n  Delayed: {} flight{}\n  Unscheduled: {} flight{}\n  Delay minutes: {}\n\nFirst break:\n  {}\n",
+                            id,
+                            minutes,
+                            report.affected.len(),
+                            if report.affected.len() == 1 { "" } else { "s" },
+                            report.unscheduled.len(),
+                            if report.unscheduled.len() == 1 {
+                                ""
+                            } else {
+                                "s"
+                            },
+                            report.delay_minutes,
+                            match &report.first_break {
+                                None => "None".to_string(),
+                                Some((flight_id, reason)) => describe_unscheduled(
+                                    report,
+                                    &schedule.delay_codes,
+                                    flight_id,
+                                    reason
+                                ),
+                            },
+                        );
+                        print_exemptions(report);
+                        print_slot_violations(report);
                     }
-                    "delay" => {
-                        if let (Some(id), Some(mins)) = (parts.get(1), parts.get(2)) {
-                            let mins_u64 = mins.parse::<u64>().unwrap_or(0);
-                            schedule.apply_delay(Arc::from(*id), mins_u64);
-                            let report = schedule.last_report().unwrap();
-                            println!(
-                                "\nFlight {} delayed by {} min\n\nImpact:\n  Delayed: {} flight{}\n  Unscheduled: {} flight{}\n\nFirst break:\n  {}\n",
-                                *id,
-                                mins_u64,
-                                report.affected.len(),
-                                if report.affected.len() == 1 { "" } else { "s " },
-                                report.unscheduled.len(),
-                                if report.unscheduled.len() == 1 {
-                                    ""
-                                } else {
-                                    "s "
-                                },
-                                match &report.first_break {
-                                    None => "None".to_string(),
-                                    Some((flight_id, reason)) =>
-                                        format!("{} ({:?})", flight_id, reason),
-                                }
-                            );
-                        } else {
-                            println!("Usage: delay <flight_id> <minutes>");
+                    Command::Capacity {
+                        airport: id,
+                        percent,
+                        from,
+                        to,
+                    } => {
+                        if !schedule.airports.contains_key(id.as_str()) {
+                            println!("{}", unknown_airport(&schedule, &id));
+                            continue;
+                        }
+                        if let Err(e) = schedule.apply_capacity_reduction(
+                            Arc::from(id.as_str()),
+                            from,
+                            to,
+                            percent,
+                        ) {
+                            println!("Cannot reduce capacity: {}", e);
+                            continue;
+                        }
+                        let report = schedule.last_report().unwrap();
+                        println!(
+                            "\nCapacity at {} cut by {}% from {} to {}\n\nImpact:\n  Delayed: {} flight{}\n  Unscheduled: {} flight{}\n  Delay minutes: {}\n\nFirst break:\n  {}\n",
+                            id,
+                            percent,
+                            from,
+                            to,
+                            report.affected.len(),
+                            if report.affected.len() == 1 { "" } else { "s" },
+                            report.unscheduled.len(),
+                            if report.unscheduled.len() == 1 {
+                                ""
+                            } else {
+                                "s"
+                            },
+                            report.delay_minutes,
+                            match &report.first_break {
+                                None => "None".to_string(),
+                                Some((flight_id, reason)) => describe_unscheduled(
+                                    report,
+                                    &schedule.delay_codes,
+                                    flight_id,
+                                    reason
+                                ),
+                            },
+                        );
+                        print_exemptions(report);
+                        print_slot_violations(report);
+                    }
+                    Command::CloseRunway {
+                        airport: id,
+                        runway,
+                        from,
+                        to,
+                    } => {
+                        if !schedule.airports.contains_key(id.as_str()) {
+                            println!("{}", unknown_airport(&schedule, &id));
+                            continue;
                         }
+                        if let Err(e) = schedule.close_runway(
+                            Arc::from(id.as_str()),
+                            Arc::from(runway.as_str()),
+                            from,
+                            to,
+                        ) {
+                            println!("Cannot close runway: {}", e);
+                            continue;
+                        }
+                        let report = schedule.last_report().unwrap();
+                        println!(
+                            "\n{}\n\nImpact:\n  Delayed: {} flight{}\n  Unscheduled: {} flight{}\n  Delay minutes: {}\n\nFirst break:\n  {}\n",
+                            match &report.kind {
+                                DisruptionType::CapacityReduction { percent, .. } => format!(
+                                    "Runway {} at {} closed from {} to {}: capacity cut by {}%",
+                                    runway, id, from, to, percent
+                                ),
+                                _ => format!(
+                                    "Runway {} is the only one at {}: airport closed from {} to {}",
+                                    runway, id, from, to
+                                ),
+                            },
+                            report.affected.len(),
+                            if report.affected.len() == 1 { "" } else { "s" },
+                            report.unscheduled.len(),
+                            if report.unscheduled.len() == 1 {
+                                ""
+                            } else {
+                                "s"
+                            },
+                            report.delay_minutes,
+                            match &report.first_break {
+                                None => "None".to_string(),
+                                Some((flight_id, reason)) => describe_unscheduled(
+                                    report,
+                                    &schedule.delay_codes,
+                                    flight_id,
+                                    reason
+                                ),
+                            },
+                        );
+                        print_exemptions(report);
+                        print_slot_violations(report);
                     }
-                    "curfew" => {
-                        if let (Some(id), Some(from), Some(to)) =
-                            (parts.get(1), parts.get(2), parts.get(3))
-                        {
-                            let from_u64 = from.parse::<u64>().unwrap_or(0);
-                            let to_u64 = to.parse::<u64>().unwrap_or(0);
-                            schedule.apply_curfew(Arc::from(*id), Time(from_u64), Time(to_u64));
-                            let report = schedule.last_report().unwrap();
-                            println!(
-                                "\nCurfew applied at {} ({} - {})\n\nImpact:\n  Unscheduled: {} flight{}\n\nFirst break:\n  {}\n",
-                                *id,
-                                Time(from_u64),
-                                Time(to_u64),
-                                report.unscheduled.len(),
-                                if report.unscheduled.len() == 1 {
-                                    ""
-                                } else {
-                                    "s "
-                                },
-                                match &report.first_break {
-                                    None => "None".to_string(),
-                                    Some((flight_id, reason)) =>
-                                        format!("{} ({:?})", flight_id, reason),
-                                },
-                            );
-                        } else {
-                            println!("Usage: curfew <airport_id> <minutes> <minutes>");
+                    Command::CloneDay { day, copies } => match schedule.clone_day(day, copies) {
+                        Ok(added) => println!(
+                            "Cloned day {} into days {}..{}: {} flight{} added, unassigned until the next recover",
+                            day,
+                            day + 1,
+                            day + copies,
+                            added,
+                            if added == 1 { "" } else { "s" }
+                        ),
+                        Err(e) => println!("Cannot clone day {}: {}", day, e),
+                    },
+                    Command::Retime { flight, minutes } => {
+                        let key = match schedule.find_flight(&flight) {
+                            Ok(key) => key,
+                            Err(e) => {
+                                println!(
+                                    "Cannot retime flight {}: {}",
+                                    flight,
+                                    flight_error(&schedule, e)
+                                );
+                                continue;
+                            }
+                        };
+                        match schedule.retime(key.clone(), minutes) {
+                            Ok(()) => {
+                                if let Some(flight) = schedule.flight(&key) {
+                                    println!(
+                                        "Flight {} retimed by {:+} min, departing {} ({})",
+                                        key, minutes, flight.departure_time, flight.status
+                                    );
+                                }
+                                if minutes > 0 {
+                                    let report = schedule.last_report().unwrap();
+                                    println!(
+                                        "Impact: {} delayed, {} unscheduled",
+                                        report.affected.len(),
+                                        report.unscheduled.len()
+                                    );
+                                }
+                            }
+                            Err(e) => println!("Cannot retime flight {}: {}", key, e),
                         }
                     }
-                    "explain" => {
+                    Command::Rollover { days } => match schedule.rollover(days) {
+                        Ok(summary) => println!(
+                            "Rolled over {} day{}: {} flight{} dropped, {} loaded from beyond the horizon; clock now {}",
+                            days,
+                            if days == 1 { "" } else { "s" },
+                            summary.dropped,
+                            if summary.dropped == 1 { "" } else { "s" },
+                            summary.loaded,
+                            schedule.clock
+                        ),
+                        Err(e) => println!("Cannot roll over: {}", e),
+                    },
+                    Command::Explain { full } => {
                         if let Some(report) = schedule.last_report() {
                             let trigger = match &report.kind {
                                 DisruptionType::Delay { flight, delay_by } => {
                                     format!("Flight {flight} delayed by {delay_by} min")
                                 }
-                                DisruptionType::Curfew { airport, from, to } => {
-                                    format!("Curfew applied at {airport} ({from} - {to})")
+                                DisruptionType::Curfew {
+                                    airport,
+                                    from,
+                                    to,
+                                    arrival_tolerance,
+                                } => {
+                                    let curfew = Curfew {
+                                        from: *from,
+                                        to: *to,
+                                        arrival_tolerance: *arrival_tolerance,
+                                    };
+                                    format!("Curfew applied at {airport} ({curfew})")
+                                }
+                                DisruptionType::Atfm { target, delays } => {
+                                    format!("ATFM regulation on {target} ({delays})")
                                 }
+                                DisruptionType::AirportDelay {
+                                    airport,
+                                    delay_by,
+                                    from,
+                                    to,
+                                } => format!(
+                                    "Departures from {airport} delayed by {delay_by} min from {from}{}",
+                                    to.map_or(String::new(), |t| format!(" to {t}"))
+                                ),
+                                DisruptionType::CapacityReduction {
+                                    airport,
+                                    runway: None,
+                                    from,
+                                    to,
+                                    percent,
+                                } => format!(
+                                    "Capacity at {airport} cut by {percent}% from {from} to {to}"
+                                ),
+                                DisruptionType::CapacityReduction {
+                                    airport,
+                                    runway: Some(runway),
+                                    from,
+                                    to,
+                                    percent,
+                                } => format!(
+                                    "Runway {runway} at {airport} closed from {from} to {to}, capacity cut by {percent}%"
+                                ),
                             };
-                            if parts.get(1) == Some(&"full") {
+                            if full {
                                 let impact = match &report.kind {
-                                    DisruptionType::Delay { .. } if report.affected.len() > 0 => {
+                                    DisruptionType::Delay { .. }
+                                    | DisruptionType::Atfm { .. }
+                                    | DisruptionType::AirportDelay { .. }
+                                    | DisruptionType::CapacityReduction { .. }
+                                        if !report.affected.is_empty() =>
+                                    {
                                         &format!(
                                             "\n\nDelayed flights ({}):{}",
                                             report.affected.len(),
                                             report
                                                 .affected
                                                 .iter()
-                                                .map(|f| format!("\n  {f}"))
+                                                .map(|f| match schedule.delay_codes.rotational {
+                                                    Some(code) if matches!(&report.kind, DisruptionType::Delay { flight, .. } if flight != f) => {
+                                                        format!("\n  {f} (IATA {code:02}){}", change_diff(report, f))
+                                                    }
+                                                    _ => format!("\n  {f}{}", change_diff(report, f)),
+                                                })
                                                 .collect::<String>()
                                         )
                                     }
-                                    DisruptionType::Delay { .. } => "\n\nDelayed flights:\n  None",
+                                    DisruptionType::Delay { .. }
+                                    | DisruptionType::Atfm { .. }
+                                    | DisruptionType::AirportDelay { .. }
+                                    | DisruptionType::CapacityReduction { .. } => {
+                                        "\n\nDelayed flights:\n  None"
+                                    }
                                     DisruptionType::Curfew { .. } => "",
                                 };
                                 println!(
-                                    "\nExplain (last disruption)\n\nTrigger:\n  {}{}{}\n",
+                                    "\nExplain (last disruption)\n\nTrigger:\n  {}{}{}\n\nCompensation exposure:\n  EUR {}\n\nSeverity:\n  {} ({} delay min, {} pax)\n",
                                     trigger,
                                     impact,
-                                    if report.unscheduled.len() == 0 {
+                                    if report.unscheduled.is_empty() {
                                         "\n\nUnscheduled:\n  None".to_string()
                                     } else {
                                         format!(
@@ -276,16 +1710,43 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 .unscheduled
                                                 .iter()
                                                 .map(|(fid, reason)| format!(
-                                                    "\n  {fid} ({:?})",
-                                                    reason
+                                                    "\n  {}{}",
+                                                    describe_unscheduled(
+                                                        report,
+                                                        &schedule.delay_codes,
+                                                        fid,
+                                                        reason
+                                                    ),
+                                                    change_diff(report, fid)
                                                 ))
                                                 .collect::<String>()
                                         )
                                     },
+                                    report.compensation,
+                                    report.severity,
+                                    report.delay_minutes,
+                                    report.passengers,
                                 );
+                                if let Some((flight_id, reason)) = &report.first_break {
+                                    println!(
+                                        "First break:\n  {}{}\n",
+                                        describe_unscheduled(
+                                            report,
+                                            &schedule.delay_codes,
+                                            flight_id,
+                                            reason
+                                        ),
+                                        worst_break(report, &schedule.delay_codes)
+                                    );
+                                }
+                                print_exemptions(report);
+                                print_slot_violations(report);
                             } else {
                                 let impact = match &report.kind {
-                                    DisruptionType::Delay { .. } => &format!(
+                                    DisruptionType::Delay { .. }
+                                    | DisruptionType::Atfm { .. }
+                                    | DisruptionType::AirportDelay { .. }
+                                    | DisruptionType::CapacityReduction { .. } => &format!(
                                         "\n  Delayed: {} flight{}",
                                         report.affected.len(),
                                         if report.affected.len() == 1 { "" } else { "s" }
@@ -293,7 +1754,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     DisruptionType::Curfew { .. } => "",
                                 };
                                 println!(
-                                    "\nExplain (last disruption)\n\nTrigger:\n  {}\n\nImpact:{}\n  Unscheduled: {} flight{}\n\nFirst break:\n  {}\n",
+                                    "\nExplain (last disruption)\n\nTrigger:\n  {}\n\nImpact:{}\n  Unscheduled: {} flight{}\n  Severity: {}\n\nFirst break:\n  {}{}\n",
                                     trigger,
                                     impact,
                                     report.unscheduled.len(),
@@ -302,106 +1763,945 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     } else {
                                         "s "
                                     },
+                                    report.severity,
                                     match &report.first_break {
                                         None => "None".to_string(),
-                                        Some((flight_id, reason)) =>
-                                            format!("{} ({:?})", flight_id, reason),
-                                    }
+                                        Some((flight_id, reason)) => describe_unscheduled(
+                                            report,
+                                            &schedule.delay_codes,
+                                            flight_id,
+                                            reason
+                                        ),
+                                    },
+                                    worst_break(report, &schedule.delay_codes)
                                 );
                             }
                         } else {
                             println!("No report to explain");
                         }
                     }
-                    "recover" => {
-                        schedule.assign();
-                        println!("Recovery cycle complete.");
+                    Command::ExportIcs { path, start } => {
+                        let result = std::fs::File::create(&path).and_then(|file| {
+                            let mut w = std::io::BufWriter::new(file);
+                            export::ics(&mut w, &schedule, start)?;
+                            w.flush()
+                        });
+                        match result {
+                            Ok(()) => println!("Exported timeline to {}", path),
+                            Err(e) => println!("Export failed: {}", e),
+                        }
+                    }
+                    Command::ExportJson { path } => {
+                        match golden::write(Path::new(&path), &golden::outcomes(&schedule)) {
+                            Ok(()) => println!("Exported flights to {}", path),
+                            Err(e) => println!("Export failed: {}", e),
+                        }
                     }
-                    "stats" => {
-                        let mut s = 0;
-                        let mut d = 0;
-                        let mut uw = 0;
-                        let mut umde = 0;
-                        let mut uam = 0;
-                        let mut uac = 0;
-                        let mut ubc = 0;
-                        let total = schedule.flights.len();
-
-                        for f in &schedule.flights {
-                            match f.status {
-                                Scheduled => s += 1,
-                                Delayed { .. } => d += 1,
-                                Unscheduled(Waiting) => uw += 1,
-                                Unscheduled(MaxDelayExceeded) => umde += 1,
-                                Unscheduled(AirportCurfew) => uac += 1,
-                                Unscheduled(AircraftMaintenance) => uam += 1,
-                                Unscheduled(BrokenChain) => ubc += 1,
+                    Command::ExportFrame { format, path } => {
+                        #[cfg(feature = "dataframe")]
+                        {
+                            use irrops::cli::commands::FrameExport;
+                            let result =
+                                std::fs::File::create(&path)
+                                    .map_err(Into::into)
+                                    .and_then(|file| match format {
+                                        FrameExport::Parquet => {
+                                            export::flights_parquet(file, &schedule)
+                                        }
+                                        FrameExport::Ipc => export::flights_ipc(file, &schedule),
+                                    });
+                            match result {
+                                Ok(()) => println!("Exported flights as {} to {}", format, path),
+                                Err(e) => println!("Export failed: {}", e),
                             }
                         }
-
-                        println!("\nFleet Utilization Summary:");
-                        println!("---------------------------");
+                        #[cfg(not(feature = "dataframe"))]
                         println!(
-                            "Scheduled:                          {} ({:.1}%)",
-                            s,
-                            (s as f64 / total as f64) * 100.0
+                            "Cannot export {} to {}: irrops was built without the dataframe feature",
+                            format, path
                         );
+                    }
+                    Command::ExportCsv { what, path } => {
+                        let result = std::fs::File::create(&path).and_then(|file| {
+                            let mut w = std::io::BufWriter::new(file);
+                            match what {
+                                CsvExport::Flights => export::flights_csv(&mut w, &schedule),
+                                CsvExport::Stats => export::stats_csv(&mut w, &schedule.flights),
+                                CsvExport::History => export::history_csv(&mut w, &schedule),
+                            }?;
+                            w.flush()
+                        });
+                        match result {
+                            Ok(()) => println!("Exported {} to {}", what, path),
+                            Err(e) => println!("Export failed: {}", e),
+                        }
+                    }
+                    Command::History { by_severity } => {
+                        let mut rows = schedule
+                            .history
+                            .iter()
+                            .enumerate()
+                            .map(|(i, report)| HistoryRow {
+                                seq: i + 1,
+                                trigger: match &report.kind {
+                                    DisruptionType::Delay { flight, delay_by } => {
+                                        format!("delay {} +{}", flight, delay_by)
+                                    }
+                                    DisruptionType::Curfew {
+                                        airport,
+                                        from,
+                                        to,
+                                        arrival_tolerance,
+                                    } => format!(
+                                        "curfew {} {}",
+                                        airport,
+                                        Curfew {
+                                            from: *from,
+                                            to: *to,
+                                            arrival_tolerance: *arrival_tolerance,
+                                        }
+                                    ),
+                                    DisruptionType::Atfm { target, delays } => {
+                                        format!("atfm {} {}", target, delays)
+                                    }
+                                    DisruptionType::AirportDelay {
+                                        airport,
+                                        delay_by,
+                                        from,
+                                        to,
+                                    } => format!(
+                                        "delay-airport {} +{} {}{}",
+                                        airport,
+                                        delay_by,
+                                        from,
+                                        to.map_or(String::new(), |t| format!("-{}", t))
+                                    ),
+                                    DisruptionType::CapacityReduction {
+                                        airport,
+                                        runway: None,
+                                        from,
+                                        to,
+                                        percent,
+                                    } => format!(
+                                        "capacity {} -{}% {}-{}",
+                                        airport, percent, from, to
+                                    ),
+                                    DisruptionType::CapacityReduction {
+                                        airport,
+                                        runway: Some(runway),
+                                        from,
+                                        to,
+                                        percent,
+                                    } => format!(
+                                        "close-runway {} {} -{}% {}-{}",
+                                        airport, runway, percent, from, to
+                                    ),
+                                },
+                                delayed: report.affected.len(),
+                                unscheduled: report.unscheduled.len(),
+                                delay_minutes: report.delay_minutes,
+                                passengers: report.passengers,
+                                compensation: report.compensation,
+                                severity: report.severity,
+                            })
+                            .collect::<Vec<_>>();
+                        if rows.is_empty() {
+                            println!("No disruptions yet.");
+                            continue;
+                        }
+                        if by_severity {
+                            rows.sort_by_key(|r| std::cmp::Reverse(r.severity));
+                        }
+                        let mut table = tabled::Table::new(&rows);
+                        style_table(&mut table, config.table_style);
+                        table.with(tabled::settings::Alignment::left());
+                        println!("{}", table);
+                    }
+                    Command::GroundOccupancy { airport: ap_id } => {
+                        let Some(airport) = schedule.airports.get(ap_id.as_str()) else {
+                            println!("{}", unknown_airport(&schedule, &ap_id));
+                            continue;
+                        };
+                        let spells = schedule.ground_spells(&airport.id);
+                        if spells.is_empty() {
+                            println!("No aircraft on the ground at {}.", ap_id);
+                            continue;
+                        }
+                        let rows = spells
+                            .iter()
+                            .map(|s| GroundRow {
+                                aircraft: s.aircraft_id.to_string(),
+                                from: s.from,
+                                to: s.to.map_or("---".to_string(), |t| t.to_string()),
+                            })
+                            .collect::<Vec<_>>();
+                        let mut table = tabled::Table::new(&rows);
+                        style_table(&mut table, config.table_style);
+                        table.with(tabled::settings::Alignment::left());
+                        if rows.len() > 20 {
+                            paginate(table.to_string());
+                        } else {
+                            println!("{}", table);
+                        }
+                        let peak = schedule
+                            .ground_occupancy(&airport.id)
+                            .iter()
+                            .map(|(_, count)| *count)
+                            .max()
+                            .unwrap_or(0);
                         println!(
-                            "Delayed:                            {} ({:.1}%)",
-                            d,
-                            (d as f64 / total as f64) * 100.0
+                            "Peak on ground: {} / {} stands",
+                            peak,
+                            airport
+                                .stands
+                                .map_or("unlimited".to_string(), |s| s.to_string())
                         );
+                        for o in schedule
+                            .stand_overflows()
+                            .iter()
+                            .filter(|o| o.airport_id == airport.id)
+                        {
+                            println!(
+                                "Over capacity from {} to {} (peak {})",
+                                o.from,
+                                o.to.map_or("end".to_string(), |t| t.to_string()),
+                                o.peak
+                            );
+                        }
+                    }
+                    Command::Slack { aircraft } => {
+                        let slack = schedule.slack();
+                        if let Some(ac_id) = aircraft {
+                            match slack.iter().find(|r| *r.aircraft_id == *ac_id) {
+                                Some(rotation) if rotation.connections.is_empty() => {
+                                    println!("Aircraft {} flies a single leg.", ac_id)
+                                }
+                                Some(rotation) => {
+                                    let rows = rotation
+                                        .connections
+                                        .iter()
+                                        .map(|c| ConnectionRow {
+                                            inbound: c.inbound.to_string(),
+                                            outbound: c.outbound.to_string(),
+                                            buffer: c.buffer,
+                                        })
+                                        .collect::<Vec<_>>();
+                                    let mut table = tabled::Table::new(&rows);
+                                    style_table(&mut table, config.table_style);
+                                    table.with(tabled::settings::Alignment::left());
+                                    println!("{}", table);
+                                    println!(
+                                        "Absorbable delay: {} min{}",
+                                        rotation.absorbable,
+                                        rotation
+                                            .limited_by
+                                            .as_ref()
+                                            .map(|f| format!(" (limited by {})", f))
+                                            .unwrap_or_default()
+                                    );
+                                }
+                                None if !schedule.aircraft.contains_key(ac_id.as_str()) => {
+                                    println!(
+                                        "{}",
+                                        did_you_mean(
+                                            format!("Unknown aircraft {}", ac_id),
+                                            &schedule.suggest_aircraft(&ac_id)
+                                        )
+                                    )
+                                }
+                                None => println!("No rotation found for aircraft {}", ac_id),
+                            }
+                        } else if slack.is_empty() {
+                            println!("No rotations found.");
+                        } else {
+                            let rows = slack
+                                .iter()
+                                .map(|r| SlackRow {
+                                    aircraft: r.aircraft_id.to_string(),
+                                    legs: r.legs,
+                                    min_buffer: r
+                                        .min_buffer
+                                        .map_or("---".to_string(), |b| b.to_string()),
+                                    absorbable: r.absorbable,
+                                    limited_by: r
+                                        .limited_by
+                                        .as_ref()
+                                        .map_or("---".to_string(), |f| f.to_string()),
+                                })
+                                .collect::<Vec<_>>();
+                            let mut table = tabled::Table::new(&rows);
+                            style_table(&mut table, config.table_style);
+                            table.with(tabled::settings::Alignment::left());
+                            if rows.len() > 20 {
+                                paginate(table.to_string());
+                            } else {
+                                println!("{}", table);
+                            }
+                        }
+                    }
+                    Command::Candidates { flight } => {
+                        let key = match schedule.find_flight(&flight) {
+                            Ok(key) => key,
+                            Err(e) => {
+                                println!("{}", flight_error(&schedule, e));
+                                continue;
+                            }
+                        };
+                        let verdict = |problem: Option<String>| match problem {
+                            Some(problem) => problem.red().to_string(),
+                            None => "ok".green().to_string(),
+                        };
+                        let rows = schedule
+                            .candidates(&key)
+                            .into_iter()
+                            .map(|c| CandidateRow {
+                                aircraft: c.aircraft_id.to_string(),
+                                feasible: if c.is_feasible() {
+                                    "yes".green().to_string()
+                                } else {
+                                    "no".red().to_string()
+                                },
+                                location: verdict(c.wrong_location.map(|ap| format!("at {}", ap))),
+                                busy: verdict(c.busy_with.map(|f| format!("flies {}", f))),
+                                maintenance: verdict(
+                                    c.maintenance.then(|| "unavailable".to_string()),
+                                ),
+                                curfew: verdict(c.curfew.map(|reason| format!("{:?}", reason))),
+                                fleet_type: verdict(
+                                    c.restricted_type
+                                        .map(|t| format!("{} restricted", t))
+                                        .or(c.out_of_range.map(|r| format!("range {} km", r)))
+                                        .or(c.lacks_etops.then(|| "not ETOPS".to_string())),
+                                ),
+                                spill: c.spill,
+                            })
+                            .collect::<Vec<_>>();
+                        if rows.is_empty() {
+                            println!("No aircraft found.");
+                        } else {
+                            let mut table = tabled::Table::new(&rows);
+                            style_table(&mut table, config.table_style);
+                            table.with(tabled::settings::Alignment::left());
+                            println!("\nCandidates for flight {}:\n{}", key, table);
+                        }
+                    }
+                    Command::Impact { delay, top } => {
+                        let rows = schedule
+                            .impact(delay)
+                            .into_iter()
+                            .take(top)
+                            .enumerate()
+                            .map(|(i, impact)| ImpactRow {
+                                rank: i + 1,
+                                flight: impact.flight_id.to_string(),
+                                delayed: impact.delayed,
+                                unscheduled: impact.unscheduled,
+                                delay_minutes: impact.delay_minutes,
+                            })
+                            .collect::<Vec<_>>();
+                        if rows.is_empty() {
+                            println!("No operating flights to analyze.");
+                        } else {
+                            let mut table = tabled::Table::new(&rows);
+                            style_table(&mut table, config.table_style);
+                            table.with(tabled::settings::Alignment::left());
+                            println!("\nImpact of a {} min delay per flight:\n{}", delay, table);
+                        }
+                    }
+                    Command::Feed {
+                        source,
+                        format,
+                        every,
+                    } => {
+                        if let Some(interval) = every {
+                            println!(
+                                "Polling {} every {}s; press Enter to stop",
+                                source,
+                                interval.as_secs()
+                            );
+                        }
+                        loop {
+                            let day_one = time::display().1;
+                            match feed::poll_as(&source, format, day_one) {
+                                Ok(observations) => {
+                                    let ingested = schedule.ingest(&observations);
+                                    print_ingested(&observations, &ingested);
+                                }
+                                Err(e) => println!("Cannot read feed {}: {}", source, e),
+                            }
+                            let Some(interval) = every else { break };
+                            // each round's reports go out now rather than when polling stops
+                            print_alerts(&config.alerts, &schedule);
+                            for report in &schedule.history[reports_before..] {
+                                webhook::notify(&webhooks, webhook::report_payload(report));
+                            }
+                            reports_before = schedule.history.len();
+                            match reader
+                                .get_or_insert_with(play::read_line)
+                                .recv_timeout(interval)
+                            {
+                                Ok(_) | Err(RecvTimeoutError::Disconnected) => {
+                                    reader = None;
+                                    println!("Stopped polling {}", source);
+                                    break;
+                                }
+                                Err(RecvTimeoutError::Timeout) => {}
+                            }
+                        }
+                    }
+                    Command::ScriptRun { path } => {
+                        let lines = match script::is_rhai(&path) {
+                            true => script::run_rhai(&mut schedule, &path),
+                            false => script::expand(&path),
+                        };
+                        match lines {
+                            Ok(lines) => lines
+                                .into_iter()
+                                .rev()
+                                .for_each(|line| pending.push_front(line)),
+                            Err(e) => println!("Cannot run script {}: {}", path, e),
+                        }
+                    }
+                    Command::Foreach { filter, command } => {
+                        match script::foreach(&schedule, &filter, &command) {
+                            Ok(commands) => commands
+                                .into_iter()
+                                .rev()
+                                .for_each(|line| pending.push_front(line)),
+                            Err(e) => println!("Cannot run foreach: {}", e),
+                        }
+                    }
+                    Command::Merge { path } => {
+                        let other = match Schedule::load_from_file(&path) {
+                            Ok(other) => other,
+                            Err(e) => {
+                                println!("Cannot load {}: {}", path, e);
+                                continue;
+                            }
+                        };
+                        match schedule.merge(other) {
+                            Ok(merged) => {
+                                println!(
+                                    "Merged {}: {} aircraft, {} airports, {} flights, {} charters added",
+                                    path,
+                                    merged.aircraft,
+                                    merged.airports,
+                                    merged.flights,
+                                    merged.charters
+                                );
+                                for issue in validate::load_warnings(&schedule) {
+                                    println!("Warning: {}", issue.message);
+                                }
+                            }
+                            Err(e) => println!("Cannot merge {}: {}", path, e),
+                        }
+                    }
+                    Command::Audit => {
+                        let violations = schedule.audit();
+                        if violations.is_empty() {
+                            println!("No invariant violations");
+                        } else {
+                            println!("\nInvariant violations:");
+                            for violation in &violations {
+                                println!("  {}", violation);
+                            }
+                            println!();
+                        }
+                    }
+                    Command::Baseline => {
+                        schedule.capture_baseline();
                         println!(
-                            "Unscheduled (Waiting):              {} ({:.1}%)",
-                            uw,
-                            (uw as f64 / total as f64) * 100.0
+                            "Baseline captured: {} flights at {}",
+                            schedule.flights.len(),
+                            schedule.clock
                         );
+                    }
+                    Command::CompareBaseline => {
+                        let Some(baseline) = &schedule.baseline else {
+                            println!("No baseline captured; run baseline first");
+                            continue;
+                        };
+                        let drift = metrics::compare_baseline(baseline, &schedule.flights);
+                        let plural = |n: usize| if n == 1 { "" } else { "s" };
                         println!(
-                            "Unscheduled (Max Delay Exceeded):   {} ({:.1}%)",
-                            umde,
-                            (umde as f64 / total as f64) * 100.0
+                            "\nDrift from baseline:\n  Moved: {} flight{} ({} min, {:.1} min average)\n  Aircraft changed: {} flight{}\n  Lost: {} flight{}\n  Recovered: {} flight{}",
+                            drift.flights_moved,
+                            plural(drift.flights_moved),
+                            drift.drift_minutes,
+                            drift.average_drift(),
+                            drift.aircraft_changed,
+                            plural(drift.aircraft_changed),
+                            drift.flights_lost.len(),
+                            plural(drift.flights_lost.len()),
+                            drift.flights_recovered,
+                            plural(drift.flights_recovered),
                         );
+                        for key in drift.flights_lost.iter().take(RECOVERY_LIST_LIMIT) {
+                            println!("    {}", key);
+                        }
+                        if drift.flights_lost.len() > RECOVERY_LIST_LIMIT {
+                            println!(
+                                "    ... and {} more",
+                                drift.flights_lost.len() - RECOVERY_LIST_LIMIT
+                            );
+                        }
+                        println!();
+                    }
+                    Command::Branch(action) => match action {
+                        BranchAction::New(name) => match branches.create(&name, &schedule) {
+                            Ok(()) => println!(
+                                "Branch {} forked from {} at {} disruptions",
+                                name,
+                                branches.current(),
+                                schedule.history.len()
+                            ),
+                            Err(e) => println!("Cannot create branch: {}", e),
+                        },
+                        BranchAction::Switch(name) => {
+                            match branches.switch(&name, &mut schedule) {
+                                Ok(()) => println!("Switched to branch {}", name),
+                                Err(e) => println!("Cannot switch branch: {}", e),
+                            }
+                            // the history now belongs to another branch; nothing new to notify
+                            continue;
+                        }
+                        BranchAction::List => {
+                            let rows = branches
+                                .list(&schedule)
+                                .into_iter()
+                                .map(|b| BranchRow {
+                                    current: if b.current { "*" } else { "" },
+                                    name: b.name,
+                                    parent: b.parent.unwrap_or_default(),
+                                    forked_at: b.forked_at,
+                                    disruptions: b.disruptions,
+                                    unscheduled: b.unscheduled,
+                                    cost: b.cost,
+                                })
+                                .collect::<Vec<_>>();
+                            let mut table = tabled::Table::new(&rows);
+                            style_table(&mut table, config.table_style);
+                            table.with(tabled::settings::Alignment::left());
+                            println!("{}", table);
+                        }
+                    },
+                    Command::Webhook(action) => match action {
+                        WebhookAction::Add(url) => match Webhook::parse(&url) {
+                            Ok(hook) => {
+                                println!("Webhook {} registered", hook.url());
+                                webhooks.push(hook);
+                            }
+                            Err(e) => println!("Cannot register webhook: {}", e),
+                        },
+                        WebhookAction::Remove(url) => {
+                            let before = webhooks.len();
+                            webhooks.retain(|hook| hook.url() != url);
+                            if webhooks.len() == before {
+                                println!("No webhook {}", url);
+                            }
+                        }
+                        WebhookAction::List if webhooks.is_empty() => {
+                            println!("No webhooks registered")
+                        }
+                        WebhookAction::List => {
+                            webhooks.iter().for_each(|hook| println!("{}", hook.url()))
+                        }
+                    },
+                    Command::Recover {
+                        strategy,
+                        objective,
+                        anneal,
+                    } => {
+                        let strategy = strategy.as_str();
+                        let builtin = matches!(strategy, "greedy" | "flow" | "anneal")
+                            || cfg!(feature = "solver-milp") && strategy == "milp";
+                        if !builtin && !schedule.strategies().any(|p| p.name() == strategy) {
+                            println!("Unknown strategy `{}`", strategy);
+                            continue;
+                        }
+                        let cost_before = schedule.total_cost();
+                        let flights_before = schedule.flights.clone();
+                        #[cfg(feature = "solver-milp")]
+                        if strategy == "milp" {
+                            match schedule.assign_milp() {
+                                Ok(()) => {
+                                    schedule.assign_charters();
+                                    schedule.call_reserves();
+                                    schedule.record_recovery(
+                                        strategy,
+                                        &flights_before,
+                                        cost_before,
+                                    );
+                                    println!(
+                                        "Recovery cost: EUR {} -> EUR {}",
+                                        cost_before,
+                                        schedule.total_cost()
+                                    );
+                                    webhook::notify(
+                                        &webhooks,
+                                        webhook::recovery_payload(
+                                            strategy,
+                                            cost_before,
+                                            schedule.total_cost(),
+                                            &metrics::summarize(&schedule.flights),
+                                        ),
+                                    );
+                                    print_charters(&schedule);
+                                    print_callouts(&schedule);
+                                    print_rest_breaches(&schedule);
+                                    print_frozen(&schedule);
+                                    print_recovery(&flights_before, &schedule);
+                                }
+                                Err(e) => println!("MILP recovery failed: {}", e),
+                            }
+                            print_stand_overflows(&schedule);
+                            continue;
+                        }
+                        match strategy {
+                            "flow" => schedule.recover(Strategy::Flow),
+                            "anneal" => schedule.recover(Strategy::Anneal(anneal)),
+                            "greedy" => schedule.recover(Strategy::Greedy(objective)),
+                            plugin => schedule.recover_with(plugin)?,
+                        }
+                        schedule.record_recovery(strategy, &flights_before, cost_before);
                         println!(
-                            "Unscheduled (Airport Curfew):       {} ({:.1}%)",
-                            uac,
-                            (uac as f64 / total as f64) * 100.0
+                            "Recovery cost: EUR {} -> EUR {}",
+                            cost_before,
+                            schedule.total_cost()
                         );
-                        println!(
-                            "Unscheduled (Aircraft Maintenance): {} ({:.1}%)",
-                            uam,
-                            (uam as f64 / total as f64) * 100.0
+                        webhook::notify(
+                            &webhooks,
+                            webhook::recovery_payload(
+                                strategy,
+                                cost_before,
+                                schedule.total_cost(),
+                                &metrics::summarize(&schedule.flights),
+                            ),
                         );
+                        print_charters(&schedule);
+                        print_callouts(&schedule);
+                        print_rest_breaches(&schedule);
+                        print_frozen(&schedule);
+                        print_stand_overflows(&schedule);
+                        print_recovery(&flights_before, &schedule);
+                    }
+                    Command::Report { path, format } => {
+                        let result = std::fs::File::create(&path).and_then(|file| {
+                            let mut w = std::io::BufWriter::new(file);
+                            report::post_mortem(&mut w, &schedule, format)?;
+                            w.flush()
+                        });
+                        match result {
+                            Ok(()) => println!("Wrote post-mortem to {}", path),
+                            Err(e) => println!("Report failed: {}", e),
+                        }
+                    }
+                    Command::ExperimentAddAircraft {
+                        fleet_type,
+                        base,
+                        count,
+                    } => {
+                        let experiment = match schedule.experiment_add_aircraft(
+                            Arc::from(fleet_type),
+                            Arc::from(base),
+                            count,
+                        ) {
+                            Ok(experiment) => experiment,
+                            Err(e) => {
+                                println!("Cannot run experiment: {}", e);
+                                continue;
+                            }
+                        };
                         println!(
-                            "Unscheduled (Broken Chain):         {} ({:.1}%)",
-                            ubc,
-                            (ubc as f64 / total as f64) * 100.0
+                            "\nExperiment: {} spare {} at {} (hypothetical, schedule unchanged)",
+                            experiment.aircraft.len(),
+                            experiment.fleet_type,
+                            experiment.base
                         );
-                        println!("---------------------------");
-                        println!("Total Flights: {}\n", total);
-                    }
-                    "help" | "?" => {
-                        println!("\nAvailable Commands:");
+                        let mut previous: Option<usize> = None;
+                        for step in &experiment.steps {
+                            println!(
+                                "  +{} aircraft: {} unscheduled{}",
+                                step.added,
+                                step.unscheduled,
+                                match previous {
+                                    Some(before) => format!(
+                                        " ({} saved by this tail)",
+                                        before.saturating_sub(step.unscheduled)
+                                    ),
+                                    None => String::new(),
+                                }
+                            );
+                            previous = Some(step.unscheduled);
+                        }
                         println!(
-                            "  ls [status]         - List all flights in a table or filter by status: u - unscheduled, s - scheduled, d - delayed"
+                            "Saved: {} flight{} ({:.1} per added aircraft)\n",
+                            experiment.saved(),
+                            if experiment.saved() == 1 { "" } else { "s" },
+                            experiment.saved_per_aircraft()
                         );
+                    }
+                    Command::Simulate(config) => {
+                        let started = Instant::now();
+                        let result = match schedule.monte_carlo(config) {
+                            Ok(result) => result,
+                            Err(e) => {
+                                println!("Cannot simulate: {}", e);
+                                continue;
+                            }
+                        };
+                        let config = &result.config;
                         println!(
-                            "  delay <id> <m>      - Inject <m> minutes of delay into flight <id>"
+                            "\nMonte Carlo: {} runs, {}% of flights delayed {}-{} min{}, seed {} (hypothetical, schedule unchanged)",
+                            config.runs,
+                            config.probability,
+                            config.min.min(config.max),
+                            config.min.max(config.max),
+                            if config.recover {
+                                " then recovered"
+                            } else {
+                                ""
+                            },
+                            config.seed
                         );
                         println!(
-                            "  curfew <id> <m> <m> - Inject a curfew from <m> to <m> minutes into airport <id>"
+                            "  Delay minutes: mean {:.1}, p50 {}, p90 {}, max {}",
+                            result.mean_delay_minutes(),
+                            result.delay_minutes_percentile(50),
+                            result.delay_minutes_percentile(90),
+                            result.delay_minutes_percentile(100)
                         );
                         println!(
-                            "  explain [full]      - Explain the most recent disruption (use 'full' for full causal trace)"
+                            "  Unscheduled:   mean {:.1}, p50 {}, p90 {}, max {}",
+                            result.mean_unscheduled(),
+                            result.unscheduled_percentile(50),
+                            result.unscheduled_percentile(90),
+                            result.unscheduled_percentile(100)
                         );
+                        println!("Finished in {:.2?}\n", started.elapsed());
+                    }
+                    Command::Forecast {
+                        aircraft: aircraft_id,
+                    } => {
+                        let Some(forecast) = schedule.forecast(&Arc::from(aircraft_id.as_str()))
+                        else {
+                            println!(
+                                "{}",
+                                did_you_mean(
+                                    format!("Unknown aircraft {}", aircraft_id),
+                                    &schedule.suggest_aircraft(&aircraft_id)
+                                )
+                            );
+                            continue;
+                        };
                         println!(
-                            "  recover             - Re-run assignment to repair unscheduled flights"
+                            "\nForecast for {} from {}:",
+                            forecast.aircraft_id, schedule.clock
                         );
-                        println!("  stats               - Display summary statistics");
-                        println!("  help / ?            - Show this help menu");
-                        println!("  exit / quit         - Exit the simulator\n");
+                        if forecast.legs.is_empty() && forecast.break_at.is_none() {
+                            println!("  No legs left to fly");
+                        }
+                        for leg in &forecast.legs {
+                            println!(
+                                "  {}  {} - {}{}",
+                                leg.flight,
+                                leg.departure_time,
+                                leg.arrival_time,
+                                match leg.knock_on {
+                                    0 => String::new(),
+                                    minutes => format!("  (+{} min knock-on)", minutes),
+                                }
+                            );
+                        }
+                        match &forecast.break_at {
+                            Some((flight_id, reason)) => println!(
+                                "\nBreaks at:\n  {} ({:?}), taking the rest of the rotation with it\n",
+                                flight_id, reason
+                            ),
+                            None => println!("\nBreaks at:\n  None\n"),
+                        }
+                    }
+                    Command::Find(text) => {
+                        let hits = schedule.search(&text);
+                        if hits.is_empty() {
+                            println!("Nothing resembles `{}`.", text);
+                            continue;
+                        }
+                        let flights =
+                            |n: usize| format!("{} flight{}", n, if n == 1 { "" } else { "s" });
+                        let rows = hits.iter().take(FIND_LIMIT).map(|h| match &h.hit {
+                            Hit::Flight {
+                                number,
+                                route,
+                                days,
+                            } => HitRow {
+                                kind: "flight",
+                                name: number.to_string(),
+                                details: match days[..] {
+                                    [day] => format!("{}, day {}", route, day),
+                                    _ => format!(
+                                        "{}, {} days from {} to {}",
+                                        route,
+                                        days.len(),
+                                        days[0],
+                                        days[days.len() - 1]
+                                    ),
+                                },
+                            },
+                            Hit::Route {
+                                route,
+                                flights: count,
+                            } => HitRow {
+                                kind: "route",
+                                name: route.clone(),
+                                details: flights(*count),
+                            },
+                            Hit::Aircraft {
+                                id,
+                                fleet_type,
+                                flights: count,
+                            } => HitRow {
+                                kind: "aircraft",
+                                name: id.to_string(),
+                                details: match fleet_type {
+                                    Some(fleet_type) => {
+                                        format!("{}, {}", fleet_type, flights(*count))
+                                    }
+                                    None => flights(*count),
+                                },
+                            },
+                        });
+                        let mut table = tabled::Table::new(rows);
+                        style_table(&mut table, config.table_style);
+                        table.with(tabled::settings::Alignment::left());
+                        println!("{}", table);
+                        if hits.len() > FIND_LIMIT {
+                            println!("... and {} more", hits.len() - FIND_LIMIT);
+                        }
+                    }
+                    Command::Strandings => {
+                        let rows = schedule
+                            .strandings()
+                            .into_iter()
+                            .map(|s| StrandingRow {
+                                airport: s.airport_id.to_string(),
+                                day: s.day,
+                                passengers: s.passengers,
+                                flights: s
+                                    .flights
+                                    .iter()
+                                    .map(|k| k.to_string())
+                                    .collect::<Vec<_>>()
+                                    .join(", "),
+                            })
+                            .collect::<Vec<_>>();
+                        if rows.is_empty() {
+                            println!("No stranded passengers.");
+                        } else {
+                            let total = rows.iter().map(|r| r.passengers).sum::<u32>();
+                            let mut table = tabled::Table::new(&rows);
+                            style_table(&mut table, config.table_style);
+                            table.with(tabled::settings::Alignment::left());
+                            println!("{}", table);
+                            println!("Stranded overnight: {} passengers", total);
+                        }
+                    }
+                    Command::Utilization => {
+                        let rows =
+                            metrics::utilization(schedule.aircraft.keys(), &schedule.flights)
+                                .into_iter()
+                                .map(|u| UtilizationRow {
+                                    aircraft: u.aircraft_id.to_string(),
+                                    block_hours: format!(
+                                        "{}:{:02}",
+                                        u.block_minutes / 60,
+                                        u.block_minutes % 60
+                                    ),
+                                    cycles: u.cycles,
+                                })
+                                .collect::<Vec<_>>();
+                        if rows.is_empty() {
+                            println!("No aircraft found.");
+                        } else {
+                            let mut table = tabled::Table::new(&rows);
+                            style_table(&mut table, config.table_style);
+                            table.with(tabled::settings::Alignment::left());
+                            println!("{}", table);
+                        }
+                    }
+                    Command::Watch(Some(view)) => {
+                        print_watch(&schedule, &view, config.table_style);
+                        watching = Some(*view);
+                    }
+                    Command::Watch(None) => match watching.take() {
+                        Some(_) => println!("Stopped watching"),
+                        None => println!("Not watching anything"),
+                    },
+                    Command::Set(None) => {
+                        let (format, day_one) = time::display();
+                        println!("timefmt {} (DAY1 is {})", format, day_one);
+                        println!("style {}", config.table_style);
+                    }
+                    Command::Set(Some(Setting::TimeFormat { format, start })) => {
+                        let day_one = start.unwrap_or(time::display().1);
+                        time::set_display(format, day_one);
+                        state.time_format = Some(format);
+                        state.start_date = Some(day_one.to_string());
+                        println!("Times now display as {}", schedule.clock);
+                    }
+                    Command::Set(Some(Setting::TableStyle(style))) => {
+                        config.table_style = style;
+                        state.table_style = Some(style);
+                        println!("Tables now drawn in the {} style", style);
+                    }
+                    Command::Help(None) => {
+                        println!("\nAvailable Commands:");
+                        for line in help::overview() {
+                            println!("  {}", line);
+                        }
+                        for command in schedule.commands() {
+                            println!("  {}", command.help());
+                        }
+                        for strategy in schedule.strategies() {
+                            println!(
+                                "  recover --strategy {} - {}",
+                                strategy.name(),
+                                strategy.description()
+                            );
+                        }
+                        println!("\nType help <command> for its arguments and examples.\n");
+                    }
+                    Command::Help(Some(name)) => {
+                        if let Some(detail) = help::detail(&name) {
+                            println!("{}", detail);
+                        } else if let Some(command) = schedule.commands().find(|c| c.name() == name)
+                        {
+                            println!("\n  {}\n", command.help());
+                        } else {
+                            println!("Unknown command: {}", name);
+                        }
+                    }
+                    Command::Exit => break,
+                    Command::Plugin { name, args } => {
+                        let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+                        match schedule.run_command(&name, &args) {
+                            Ok(output) => println!("{}", output),
+                            Err(_) => println!("Unknown command: {}", name),
+                        }
+                    }
+                }
+                if let Some(view) = &watching {
+                    redraw |= mutated || switched || schedule.clock != clock_before;
+                    if redraw && pending.is_empty() {
+                        print_watch(&schedule, view, config.table_style);
+                        redraw = false;
                     }
-                    "exit" | "quit" => break,
-                    _ => println!("Unknown command: {}", parts[0]),
+                }
+                if mutated {
+                    print_alerts(&config.alerts, &schedule);
+                }
+                for report in &schedule.history[reports_before..] {
+                    webhook::notify(&webhooks, webhook::report_payload(report));
                 }
             }
             Err(ReadlineError::Interrupted) => {
@@ -418,5 +2718,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
     }
+    if let Some(dir) = &session_dir
+        && let Err(e) = state.save(dir).and_then(|()| {
+            rl.save_history(&SessionState::history_path(dir))
+                .map_err(io::Error::other)
+        })
+    {
+        println!("Warning: cannot save the session: {}", e);
+    }
     Ok(())
 }
@@ -0,0 +1,312 @@
+use crate::aircraft::AircraftId;
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::*;
+use crate::flight::{Flight, FlightKey, FlightStatus, UnscheduledReason};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GroupBy {
+    Day,
+    Airport,
+    Aircraft,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GroupKey {
+    Day(u64),
+    Id(Arc<str>),
+    Unassigned,
+}
+
+impl fmt::Display for GroupKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GroupKey::Day(d) => write!(f, "DAY{}", d),
+            GroupKey::Id(id) => write!(f, "{}", id),
+            GroupKey::Unassigned => write!(f, "---"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Summary {
+    pub total: usize,
+    pub scheduled: usize,
+    pub delayed: usize,
+    pub waiting: usize,
+    pub max_delay_exceeded: usize,
+    pub airport_curfew: usize,
+    pub outside_operating_hours: usize,
+    pub aircraft_maintenance: usize,
+    pub out_of_position: usize,
+    pub turn_time_violation: usize,
+    pub maintenance_due: usize,
+    pub stand_capacity: usize,
+    pub movement_capacity: usize,
+    pub constraint_violated: usize,
+    pub broken_chain: usize,
+    pub delay_minutes: u64,
+}
+
+impl Summary {
+    pub fn add(&mut self, status: &FlightStatus) {
+        self.total += 1;
+        match status {
+            Scheduled => self.scheduled += 1,
+            Delayed { minutes } => {
+                self.delayed += 1;
+                self.delay_minutes += minutes;
+            }
+            Unscheduled(Waiting) => self.waiting += 1,
+            Unscheduled(MaxDelayExceeded) => self.max_delay_exceeded += 1,
+            Unscheduled(AirportCurfew) => self.airport_curfew += 1,
+            Unscheduled(OutsideOperatingHours) => self.outside_operating_hours += 1,
+            Unscheduled(AircraftMaintenance) => self.aircraft_maintenance += 1,
+            Unscheduled(OutOfPosition { .. }) => self.out_of_position += 1,
+            Unscheduled(TurnTimeViolation { .. }) => self.turn_time_violation += 1,
+            Unscheduled(MaintenanceDue) => self.maintenance_due += 1,
+            Unscheduled(StandCapacity) => self.stand_capacity += 1,
+            Unscheduled(MovementCapacity) => self.movement_capacity += 1,
+            Unscheduled(ConstraintViolated) => self.constraint_violated += 1,
+            Unscheduled(BrokenChain) => self.broken_chain += 1,
+        }
+    }
+
+    pub fn unscheduled(&self) -> usize {
+        self.total - self.scheduled - self.delayed
+    }
+
+    /// Share of all flights, as a percentage.
+    pub fn pct(&self, count: usize) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (count as f64 / self.total as f64) * 100.0
+        }
+    }
+}
+
+pub fn summarize<'a>(flights: impl IntoIterator<Item = &'a Flight>) -> Summary {
+    flights.into_iter().fold(Summary::default(), |mut acc, f| {
+        acc.add(&f.status);
+        acc
+    })
+}
+
+/// Day a flight is listed under by `ls` and `stats by=day`: the one it currently departs
+/// on, so a flight delayed past midnight moves to the next day.
+pub fn listing_day(flight: &Flight) -> u64 {
+    flight.departure_time.day()
+}
+
+/// Flights per listing day, in day order and keeping their order within a day.
+pub fn by_day<'a>(flights: impl IntoIterator<Item = &'a Flight>) -> BTreeMap<u64, Vec<&'a Flight>> {
+    flights.into_iter().fold(
+        BTreeMap::new(),
+        |mut acc: BTreeMap<u64, Vec<&Flight>>, f| {
+            acc.entry(listing_day(f)).or_default().push(f);
+            acc
+        },
+    )
+}
+
+/// Aggregates flights per group; airports group by departure station.
+pub fn group<'a>(
+    flights: impl IntoIterator<Item = &'a Flight>,
+    by: GroupBy,
+) -> BTreeMap<GroupKey, Summary> {
+    flights.into_iter().fold(
+        BTreeMap::new(),
+        |mut acc: BTreeMap<GroupKey, Summary>, f| {
+            let key = match by {
+                GroupBy::Day => GroupKey::Day(listing_day(f)),
+                GroupBy::Airport => GroupKey::Id(f.origin_id.clone()),
+                GroupBy::Aircraft => f
+                    .aircraft_id
+                    .clone()
+                    .map_or(GroupKey::Unassigned, GroupKey::Id),
+            };
+            acc.entry(key).or_default().add(&f.status);
+            acc
+        },
+    )
+}
+
+/// Histogram and percentiles of delay minutes across operating flights, on-time ones
+/// counting as zero.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DelayDistribution {
+    pub flights: usize,
+    pub bucket_width: u64,
+    /// Flight count per bucket; bucket `i` covers `[i * width, (i + 1) * width)` minutes.
+    pub buckets: Vec<usize>,
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub max: u64,
+}
+
+/// Bucket widths tried in turn until the histogram fits in `MAX_BUCKETS` rows.
+const BUCKET_WIDTHS: [u64; 8] = [5, 10, 15, 30, 60, 120, 240, 480];
+const MAX_BUCKETS: u64 = 12;
+
+pub fn delay_distribution<'a>(flights: impl IntoIterator<Item = &'a Flight>) -> DelayDistribution {
+    let mut delays = flights
+        .into_iter()
+        .filter_map(|f| match f.status {
+            Scheduled => Some(0),
+            Delayed { minutes } => Some(minutes),
+            Unscheduled(_) => None,
+        })
+        .collect::<Vec<_>>();
+    if delays.is_empty() {
+        return DelayDistribution::default();
+    }
+    delays.sort_unstable();
+    let max = *delays.last().unwrap();
+    let bucket_width = BUCKET_WIDTHS
+        .into_iter()
+        .find(|w| max / w < MAX_BUCKETS)
+        .unwrap_or(max / MAX_BUCKETS + 1);
+    let mut buckets = vec![0; (max / bucket_width) as usize + 1];
+    for delay in &delays {
+        buckets[(delay / bucket_width) as usize] += 1;
+    }
+    // nearest-rank percentile
+    let percentile = |p: usize| delays[(p * delays.len()).div_ceil(100).max(1) - 1];
+    DelayDistribution {
+        flights: delays.len(),
+        bucket_width,
+        buckets,
+        p50: percentile(50),
+        p90: percentile(90),
+        p99: percentile(99),
+        max,
+    }
+}
+
+/// Flying done by one tail across the flights assigned to it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Utilization {
+    pub aircraft_id: AircraftId,
+    pub block_minutes: u64,
+    pub cycles: usize,
+}
+
+/// Block time and cycles per aircraft, ordered by id; aircraft without flights are
+/// listed with zero.
+pub fn utilization<'a>(
+    aircraft: impl IntoIterator<Item = &'a AircraftId>,
+    flights: impl IntoIterator<Item = &'a Flight>,
+) -> Vec<Utilization> {
+    let mut by_aircraft = aircraft
+        .into_iter()
+        .map(|id| (id.clone(), (0, 0)))
+        .collect::<HashMap<AircraftId, (u64, usize)>>();
+    for f in flights.into_iter().filter(|f| !f.status.is_unscheduled()) {
+        if let Some(id) = &f.aircraft_id {
+            let (block, cycles) = by_aircraft.entry(id.clone()).or_default();
+            *block += f.arrival_time.0 - f.departure_time.0;
+            *cycles += 1;
+        }
+    }
+    let mut rows = by_aircraft
+        .into_iter()
+        .map(|(aircraft_id, (block_minutes, cycles))| Utilization {
+            aircraft_id,
+            block_minutes,
+            cycles,
+        })
+        .collect::<Vec<_>>();
+    rows.sort_by(|a, b| a.aircraft_id.cmp(&b.aircraft_id));
+    rows
+}
+
+/// What a recovery run changed: the flights it put back on a tail and those still
+/// unscheduled afterwards, with the summaries on either side.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecoveryOutcome {
+    pub fixed: Vec<(FlightKey, AircraftId)>,
+    pub broken: Vec<(FlightKey, UnscheduledReason)>,
+    pub before: Summary,
+    pub after: Summary,
+}
+
+/// Compares the flights as they were before a recovery with the flights after it.
+pub fn compare_recovery(before: &[Flight], after: &[Flight]) -> RecoveryOutcome {
+    let was_unscheduled = before
+        .iter()
+        .filter(|f| f.status.is_unscheduled())
+        .map(|f| f.key())
+        .collect::<HashSet<_>>();
+    let mut outcome = RecoveryOutcome {
+        fixed: vec![],
+        broken: vec![],
+        before: summarize(before),
+        after: summarize(after),
+    };
+    for f in after {
+        match (&f.status, &f.aircraft_id) {
+            (Unscheduled(reason), _) => outcome.broken.push((f.key(), reason.clone())),
+            (_, Some(aircraft_id)) if was_unscheduled.contains(&f.key()) => {
+                outcome.fixed.push((f.key(), aircraft_id.clone()))
+            }
+            _ => {}
+        }
+    }
+    outcome
+}
+
+/// How far the flights have drifted from a baseline: operating flights now departing at
+/// another time or on another tail, and flights lost or won back since.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Drift {
+    pub flights_moved: usize,
+    /// Minutes the moved flights depart away from their baseline times, summed.
+    pub drift_minutes: u64,
+    pub aircraft_changed: usize,
+    /// Flights operating in the baseline that are unscheduled or gone now.
+    pub flights_lost: Vec<FlightKey>,
+    /// Flights unscheduled in the baseline that operate now.
+    pub flights_recovered: usize,
+}
+
+impl Drift {
+    pub fn average_drift(&self) -> f64 {
+        match self.flights_moved {
+            0 => 0.0,
+            n => self.drift_minutes as f64 / n as f64,
+        }
+    }
+}
+
+/// Compares the current flights with a baseline, matching them by key.
+pub fn compare_baseline(baseline: &[Flight], current: &[Flight]) -> Drift {
+    let current = current
+        .iter()
+        .map(|f| (f.key(), f))
+        .collect::<HashMap<_, _>>();
+    let mut drift = Drift::default();
+    for before in baseline {
+        let after = current
+            .get(&before.key())
+            .filter(|f| !f.status.is_unscheduled());
+        match (before.status.is_unscheduled(), after) {
+            (false, None) => drift.flights_lost.push(before.key()),
+            (true, Some(_)) => drift.flights_recovered += 1,
+            (false, Some(after)) => {
+                if after.departure_time != before.departure_time {
+                    drift.flights_moved += 1;
+                    drift.drift_minutes += after.departure_time.0.abs_diff(before.departure_time.0);
+                }
+                if after.aircraft_id != before.aircraft_id {
+                    drift.aircraft_changed += 1;
+                }
+            }
+            (true, None) => {}
+        }
+    }
+    drift
+}
@@ -0,0 +1,104 @@
+use crate::airport::AirportId;
+use crate::flight::FlightKey;
+use crate::schedule::schedule::Schedule;
+use crate::time::Time;
+use std::fmt;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Wall-clock pacing of `play`: `speed` simulated seconds pass per real second, counted
+/// from the clock when play started, so speed 60 runs a simulated minute every second.
+pub struct Pace {
+    pub speed: u32,
+    start: Time,
+    started: Instant,
+}
+
+impl Pace {
+    pub fn new(speed: u32, start: Time) -> Pace {
+        Pace {
+            speed,
+            start,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn clock(&self) -> Time {
+        self.clock_after(self.started.elapsed())
+    }
+
+    /// Simulation clock `elapsed` real time after play started.
+    pub fn clock_after(&self, elapsed: Duration) -> Time {
+        self.start + (elapsed.as_secs_f64() * self.speed as f64 / 60.0) as u64
+    }
+}
+
+/// A flight taking off or landing while the clock runs.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Movement {
+    Departed(FlightKey, AirportId, Time),
+    Landed(FlightKey, AirportId, Time),
+}
+
+impl Movement {
+    pub fn time(&self) -> Time {
+        match self {
+            Movement::Departed(_, _, at) | Movement::Landed(_, _, at) => *at,
+        }
+    }
+}
+
+impl fmt::Display for Movement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Movement::Departed(key, airport, at) => {
+                write!(f, "{} {} departed {}", at, key, airport)
+            }
+            Movement::Landed(key, airport, at) => write!(f, "{} {} landed at {}", at, key, airport),
+        }
+    }
+}
+
+/// Departures and arrivals of operating flights after `from` and up to `to`, in time
+/// order.
+pub fn movements(schedule: &Schedule, from: Time, to: Time) -> Vec<Movement> {
+    let within = |t: Time| t > from && t <= to;
+    let mut movements = schedule
+        .flights
+        .iter()
+        .filter(|f| !f.status.is_unscheduled())
+        .flat_map(|f| {
+            [
+                Some(Movement::Departed(
+                    f.key(),
+                    f.origin_id.clone(),
+                    f.departure_time,
+                ))
+                .filter(|_| within(f.departure_time)),
+                Some(Movement::Landed(
+                    f.key(),
+                    f.destination_id.clone(),
+                    f.arrival_time,
+                ))
+                .filter(|_| within(f.arrival_time)),
+            ]
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+    movements.sort_by_key(|m| m.time());
+    movements
+}
+
+/// Reads one line from stdin on a background thread, so `play` can keep the clock
+/// running while it waits for `pause`, `stop` or a command.
+pub fn read_line() -> Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line).is_ok_and(|n| n > 0) {
+            let _ = tx.send(line);
+        }
+    });
+    rx
+}
@@ -0,0 +1,343 @@
+use crate::metrics::{self, Summary};
+use crate::schedule::schedule::{DisruptionReport, DisruptionType, RecoveryRecord, Schedule};
+use std::io;
+use std::io::Write;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+impl Format {
+    /// Picks the format from a `.md` or `.html` file name.
+    pub fn from_path(path: &str) -> Option<Format> {
+        match path
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_ascii_lowercase())
+        {
+            Some(ext) if ext == "md" || ext == "markdown" => Some(Format::Markdown),
+            Some(ext) if ext == "html" || ext == "htm" => Some(Format::Html),
+            _ => None,
+        }
+    }
+}
+
+/// Building blocks of the post-mortem, rendered the same way into either format.
+enum Block {
+    Heading(u8, String),
+    Paragraph(String),
+    List(Vec<String>),
+    Table(Vec<&'static str>, Vec<Vec<String>>),
+}
+
+/// Writes a post-mortem of the whole session: the scenario, every disruption with its
+/// causal trace and every recorded recovery in the order they happened, then final KPIs.
+pub fn post_mortem<W: Write>(w: &mut W, schedule: &Schedule, format: Format) -> io::Result<()> {
+    let blocks = post_mortem_blocks(schedule);
+    match format {
+        Format::Markdown => markdown(w, &blocks),
+        Format::Html => html(w, &blocks),
+    }
+}
+
+fn post_mortem_blocks(schedule: &Schedule) -> Vec<Block> {
+    let mut blocks = vec![Block::Heading(1, "IRROPs post-mortem".to_string())];
+
+    blocks.push(Block::Heading(2, "Scenario".to_string()));
+    let first = schedule.flights.iter().map(|f| f.day).min();
+    let last = schedule.flights.iter().map(|f| f.day).max();
+    blocks.push(Block::List(vec![
+        format!("Aircraft: {}", schedule.aircraft.len()),
+        format!("Airports: {}", schedule.airports.len()),
+        match (first, last) {
+            (Some(first), Some(last)) => format!(
+                "Flights: {} on days {}-{}",
+                schedule.flights.len(),
+                first,
+                last
+            ),
+            _ => "Flights: 0".to_string(),
+        },
+        format!("Clock: {}", schedule.clock),
+        format!("Max delay: {} min", schedule.max_delay),
+    ]));
+
+    blocks.push(Block::Heading(2, "Session".to_string()));
+    if schedule.history.is_empty() && schedule.recoveries.is_empty() {
+        blocks.push(Block::Paragraph(
+            "No disruptions or recoveries.".to_string(),
+        ));
+    }
+    let mut recoveries = schedule.recoveries.iter().peekable();
+    for (i, report) in schedule.history.iter().enumerate() {
+        while let Some(recovery) = recoveries.next_if(|r| r.after_disruptions <= i) {
+            recovery_blocks(&mut blocks, recovery);
+        }
+        disruption_blocks(&mut blocks, i + 1, report);
+    }
+    for recovery in recoveries {
+        recovery_blocks(&mut blocks, recovery);
+    }
+
+    blocks.push(Block::Heading(2, "Final KPIs".to_string()));
+    let summary = metrics::summarize(&schedule.flights);
+    let mut rows = kpi_rows(&summary);
+    rows.push(vec![
+        "Delay minutes".to_string(),
+        summary.delay_minutes.to_string(),
+        String::new(),
+    ]);
+    rows.push(vec![
+        "Compensation exposure (EUR)".to_string(),
+        schedule
+            .compensation
+            .exposure(&schedule.flights)
+            .to_string(),
+        String::new(),
+    ]);
+    rows.push(vec![
+        "Total cost (EUR)".to_string(),
+        schedule.total_cost().to_string(),
+        String::new(),
+    ]);
+    blocks.push(Block::Table(vec!["Metric", "Value", "%"], rows));
+    blocks
+}
+
+fn kpi_rows(summary: &Summary) -> Vec<Vec<String>> {
+    [
+        ("Flights", summary.total),
+        ("Scheduled", summary.scheduled),
+        ("Delayed", summary.delayed),
+        ("Unscheduled", summary.unscheduled()),
+    ]
+    .into_iter()
+    .map(|(label, count)| {
+        vec![
+            label.to_string(),
+            count.to_string(),
+            format!("{:.1}", summary.pct(count)),
+        ]
+    })
+    .collect()
+}
+
+fn disruption_blocks(blocks: &mut Vec<Block>, seq: usize, report: &DisruptionReport) {
+    let title = match &report.kind {
+        DisruptionType::Delay { flight, delay_by } => {
+            format!("{}. Delay {} by {} min", seq, flight, delay_by)
+        }
+        DisruptionType::Curfew {
+            airport, from, to, ..
+        } => {
+            format!("{}. Curfew at {} from {} to {}", seq, airport, from, to)
+        }
+        DisruptionType::Atfm { target, delays } => {
+            format!("{}. ATFM regulation on {}, {}", seq, target, delays)
+        }
+        DisruptionType::AirportDelay {
+            airport,
+            delay_by,
+            from,
+            to,
+        } => format!(
+            "{}. Departures from {} delayed by {} min from {}{}",
+            seq,
+            airport,
+            delay_by,
+            from,
+            to.map_or(String::new(), |t| format!(" to {}", t))
+        ),
+        DisruptionType::CapacityReduction {
+            airport,
+            runway: None,
+            from,
+            to,
+            percent,
+        } => format!(
+            "{}. Capacity at {} cut by {}% from {} to {}",
+            seq, airport, percent, from, to
+        ),
+        DisruptionType::CapacityReduction {
+            airport,
+            runway: Some(runway),
+            from,
+            to,
+            percent,
+        } => format!(
+            "{}. Runway {} at {} closed from {} to {}, capacity cut by {}%",
+            seq, runway, airport, from, to, percent
+        ),
+    };
+    blocks.push(Block::Heading(3, title));
+    blocks.push(Block::Paragraph(format!(
+        "{} delayed, {} unscheduled, {} delay min, {} pax, EUR {} added exposure, severity {}.",
+        report.affected.len(),
+        report.unscheduled.len(),
+        report.delay_minutes,
+        report.passengers,
+        report.compensation,
+        report.severity
+    )));
+
+    // the causal trace: delays pushed down the rotations, then what broke and why
+    let mut trace = report
+        .affected
+        .iter()
+        .map(|f| format!("{} delayed", f))
+        .collect::<Vec<_>>();
+    trace.extend(report.unscheduled.iter().map(|(f, reason)| {
+        match report.violations.iter().find(|(id, _)| id == f) {
+            Some((_, violation)) => format!("{} unscheduled ({})", f, violation),
+            None => format!("{} unscheduled ({:?})", f, reason),
+        }
+    }));
+    trace.extend(
+        report
+            .exempted
+            .iter()
+            .map(|(f, reason)| format!("{} operates despite {:?} (curfew exempt)", f, reason)),
+    );
+    trace.extend(report.slot_violations.iter().map(|v| {
+        format!(
+            "{} outside its slot at {} ({} instead of {})",
+            v.flight, v.airport_id, v.actual, v.slot
+        )
+    }));
+    if !trace.is_empty() {
+        blocks.push(Block::List(trace));
+    }
+}
+
+fn recovery_blocks(blocks: &mut Vec<Block>, recovery: &RecoveryRecord) {
+    blocks.push(Block::Heading(
+        3,
+        format!("Recovery ({})", recovery.strategy),
+    ));
+    blocks.push(Block::Paragraph(format!(
+        "{} recovered, {} still unscheduled, cost EUR {} -> EUR {}.",
+        recovery.outcome.fixed.len(),
+        recovery.outcome.broken.len(),
+        recovery.cost_before,
+        recovery.cost_after
+    )));
+    let actions = recovery
+        .outcome
+        .fixed
+        .iter()
+        .map(|(f, aircraft_id)| format!("{} assigned to {}", f, aircraft_id))
+        .chain(
+            recovery
+                .outcome
+                .broken
+                .iter()
+                .map(|(f, reason)| format!("{} still unscheduled ({:?})", f, reason)),
+        )
+        .collect::<Vec<_>>();
+    if !actions.is_empty() {
+        blocks.push(Block::List(actions));
+    }
+    if !recovery.callouts.is_empty() {
+        blocks.push(Block::Paragraph(format!(
+            "Reserve crews called out: {} ({}), EUR {}.",
+            recovery.callouts.len(),
+            recovery
+                .callouts
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            recovery.callout_cost
+        )));
+    }
+    if !recovery.frozen.is_empty() {
+        blocks.push(Block::Paragraph(format!(
+            "Left untouched inside the freeze window before {}: {}.",
+            recovery.frozen_until,
+            recovery
+                .frozen
+                .iter()
+                .map(|f| f.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+}
+
+fn markdown<W: Write>(w: &mut W, blocks: &[Block]) -> io::Result<()> {
+    let cell = |s: &str| s.replace('|', "\\|");
+    for block in blocks {
+        match block {
+            Block::Heading(level, text) => {
+                writeln!(w, "{} {}\n", "#".repeat(*level as usize), text)?
+            }
+            Block::Paragraph(text) => writeln!(w, "{}\n", text)?,
+            Block::List(items) => {
+                for item in items {
+                    writeln!(w, "- {}", item)?;
+                }
+                writeln!(w)?;
+            }
+            Block::Table(headers, rows) => {
+                writeln!(w, "| {} |", headers.join(" | "))?;
+                writeln!(w, "|{}", "---|".repeat(headers.len()))?;
+                for row in rows {
+                    let cells = row.iter().map(|c| cell(c)).collect::<Vec<_>>();
+                    writeln!(w, "| {} |", cells.join(" | "))?;
+                }
+                writeln!(w)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html<W: Write>(w: &mut W, blocks: &[Block]) -> io::Result<()> {
+    writeln!(
+        w,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>IRROPs post-mortem</title>\n</head>\n<body>"
+    )?;
+    for block in blocks {
+        match block {
+            Block::Heading(level, text) => writeln!(w, "<h{0}>{1}</h{0}>", level, escape(text))?,
+            Block::Paragraph(text) => writeln!(w, "<p>{}</p>", escape(text))?,
+            Block::List(items) => {
+                writeln!(w, "<ul>")?;
+                for item in items {
+                    writeln!(w, "<li>{}</li>", escape(item))?;
+                }
+                writeln!(w, "</ul>")?;
+            }
+            Block::Table(headers, rows) => {
+                writeln!(w, "<table>")?;
+                writeln!(
+                    w,
+                    "<tr>{}</tr>",
+                    headers
+                        .iter()
+                        .map(|h| format!("<th>{}</th>", escape(h)))
+                        .collect::<String>()
+                )?;
+                for row in rows {
+                    writeln!(
+                        w,
+                        "<tr>{}</tr>",
+                        row.iter()
+                            .map(|c| format!("<td>{}</td>", escape(c)))
+                            .collect::<String>()
+                    )?;
+                }
+                writeln!(w, "</table>")?;
+            }
+        }
+    }
+    writeln!(w, "</body>\n</html>")
+}
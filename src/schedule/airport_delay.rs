@@ -0,0 +1,37 @@
+use crate::airport::AirportId;
+use crate::schedule::schedule::{DisruptionType, Schedule};
+use crate::time::Time;
+use tracing::instrument;
+
+impl Schedule {
+    /// Delays every operating departure from `airport` scheduled at or after `from`, and
+    /// before `to` if given, by `minutes`, as a snow event or ground stop does. Departures
+    /// are taken in scheduled order and each pushed through `apply_delay`, so the rest of
+    /// its aircraft's rotation follows; one already pushed far enough by an earlier
+    /// departure is left alone. Everything lands in a single report.
+    #[instrument(level = "debug", skip(self), fields(airport = %airport, from = %from))]
+    pub fn delay_airport(
+        &mut self,
+        airport: AirportId,
+        minutes: u64,
+        from: Time,
+        to: Option<Time>,
+    ) {
+        let take_offs = self
+            .flights
+            .iter()
+            .filter(|f| !f.status.is_unscheduled() && f.origin_id == airport)
+            .filter(|f| f.departure_time >= from && to.is_none_or(|to| f.departure_time < to))
+            .map(|f| (f.key(), f.departure_time + minutes))
+            .collect();
+        self.push_departures(
+            DisruptionType::AirportDelay {
+                airport,
+                delay_by: minutes,
+                from,
+                to,
+            },
+            take_offs,
+        );
+    }
+}
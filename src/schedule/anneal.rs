@@ -0,0 +1,510 @@
+use crate::aircraft::AircraftId;
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::capacity::MovementBook;
+use crate::schedule::constraint::ConstraintContext;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::stands::StandBook;
+use crate::time::Time;
+use std::time::{Duration, Instant};
+use tracing::{debug, instrument};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AnnealConfig {
+    pub seed: u64,
+    pub time_limit: Duration,
+    /// Caps the search independently of wall-clock time, for reproducible runs.
+    pub max_iterations: Option<u64>,
+}
+
+impl Default for AnnealConfig {
+    fn default() -> Self {
+        AnnealConfig {
+            seed: 0,
+            time_limit: Duration::from_secs(5),
+            max_iterations: None,
+        }
+    }
+}
+
+/// SplitMix64, good enough for move selection and fully reproducible from a seed.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub(crate) fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Per-flight data the search needs, detached from the schedule so it can be mutated freely.
+struct Leg {
+    earliest: Time,
+    block: u64,
+    base_delay: u64,
+    /// Inside the freeze window: must keep its aircraft and departure time.
+    frozen: bool,
+}
+
+impl Schedule {
+    /// Cost and departure times of a rotation, or `None` when it breaks continuity,
+    /// the max delay limit, maintenance or its due limits, a curfew or moves a frozen leg.
+    fn evaluate_rotation(
+        &self,
+        aircraft_id: &AircraftId,
+        legs: &[Leg],
+        rotation: &[usize],
+    ) -> Option<(u64, Vec<Time>)> {
+        let aircraft = self.aircraft.get(aircraft_id)?;
+        let mut location = aircraft.initial_location_id.clone();
+        let mut arrived = Time(0);
+        let mut ready = Time(0);
+        let mut cost = 0;
+        let mut usage = aircraft.maintenance.as_ref().map(|check| check.usage());
+        let mut departures = Vec::with_capacity(rotation.len());
+        for &f in rotation {
+            let flight = &self.flights[f];
+            let leg = &legs[f];
+            if flight.origin_id != location {
+                return None;
+            }
+            let dep = ready.max(leg.earliest);
+            let arr = dep + leg.block;
+            let delay = leg.base_delay + (dep - leg.earliest).0;
+            if leg.frozen
+                && (dep != leg.earliest || flight.aircraft_id.as_ref() != Some(aircraft_id))
+            {
+                return None;
+            }
+            if delay > self.max_delay
+                || Self::violates_aircraft_maintenance(&aircraft.disruptions, dep, arr)
+                || Self::is_at_wrong_airport(
+                    &aircraft.disruptions,
+                    dep,
+                    Some(&(location.clone(), arrived)),
+                )
+                || Self::is_airport_closed(&self.airports, flight, dep, arr)
+                || Self::is_type_restricted(&self.airports, aircraft.fleet_type.as_ref(), flight)
+                || Self::is_out_of_range(&self.ranges, aircraft.fleet_type.as_ref(), flight)
+                || Self::lacks_etops(aircraft.etops, flight)
+                || Self::custom_violation(
+                    &self.constraints,
+                    flight,
+                    &ConstraintContext {
+                        aircraft,
+                        departure: dep,
+                        arrival: arr,
+                        airports: &self.airports,
+                    },
+                )
+                .is_some()
+            {
+                return None;
+            }
+            if let (Some(check), Some(current)) = (&aircraft.maintenance, usage) {
+                usage = Some(check.fly(current, dep, arr, &flight.destination_id)?);
+            }
+            cost += self.cost.delay_cost(&self.compensation, flight, delay)
+                + self.spill_on(flight, aircraft_id) as u64 * self.cost.spilled_passenger;
+            departures.push(dep);
+            location = flight.destination_id.clone();
+            arrived = arr;
+            ready = Self::get_ready_time(&self.airports, arr, &location);
+        }
+        Some((cost, departures))
+    }
+
+    /// Simulated annealing over tail rotations. Moves insert an unscheduled flight into a
+    /// rotation (optionally cutting off the rest of it), drop a leg from one, or exchange
+    /// rotation tails between two aircraft;
+    /// departures are retimed to the earliest feasible slot. The best solution found
+    /// within the time box replaces the current assignment if it is cheaper. The search
+    /// starts from a greedy pass, so it never does worse than `assign()`.
+    #[instrument(
+        level = "debug",
+        skip_all,
+        fields(
+            seed = config.seed,
+            time_limit = ?config.time_limit,
+            max_iterations = ?config.max_iterations
+        )
+    )]
+    pub fn assign_anneal(&mut self, config: &AnnealConfig) {
+        self.assign();
+
+        let mut aircraft_ids = self.aircraft.keys().cloned().collect::<Vec<_>>();
+        aircraft_ids.sort();
+
+        let legs = self
+            .flights
+            .iter()
+            .map(|f| Leg {
+                earliest: f.departure_time,
+                block: (f.arrival_time - f.departure_time).0,
+                base_delay: match f.status {
+                    Delayed { minutes } => minutes,
+                    _ => 0,
+                },
+                frozen: self.is_frozen(f),
+            })
+            .collect::<Vec<_>>();
+        let cancellation = self.cancellation_costs();
+
+        let mut rotations = aircraft_ids
+            .iter()
+            .map(|ac_id| {
+                let mut rotation = (0..self.flights.len())
+                    .filter(|f| self.flights[*f].aircraft_id.as_ref() == Some(ac_id))
+                    .collect::<Vec<_>>();
+                rotation.sort_by_key(|f| legs[*f].earliest);
+                rotation
+            })
+            .collect::<Vec<_>>();
+        let mut unassigned = (0..self.flights.len())
+            .filter(|f| self.flights[*f].aircraft_id.is_none())
+            .collect::<Vec<_>>();
+
+        // rotations that are already infeasible (e.g. pre-assigned or left over from an
+        // earlier pass) are kept as they are and excluded from the search
+        let mut costs = vec![0; rotations.len()];
+        let mut departures = rotations
+            .iter()
+            .map(|rotation| rotation.iter().map(|f| legs[*f].earliest).collect())
+            .collect::<Vec<Vec<Time>>>();
+        let mut movable = vec![];
+        for (a, rotation) in rotations.iter().enumerate() {
+            if let Some((cost, times)) = self.evaluate_rotation(&aircraft_ids[a], &legs, rotation) {
+                costs[a] = cost;
+                departures[a] = times;
+                movable.push(a);
+            }
+        }
+
+        // stands are shared between rotations, so a move may not park more aircraft
+        // beyond them than there already are
+        let limited = !StandBook::new(self).is_empty();
+        let stand_excess = |rotations: &[Vec<usize>],
+                            departures: &[Vec<Time>],
+                            changed: &[(usize, &[usize], &[Time])]| {
+            if !limited {
+                return 0;
+            }
+            StandBook::of_rotations(
+                self,
+                (0..rotations.len()).map(|a| {
+                    let (rotation, times) = changed
+                        .iter()
+                        .find(|(c, _, _)| *c == a)
+                        .map_or((&rotations[a][..], &departures[a][..]), |(_, r, t)| {
+                            (*r, *t)
+                        });
+                    let legs = rotation
+                        .iter()
+                        .zip(times)
+                        .map(|(f, dep)| (*f, *dep, *dep + legs[*f].block))
+                        .collect();
+                    (&aircraft_ids[a], legs)
+                }),
+            )
+            .excess()
+        };
+        // and so are the movements an airport under a capacity reduction meters: a move
+        // may not book more legs into a full airport than there already are
+        let fixed = MovementBook::of(self, self.airports.keys(), |f| {
+            f.aircraft_id
+                .as_ref()
+                .and_then(|id| aircraft_ids.binary_search(id).ok())
+                .is_none_or(|a| !movable.contains(&a))
+        });
+        let movement_excess =
+            |rotations: &[Vec<usize>],
+             departures: &[Vec<Time>],
+             changed: &[(usize, &[usize], &[Time])]| {
+                if fixed.is_empty() {
+                    return 0;
+                }
+                let mut book = fixed.clone();
+                let mut excess = 0;
+                for &a in &movable {
+                    let (rotation, times) = changed
+                        .iter()
+                        .find(|(c, _, _)| *c == a)
+                        .map_or((&rotations[a][..], &departures[a][..]), |(_, r, t)| {
+                            (*r, *t)
+                        });
+                    for (f, dep) in rotation.iter().zip(times) {
+                        let flight = &self.flights[*f];
+                        let departure = (&flight.origin_id, *dep);
+                        let arrival = (&flight.destination_id, *dep + legs[*f].block);
+                        if book.is_full(departure.0, departure.1)
+                            || book.is_full(arrival.0, arrival.1)
+                        {
+                            excess += 1;
+                        } else {
+                            book.fly(departure, arrival);
+                        }
+                    }
+                }
+                excess
+            };
+        let excess_of = |rotations: &[Vec<usize>],
+                         departures: &[Vec<Time>],
+                         changed: &[(usize, &[usize], &[Time])]| {
+            (
+                stand_excess(rotations, departures, changed),
+                movement_excess(rotations, departures, changed),
+            )
+        };
+        let mut excess = excess_of(&rotations, &departures, &[]);
+        let mut total: u64 =
+            costs.iter().sum::<u64>() + unassigned.iter().map(|f| cancellation[*f]).sum::<u64>();
+        let initial_total = total;
+        let mut best = (total, rotations.clone());
+
+        let mut rng = Rng::new(config.seed);
+        let start = Instant::now();
+        let t0 = (self.cost.cancellation / 10).max(1) as f64;
+        let mut iteration = 0u64;
+        while !movable.is_empty() {
+            let elapsed = start.elapsed().as_secs_f64() / config.time_limit.as_secs_f64().max(1e-9);
+            let progress = match config.max_iterations {
+                Some(max) => elapsed.max(iteration as f64 / max.max(1) as f64),
+                None => elapsed,
+            };
+            if progress >= 1.0 {
+                break;
+            }
+            iteration += 1;
+            let temperature = t0 * (1.0 - progress).powi(3) + 1e-6;
+
+            let a = movable[rng.below(movable.len())];
+            let position = |rotation: &[usize], at: Time| {
+                rotation
+                    .iter()
+                    .take_while(|f| legs[**f].earliest < at)
+                    .count()
+            };
+            match rng.below(4) {
+                // insert an unscheduled flight
+                0 if !unassigned.is_empty() => {
+                    let u = rng.below(unassigned.len());
+                    let f = unassigned[u];
+                    // only tails that are at the origin airport at that point in time
+                    let candidates = movable
+                        .iter()
+                        .copied()
+                        .filter(|a| {
+                            let p = position(&rotations[*a], legs[f].earliest);
+                            let location = match p {
+                                0 => &self.aircraft[&aircraft_ids[*a]].initial_location_id,
+                                _ => &self.flights[rotations[*a][p - 1]].destination_id,
+                            };
+                            *location == self.flights[f].origin_id
+                        })
+                        .collect::<Vec<_>>();
+                    if candidates.is_empty() {
+                        continue;
+                    }
+                    let a = candidates[rng.below(candidates.len())];
+                    let mut candidate = rotations[a].clone();
+                    candidate.insert(position(&candidate, legs[f].earliest), f);
+                    if let Some((cost, times)) =
+                        self.evaluate_rotation(&aircraft_ids[a], &legs, &candidate)
+                    {
+                        let delta = cost as f64 - costs[a] as f64 - cancellation[f] as f64;
+                        if (delta <= 0.0 || rng.unit() < (-delta / temperature).exp())
+                            && let after =
+                                excess_of(&rotations, &departures, &[(a, &candidate, &times)])
+                            && after.0 <= excess.0
+                            && after.1 <= excess.1
+                        {
+                            total = total + cost - costs[a] - cancellation[f];
+                            rotations[a] = candidate;
+                            departures[a] = times;
+                            costs[a] = cost;
+                            excess = after;
+                            unassigned.swap_remove(u);
+                        }
+                    }
+                }
+                // insert an unscheduled flight, cancelling the rest of the rotation
+                3 if !unassigned.is_empty() => {
+                    let u = rng.below(unassigned.len());
+                    let f = unassigned[u];
+                    let rotation = &rotations[a];
+                    let p = position(rotation, legs[f].earliest);
+                    let Some(k) = (0..=p).rev().find(|k| {
+                        let location = match k {
+                            0 => &self.aircraft[&aircraft_ids[a]].initial_location_id,
+                            _ => &self.flights[rotation[k - 1]].destination_id,
+                        };
+                        *location == self.flights[f].origin_id
+                    }) else {
+                        continue;
+                    };
+                    let mut candidate = rotation[..k].to_vec();
+                    candidate.push(f);
+                    let dropped = rotation[k..].to_vec();
+                    if dropped.iter().any(|d| legs[*d].frozen) {
+                        continue;
+                    }
+                    if let Some((cost, times)) =
+                        self.evaluate_rotation(&aircraft_ids[a], &legs, &candidate)
+                    {
+                        let dropped_cost = dropped.iter().map(|d| cancellation[*d]).sum::<u64>();
+                        let delta =
+                            (cost + dropped_cost) as f64 - (costs[a] + cancellation[f]) as f64;
+                        if (delta <= 0.0 || rng.unit() < (-delta / temperature).exp())
+                            && let after =
+                                excess_of(&rotations, &departures, &[(a, &candidate, &times)])
+                            && after.0 <= excess.0
+                            && after.1 <= excess.1
+                        {
+                            total = total + cost + dropped_cost - costs[a] - cancellation[f];
+                            rotations[a] = candidate;
+                            departures[a] = times;
+                            costs[a] = cost;
+                            excess = after;
+                            unassigned.swap_remove(u);
+                            unassigned.extend(dropped);
+                        }
+                    }
+                }
+                // cancel a leg
+                1 if !rotations[a].is_empty() => {
+                    let i = rng.below(rotations[a].len());
+                    if legs[rotations[a][i]].frozen {
+                        continue;
+                    }
+                    let mut candidate = rotations[a].clone();
+                    let f = candidate.remove(i);
+                    if let Some((cost, times)) =
+                        self.evaluate_rotation(&aircraft_ids[a], &legs, &candidate)
+                    {
+                        let delta = cost as f64 + cancellation[f] as f64 - costs[a] as f64;
+                        if (delta <= 0.0 || rng.unit() < (-delta / temperature).exp())
+                            && let after =
+                                excess_of(&rotations, &departures, &[(a, &candidate, &times)])
+                            && after.0 <= excess.0
+                            && after.1 <= excess.1
+                        {
+                            total = total + cost + cancellation[f] - costs[a];
+                            rotations[a] = candidate;
+                            departures[a] = times;
+                            costs[a] = cost;
+                            excess = after;
+                            unassigned.push(f);
+                        }
+                    }
+                }
+                // swap rotation tails
+                2 if movable.len() > 1 => {
+                    let b = movable[rng.below(movable.len())];
+                    if a == b {
+                        continue;
+                    }
+                    let i = rng.below(rotations[a].len() + 1);
+                    let at = rotations[a]
+                        .get(i)
+                        .map_or(Time(u64::MAX), |f| legs[*f].earliest);
+                    let j = position(&rotations[b], at);
+                    let mut new_a = rotations[a][..i].to_vec();
+                    new_a.extend_from_slice(&rotations[b][j..]);
+                    let mut new_b = rotations[b][..j].to_vec();
+                    new_b.extend_from_slice(&rotations[a][i..]);
+                    if let (Some((cost_a, times_a)), Some((cost_b, times_b))) = (
+                        self.evaluate_rotation(&aircraft_ids[a], &legs, &new_a),
+                        self.evaluate_rotation(&aircraft_ids[b], &legs, &new_b),
+                    ) {
+                        let delta = (cost_a + cost_b) as f64 - (costs[a] + costs[b]) as f64;
+                        if (delta <= 0.0 || rng.unit() < (-delta / temperature).exp())
+                            && let after = excess_of(
+                                &rotations,
+                                &departures,
+                                &[(a, &new_a, &times_a), (b, &new_b, &times_b)],
+                            )
+                            && after.0 <= excess.0
+                            && after.1 <= excess.1
+                        {
+                            total = total + cost_a + cost_b - costs[a] - costs[b];
+                            rotations[a] = new_a;
+                            rotations[b] = new_b;
+                            departures[a] = times_a;
+                            departures[b] = times_b;
+                            costs[a] = cost_a;
+                            costs[b] = cost_b;
+                            excess = after;
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            if total < best.0 {
+                best = (total, rotations.clone());
+            }
+        }
+
+        debug!(
+            "anneal: {} iterations, cost {} -> {}",
+            iteration, initial_total, best.0
+        );
+        if best.0 >= initial_total {
+            return;
+        }
+
+        let mut assigned = vec![false; self.flights.len()];
+        for &a in &movable {
+            let rotation = &best.1[a];
+            let Some((_, departures)) = self.evaluate_rotation(&aircraft_ids[a], &legs, rotation)
+            else {
+                continue;
+            };
+            for (&f, dep) in rotation.iter().zip(departures) {
+                let delay = legs[f].base_delay + (dep - legs[f].earliest).0;
+                let flight = &mut self.flights[f];
+                flight.aircraft_id = Some(aircraft_ids[a].clone());
+                flight.departure_time = dep;
+                flight.arrival_time = dep + legs[f].block;
+                flight.status = if delay == 0 {
+                    Scheduled
+                } else {
+                    Delayed { minutes: delay }
+                };
+                assigned[f] = true;
+            }
+        }
+        for (f, flight) in self.flights.iter_mut().enumerate() {
+            let kept = legs[f].frozen
+                || flight
+                    .aircraft_id
+                    .as_ref()
+                    .and_then(|id| aircraft_ids.binary_search(id).ok())
+                    .is_some_and(|a| !movable.contains(&a));
+            if !assigned[f] && !kept && flight.aircraft_id.is_some() {
+                flight.aircraft_id = None;
+                flight.status = Unscheduled(Waiting);
+            }
+        }
+        self.index_rotations();
+        self.sort_airport_legs();
+
+        #[cfg(debug_assertions)]
+        self.assert_invariants();
+    }
+}
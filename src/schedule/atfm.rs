@@ -0,0 +1,180 @@
+use crate::airport::AirportId;
+use crate::flight::FlightKey;
+use crate::schedule::anneal::Rng;
+use crate::schedule::schedule::{DisruptionReport, DisruptionType, Schedule};
+use crate::time::Time;
+use std::collections::HashMap;
+use std::fmt;
+use tracing::instrument;
+
+/// Flights an air-traffic flow regulation applies to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AtfmTarget {
+    Flights(Vec<FlightKey>),
+    /// Departures from the airport scheduled inside the window.
+    Airport {
+        airport: AirportId,
+        from: Time,
+        to: Time,
+    },
+}
+
+impl fmt::Display for AtfmTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AtfmTarget::Flights(keys) => write!(
+                f,
+                "{}",
+                keys.iter()
+                    .map(|k| k.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+            AtfmTarget::Airport { airport, from, to } => {
+                write!(f, "{} {}-{}", airport, from, to)
+            }
+        }
+    }
+}
+
+/// How the regulation hands out departure delays to the regulated flights.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DelayDistribution {
+    /// The same delay for every flight.
+    Fixed(u64),
+    /// A delay drawn uniformly between both ends, reproducible from the seed.
+    Uniform { min: u64, max: u64, seed: u64 },
+    /// Departures released at most `n` per hour from the first regulated departure, in
+    /// scheduled order, as a flow rate does.
+    Rate(u32),
+}
+
+impl fmt::Display for DelayDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DelayDistribution::Fixed(minutes) => write!(f, "+{} min", minutes),
+            DelayDistribution::Uniform { min, max, seed } => {
+                write!(f, "+{}-{} min (seed {})", min, max, seed)
+            }
+            DelayDistribution::Rate(n) => write!(f, "{}/h", n),
+        }
+    }
+}
+
+impl DelayDistribution {
+    /// Delay of each departure, given in scheduled order.
+    fn delays(&self, departures: &[Time]) -> Vec<u64> {
+        match *self {
+            DelayDistribution::Fixed(minutes) => vec![minutes; departures.len()],
+            DelayDistribution::Uniform { min, max, seed } => {
+                let mut rng = Rng::new(seed);
+                let (min, max) = (min.min(max), min.max(max));
+                departures
+                    .iter()
+                    .map(|_| min + rng.next_u64() % (max - min + 1))
+                    .collect()
+            }
+            DelayDistribution::Rate(0) => vec![0; departures.len()],
+            DelayDistribution::Rate(n) => {
+                let interval = 60.0 / n as f64;
+                let start = departures.first().map_or(0, |t| t.0);
+                departures
+                    .iter()
+                    .enumerate()
+                    .map(|(i, t)| {
+                        let released = start + (i as f64 * interval).ceil() as u64;
+                        released.saturating_sub(t.0)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Schedule {
+    /// Applies an air-traffic flow regulation: every regulated operating flight gets a
+    /// calculated take-off time from `delays`, counted from its departure when the
+    /// regulation comes in, and is pushed to it through `apply_delay`. A flight already
+    /// pushed past its take-off time by an earlier one is left alone. Everything lands in
+    /// a single report.
+    #[instrument(level = "debug", skip_all, fields(target = %target, delays = %delays))]
+    pub fn apply_atfm(&mut self, target: AtfmTarget, delays: DelayDistribution) {
+        let mut regulated = self
+            .flights
+            .iter()
+            .filter(|f| !f.status.is_unscheduled())
+            .filter(|f| match &target {
+                AtfmTarget::Flights(keys) => keys.contains(&f.key()),
+                AtfmTarget::Airport { airport, from, to } => {
+                    f.origin_id == *airport && f.departure_time >= *from && f.departure_time < *to
+                }
+            })
+            .map(|f| (f.departure_time, f.key()))
+            .collect::<Vec<_>>();
+        regulated.sort();
+        let departures = regulated.iter().map(|(t, _)| *t).collect::<Vec<_>>();
+        let take_offs = regulated
+            .into_iter()
+            .zip(delays.delays(&departures))
+            .map(|((t, key), delay)| (key, t + delay))
+            .collect();
+        self.push_departures(DisruptionType::Atfm { target, delays }, take_offs);
+    }
+
+    /// Pushes each flight to its take-off time through `apply_delay`, in the order given,
+    /// and records everything as a single report of `kind`. A flight already pushed past
+    /// its take-off time by an earlier one is left alone.
+    pub(crate) fn push_departures(
+        &mut self,
+        kind: DisruptionType,
+        take_offs: Vec<(FlightKey, Time)>,
+    ) -> &DisruptionReport {
+        let mut report = DisruptionReport {
+            kind,
+            affected: vec![],
+            unscheduled: vec![],
+            first_break: None,
+            worst_break: None,
+            compensation: 0,
+            violations: vec![],
+            exempted: vec![],
+            slot_violations: vec![],
+            delay_minutes: 0,
+            passengers: 0,
+            severity: 0,
+            changes: vec![],
+        };
+        // the pushes bring the states of the flights they change
+        let mut states_before = HashMap::new();
+
+        for (key, take_off) in take_offs {
+            let Some(flight) = self.flight(&key) else {
+                continue;
+            };
+            if flight.status.is_unscheduled() || flight.departure_time >= take_off {
+                continue;
+            }
+            let push = (take_off - flight.departure_time).0;
+            if self.apply_delay(key.clone(), push).is_ok()
+                && let Some(delay) = self.history.pop()
+            {
+                for change in &delay.changes {
+                    states_before
+                        .entry(change.flight.clone())
+                        .or_insert_with(|| change.before.clone());
+                }
+                report.absorb(delay);
+            }
+        }
+
+        report
+            .affected
+            .retain(|id| report.unscheduled.iter().all(|(u, _)| u != id));
+        self.record_changes(&mut report, &states_before);
+        self.score(&mut report);
+        report.log();
+
+        self.history.push(report.clone());
+        self.last_report.insert(report)
+    }
+}
@@ -0,0 +1,52 @@
+use crate::flight::FlightKey;
+use crate::schedule::feasibility::ChainViolation;
+use crate::schedule::schedule::Schedule;
+use std::fmt;
+
+/// A state the schedule should never be left in by recovery, delays or any other
+/// mutation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InvariantViolation {
+    Chain(ChainViolation),
+    /// The flight lookup does not lead to this flight, e.g. after `flights` was edited
+    /// without reindexing.
+    StaleIndex(FlightKey),
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantViolation::Chain(violation) => write!(f, "{}", violation),
+            InvariantViolation::StaleIndex(flight) => {
+                write!(f, "flight {} is not found by its key", flight)
+            }
+        }
+    }
+}
+
+impl Schedule {
+    /// Checks the invariants every mutation keeps: the chain checks of
+    /// `chain_violations` and a flight lookup matching `flights`. Maintenance overlaps,
+    /// which recovery reports rather than prevents, and flights that were already
+    /// infeasible when the scenario was loaded are left out. Empty when the state is
+    /// intact.
+    pub fn audit(&self) -> Vec<InvariantViolation> {
+        let mut violations = self
+            .chain_violations()
+            .into_iter()
+            .filter(|v| !matches!(v, ChainViolation::Maintenance(..)))
+            .filter(|v| !self.infeasible_at_load.contains(v.flight()))
+            .map(InvariantViolation::Chain)
+            .collect::<Vec<_>>();
+        violations.extend(
+            self.flights
+                .iter()
+                .filter(|f| {
+                    self.flight(&f.key())
+                        .is_none_or(|indexed| indexed.key() != f.key())
+                })
+                .map(|f| InvariantViolation::StaleIndex(f.key())),
+        );
+        violations
+    }
+}
@@ -0,0 +1,140 @@
+use crate::airport::{AirportId, Bank};
+use crate::flight::FlightKey;
+use crate::schedule::schedule::Schedule;
+
+/// A passenger connection planned through a hub bank, from the scheduled times of its
+/// two flights.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BankConnection {
+    pub hub: AirportId,
+    /// Index of the bank among the hub's banks.
+    pub bank: usize,
+    pub arrival: FlightKey,
+    pub departure: FlightKey,
+    /// Whether both flights operate with at least the bank's minimum connection time
+    /// between them.
+    pub protected: bool,
+}
+
+/// Planned and still protected connections of one bank, over every day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BankIntegrity {
+    pub hub: AirportId,
+    pub bank: Bank,
+    pub planned: usize,
+    pub protected: usize,
+}
+
+impl BankIntegrity {
+    pub fn pct(&self) -> f64 {
+        if self.planned == 0 {
+            100.0
+        } else {
+            self.protected as f64 * 100.0 / self.planned as f64
+        }
+    }
+}
+
+impl Schedule {
+    /// Connections planned through every hub bank, by hub, bank and flights.
+    pub fn bank_connections(&self) -> Vec<BankConnection> {
+        let mut hubs = self
+            .airports
+            .values()
+            .filter(|ap| !ap.banks.is_empty())
+            .collect::<Vec<_>>();
+        hubs.sort_by(|a, b| a.id.cmp(&b.id));
+        let mut connections = vec![];
+        for hub in hubs {
+            for (i, bank) in hub.banks.iter().enumerate() {
+                let arrivals = self.flights.iter().filter(|f| {
+                    f.destination_id == hub.id && bank.is_arrival(f.scheduled_times().1)
+                });
+                for arrival in arrivals {
+                    let day = arrival.scheduled_times().1.day();
+                    let departures = self.flights.iter().filter(|f| {
+                        let departure = f.scheduled_times().0;
+                        f.origin_id == hub.id
+                            && departure.day() == day
+                            && bank.is_departure(departure)
+                    });
+                    for departure in departures {
+                        connections.push(BankConnection {
+                            hub: hub.id.clone(),
+                            bank: i,
+                            arrival: arrival.key(),
+                            departure: departure.key(),
+                            protected: !arrival.status.is_unscheduled()
+                                && !departure.status.is_unscheduled()
+                                && arrival.arrival_time.0 + bank.min_connection
+                                    <= departure.departure_time.0,
+                        });
+                    }
+                }
+            }
+        }
+        connections.sort_by(|a, b| {
+            (&a.hub, a.bank, &a.arrival, &a.departure).cmp(&(
+                &b.hub,
+                b.bank,
+                &b.arrival,
+                &b.departure,
+            ))
+        });
+        connections
+    }
+
+    /// Share of planned connections each hub bank still protects.
+    pub fn bank_integrity(&self) -> Vec<BankIntegrity> {
+        let connections = self.bank_connections();
+        let mut hubs = self
+            .airports
+            .values()
+            .filter(|ap| !ap.banks.is_empty())
+            .collect::<Vec<_>>();
+        hubs.sort_by(|a, b| a.id.cmp(&b.id));
+        hubs.into_iter()
+            .flat_map(|hub| {
+                let connections = &connections;
+                hub.banks.iter().enumerate().map(move |(i, bank)| {
+                    let of_bank = connections
+                        .iter()
+                        .filter(|c| c.hub == hub.id && c.bank == i)
+                        .collect::<Vec<_>>();
+                    BankIntegrity {
+                        hub: hub.id.clone(),
+                        bank: *bank,
+                        planned: of_bank.len(),
+                        protected: of_bank.iter().filter(|c| c.protected).count(),
+                    }
+                })
+            })
+            .collect()
+    }
+
+    pub fn broken_connection_cost(&self) -> u64 {
+        self.bank_connections()
+            .iter()
+            .filter(|c| !c.protected)
+            .count() as u64
+            * self.cost.broken_connection
+    }
+
+    /// Cost of cancelling each flight, by index: the cancellation itself plus every bank
+    /// connection it would break, so recovery prefers delaying flights that feed a bank.
+    pub(crate) fn cancellation_costs(&self) -> Vec<u64> {
+        let connections = self.bank_connections();
+        self.flights
+            .iter()
+            .map(|f| {
+                let key = f.key();
+                let feeds = connections
+                    .iter()
+                    .filter(|c| c.arrival == key || c.departure == key)
+                    .count() as u64;
+                self.cost.cancellation_cost(&self.compensation, f)
+                    + feeds * self.cost.broken_connection
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,53 @@
+use crate::aircraft::Aircraft;
+use crate::airport::Airport;
+use crate::flight::Flight;
+use crate::schedule::constraint::Rule;
+use crate::schedule::schedule::{Scenario, Schedule};
+use crate::schedule::validate::{Issue, Severity, validate};
+
+/// Puts a schedule together in code the way a scenario file would, from aircraft, airports
+/// and flights made with their `new` constructors.
+#[derive(Default)]
+pub struct ScheduleBuilder {
+    scenario: Scenario,
+}
+
+impl ScheduleBuilder {
+    pub fn new() -> ScheduleBuilder {
+        ScheduleBuilder::default()
+    }
+
+    pub fn aircraft(mut self, aircraft: Aircraft) -> ScheduleBuilder {
+        self.scenario.aircraft.push(aircraft);
+        self
+    }
+
+    pub fn airport(mut self, airport: Airport) -> ScheduleBuilder {
+        self.scenario.airports.push(airport);
+        self
+    }
+
+    pub fn flight(mut self, flight: Flight) -> ScheduleBuilder {
+        self.scenario.flights.push(flight);
+        self
+    }
+
+    pub fn rule(mut self, rule: Rule) -> ScheduleBuilder {
+        self.scenario.rules.push(rule);
+        self
+    }
+
+    /// Checks ids, references and time windows as `validate` does for a scenario file and
+    /// loads the schedule, or returns the errors. Warnings, such as a pre-assigned rotation
+    /// that cannot be flown, are left to `load_warnings`.
+    pub fn build(self) -> Result<Schedule, Vec<Issue>> {
+        let errors = validate(&self.scenario)
+            .into_iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .collect::<Vec<_>>();
+        match errors.is_empty() {
+            true => Ok(Schedule::from_scenario(self.scenario)),
+            false => Err(errors),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::aircraft::{Aircraft, AircraftId, FleetType};
+use crate::airport::AirportId;
+use crate::flight::{Flight, FlightKey, UnscheduledReason};
+use crate::schedule::schedule::Schedule;
+use crate::time::Time;
+
+/// How one aircraft fares against the built-in checks for flying a given flight. Every
+/// check is evaluated, so a row shows all the reasons an aircraft cannot take the flight.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate {
+    pub aircraft_id: AircraftId,
+    /// Where the aircraft is before the flight, when that is not the flight's origin.
+    pub wrong_location: Option<AirportId>,
+    /// Another flight of the aircraft overlapping the block plus turn time.
+    pub busy_with: Option<FlightKey>,
+    /// Whether an availability window of the aircraft overlaps the flight.
+    pub maintenance: bool,
+    /// Closure at either end of the flight; the same for every aircraft.
+    pub curfew: Option<UnscheduledReason>,
+    /// Fleet type that an airport at either end does not accept.
+    pub restricted_type: Option<FleetType>,
+    /// Range of the aircraft's fleet type, when the flight is longer.
+    pub out_of_range: Option<u64>,
+    /// An ETOPS sector for an aircraft without the certification.
+    pub lacks_etops: bool,
+    /// Booked passengers that would not fit; a downgauge, not a reason to refuse.
+    pub spill: u32,
+}
+
+impl Candidate {
+    pub fn is_feasible(&self) -> bool {
+        self.wrong_location.is_none()
+            && self.busy_with.is_none()
+            && !self.maintenance
+            && self.curfew.is_none()
+            && self.restricted_type.is_none()
+            && self.out_of_range.is_none()
+            && !self.lacks_etops
+    }
+}
+
+impl Schedule {
+    /// Verdict per aircraft, ordered by id, on flying `flight` at its current times in
+    /// place of whichever aircraft has it now.
+    pub fn candidates(&self, key: &FlightKey) -> Vec<Candidate> {
+        let Some(flight) = self.flight(key) else {
+            return vec![];
+        };
+        let curfew = Self::airport_closure(
+            &self.airports,
+            flight,
+            flight.departure_time,
+            flight.arrival_time,
+        );
+        let mut aircraft = self.aircraft.values().collect::<Vec<_>>();
+        aircraft.sort_by(|a, b| a.id.cmp(&b.id));
+        aircraft
+            .into_iter()
+            .map(|ac| {
+                let legs = self
+                    .flights
+                    .iter()
+                    .filter(|f| f.key() != *key && !f.status.is_unscheduled())
+                    .filter(|f| f.aircraft_id.as_ref() == Some(&ac.id))
+                    .collect::<Vec<_>>();
+                Candidate {
+                    aircraft_id: ac.id.clone(),
+                    wrong_location: Self::location_before(ac, &legs, flight.departure_time)
+                        .filter(|location| *location != flight.origin_id),
+                    busy_with: legs
+                        .iter()
+                        .find(|f| {
+                            Time::is_overlapping(
+                                &(flight.departure_time, flight.arrival_time),
+                                &(
+                                    f.departure_time,
+                                    Self::get_ready_time(
+                                        &self.airports,
+                                        f.arrival_time,
+                                        &f.destination_id,
+                                    ),
+                                ),
+                            )
+                        })
+                        .map(|f| f.key()),
+                    maintenance: Self::violates_aircraft_maintenance(
+                        &ac.disruptions,
+                        flight.departure_time,
+                        flight.arrival_time,
+                    ),
+                    curfew: curfew.clone(),
+                    restricted_type: ac.fleet_type.clone().filter(|fleet_type| {
+                        Self::is_type_restricted(&self.airports, Some(fleet_type), flight)
+                    }),
+                    out_of_range: ac
+                        .fleet_type
+                        .as_ref()
+                        .filter(|t| Self::is_out_of_range(&self.ranges, Some(t), flight))
+                        .map(|t| self.ranges[t]),
+                    lacks_etops: Self::lacks_etops(ac.etops, flight),
+                    spill: self.spill_on(flight, &ac.id),
+                }
+            })
+            .collect()
+    }
+
+    /// Airport the aircraft sits at by `departure`: the destination of its last leg landing
+    /// by then, or where it starts the day; `None` when it is still airborne.
+    fn location_before(
+        aircraft: &Aircraft,
+        legs: &[&Flight],
+        departure: Time,
+    ) -> Option<AirportId> {
+        let mut earlier = legs
+            .iter()
+            .filter(|f| f.departure_time < departure)
+            .collect::<Vec<_>>();
+        earlier.sort_by_key(|f| f.departure_time);
+        match earlier.last() {
+            Some(f) if f.arrival_time > departure => None,
+            Some(f) => Some(f.destination_id.clone()),
+            None => Some(aircraft.initial_location_id.clone()),
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use crate::airport::{AirportId, CapacityReduction, RunwayId};
+use crate::error::IrropsError;
+use crate::flight::{Flight, FlightKey};
+use crate::schedule::schedule::{DisruptionReport, DisruptionType, Schedule};
+use crate::time::Time;
+use std::collections::HashMap;
+use tracing::debug_span;
+
+impl Schedule {
+    /// Cuts the movement rate of `airport` by `percent` between `from` and `to`. The
+    /// nominal rate is the airport's `movement_rate`, or else its busiest scheduled hour,
+    /// so the schedule fits it. Operating departures and arrivals inside the window are
+    /// queued in scheduled order for the reduced rate; each one released late is held at
+    /// its origin through `apply_delay`, so the rest of its rotation follows. Movements
+    /// are metered on the times when the reduction comes in, and the queue may run past
+    /// `to`. The reduction then stays on the airport, so later assignments and delays
+    /// keep to the reduced rate. Everything lands in a single report.
+    pub fn apply_capacity_reduction(
+        &mut self,
+        airport: AirportId,
+        from: Time,
+        to: Time,
+        percent: u32,
+    ) -> Result<&DisruptionReport, IrropsError> {
+        if !self.airports.contains_key(&airport) {
+            return Err(IrropsError::UnknownAirport(airport));
+        }
+        if from >= to {
+            return Err(IrropsError::EmptyReduction(airport));
+        }
+        if percent == 0 || percent >= 100 {
+            return Err(IrropsError::InvalidReduction(percent));
+        }
+        let rate = (self.nominal_rate(&airport) * (100 - percent) / 100).max(1);
+        let kind = DisruptionType::CapacityReduction {
+            airport,
+            runway: None,
+            from,
+            to,
+            percent,
+        };
+        Ok(self.meter(kind, rate))
+    }
+
+    /// Closes one runway of `airport` between `from` and `to`. The airport keeps the rate
+    /// of its other runways and its movements are metered to it as by
+    /// `apply_capacity_reduction`; closing its only runway closes the airport, which is a
+    /// curfew. Each closure is measured against all runways, so two closed at once are
+    /// better given as a single reduction.
+    pub fn close_runway(
+        &mut self,
+        airport: AirportId,
+        runway: RunwayId,
+        from: Time,
+        to: Time,
+    ) -> Result<&DisruptionReport, IrropsError> {
+        let Some(found) = self.airports.get(&airport) else {
+            return Err(IrropsError::UnknownAirport(airport));
+        };
+        let Some(closed) = found.runways.iter().find(|r| r.id == runway) else {
+            return Err(IrropsError::UnknownRunway(airport, runway));
+        };
+        if from >= to {
+            return Err(IrropsError::EmptyReduction(airport));
+        }
+        let total = found.movement_rate().unwrap_or(0);
+        let rate = total.saturating_sub(closed.movements_per_hour);
+        if rate == 0 {
+            return self.apply_curfew(airport, from, to);
+        }
+        let percent = ((total - rate) * 100 / total).clamp(1, 99);
+        let kind = DisruptionType::CapacityReduction {
+            airport,
+            runway: Some(runway),
+            from,
+            to,
+            percent,
+        };
+        Ok(self.meter(kind, rate))
+    }
+
+    /// The airport's `movement_rate`, or its busiest scheduled clock hour without one.
+    fn nominal_rate(&self, airport: &AirportId) -> u32 {
+        if let Some(rate) = self.airports.get(airport).and_then(|a| a.movement_rate()) {
+            return rate;
+        }
+        let mut per_hour: HashMap<u64, u32> = HashMap::new();
+        let departures = self.departures(airport);
+        let arrivals = self.arrivals(airport);
+        let movements = departures.iter().map(|f| f.departure_time);
+        for t in movements.chain(arrivals.iter().map(|f| f.arrival_time)) {
+            *per_hour.entry(t.0 / 60).or_default() += 1;
+        }
+        per_hour.into_values().max().unwrap_or(0)
+    }
+
+    /// Whether moving `flight` to `dep_time..arr_time` puts either of its movements closer
+    /// to another operating one than a capacity reduction in force there allows.
+    pub(crate) fn is_metered_out(&self, flight: &Flight, dep_time: Time, arr_time: Time) -> bool {
+        let (origin, destination) = (&flight.origin_id, &flight.destination_id);
+        let key = flight.key();
+        let book = MovementBook::of(self, [origin, destination], |f| f.key() != key);
+        book.is_full(origin, dep_time) || book.is_full(destination, arr_time)
+    }
+
+    /// Queues the movements inside the window of a `CapacityReduction` for `rate` an hour,
+    /// holds the late ones at their origin and keeps the reduction on the airport.
+    fn meter(&mut self, kind: DisruptionType, rate: u32) -> &DisruptionReport {
+        let DisruptionType::CapacityReduction {
+            airport, from, to, ..
+        } = &kind
+        else {
+            unreachable!("only capacity reductions are metered");
+        };
+        let _span = debug_span!("meter", %airport, rate).entered();
+        let (from, to) = (*from, *to);
+        let airport = airport.clone();
+        let interval = 60.0 / rate as f64;
+
+        // (movement time, flight, its departure): an arrival is held at its origin
+        let departures = self.departures(&airport);
+        let arrivals = self.arrivals(&airport);
+        let mut movements = departures
+            .iter()
+            .map(|f| (f.departure_time, f))
+            .chain(arrivals.iter().map(|f| (f.arrival_time, f)))
+            .filter(|(t, f)| !f.status.is_unscheduled() && *t >= from && *t < to)
+            .map(|(t, f)| (t, f.key(), f.departure_time))
+            .collect::<Vec<_>>();
+        movements.sort();
+        let mut next_free = from.0 as f64;
+        let mut take_offs: Vec<(FlightKey, Time)> = vec![];
+        for (t, key, departure) in movements {
+            let released = (t.0 as f64).max(next_free.ceil());
+            next_free = released + interval;
+            let hold = released as u64 - t.0;
+            if hold > 0 {
+                take_offs.push((key, departure + hold));
+            }
+        }
+        take_offs.sort_by_key(|(_, take_off)| *take_off);
+
+        // the queue above is already spaced out, so the reduction binds from here on
+        self.push_departures(kind, take_offs);
+        if let Some(found) = self.airports.get_mut(&airport) {
+            found.reductions.push(CapacityReduction { from, to, rate });
+        }
+        self.last_report
+            .as_ref()
+            .expect("push_departures records its report")
+    }
+}
+
+/// Times of the operating movements at the airports under a capacity reduction, kept up
+/// to date while assign places flights so it never meters in more than the reduced rate.
+#[derive(Clone, Default)]
+pub(crate) struct MovementBook {
+    airports: HashMap<AirportId, (Vec<CapacityReduction>, Vec<Time>)>,
+}
+
+impl MovementBook {
+    /// The movements of `schedule` at every airport with a capacity reduction.
+    pub(crate) fn new(schedule: &Schedule) -> MovementBook {
+        MovementBook::of(schedule, schedule.airports.keys(), |_| true)
+    }
+
+    /// The movements at those of `airport_ids` with a capacity reduction, of the operating
+    /// flights that `keep` holds on to.
+    pub(crate) fn of<'a>(
+        schedule: &Schedule,
+        airport_ids: impl IntoIterator<Item = &'a AirportId>,
+        keep: impl Fn(&Flight) -> bool,
+    ) -> MovementBook {
+        let mut book = MovementBook::default();
+        for airport_id in airport_ids {
+            let Some(airport) = schedule.airports.get(airport_id) else {
+                continue;
+            };
+            if airport.reductions.is_empty() || book.airports.contains_key(airport_id) {
+                continue;
+            }
+            let operating = |f: &&Flight| !f.status.is_unscheduled() && keep(f);
+            let departures = schedule.departures(airport_id);
+            let arrivals = schedule.arrivals(airport_id);
+            let times = departures
+                .iter()
+                .copied()
+                .filter(operating)
+                .map(|f| f.departure_time)
+                .chain(
+                    arrivals
+                        .iter()
+                        .copied()
+                        .filter(operating)
+                        .map(|f| f.arrival_time),
+                )
+                .collect();
+            book.airports
+                .insert(airport_id.clone(), (airport.reductions.clone(), times));
+        }
+        book
+    }
+
+    /// Whether one more movement at `airport_id` at `at` comes closer to another one than
+    /// the spacing of a reduction in force then; movements outside it are not metered.
+    pub(crate) fn is_full(&self, airport_id: &AirportId, at: Time) -> bool {
+        let Some((reductions, times)) = self.airports.get(airport_id) else {
+            return false;
+        };
+        reductions.iter().filter(|r| r.covers(at)).any(|r| {
+            times
+                .iter()
+                .filter(|t| r.covers(**t))
+                .any(|t| (t.0 as f64 - at.0 as f64).abs() < r.spacing())
+        })
+    }
+
+    /// Whether no airport has a capacity reduction.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.airports.is_empty()
+    }
+
+    /// Books the take-off of a flight at `origin` and its landing at `destination`.
+    pub(crate) fn fly(
+        &mut self,
+        (origin, departure): (&AirportId, Time),
+        (destination, arrival): (&AirportId, Time),
+    ) {
+        for (airport_id, at) in [(origin, departure), (destination, arrival)] {
+            if let Some((_, times)) = self.airports.get_mut(airport_id) {
+                times.push(at);
+            }
+        }
+    }
+}
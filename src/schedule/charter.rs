@@ -0,0 +1,151 @@
+use crate::aircraft::{Aircraft, AircraftId, Availability};
+use crate::flight::FlightKey;
+use crate::flight::FlightStatus::Scheduled;
+use crate::schedule::constraint::ConstraintContext;
+use crate::schedule::schedule::Schedule;
+use crate::time::Time;
+use tracing::debug;
+
+pub struct CharterUsage {
+    pub charter_id: AircraftId,
+    pub flights: Vec<FlightKey>,
+    pub block_minutes: u64,
+    pub cost: u64,
+}
+
+impl Schedule {
+    /// Hired charters with the flights they operate and what they cost.
+    pub fn charter_usage(&self) -> Vec<CharterUsage> {
+        self.charters
+            .iter()
+            .filter_map(|charter| {
+                let flights = self
+                    .flights
+                    .iter()
+                    .filter(|f| f.aircraft_id.as_ref() == Some(&charter.id))
+                    .collect::<Vec<_>>();
+                let block_minutes = flights
+                    .iter()
+                    .map(|f| (f.arrival_time - f.departure_time).0)
+                    .sum();
+                (!flights.is_empty()).then(|| CharterUsage {
+                    charter_id: charter.id.clone(),
+                    flights: flights.iter().map(|f| f.key()).collect(),
+                    block_minutes,
+                    cost: charter.block_cost(block_minutes),
+                })
+            })
+            .collect()
+    }
+
+    pub fn charter_cost(&self) -> u64 {
+        self.charter_usage().iter().map(|u| u.cost).sum()
+    }
+
+    /// Hires unused charters for flights still unscheduled after recovery. Each charter
+    /// flies a first-fit rotation from its base inside its availability window and is
+    /// only taken when its block-hour cost stays below the cancellations it avoids.
+    pub fn assign_charters(&mut self) {
+        let cancellation = self.cancellation_costs();
+        for charter in self.charters.clone() {
+            if self.aircraft.contains_key(&charter.id) {
+                continue;
+            }
+            let aircraft = Aircraft {
+                id: charter.id.clone(),
+                initial_location_id: charter.base_id.clone(),
+                // outside its window the charter is as good as in maintenance
+                disruptions: vec![
+                    Availability {
+                        from: Time(0),
+                        to: charter.from,
+                        location_id: None,
+                    },
+                    Availability {
+                        from: charter.to,
+                        to: Time(u64::MAX),
+                        location_id: None,
+                    },
+                ],
+                maintenance: None,
+                fleet_type: charter.fleet_type.clone(),
+                etops: charter.etops,
+            };
+            let mut location = charter.base_id.clone();
+            let mut ready = charter.from;
+            let mut rotation = vec![];
+            for (i, flight) in self.flights.iter().enumerate() {
+                if flight.status.is_unscheduled()
+                    && !self.is_frozen(flight)
+                    && flight.origin_id == location
+                    && flight.departure_time >= ready
+                    && flight.arrival_time <= charter.to
+                    && !Self::is_type_restricted(
+                        &self.airports,
+                        charter.fleet_type.as_ref(),
+                        flight,
+                    )
+                    && !Self::is_out_of_range(&self.ranges, charter.fleet_type.as_ref(), flight)
+                    && !Self::lacks_etops(charter.etops, flight)
+                    && !Self::is_airport_closed(
+                        &self.airports,
+                        flight,
+                        flight.departure_time,
+                        flight.arrival_time,
+                    )
+                    && Self::custom_violation(
+                        &self.constraints,
+                        flight,
+                        &ConstraintContext {
+                            aircraft: &aircraft,
+                            departure: flight.departure_time,
+                            arrival: flight.arrival_time,
+                            airports: &self.airports,
+                        },
+                    )
+                    .is_none()
+                {
+                    rotation.push(i);
+                    location = flight.destination_id.clone();
+                    ready = Self::get_ready_time(
+                        &self.airports,
+                        flight.arrival_time,
+                        &flight.destination_id,
+                    );
+                }
+            }
+
+            let block_minutes = rotation
+                .iter()
+                .map(|i| (self.flights[*i].arrival_time - self.flights[*i].departure_time).0)
+                .sum();
+            let avoided = rotation.iter().map(|i| cancellation[*i]).sum::<u64>();
+            if rotation.is_empty() || charter.block_cost(block_minutes) >= avoided {
+                debug!(
+                    "charter {} not hired: {} legs, EUR {} block cost vs EUR {} avoided",
+                    charter.id,
+                    rotation.len(),
+                    charter.block_cost(block_minutes),
+                    avoided
+                );
+                continue;
+            }
+
+            debug!(
+                "charter {} hired for {} legs, EUR {}",
+                charter.id,
+                rotation.len(),
+                charter.block_cost(block_minutes)
+            );
+            self.aircraft.insert(charter.id.clone(), aircraft);
+            for i in rotation {
+                self.flights[i].aircraft_id = Some(charter.id.clone());
+                self.flights[i].status = Scheduled;
+            }
+        }
+        self.index_rotations();
+
+        #[cfg(debug_assertions)]
+        self.assert_invariants();
+    }
+}
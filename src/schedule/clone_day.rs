@@ -0,0 +1,72 @@
+use crate::flight::Flight;
+use crate::flight::FlightStatus::{Delayed, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::schedule::Schedule;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum CloneDayError {
+    NoFlights(u64),
+    NoCopies,
+}
+
+impl fmt::Display for CloneDayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CloneDayError::NoFlights(day) => write!(f, "no flights operate on day {}", day),
+            CloneDayError::NoCopies => write!(f, "at least one copy is needed"),
+        }
+    }
+}
+
+impl std::error::Error for CloneDayError {}
+
+impl Schedule {
+    /// Copies the flights operating on `day` into each of the following `copies` days,
+    /// 1440 minutes apart. Copies keep their flight number, so each is told apart by its
+    /// new operating day, and start out unassigned at the originally scheduled times;
+    /// days that already fly a number keep their own flight. Returns how many flights
+    /// were added.
+    pub fn clone_day(&mut self, day: u64, copies: u64) -> Result<usize, CloneDayError> {
+        if copies == 0 {
+            return Err(CloneDayError::NoCopies);
+        }
+        let template = self
+            .flights
+            .iter()
+            .filter(|f| f.day == day)
+            .map(|f| {
+                let delay = match f.status {
+                    Delayed { minutes } => minutes,
+                    _ => 0,
+                };
+                (f, delay)
+            })
+            .collect::<Vec<_>>();
+        if template.is_empty() {
+            return Err(CloneDayError::NoFlights(day));
+        }
+
+        let mut added = vec![];
+        for n in 1..=copies {
+            let offset = n * 1440;
+            for &(f, delay) in &template {
+                let copy = Flight {
+                    day: day + n,
+                    departure_time: f.departure_time - delay + offset,
+                    arrival_time: f.arrival_time - delay + offset,
+                    aircraft_id: None,
+                    status: Unscheduled(Waiting),
+                    ..f.clone()
+                };
+                if self.flight(&copy.key()).is_none() {
+                    added.push(copy);
+                }
+            }
+        }
+        let count = added.len();
+        self.flights.extend(added);
+        self.reindex();
+        Ok(count)
+    }
+}
@@ -0,0 +1,89 @@
+use crate::aircraft::{Aircraft, AircraftId};
+use crate::airport::{Airport, AirportId};
+use crate::flight::{Flight, FlightId};
+use crate::schedule::schedule::Schedule;
+use crate::time::Time;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// A placement being judged: `aircraft` flying the flight from `departure` to `arrival`,
+/// which may differ from the flight's current times while a delay propagates.
+pub struct ConstraintContext<'a> {
+    pub aircraft: &'a Aircraft,
+    pub departure: Time,
+    pub arrival: Time,
+    pub airports: &'a HashMap<AirportId, Airport>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    pub rule: String,
+    pub detail: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.rule, self.detail)
+    }
+}
+
+/// A custom rule evaluated alongside the built-in checks by `assign()`, the other
+/// recovery backends and delay propagation.
+pub trait Constraint: Send + Sync {
+    fn violated(&self, flight: &Flight, context: &ConstraintContext) -> Option<Violation>;
+}
+
+/// Declarative rules from the `rules` section of a scenario file, e.g.
+/// `{"kind": "avoid_airport", "aircraft_id": "SP-LRA", "airport_id": "GDN"}`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Rule {
+    /// The aircraft must neither depart from nor land at the airport.
+    AvoidAirport {
+        aircraft_id: AircraftId,
+        airport_id: AirportId,
+    },
+    /// The flight must depart no later than `latest`.
+    DepartBy { flight_id: FlightId, latest: Time },
+}
+
+impl Constraint for Rule {
+    fn violated(&self, flight: &Flight, context: &ConstraintContext) -> Option<Violation> {
+        match self {
+            Rule::AvoidAirport {
+                aircraft_id,
+                airport_id,
+            } => (context.aircraft.id == *aircraft_id
+                && (flight.origin_id == *airport_id || flight.destination_id == *airport_id))
+                .then(|| Violation {
+                    rule: "avoid_airport".to_string(),
+                    detail: format!("{} must not visit {}", aircraft_id, airport_id),
+                }),
+            Rule::DepartBy { flight_id, latest } => {
+                (flight.id == *flight_id && context.departure > *latest).then(|| Violation {
+                    rule: "depart_by".to_string(),
+                    detail: format!("{} must depart by {}", flight_id, latest),
+                })
+            }
+        }
+    }
+}
+
+impl Schedule {
+    /// Registers a custom rule; flights assigned before are not re-checked.
+    pub fn add_constraint(&mut self, constraint: impl Constraint + 'static) {
+        self.constraints.push(Arc::new(constraint));
+    }
+
+    /// First registered rule the placement breaks, if any.
+    pub(crate) fn custom_violation(
+        constraints: &[Arc<dyn Constraint>],
+        flight: &Flight,
+        context: &ConstraintContext,
+    ) -> Option<Violation> {
+        constraints.iter().find_map(|c| c.violated(flight, context))
+    }
+}
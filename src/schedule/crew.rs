@@ -0,0 +1,196 @@
+use crate::crew::{CrewId, RestBreach};
+use crate::flight::{Flight, FlightKey};
+use crate::schedule::schedule::Schedule;
+use tracing::debug;
+
+/// A reserve crew called out by recovery, with the flights it took over.
+pub struct Callout {
+    pub crew_id: CrewId,
+    pub flights: Vec<FlightKey>,
+    pub cost: u64,
+}
+
+impl Schedule {
+    fn max_duty(&self, crew_id: &CrewId) -> Option<u64> {
+        self.crews
+            .iter()
+            .find(|c| c.id == *crew_id)
+            .map(|c| c.max_duty)
+            .or_else(|| {
+                self.reserves
+                    .iter()
+                    .find(|r| r.id == *crew_id)
+                    .map(|r| r.max_duty)
+            })
+    }
+
+    /// Operating flights of a crew in departure order.
+    fn crew_flights(&self, crew_id: &CrewId) -> Vec<&Flight> {
+        let mut flights = self
+            .flights
+            .iter()
+            .filter(|f| !f.status.is_unscheduled() && f.crew_id.as_ref() == Some(crew_id))
+            .collect::<Vec<_>>();
+        flights.sort_by_key(|f| f.departure_time);
+        flights
+    }
+
+    /// Duties of a crew, one per operating day, each in departure order.
+    fn duties(&self, crew_id: &CrewId) -> Vec<Vec<&Flight>> {
+        let mut duties: Vec<Vec<&Flight>> = vec![];
+        for flight in self.crew_flights(crew_id) {
+            match duties.last_mut() {
+                Some(duty) if duty[0].day == flight.day => duty.push(flight),
+                _ => duties.push(vec![flight]),
+            }
+        }
+        duties
+    }
+
+    /// Operating flights landing after their crew's duty limit, counted from the first
+    /// departure of the duty, in departure order.
+    pub fn duty_breaches(&self) -> Vec<FlightKey> {
+        let mut crew_ids = self
+            .crews
+            .iter()
+            .map(|c| &c.id)
+            .chain(self.reserves.iter().map(|r| &r.id))
+            .collect::<Vec<_>>();
+        crew_ids.sort();
+        let mut breaches = crew_ids
+            .into_iter()
+            .flat_map(|crew_id| {
+                let max_duty = self.max_duty(crew_id).unwrap_or(u64::MAX);
+                self.duties(crew_id).into_iter().flat_map(move |duty| {
+                    let start = duty[0].departure_time;
+                    duty.into_iter()
+                        .filter(move |f| (f.arrival_time - start).0 > max_duty)
+                        .map(|f| (f.departure_time, f.key()))
+                })
+            })
+            .collect::<Vec<_>>();
+        breaches.sort();
+        breaches.into_iter().map(|(_, key)| key).collect()
+    }
+
+    /// First flights of duties a crew cannot start as planned, having rested too little or
+    /// somewhere else after its previous duty, in departure order.
+    pub fn rest_breaches(&self) -> Vec<(FlightKey, CrewId, RestBreach)> {
+        let mut breaches = self
+            .crews
+            .iter()
+            .flat_map(|crew| {
+                self.duties(&crew.id)
+                    .windows(2)
+                    .filter_map(|pair| {
+                        let (last, next) = (pair[0].last()?, pair[1].first()?);
+                        let rested_at = &last.destination_id;
+                        let breach = if next.origin_id != *rested_at {
+                            RestBreach::Positioning {
+                                rested_at: rested_at.clone(),
+                                origin: next.origin_id.clone(),
+                            }
+                        } else {
+                            let required = crew.required_rest(rested_at);
+                            let available =
+                                next.departure_time.0.saturating_sub(last.arrival_time.0);
+                            if available >= required {
+                                return None;
+                            }
+                            RestBreach::Short {
+                                required,
+                                available,
+                            }
+                        };
+                        Some((next.departure_time, next.key(), crew.id.clone(), breach))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        breaches.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+        breaches
+            .into_iter()
+            .map(|(_, key, crew_id, breach)| (key, crew_id, breach))
+            .collect()
+    }
+
+    /// Reserve crews called out so far, with the flights they fly and their cost.
+    pub fn callouts(&self) -> Vec<Callout> {
+        self.reserves
+            .iter()
+            .filter_map(|reserve| {
+                let flights = self
+                    .crew_flights(&reserve.id)
+                    .iter()
+                    .map(|f| f.key())
+                    .collect::<Vec<_>>();
+                (!flights.is_empty()).then(|| Callout {
+                    crew_id: reserve.id.clone(),
+                    flights,
+                    cost: self.cost.reserve_callout,
+                })
+            })
+            .collect()
+    }
+
+    pub fn callout_cost(&self) -> u64 {
+        self.callouts().iter().map(|c| c.cost).sum()
+    }
+
+    /// Calls out reserve crews for flights whose crew runs out of duty time or cannot
+    /// start the duty after its rest. A reserve takes over the breached flight and the
+    /// rest of that duty, provided it is based at the flight's origin, available for all
+    /// of it and within its own duty limit. Each reserve is called out at most once.
+    pub fn call_reserves(&mut self) {
+        let mut breaches = self
+            .duty_breaches()
+            .into_iter()
+            .chain(self.rest_breaches().into_iter().map(|(key, _, _)| key))
+            .filter_map(|key| self.flight(&key).map(|f| (f.departure_time, key)))
+            .collect::<Vec<_>>();
+        breaches.sort();
+        breaches.dedup();
+        for (_, key) in breaches {
+            let Some(flight) = self.flight(&key) else {
+                continue;
+            };
+            // an earlier callout in this pass may already cover the flight
+            let Some(crew_id) = flight.crew_id.clone() else {
+                continue;
+            };
+            if self.reserves.iter().any(|r| r.id == crew_id) {
+                continue;
+            }
+            let taken = self
+                .duties(&crew_id)
+                .into_iter()
+                .find(|duty| duty[0].day == flight.day)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|f| f.departure_time >= flight.departure_time)
+                .map(|f| (f.key(), f.departure_time, f.arrival_time))
+                .collect::<Vec<_>>();
+            let (Some(first), Some(last)) = (taken.first(), taken.last()) else {
+                continue;
+            };
+            let (start, end) = (first.1, last.2);
+            let reserve = self
+                .reserves
+                .iter()
+                .filter(|r| r.base_id == flight.origin_id)
+                .filter(|r| r.from <= start && end <= r.to && (end - start).0 <= r.max_duty)
+                .find(|r| self.crew_flights(&r.id).is_empty())
+                .map(|r| r.id.clone());
+            let Some(reserve) = reserve else {
+                debug!("no reserve crew for {} at {}", key, flight.origin_id);
+                continue;
+            };
+            debug!("reserve crew {} called out from {}", reserve, key);
+            for (key, _, _) in taken {
+                if let Some(f) = self.flights.iter_mut().find(|f| f.key() == key) {
+                    f.crew_id = Some(reserve.clone());
+                }
+            }
+        }
+    }
+}
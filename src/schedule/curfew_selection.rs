@@ -0,0 +1,142 @@
+use crate::aircraft::Aircraft;
+use crate::airport::{AirportId, Curfew};
+use crate::flight::FlightStatus::{Delayed, Scheduled};
+use crate::flight::UnscheduledReason::AirportCurfew;
+use crate::flight::{Flight, FlightKey, UnscheduledReason};
+use crate::schedule::schedule::{CurfewSelection, Schedule};
+use crate::time::Time;
+
+/// What a new curfew cancels, and the flights it moves back towards schedule when a
+/// loop before them is cancelled instead.
+pub(crate) struct CurfewPlan {
+    pub(crate) unscheduled: Vec<(FlightKey, UnscheduledReason)>,
+    /// New departure and arrival of each flight moved.
+    pub(crate) retimed: Vec<(FlightKey, Time, Time)>,
+}
+
+impl Schedule {
+    /// The flights `curfew_breaks` cancels, or with `CurfewSelection::Priority` per broken
+    /// aircraft the lightest loop before its first hit leg whose cancellation lets the
+    /// rest of the rotation operate, when that weighs less than what the curfew breaks.
+    pub(crate) fn curfew_plan(&self, airport_id: &AirportId, curfew: &Curfew) -> CurfewPlan {
+        let mut unscheduled = self.curfew_breaks(airport_id, curfew);
+        let mut retimed = vec![];
+        if self.curfew_selection == CurfewSelection::Blind {
+            return CurfewPlan {
+                unscheduled,
+                retimed,
+            };
+        }
+
+        let mut aircraft_ids = unscheduled
+            .iter()
+            .filter(|(_, reason)| *reason == AirportCurfew)
+            .filter_map(|(key, _)| self.flight(key)?.aircraft_id.clone())
+            .collect::<Vec<_>>();
+        aircraft_ids.sort();
+        aircraft_ids.dedup();
+        for aircraft_id in aircraft_ids {
+            let Some(aircraft) = self.aircraft.get(&aircraft_id) else {
+                continue;
+            };
+            let legs = self
+                .flights
+                .iter()
+                .filter(|f| {
+                    !f.status.is_unscheduled() && f.aircraft_id.as_ref() == Some(&aircraft_id)
+                })
+                .collect::<Vec<_>>();
+            let Some(hit) = legs
+                .iter()
+                .position(|f| unscheduled.contains(&(f.key(), AirportCurfew)))
+            else {
+                continue;
+            };
+            let blind = legs
+                .iter()
+                .filter(|f| unscheduled.iter().any(|(key, _)| *key == f.key()))
+                .map(|f| f.weight())
+                .sum::<u64>();
+            let best = (0..hit)
+                .filter_map(|start| {
+                    let times = self.truncation(airport_id, curfew, aircraft, &legs, start, hit)?;
+                    let weight = legs[start..hit].iter().map(|f| f.weight()).sum::<u64>();
+                    Some((weight, start, times))
+                })
+                .min_by_key(|(weight, ..)| *weight);
+            if let Some((weight, start, times)) = best
+                && weight < blind
+            {
+                let keys = legs.iter().map(|f| f.key()).collect::<Vec<_>>();
+                unscheduled.retain(|(key, _)| !keys.contains(key));
+                unscheduled.extend(legs[start..hit].iter().map(|f| (f.key(), AirportCurfew)));
+                retimed.extend(times);
+            }
+        }
+        CurfewPlan {
+            unscheduled,
+            retimed,
+        }
+    }
+
+    /// New times of `legs[hit..]` when the loop `legs[start..hit]`, which returns to where
+    /// the hit leg departs, is cancelled and every later leg departs at its scheduled time
+    /// or as soon as the aircraft is ready, whichever is later; `None` unless the loop
+    /// can be dropped and all of them then operate clear of the curfew.
+    fn truncation(
+        &self,
+        airport_id: &AirportId,
+        curfew: &Curfew,
+        aircraft: &Aircraft,
+        legs: &[&Flight],
+        start: usize,
+        hit: usize,
+    ) -> Option<Vec<(FlightKey, Time, Time)>> {
+        if legs[start].origin_id != legs[hit].origin_id
+            || legs[hit - 1].destination_id != legs[hit].origin_id
+            || legs[start..hit].iter().any(|f| self.is_frozen(f))
+        {
+            return None;
+        }
+        let mut ready = match start {
+            0 => Time(0),
+            _ => Self::get_ready_time(
+                &self.airports,
+                legs[start - 1].arrival_time,
+                &legs[start - 1].destination_id,
+            ),
+        };
+        let mut times = vec![];
+        for f in &legs[hit..] {
+            let (scheduled, _) = f.scheduled_times();
+            let dep = scheduled.max(ready).min(f.departure_time);
+            let arr = dep + (f.arrival_time - f.departure_time).0;
+            if (dep < f.departure_time && self.is_frozen(f))
+                || self.is_curfew_hit(airport_id, curfew, f, dep, arr)
+                || Self::airport_closure(&self.airports, f, dep, arr).is_some()
+                || Self::violates_aircraft_maintenance(&aircraft.disruptions, dep, arr)
+            {
+                return None;
+            }
+            if dep < f.departure_time {
+                times.push((f.key(), dep, arr));
+            }
+            ready = Self::get_ready_time(&self.airports, arr, &f.destination_id);
+        }
+        Some(times)
+    }
+
+    /// Moves a flight to `dep..arr`, earlier than now, keeping what is left of its delay.
+    pub(crate) fn move_back(&mut self, key: &FlightKey, dep: Time, arr: Time) {
+        let Some(flight) = self.flights.iter_mut().find(|f| f.key() == *key) else {
+            return;
+        };
+        let (scheduled, _) = flight.scheduled_times();
+        flight.departure_time = dep;
+        flight.arrival_time = arr;
+        flight.status = match (dep - scheduled).0 {
+            0 => Scheduled,
+            minutes => Delayed { minutes },
+        };
+    }
+}
@@ -0,0 +1,93 @@
+use crate::schedule::schedule::Schedule;
+use crate::time::Time;
+use std::fmt;
+
+/// A REPL command queued with `at` to run once the clock reaches `at`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueuedEvent {
+    pub id: usize,
+    pub at: Time,
+    pub command: String,
+}
+
+/// Commands waiting for the simulation clock, numbered in the order they were queued.
+#[derive(Clone, Default)]
+pub struct Timeline {
+    next_id: usize,
+    events: Vec<QueuedEvent>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EventError {
+    /// The time is before the clock.
+    Past(Time),
+    EmptyCommand,
+    Unknown(usize),
+}
+
+impl fmt::Display for EventError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventError::Past(clock) => write!(f, "the clock is already at {}", clock),
+            EventError::EmptyCommand => write!(f, "no command to queue"),
+            EventError::Unknown(id) => write!(f, "no queued event {}", id),
+        }
+    }
+}
+
+impl std::error::Error for EventError {}
+
+impl Schedule {
+    /// Queues `command` to fire when the clock reaches `at`; returns its event number.
+    pub fn queue_event(&mut self, at: Time, command: &str) -> Result<usize, EventError> {
+        if at < self.clock {
+            return Err(EventError::Past(self.clock));
+        }
+        if command.trim().is_empty() {
+            return Err(EventError::EmptyCommand);
+        }
+        self.timeline.next_id += 1;
+        let id = self.timeline.next_id;
+        self.timeline.events.push(QueuedEvent {
+            id,
+            at,
+            command: command.trim().to_string(),
+        });
+        Ok(id)
+    }
+
+    /// Queued events in firing order: by time, then in the order they were queued.
+    pub fn events(&self) -> Vec<&QueuedEvent> {
+        let mut events = self.timeline.events.iter().collect::<Vec<_>>();
+        events.sort_by_key(|e| (e.at, e.id));
+        events
+    }
+
+    pub fn cancel_event(&mut self, id: usize) -> Result<QueuedEvent, EventError> {
+        let idx = self
+            .timeline
+            .events
+            .iter()
+            .position(|e| e.id == id)
+            .ok_or(EventError::Unknown(id))?;
+        Ok(self.timeline.events.remove(idx))
+    }
+
+    /// Takes the events due by `until` off the timeline, in firing order, for the caller
+    /// to run with the clock moved to each one's time.
+    pub fn take_due_events(&mut self, until: Time) -> Vec<QueuedEvent> {
+        let (mut due, waiting) = std::mem::take(&mut self.timeline.events)
+            .into_iter()
+            .partition::<Vec<_>, _>(|e| e.at <= until);
+        self.timeline.events = waiting;
+        due.sort_by_key(|e| (e.at, e.id));
+        due
+    }
+
+    /// Moves every queued event `minutes` earlier, for `rollover`.
+    pub(crate) fn rebase_events(&mut self, minutes: u64) {
+        for e in self.timeline.events.iter_mut() {
+            e.at = Time(e.at.0.saturating_sub(minutes));
+        }
+    }
+}
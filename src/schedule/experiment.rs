@@ -0,0 +1,125 @@
+use crate::aircraft::{Aircraft, AircraftId, FleetType};
+use crate::airport::AirportId;
+use crate::schedule::schedule::{Objective, Schedule, Strategy};
+use std::fmt;
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq)]
+pub enum ExperimentError {
+    UnknownAirport(AirportId),
+    NoAircraft,
+}
+
+impl fmt::Display for ExperimentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExperimentError::UnknownAirport(id) => write!(f, "unknown airport {}", id),
+            ExperimentError::NoAircraft => write!(f, "at least one aircraft is needed"),
+        }
+    }
+}
+
+impl std::error::Error for ExperimentError {}
+
+/// Unscheduled flights left by recovery with `added` hypothetical spares in the fleet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExperimentStep {
+    pub added: usize,
+    pub unscheduled: usize,
+}
+
+/// What adding spare tails would buy: recovery rerun on copies of the schedule with
+/// none, one, two and so on up to all of them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FleetExperiment {
+    pub fleet_type: FleetType,
+    pub base: AirportId,
+    /// Tails the spares were given, in the order they were added.
+    pub aircraft: Vec<AircraftId>,
+    /// Starting with no spares added.
+    pub steps: Vec<ExperimentStep>,
+}
+
+impl FleetExperiment {
+    /// Flights saved by all spares together.
+    pub fn saved(&self) -> usize {
+        match (self.steps.first(), self.steps.last()) {
+            (Some(first), Some(last)) => first.unscheduled.saturating_sub(last.unscheduled),
+            _ => 0,
+        }
+    }
+
+    pub fn saved_per_aircraft(&self) -> f64 {
+        match self.aircraft.len() {
+            0 => 0.0,
+            n => self.saved() as f64 / n as f64,
+        }
+    }
+}
+
+impl Schedule {
+    /// Reruns greedy recovery on copies of the schedule with up to `count` spare
+    /// aircraft of `fleet_type` based at `base` and free all day, and reports how many
+    /// flights stay unscheduled with each. The schedule itself is left alone.
+    pub fn experiment_add_aircraft(
+        &self,
+        fleet_type: FleetType,
+        base: AirportId,
+        count: usize,
+    ) -> Result<FleetExperiment, ExperimentError> {
+        if !self.airports.contains_key(&base) {
+            return Err(ExperimentError::UnknownAirport(base));
+        }
+        if count == 0 {
+            return Err(ExperimentError::NoAircraft);
+        }
+        let mut aircraft = vec![];
+        let mut n = 0;
+        while aircraft.len() < count {
+            n += 1;
+            let id: AircraftId = Arc::from(format!("SPARE_{}", n));
+            if !self.aircraft.contains_key(&id) {
+                aircraft.push(id);
+            }
+        }
+
+        // spares are certified like the tails of their type already flying
+        let etops = self
+            .aircraft
+            .values()
+            .any(|a| a.etops && a.fleet_type.as_ref() == Some(&fleet_type));
+        let steps = (0..=count)
+            .map(|added| {
+                let mut trial = self.fork();
+                for id in &aircraft[..added] {
+                    trial.aircraft.insert(
+                        id.clone(),
+                        Aircraft {
+                            id: id.clone(),
+                            disruptions: vec![],
+                            initial_location_id: base.clone(),
+                            maintenance: None,
+                            fleet_type: Some(fleet_type.clone()),
+                            etops,
+                        },
+                    );
+                }
+                trial.recover(Strategy::Greedy(Objective::FirstFit));
+                ExperimentStep {
+                    added,
+                    unscheduled: trial
+                        .flights
+                        .iter()
+                        .filter(|f| f.status.is_unscheduled())
+                        .count(),
+                }
+            })
+            .collect();
+        Ok(FleetExperiment {
+            fleet_type,
+            base,
+            aircraft,
+            steps,
+        })
+    }
+}
@@ -0,0 +1,160 @@
+use crate::aircraft::AircraftId;
+use crate::airport::AirportId;
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::flight::{Flight, FlightKey};
+use crate::schedule::schedule::Schedule;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Something the schedule cannot fly as it stands, typically a rotation pre-assigned in a
+/// scenario.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChainViolation {
+    /// Operating without an aircraft, or unscheduled with one.
+    Assignment(FlightKey),
+    UnknownAircraft(FlightKey, AircraftId),
+    /// Delayed by zero minutes.
+    ZeroDelay(FlightKey),
+    /// Departs from somewhere other than where its aircraft is.
+    Continuity {
+        flight: FlightKey,
+        expected: AirportId,
+        actual: AirportId,
+    },
+    /// Departs before its aircraft has turned after the previous leg.
+    TurnTime {
+        flight: FlightKey,
+        required: u64,
+        available: u64,
+    },
+    /// Flies into a maintenance window of its aircraft.
+    Maintenance(FlightKey, AircraftId),
+}
+
+impl ChainViolation {
+    pub fn flight(&self) -> &FlightKey {
+        match self {
+            ChainViolation::Assignment(flight)
+            | ChainViolation::UnknownAircraft(flight, _)
+            | ChainViolation::ZeroDelay(flight)
+            | ChainViolation::Continuity { flight, .. }
+            | ChainViolation::TurnTime { flight, .. }
+            | ChainViolation::Maintenance(flight, _) => flight,
+        }
+    }
+}
+
+impl fmt::Display for ChainViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainViolation::Assignment(flight) => write!(
+                f,
+                "flight {} has a status that does not match its aircraft assignment",
+                flight
+            ),
+            ChainViolation::UnknownAircraft(flight, aircraft_id) => write!(
+                f,
+                "flight {} is assigned to unknown aircraft {}",
+                flight, aircraft_id
+            ),
+            ChainViolation::ZeroDelay(flight) => {
+                write!(f, "flight {} is delayed by 0 minutes", flight)
+            }
+            ChainViolation::Continuity {
+                flight,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "flight {} departs {} but its aircraft is at {}",
+                flight, actual, expected
+            ),
+            ChainViolation::TurnTime {
+                flight,
+                required,
+                available,
+            } => write!(
+                f,
+                "flight {} departs {} min after its aircraft lands, {} min turn required",
+                flight, available, required
+            ),
+            ChainViolation::Maintenance(flight, aircraft_id) => write!(
+                f,
+                "flight {} overlaps a maintenance window of {}",
+                flight, aircraft_id
+            ),
+        }
+    }
+}
+
+impl Schedule {
+    /// Checks every assigned rotation the way recovery keeps them: statuses matching
+    /// assignments, each leg departing where the previous one landed, or where the
+    /// aircraft starts, after the minimum turn time and clear of maintenance. In flight
+    /// order, then rotation order.
+    pub fn chain_violations(&self) -> Vec<ChainViolation> {
+        let mut violations = vec![];
+        let mut rotations: HashMap<&AircraftId, Vec<&Flight>> = HashMap::new();
+        for flight in &self.flights {
+            // scenarios leave the aircraft of unassigned flights as ""
+            let aircraft_id = flight.aircraft_id.as_ref().filter(|id| !id.is_empty());
+            match (&flight.status, aircraft_id) {
+                (Unscheduled(_), None) => {}
+                (Scheduled | Delayed { .. }, Some(aircraft_id)) => {
+                    rotations.entry(aircraft_id).or_default().push(flight)
+                }
+                _ => violations.push(ChainViolation::Assignment(flight.key())),
+            }
+            if flight.status == (Delayed { minutes: 0 }) {
+                violations.push(ChainViolation::ZeroDelay(flight.key()));
+            }
+        }
+
+        let mut rotations = rotations.into_iter().collect::<Vec<_>>();
+        rotations.sort_by_key(|(aircraft_id, _)| *aircraft_id);
+        for (aircraft_id, mut legs) in rotations {
+            let Some(aircraft) = self.aircraft.get(aircraft_id) else {
+                violations.extend(
+                    legs.iter()
+                        .map(|f| ChainViolation::UnknownAircraft(f.key(), aircraft_id.clone())),
+                );
+                continue;
+            };
+            legs.sort_by_key(|f| f.departure_time);
+            let mut location = &aircraft.initial_location_id;
+            let mut landed = None;
+            for flight in legs {
+                if flight.origin_id != *location {
+                    violations.push(ChainViolation::Continuity {
+                        flight: flight.key(),
+                        expected: location.clone(),
+                        actual: flight.origin_id.clone(),
+                    });
+                } else if let Some(arrival) = landed {
+                    let required = self.airports.get(location).map_or(0, |a| a.mtt);
+                    let available = flight.departure_time.0.saturating_sub(arrival);
+                    if available < required {
+                        violations.push(ChainViolation::TurnTime {
+                            flight: flight.key(),
+                            required,
+                            available,
+                        });
+                    }
+                }
+                if Self::violates_aircraft_maintenance(
+                    &aircraft.disruptions,
+                    flight.departure_time,
+                    flight.arrival_time,
+                ) {
+                    violations.push(ChainViolation::Maintenance(
+                        flight.key(),
+                        aircraft_id.clone(),
+                    ));
+                }
+                location = &flight.destination_id;
+                landed = Some(flight.arrival_time.0);
+            }
+        }
+        violations
+    }
+}
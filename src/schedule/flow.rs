@@ -0,0 +1,319 @@
+use crate::aircraft::AircraftId;
+use crate::airport::AirportId;
+use crate::flight::FlightStatus::Scheduled;
+use crate::schedule::capacity::MovementBook;
+use crate::schedule::constraint::ConstraintContext;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::stands::StandBook;
+use crate::time::Time;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use tracing::instrument;
+
+struct Edge {
+    to: usize,
+    cap: i64,
+    cost: i64,
+}
+
+/// Successive shortest path min-cost flow with Johnson potentials.
+struct MinCostFlow {
+    edges: Vec<Edge>,
+    graph: Vec<Vec<usize>>,
+}
+
+impl MinCostFlow {
+    fn new(nodes: usize) -> Self {
+        MinCostFlow {
+            edges: vec![],
+            graph: vec![vec![]; nodes],
+        }
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, cap: i64, cost: i64) -> usize {
+        let id = self.edges.len();
+        self.graph[from].push(id);
+        self.edges.push(Edge { to, cap, cost });
+        self.graph[to].push(id + 1);
+        self.edges.push(Edge {
+            to: from,
+            cap: 0,
+            cost: -cost,
+        });
+        id
+    }
+
+    /// Pushes up to `limit` units from `s` to `t`. `order` must be a topological order
+    /// of the initial graph, used to seed potentials despite negative arc costs.
+    fn run(&mut self, s: usize, t: usize, limit: i64, order: &[usize]) {
+        let n = self.graph.len();
+        let mut potential = vec![i64::MAX; n];
+        potential[s] = 0;
+        for &u in order {
+            if potential[u] == i64::MAX {
+                continue;
+            }
+            for &e in &self.graph[u] {
+                let edge = &self.edges[e];
+                if edge.cap > 0 && potential[u] + edge.cost < potential[edge.to] {
+                    potential[edge.to] = potential[u] + edge.cost;
+                }
+            }
+        }
+        potential
+            .iter_mut()
+            .filter(|p| **p == i64::MAX)
+            .for_each(|p| *p = 0);
+
+        let mut flow = 0;
+        while flow < limit {
+            let mut dist = vec![i64::MAX; n];
+            let mut prev = vec![usize::MAX; n];
+            let mut heap = BinaryHeap::new();
+            dist[s] = 0;
+            heap.push(Reverse((0, s)));
+            while let Some(Reverse((d, u))) = heap.pop() {
+                if d > dist[u] {
+                    continue;
+                }
+                for &e in &self.graph[u] {
+                    let edge = &self.edges[e];
+                    if edge.cap <= 0 {
+                        continue;
+                    }
+                    let nd = d + edge.cost + potential[u] - potential[edge.to];
+                    if nd < dist[edge.to] {
+                        dist[edge.to] = nd;
+                        prev[edge.to] = e;
+                        heap.push(Reverse((nd, edge.to)));
+                    }
+                }
+            }
+            if dist[t] == i64::MAX {
+                break;
+            }
+            (0..n)
+                .filter(|v| dist[*v] != i64::MAX)
+                .for_each(|v| potential[v] += dist[v]);
+
+            let mut v = t;
+            while v != s {
+                let e = prev[v];
+                self.edges[e].cap -= 1;
+                self.edges[e ^ 1].cap += 1;
+                v = self.edges[e ^ 1].to;
+            }
+            flow += 1;
+        }
+    }
+}
+
+impl Schedule {
+    /// Tail assignment as a min-cost flow: every aircraft is one unit of flow leaving
+    /// its current position, every unscheduled flight is a node worth -1, and legs
+    /// connect when airports match and the turn, stretched by ground handling, fits.
+    /// Unlike first-fit, this never spends an aircraft on a flight another tail could
+    /// have covered.
+    ///
+    /// The flow is only exact for interchangeable tails. Whatever holds for one aircraft
+    /// but not another (maintenance, fleet type, range, ETOPS, custom rules, stands,
+    /// metered movements) is checked on the first leg of a path and otherwise only once
+    /// the flow is solved, cutting the path where it fails; the legs cut off go to a
+    /// first-fit pass.
+    #[instrument(level = "debug", skip_all, fields(aircraft = self.aircraft.len()))]
+    pub fn assign_flow(&mut self) {
+        let mut sorted_ids = self.aircraft.keys().cloned().collect::<Vec<AircraftId>>();
+        sorted_ids.sort();
+
+        let mut ready: HashMap<AircraftId, (AirportId, Time)> = self
+            .aircraft
+            .iter()
+            .map(|(id, ac)| (id.clone(), (ac.initial_location_id.clone(), Time(0))))
+            .collect();
+        for f in self.flights.iter().filter(|f| !f.status.is_unscheduled()) {
+            if let Some(ac_id) = &f.aircraft_id {
+                let ready_at = self.turn_ready_time(&f.destination_id, f.arrival_time, Some(ac_id));
+                if ready.get(ac_id).is_none_or(|(_, t)| *t <= ready_at) {
+                    ready.insert(ac_id.clone(), (f.destination_id.clone(), ready_at));
+                }
+            }
+        }
+
+        // flights that can operate at all, in departure order
+        let mut open = self
+            .flights
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.status.is_unscheduled() && !self.is_frozen(f))
+            .filter(|(_, f)| {
+                !Self::is_airport_closed(&self.airports, f, f.departure_time, f.arrival_time)
+            })
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        open.sort_by_key(|i| self.flights[*i].departure_time);
+
+        // nodes: source, aircraft, flight in/out pairs, sink
+        let source = 0;
+        let ac_node = |a: usize| 1 + a;
+        let in_node = |f: usize| 1 + sorted_ids.len() + 2 * f;
+        let out_node = |f: usize| 2 + sorted_ids.len() + 2 * f;
+        let sink = 1 + sorted_ids.len() + 2 * open.len();
+        let mut mcf = MinCostFlow::new(sink + 1);
+
+        let mut start_edges = HashMap::new();
+        for (a, ac_id) in sorted_ids.iter().enumerate() {
+            mcf.add_edge(source, ac_node(a), 1, 0);
+            mcf.add_edge(ac_node(a), sink, 1, 0);
+            let (location, ready_at) = &ready[ac_id];
+            let aircraft = &self.aircraft[ac_id];
+            let disruptions = &aircraft.disruptions;
+            for (f, idx) in open.iter().enumerate() {
+                let flight = &self.flights[*idx];
+                if flight.origin_id == *location
+                    && flight.departure_time >= *ready_at
+                    && !Self::is_type_restricted(
+                        &self.airports,
+                        aircraft.fleet_type.as_ref(),
+                        flight,
+                    )
+                    && !Self::is_out_of_range(&self.ranges, aircraft.fleet_type.as_ref(), flight)
+                    && !Self::lacks_etops(aircraft.etops, flight)
+                    && !Self::violates_aircraft_maintenance(
+                        disruptions,
+                        flight.departure_time,
+                        flight.arrival_time,
+                    )
+                    && !Self::is_at_wrong_airport(
+                        disruptions,
+                        flight.departure_time,
+                        Some(&(location.clone(), *ready_at)),
+                    )
+                {
+                    start_edges.insert(mcf.add_edge(ac_node(a), in_node(f), 1, 0), (a, f));
+                }
+            }
+        }
+
+        let mut leg_edges = HashMap::new();
+        for (f, idx) in open.iter().enumerate() {
+            mcf.add_edge(in_node(f), out_node(f), 1, -1);
+            mcf.add_edge(out_node(f), sink, 1, 0);
+            let flight = &self.flights[*idx];
+            let ready_at = self.turn_ready_time(&flight.destination_id, flight.arrival_time, None);
+            for (g, next_idx) in open.iter().enumerate() {
+                let next = &self.flights[*next_idx];
+                if next.origin_id == flight.destination_id && next.departure_time >= ready_at {
+                    leg_edges.insert(mcf.add_edge(out_node(f), in_node(g), 1, 0), (f, g));
+                }
+            }
+        }
+
+        // topological order: flights are sorted by departure and only connect forward
+        let mut order = vec![source];
+        order.extend((0..sorted_ids.len()).map(ac_node));
+        for f in 0..open.len() {
+            order.push(in_node(f));
+            order.push(out_node(f));
+        }
+        order.push(sink);
+        mcf.run(source, sink, sorted_ids.len() as i64, &order);
+
+        let used = |e: &usize| mcf.edges[*e].cap == 0;
+        let next_leg = leg_edges
+            .iter()
+            .filter(|(e, _)| used(e))
+            .map(|(_, (f, g))| (*f, *g))
+            .collect::<HashMap<usize, usize>>();
+        let mut starts = start_edges
+            .iter()
+            .filter(|(e, _)| used(e))
+            .map(|(_, (a, f))| (*a, *f))
+            .collect::<Vec<_>>();
+        starts.sort();
+
+        let mut stands = StandBook::new(self);
+        let mut movements = MovementBook::new(self);
+        for (a, first) in starts {
+            let ac_id = &sorted_ids[a];
+            let aircraft = &self.aircraft[ac_id];
+            let disruptions = &aircraft.disruptions;
+            let mut usage = self.maintenance_usage(aircraft);
+            let mut position = ready[ac_id].clone();
+            let mut leg = Some(first);
+            while let Some(f) = leg {
+                let flight = &self.flights[open[f]];
+                // legs after the first are shared between tails, so re-check the
+                // aircraft-specific constraints and stop the chain where they fail
+                if Self::violates_aircraft_maintenance(
+                    disruptions,
+                    flight.departure_time,
+                    flight.arrival_time,
+                ) || Self::is_at_wrong_airport(
+                    disruptions,
+                    flight.departure_time,
+                    Some(&position),
+                ) || Self::is_type_restricted(
+                    &self.airports,
+                    aircraft.fleet_type.as_ref(),
+                    flight,
+                ) || Self::is_out_of_range(&self.ranges, aircraft.fleet_type.as_ref(), flight)
+                    || Self::lacks_etops(aircraft.etops, flight)
+                    || Self::custom_violation(
+                        &self.constraints,
+                        flight,
+                        &ConstraintContext {
+                            aircraft,
+                            departure: flight.departure_time,
+                            arrival: flight.arrival_time,
+                            airports: &self.airports,
+                        },
+                    )
+                    .is_some()
+                {
+                    break;
+                }
+                if let Some(check) = &aircraft.maintenance {
+                    usage = usage.and_then(|current| {
+                        check.fly(
+                            current,
+                            flight.departure_time,
+                            flight.arrival_time,
+                            &flight.destination_id,
+                        )
+                    });
+                    if usage.is_none() {
+                        break;
+                    }
+                }
+                // stands are shared by every tail, so the network cannot hold them
+                let next = next_leg
+                    .get(&f)
+                    .map(|g| self.flights[open[*g]].departure_time);
+                let arrival = (&flight.destination_id, flight.arrival_time);
+                let departure = (&flight.origin_id, flight.departure_time);
+                if stands.is_full(arrival.0, arrival.1, next) {
+                    break;
+                }
+                // and so are the movements an airport under a capacity reduction meters
+                if movements.is_full(departure.0, departure.1)
+                    || movements.is_full(arrival.0, arrival.1)
+                {
+                    break;
+                }
+                stands.fly(ac_id, departure, arrival, next);
+                movements.fly(departure, arrival);
+                position = (
+                    flight.destination_id.clone(),
+                    self.turn_ready_time(&flight.destination_id, flight.arrival_time, Some(ac_id)),
+                );
+                let flight = &mut self.flights[open[f]];
+                flight.aircraft_id = Some(ac_id.clone());
+                flight.status = Scheduled;
+                leg = next_leg.get(&f).copied();
+            }
+        }
+
+        // legs dropped by the per-tail re-check may still fit another aircraft
+        self.assign();
+    }
+}
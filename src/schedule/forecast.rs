@@ -0,0 +1,125 @@
+use crate::aircraft::AircraftId;
+use crate::flight::FlightStatus::Delayed;
+use crate::flight::UnscheduledReason::{
+    AircraftMaintenance, BrokenChain, ConstraintViolated, MaxDelayExceeded, OutOfPosition,
+};
+use crate::flight::{FlightKey, UnscheduledReason};
+use crate::schedule::constraint::ConstraintContext;
+use crate::schedule::schedule::Schedule;
+use crate::time::Time;
+
+/// A remaining leg of the rotation at the times it is expected to operate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForecastLeg {
+    pub flight: FlightKey,
+    pub departure_time: Time,
+    pub arrival_time: Time,
+    /// Knock-on delay from late turns on top of the delay the flight already has.
+    pub knock_on: u64,
+}
+
+/// Where an aircraft's rotation is heading if nothing is done.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Forecast {
+    pub aircraft_id: AircraftId,
+    /// Legs expected to operate, up to the break.
+    pub legs: Vec<ForecastLeg>,
+    /// The first leg that will not operate, and why; the rest of the rotation goes
+    /// with it.
+    pub break_at: Option<(FlightKey, UnscheduledReason)>,
+}
+
+impl Schedule {
+    /// Walks the operating legs of `aircraft_id` not yet departed by the clock, turning
+    /// each as soon as the previous one lands, and checks them against the aircraft's
+    /// maintenance windows, airport closures, custom rules and the max delay the way
+    /// delay propagation would. Nothing in the schedule changes. `None` for an unknown
+    /// aircraft.
+    pub fn forecast(&self, aircraft_id: &AircraftId) -> Option<Forecast> {
+        let aircraft = self.aircraft.get(aircraft_id)?;
+        let mut rotation = self
+            .flights
+            .iter()
+            .filter(|f| !f.status.is_unscheduled() && f.aircraft_id.as_ref() == Some(aircraft_id))
+            .collect::<Vec<_>>();
+        rotation.sort_by_key(|f| f.departure_time);
+
+        let mut forecast = Forecast {
+            aircraft_id: aircraft_id.clone(),
+            legs: vec![],
+            break_at: None,
+        };
+        let mut position = (aircraft.initial_location_id.clone(), None);
+        for flight in rotation {
+            if flight.departure_time <= self.clock {
+                position = (flight.destination_id.clone(), Some(flight.arrival_time));
+                continue;
+            }
+            let (location, landed) = &position;
+            let ready = landed
+                .map(|arrival| self.turn_ready_time(&flight.origin_id, arrival, Some(aircraft_id)));
+            let departure = ready.map_or(flight.departure_time, |r| r.max(flight.departure_time));
+            let arrival = departure + Self::shifted_block(&self.airports, flight, departure);
+            let knock_on = (departure - flight.departure_time).0;
+            let own = match flight.status {
+                Delayed { minutes } => minutes,
+                _ => 0,
+            };
+
+            let reason = if flight.origin_id != *location {
+                Some(BrokenChain)
+            } else if Self::violates_aircraft_maintenance(&aircraft.disruptions, departure, arrival)
+            {
+                Some(AircraftMaintenance)
+            } else if let Some(expected) = landed.and_then(|landed| {
+                Self::required_position(
+                    &aircraft.disruptions,
+                    departure,
+                    Some(&(location.clone(), landed)),
+                )
+            }) {
+                Some(OutOfPosition {
+                    expected,
+                    actual: location.clone(),
+                })
+            } else if let Some(reason) = Self::propagation_closure(
+                &self.airports,
+                self.closure_window,
+                flight,
+                departure,
+                arrival,
+            ) {
+                Some(reason)
+            } else if Self::custom_violation(
+                &self.constraints,
+                flight,
+                &ConstraintContext {
+                    aircraft,
+                    departure,
+                    arrival,
+                    airports: &self.airports,
+                },
+            )
+            .is_some()
+            {
+                Some(ConstraintViolated)
+            } else if knock_on > 0 && own + knock_on > self.max_delay {
+                Some(MaxDelayExceeded)
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                forecast.break_at = Some((flight.key(), reason));
+                break;
+            }
+            forecast.legs.push(ForecastLeg {
+                flight: flight.key(),
+                departure_time: departure,
+                arrival_time: arrival,
+                knock_on,
+            });
+            position = (flight.destination_id.clone(), Some(arrival));
+        }
+        Some(forecast)
+    }
+}
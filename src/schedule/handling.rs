@@ -0,0 +1,50 @@
+use crate::aircraft::AircraftId;
+use crate::airport::AirportId;
+use crate::schedule::schedule::Schedule;
+use crate::time::Time;
+
+impl Schedule {
+    /// When an aircraft landing at `arrival` is turned around and ready to leave. Without a
+    /// handling capacity that is the arrival plus the minimum turn time; with one, the
+    /// turn waits until fewer than `handling` turns of other aircraft that landed before it
+    /// are in progress. Each of those is taken to last the minimum turn time. Without an
+    /// `aircraft_id`, for a leg no tail flies yet, every operating turn counts.
+    pub(crate) fn turn_ready_time(
+        &self,
+        airport_id: &AirportId,
+        arrival: Time,
+        aircraft_id: Option<&AircraftId>,
+    ) -> Time {
+        let Some(airport) = self.airports.get(airport_id) else {
+            return arrival;
+        };
+        let Some(handling) = airport.handling else {
+            return arrival + airport.mtt;
+        };
+        let turns = self
+            .flights
+            .iter()
+            .filter(|f| !f.status.is_unscheduled() && f.destination_id == *airport_id)
+            .filter(|f| aircraft_id.is_none() || f.aircraft_id.as_ref() != aircraft_id)
+            .filter(|f| f.arrival_time <= arrival)
+            .map(|f| (f.arrival_time, f.arrival_time + airport.mtt))
+            .collect::<Vec<_>>();
+        let mut start = arrival;
+        loop {
+            let busy = turns
+                .iter()
+                .filter(|turn| Time::is_overlapping(&(start, start + airport.mtt.max(1)), turn))
+                .collect::<Vec<_>>();
+            if (busy.len() as u32) < handling.max(1) {
+                return start + airport.mtt;
+            }
+            // wait for the first handler to free up
+            start = busy
+                .iter()
+                .map(|(_, end)| *end)
+                .min()
+                .unwrap_or(start)
+                .max(start + 1);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::flight::FlightKey;
+use crate::schedule::schedule::Schedule;
+
+pub struct FlightImpact {
+    pub flight_id: FlightKey,
+    pub delayed: usize,
+    pub unscheduled: usize,
+    pub delay_minutes: u64,
+}
+
+impl Schedule {
+    /// Simulates `delay` minutes on every operating flight, each on its own clone of the
+    /// schedule, and ranks flights by the damage done: unscheduled flights first,
+    /// then total propagated delay minutes.
+    pub fn impact(&self, delay: u64) -> Vec<FlightImpact> {
+        let mut impacts = self
+            .flights
+            .iter()
+            .filter(|f| !f.status.is_unscheduled())
+            .map(|f| {
+                let mut what_if = self.fork();
+                // a zero delay changes nothing and leaves no report
+                let report = what_if
+                    .apply_delay(f.key(), delay)
+                    .ok()
+                    .map(|r| (r.affected.len(), r.unscheduled.len()));
+                let delay_minutes = self
+                    .flights
+                    .iter()
+                    .zip(what_if.flights.iter())
+                    .filter(|(_, after)| !after.status.is_unscheduled())
+                    .map(|(before, after)| (after.departure_time - before.departure_time).0)
+                    .sum();
+                FlightImpact {
+                    flight_id: f.key(),
+                    delayed: report.map_or(0, |(delayed, _)| delayed),
+                    unscheduled: report.map_or(0, |(_, unscheduled)| unscheduled),
+                    delay_minutes,
+                }
+            })
+            .collect::<Vec<_>>();
+        impacts.sort_by(|a, b| {
+            b.unscheduled
+                .cmp(&a.unscheduled)
+                .then(b.delay_minutes.cmp(&a.delay_minutes))
+                .then(a.flight_id.cmp(&b.flight_id))
+        });
+        impacts
+    }
+}
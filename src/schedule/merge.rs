@@ -0,0 +1,122 @@
+use crate::aircraft::AircraftId;
+use crate::airport::AirportId;
+use crate::flight::FlightKey;
+use crate::schedule::schedule::Schedule;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum MergeConflict {
+    Aircraft(AircraftId),
+    /// Same id as an airport already loaded, but a different definition.
+    Airport(AirportId),
+    Flight(FlightKey),
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeConflict::Aircraft(id) => write!(f, "aircraft {}", id),
+            MergeConflict::Airport(id) => write!(f, "airport {}", id),
+            MergeConflict::Flight(key) => write!(f, "flight {}", key),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MergeError(pub Vec<MergeConflict>);
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "conflicting ids: {}",
+            self.0
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+/// What a merge added to the session.
+#[derive(Debug, Default, PartialEq)]
+pub struct MergeSummary {
+    pub aircraft: usize,
+    pub airports: usize,
+    pub flights: usize,
+    pub charters: usize,
+}
+
+impl Schedule {
+    /// Adds another scenario's aircraft, airports, flights, charters and rules to this one.
+    /// Airports both define identically are shared; any other id present on both sides is
+    /// a conflict, and then nothing is merged. Flights keep their status and aircraft, and
+    /// the other scenario's horizon applies when this one has none.
+    pub fn merge(&mut self, other: Schedule) -> Result<MergeSummary, MergeError> {
+        let mut conflicts = vec![];
+        let mut aircraft_ids = other
+            .aircraft
+            .keys()
+            .chain(other.charters.iter().map(|c| &c.id))
+            .filter(|id| {
+                self.aircraft.contains_key(*id) || self.charters.iter().any(|c| c.id == **id)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        aircraft_ids.sort();
+        conflicts.extend(aircraft_ids.into_iter().map(MergeConflict::Aircraft));
+        let mut airport_ids = other
+            .airports
+            .values()
+            .filter(|ap| self.airports.get(&ap.id).is_some_and(|own| own != *ap))
+            .map(|ap| ap.id.clone())
+            .collect::<Vec<_>>();
+        airport_ids.sort();
+        conflicts.extend(airport_ids.into_iter().map(MergeConflict::Airport));
+        conflicts.extend(
+            other
+                .flights
+                .iter()
+                .filter(|f| self.flight(&f.key()).is_some())
+                .map(|f| MergeConflict::Flight(f.key())),
+        );
+        if !conflicts.is_empty() {
+            return Err(MergeError(conflicts));
+        }
+
+        let summary = MergeSummary {
+            aircraft: other.aircraft.len(),
+            airports: other
+                .airports
+                .keys()
+                .filter(|id| !self.airports.contains_key(*id))
+                .count(),
+            flights: other.flights.len(),
+            charters: other.charters.len(),
+        };
+        self.aircraft.extend(other.aircraft);
+        for (id, airport) in other.airports {
+            self.airports.entry(id).or_insert(airport);
+        }
+        self.flights.extend(other.flights.into_inner());
+        self.charters.extend(other.charters);
+        self.crews.extend(other.crews);
+        self.reserves.extend(other.reserves);
+        for (fleet_type, seats) in other.seats {
+            self.seats.entry(fleet_type).or_insert(seats);
+        }
+        for (fleet_type, range) in other.ranges {
+            self.ranges.entry(fleet_type).or_insert(range);
+        }
+        self.deferred.flights.extend(other.deferred.flights);
+        self.deferred.maintenance.extend(other.deferred.maintenance);
+        self.constraints.extend(other.constraints);
+        self.horizon = self.horizon.or(other.horizon);
+        self.apply_horizon();
+        self.reindex();
+        Ok(summary)
+    }
+}
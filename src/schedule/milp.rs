@@ -0,0 +1,384 @@
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::{MovementCapacity, StandCapacity, Waiting};
+use crate::schedule::capacity::MovementBook;
+use crate::schedule::constraint::ConstraintContext;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::stands::StandBook;
+use crate::time::Time;
+use good_lp::{
+    Expression, ProblemVariables, Solution, SolverModel, Variable, constraint, microlp, variable,
+};
+use std::collections::BTreeMap;
+use std::fmt;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub enum MilpError {
+    TooLarge(usize),
+    Solver(String),
+}
+
+impl fmt::Display for MilpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MilpError::TooLarge(n) => write!(
+                f,
+                "{} flights exceed the MILP limit of {}",
+                n,
+                Schedule::MILP_MAX_FLIGHTS
+            ),
+            MilpError::Solver(e) => write!(f, "solver error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for MilpError {}
+
+impl Schedule {
+    pub const MILP_MAX_FLIGHTS: usize = 300;
+
+    /// Latest departure of a flight on a given aircraft that avoids its maintenance, the
+    /// airports' curfews and type restrictions and the max delay limit, or `None` if even
+    /// `earliest` is infeasible.
+    fn latest_departure(
+        &self,
+        f: usize,
+        aircraft_id: &str,
+        earliest: Time,
+        base_delay: u64,
+    ) -> Option<Time> {
+        let flight = &self.flights[f];
+        let block = flight.arrival_time - flight.departure_time;
+        let aircraft = self.aircraft.get(aircraft_id)?;
+        let disruptions = &aircraft.disruptions;
+        if base_delay > self.max_delay
+            || Self::is_type_restricted(&self.airports, aircraft.fleet_type.as_ref(), flight)
+            || Self::is_out_of_range(&self.ranges, aircraft.fleet_type.as_ref(), flight)
+            || Self::lacks_etops(aircraft.etops, flight)
+            || Self::violates_aircraft_maintenance(disruptions, earliest, earliest + block)
+            || Self::is_airport_closed(&self.airports, flight, earliest, earliest + block)
+            || Self::custom_violation(
+                &self.constraints,
+                flight,
+                &ConstraintContext {
+                    aircraft,
+                    departure: earliest,
+                    arrival: earliest + block,
+                    airports: &self.airports,
+                },
+            )
+            .is_some()
+        {
+            return None;
+        }
+        let arrival = earliest + block;
+        let mut latest = earliest.0 + (self.max_delay - base_delay);
+        for d in disruptions.iter().filter(|d| d.to > earliest) {
+            latest = latest.min(earliest.0 + (d.from.0 - arrival.0));
+        }
+        // the whole block has to clear closures at either end, unless the flight is exempt
+        let span = (earliest, arrival + self.max_delay + 1);
+        for (from, _) in self
+            .airports
+            .get(&flight.origin_id)
+            .into_iter()
+            .flat_map(|ap| ap.closed_windows(span))
+            .chain(
+                self.airports
+                    .get(&flight.destination_id)
+                    .into_iter()
+                    .flat_map(|ap| ap.arrival_closed_windows(span)),
+            )
+            .filter(|_| !flight.curfew_exempt)
+        {
+            latest = latest.min(from.0 - block.0);
+        }
+        Some(Time(latest))
+    }
+
+    /// Exact recovery via a mixed-integer program: every aircraft picks a path through the
+    /// connection network, every flight departs inside its feasible window, and the model
+    /// minimizes cancellation and delay-minute costs from `CostModel`. Intended for small
+    /// instances to benchmark the heuristics against.
+    #[instrument(level = "debug", skip_all, fields(flights = self.flights.len()))]
+    pub fn assign_milp(&mut self) -> Result<(), MilpError> {
+        if self.flights.len() > Self::MILP_MAX_FLIGHTS {
+            return Err(MilpError::TooLarge(self.flights.len()));
+        }
+
+        let mut aircraft_ids = self.aircraft.keys().cloned().collect::<Vec<_>>();
+        aircraft_ids.sort();
+
+        let earliest = self
+            .flights
+            .iter()
+            .map(|f| f.departure_time)
+            .collect::<Vec<_>>();
+        let base_delay = self
+            .flights
+            .iter()
+            .map(|f| match f.status {
+                Delayed { minutes } => minutes,
+                _ => 0,
+            })
+            .collect::<Vec<_>>();
+        let frozen = self
+            .flights
+            .iter()
+            .map(|f| self.is_frozen(f))
+            .collect::<Vec<_>>();
+        let block = self
+            .flights
+            .iter()
+            .map(|f| (f.arrival_time - f.departure_time).0)
+            .collect::<Vec<_>>();
+        let big_m = (self.max_delay * 2
+            + self
+                .flights
+                .iter()
+                .map(|f| f.arrival_time.0)
+                .max()
+                .unwrap_or(0)) as f64;
+
+        let mut vars = ProblemVariables::new();
+        let departure = (0..self.flights.len())
+            .map(|f| {
+                vars.add(variable().min(earliest[f].0 as f64).max(match frozen[f] {
+                    true => earliest[f].0 as f64,
+                    false => (earliest[f].0 + self.max_delay) as f64,
+                }))
+            })
+            .collect::<Vec<_>>();
+
+        // windows[a][f]: latest departure of f when flown by aircraft a; frozen flights
+        // can only stay on the aircraft they already have
+        let windows = aircraft_ids
+            .iter()
+            .map(|ac_id| {
+                (0..self.flights.len())
+                    .filter(|f| !frozen[*f] || self.flights[*f].aircraft_id.as_ref() == Some(ac_id))
+                    .filter_map(|f| {
+                        self.latest_departure(f, ac_id, earliest[f], base_delay[f])
+                            .map(|latest| (f, latest))
+                    })
+                    .collect::<BTreeMap<usize, Time>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut starts: Vec<BTreeMap<usize, Variable>> = vec![BTreeMap::new(); aircraft_ids.len()];
+        let mut ends: Vec<BTreeMap<usize, Variable>> = vec![BTreeMap::new(); aircraft_ids.len()];
+        let mut arcs: Vec<BTreeMap<(usize, usize), Variable>> =
+            vec![BTreeMap::new(); aircraft_ids.len()];
+        for (a, ac_id) in aircraft_ids.iter().enumerate() {
+            let aircraft = &self.aircraft[ac_id];
+            for (&f, latest) in &windows[a] {
+                let flight = &self.flights[f];
+                if flight.origin_id == aircraft.initial_location_id {
+                    starts[a].insert(f, vars.add(variable().binary()));
+                }
+                ends[a].insert(f, vars.add(variable().binary()));
+                let turn = block[f]
+                    + self
+                        .airports
+                        .get(&flight.destination_id)
+                        .map_or(0, |ap| ap.mtt);
+                for (&g, latest_g) in &windows[a] {
+                    let next = &self.flights[g];
+                    if g != f
+                        && next.origin_id == flight.destination_id
+                        && earliest[f].0 + turn <= latest_g.0
+                        && earliest[g] > earliest[f]
+                        && !Self::is_at_wrong_airport(
+                            &aircraft.disruptions,
+                            earliest[g],
+                            Some(&(flight.destination_id.clone(), *latest + block[f])),
+                        )
+                    {
+                        arcs[a].insert((f, g), vars.add(variable().binary()));
+                    }
+                }
+            }
+        }
+
+        // flown[a][f]: inflow into f on aircraft a
+        let flown = (0..aircraft_ids.len())
+            .map(|a| {
+                windows[a]
+                    .keys()
+                    .map(|&f| {
+                        let mut inflow = Expression::from(0.0);
+                        if let Some(s) = starts[a].get(&f) {
+                            inflow += *s;
+                        }
+                        for ((_, g), z) in arcs[a].iter() {
+                            if *g == f {
+                                inflow += *z;
+                            }
+                        }
+                        (f, inflow)
+                    })
+                    .collect::<BTreeMap<usize, Expression>>()
+            })
+            .collect::<Vec<_>>();
+
+        let cancellation = self.cancellation_costs();
+        let mut objective = Expression::from(0.0);
+        for f in 0..self.flights.len() {
+            let cancellation = cancellation[f];
+            let mut operated = Expression::from(0.0);
+            for flown_a in &flown {
+                if let Some(e) = flown_a.get(&f) {
+                    operated += e.clone();
+                }
+            }
+            objective += cancellation as f64 * (1.0 - operated);
+            objective += self.cost.delay_minute as f64 * (departure[f] - earliest[f].0 as f64);
+        }
+
+        let mut model = vars.minimise(objective).using(microlp);
+        for (f, flight) in self.flights.iter().enumerate() {
+            let mut operated = Expression::from(0.0);
+            for flown_a in &flown {
+                if let Some(e) = flown_a.get(&f) {
+                    operated += e.clone();
+                }
+            }
+            let pinned = frozen[f]
+                && aircraft_ids
+                    .iter()
+                    .position(|id| flight.aircraft_id.as_ref() == Some(id))
+                    .is_some_and(|a| windows[a].contains_key(&f));
+            if pinned {
+                model = model.with(constraint!(operated == 1.0));
+            } else {
+                model = model.with(constraint!(operated <= 1.0));
+            }
+        }
+        for a in 0..aircraft_ids.len() {
+            let mut started = Expression::from(0.0);
+            for s in starts[a].values() {
+                started += *s;
+            }
+            model = model.with(constraint!(started <= 1.0));
+            for (&f, latest) in &windows[a] {
+                let mut outflow = Expression::from(ends[a][&f]);
+                for ((from, _), z) in arcs[a].iter() {
+                    if *from == f {
+                        outflow += *z;
+                    }
+                }
+                let inflow = flown[a][&f].clone();
+                model = model.with(constraint!(inflow.clone() == outflow));
+                model = model.with(constraint!(
+                    departure[f] <= latest.0 as f64 + big_m * (1.0 - inflow)
+                ));
+            }
+            for ((f, g), z) in arcs[a].iter() {
+                let turn = block[*f]
+                    + self
+                        .airports
+                        .get(&self.flights[*f].destination_id)
+                        .map_or(0, |ap| ap.mtt);
+                model = model.with(constraint!(
+                    departure[*g] - departure[*f] >= turn as f64 - big_m * (1.0 - *z)
+                ));
+            }
+        }
+
+        let solution = model
+            .solve()
+            .map_err(|e| MilpError::Solver(e.to_string()))?;
+
+        let mut assigned = vec![false; self.flights.len()];
+        let mut refused = vec![];
+        let mut stands = StandBook::new(self);
+        // every flight but a frozen one is placed again below
+        let mut movements = MovementBook::of(self, self.airports.keys(), |f| self.is_frozen(f));
+        let mut metered = vec![];
+        for (a, ac_id) in aircraft_ids.iter().enumerate() {
+            let mut leg = starts[a]
+                .iter()
+                .find(|(_, s)| solution.value(**s) > 0.5)
+                .map(|(f, _)| *f);
+            // cumulative maintenance limits are not part of the model, so cut the path
+            // where the tail would fly past one
+            let maintenance = self.aircraft[ac_id].maintenance.clone();
+            let mut usage = maintenance.as_ref().map(|check| check.usage());
+            while let Some(f) = leg {
+                let dep = Time(solution.value(departure[f]).round() as u64).max(earliest[f]);
+                let delay = base_delay[f] + (dep - earliest[f]).0;
+                if let Some(check) = &maintenance {
+                    usage = usage.and_then(|current| {
+                        check.fly(
+                            current,
+                            dep,
+                            dep + block[f],
+                            &self.flights[f].destination_id,
+                        )
+                    });
+                    if usage.is_none() {
+                        break;
+                    }
+                }
+                let next = arcs[a]
+                    .iter()
+                    .find(|((from, _), z)| *from == f && solution.value(**z) > 0.5)
+                    .map(|((_, g), _)| *g);
+                // nor are stands, which every tail shares
+                let next_departure = next
+                    .map(|g| Time(solution.value(departure[g]).round() as u64).max(earliest[g]));
+                let destination = &self.flights[f].destination_id;
+                if stands.is_full(destination, dep + block[f], next_departure) {
+                    refused.push(f);
+                    break;
+                }
+                // nor the movements an airport under a capacity reduction meters
+                let origin = &self.flights[f].origin_id;
+                if movements.is_full(origin, dep) || movements.is_full(destination, dep + block[f])
+                {
+                    metered.push(f);
+                    break;
+                }
+                stands.fly(
+                    ac_id,
+                    (origin, dep),
+                    (destination, dep + block[f]),
+                    next_departure,
+                );
+                movements.fly((origin, dep), (destination, dep + block[f]));
+                let flight = &mut self.flights[f];
+                flight.aircraft_id = Some(ac_id.clone());
+                flight.departure_time = dep;
+                flight.arrival_time = dep + block[f];
+                flight.status = if delay == 0 {
+                    Scheduled
+                } else {
+                    Delayed { minutes: delay }
+                };
+                assigned[f] = true;
+                leg = next;
+            }
+        }
+        for (f, flight) in self.flights.iter_mut().enumerate() {
+            if assigned[f] || frozen[f] {
+                continue;
+            }
+            if refused.contains(&f) {
+                flight.aircraft_id = None;
+                flight.status = Unscheduled(StandCapacity);
+            } else if metered.contains(&f) {
+                flight.aircraft_id = None;
+                flight.status = Unscheduled(MovementCapacity);
+            } else if flight.aircraft_id.is_some() {
+                flight.aircraft_id = None;
+                flight.status = Unscheduled(Waiting);
+            }
+        }
+        self.index_rotations();
+        self.sort_airport_legs();
+
+        #[cfg(debug_assertions)]
+        self.assert_invariants();
+        Ok(())
+    }
+}
@@ -1,4 +1,41 @@
+pub mod airport_delay;
+pub mod anneal;
+pub mod atfm;
+pub mod audit;
+pub mod banks;
+pub mod builder;
+pub mod candidates;
+pub mod capacity;
+pub mod charter;
+pub mod clone_day;
+pub mod constraint;
+pub mod crew;
+pub mod curfew_selection;
+pub mod events;
+pub mod experiment;
+pub mod feasibility;
+pub mod flow;
+pub mod forecast;
+pub mod handling;
+pub mod impact;
+pub mod merge;
+#[cfg(feature = "solver-milp")]
+pub mod milp;
+pub mod monte_carlo;
+pub mod plugin;
+pub mod retime;
+pub mod rollover;
+#[allow(clippy::module_inception)]
 pub mod schedule;
+pub mod search;
+pub mod shared;
+pub mod slack;
+pub mod slots;
+pub mod spill;
+pub mod stands;
+pub mod strandings;
+pub(crate) mod turn_times;
+pub mod validate;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,186 @@
+use crate::metrics::{Summary, summarize};
+use crate::schedule::anneal::Rng;
+use crate::schedule::schedule::{Objective, Schedule, Strategy};
+use rayon::prelude::*;
+use std::fmt;
+use tracing::debug_span;
+
+#[derive(Debug, PartialEq)]
+pub enum MonteCarloError {
+    NoRuns,
+    InvalidProbability(u32),
+}
+
+impl fmt::Display for MonteCarloError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MonteCarloError::NoRuns => write!(f, "at least one run is needed"),
+            MonteCarloError::InvalidProbability(p) => {
+                write!(f, "the delay probability is a percentage, not {}", p)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MonteCarloError {}
+
+/// How each run disrupts its copy of the schedule: every operating flight is delayed
+/// with `probability` percent, by minutes drawn uniformly between `min` and `max`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonteCarloConfig {
+    pub runs: usize,
+    pub probability: u32,
+    pub min: u64,
+    pub max: u64,
+    /// Master seed the seed of every run is derived from.
+    pub seed: u64,
+    /// Rerun greedy recovery after the delays of each run.
+    pub recover: bool,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        MonteCarloConfig {
+            runs: 100,
+            probability: 10,
+            min: 10,
+            max: 60,
+            seed: 0,
+            recover: false,
+        }
+    }
+}
+
+/// Where one run left the flights.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonteCarloRun {
+    pub seed: u64,
+    /// Flights given a primary delay, before propagation.
+    pub delayed: usize,
+    pub summary: Summary,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonteCarloResult {
+    pub config: MonteCarloConfig,
+    /// In run order, whatever thread each one ran on.
+    pub runs: Vec<MonteCarloRun>,
+}
+
+impl MonteCarloResult {
+    pub fn mean_delay_minutes(&self) -> f64 {
+        self.mean(|r| r.summary.delay_minutes as f64)
+    }
+
+    pub fn mean_unscheduled(&self) -> f64 {
+        self.mean(|r| r.summary.unscheduled() as f64)
+    }
+
+    /// Nearest-rank percentile of total delay minutes across runs.
+    pub fn delay_minutes_percentile(&self, p: usize) -> u64 {
+        percentile(self.runs.iter().map(|r| r.summary.delay_minutes), p)
+    }
+
+    /// Nearest-rank percentile of unscheduled flights across runs.
+    pub fn unscheduled_percentile(&self, p: usize) -> usize {
+        percentile(self.runs.iter().map(|r| r.summary.unscheduled()), p)
+    }
+
+    fn mean(&self, value: impl Fn(&MonteCarloRun) -> f64) -> f64 {
+        match self.runs.len() {
+            0 => 0.0,
+            n => self.runs.iter().map(value).sum::<f64>() / n as f64,
+        }
+    }
+}
+
+fn percentile<T: Ord + Copy + Default>(values: impl Iterator<Item = T>, p: usize) -> T {
+    let mut values = values.collect::<Vec<_>>();
+    if values.is_empty() {
+        return T::default();
+    }
+    values.sort_unstable();
+    values[(p * values.len()).div_ceil(100).clamp(1, values.len()) - 1]
+}
+
+impl Schedule {
+    /// Runs `config.runs` independent simulations on forks of the schedule, spread over
+    /// the rayon thread pool (`RAYON_NUM_THREADS` caps it). Run seeds are drawn from the
+    /// master seed up front, so the result is the same whatever the thread count. The
+    /// schedule itself is left alone.
+    pub fn monte_carlo(
+        &self,
+        config: MonteCarloConfig,
+    ) -> Result<MonteCarloResult, MonteCarloError> {
+        if config.runs == 0 {
+            return Err(MonteCarloError::NoRuns);
+        }
+        if config.probability > 100 {
+            return Err(MonteCarloError::InvalidProbability(config.probability));
+        }
+        let _span = debug_span!(
+            "monte_carlo",
+            runs = config.runs,
+            probability = config.probability,
+            min = config.min,
+            max = config.max,
+            seed = config.seed
+        )
+        .entered();
+        let mut master = Rng::new(config.seed);
+        let seeds = (0..config.runs)
+            .map(|_| master.next_u64())
+            .collect::<Vec<_>>();
+        let runs = seeds
+            .into_par_iter()
+            .map(|seed| self.simulate(&config, seed))
+            .collect();
+        Ok(MonteCarloResult { config, runs })
+    }
+
+    fn simulate(&self, config: &MonteCarloConfig, seed: u64) -> MonteCarloRun {
+        let mut rng = Rng::new(seed);
+        let (min, max) = (config.min.min(config.max), config.min.max(config.max));
+        // `None` for the whole of 0..=u64::MAX, which every draw falls in
+        let span = (max - min).checked_add(1);
+        let mut departures = self
+            .flights
+            .iter()
+            .filter(|f| !f.status.is_unscheduled())
+            .map(|f| (f.departure_time, f.key()))
+            .collect::<Vec<_>>();
+        departures.sort();
+        // drawn for every flight, so a run does not depend on how earlier delays played out
+        let delays = departures
+            .into_iter()
+            .filter_map(|(_, key)| {
+                let hit = rng.below(100) < config.probability as usize;
+                let minutes = match span {
+                    Some(span) => min + rng.next_u64() % span,
+                    None => rng.next_u64(),
+                };
+                (hit && minutes > 0).then_some((key, minutes))
+            })
+            .collect::<Vec<_>>();
+
+        let mut trial = self.fork();
+        let delayed = delays.len();
+        for (key, minutes) in delays {
+            // an earlier delay of its rotation may have unscheduled it already
+            if trial
+                .flight(&key)
+                .is_some_and(|f| !f.status.is_unscheduled())
+            {
+                let _ = trial.apply_delay(key, minutes);
+            }
+        }
+        if config.recover {
+            trial.recover(Strategy::Greedy(Objective::FirstFit));
+        }
+        MonteCarloRun {
+            seed,
+            delayed,
+            summary: summarize(trial.flights.iter()),
+        }
+    }
+}
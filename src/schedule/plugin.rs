@@ -0,0 +1,91 @@
+use crate::schedule::constraint::Constraint;
+use crate::schedule::schedule::Schedule;
+use std::fmt;
+use std::sync::Arc;
+
+/// A recovery backend contributed by another crate, offered by `recover --strategy <name>`
+/// next to the built-in ones.
+pub trait RecoveryStrategy: Send + Sync {
+    fn name(&self) -> &str;
+    /// One line for the REPL help.
+    fn description(&self) -> &str;
+    fn recover(&self, schedule: &mut Schedule);
+}
+
+/// A REPL command contributed by another crate. It receives the words after its name
+/// and returns what to print.
+pub trait ReplCommand: Send + Sync {
+    fn name(&self) -> &str;
+    /// One line for the REPL help, starting with the usage, e.g. `tails <airport> - ...`.
+    fn help(&self) -> &str;
+    fn run(&self, schedule: &mut Schedule, args: &[&str]) -> String;
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PluginError {
+    UnknownStrategy(String),
+    UnknownCommand(String),
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginError::UnknownStrategy(name) => write!(f, "no recovery strategy {}", name),
+            PluginError::UnknownCommand(name) => write!(f, "no command {}", name),
+        }
+    }
+}
+
+impl std::error::Error for PluginError {}
+
+impl Schedule {
+    /// Registers a recovery strategy; one registered later under the same name replaces it.
+    pub fn register_strategy(&mut self, strategy: impl RecoveryStrategy + 'static) {
+        self.strategies.retain(|s| s.name() != strategy.name());
+        self.strategies.push(Arc::new(strategy));
+    }
+
+    /// Same as `add_constraint`, named to match the other registration calls.
+    pub fn register_constraint(&mut self, constraint: impl Constraint + 'static) {
+        self.add_constraint(constraint);
+    }
+
+    /// Registers a REPL command; one registered later under the same name replaces it.
+    pub fn register_command(&mut self, command: impl ReplCommand + 'static) {
+        self.commands.retain(|c| c.name() != command.name());
+        self.commands.push(Arc::new(command));
+    }
+
+    /// Registered strategies, in registration order.
+    pub fn strategies(&self) -> impl Iterator<Item = &dyn RecoveryStrategy> {
+        self.strategies.iter().map(|s| s.as_ref())
+    }
+
+    /// Registered commands, in registration order.
+    pub fn commands(&self) -> impl Iterator<Item = &dyn ReplCommand> {
+        self.commands.iter().map(|c| c.as_ref())
+    }
+
+    pub fn recover_with(&mut self, name: &str) -> Result<(), PluginError> {
+        let strategy = self
+            .strategies
+            .iter()
+            .find(|s| s.name() == name)
+            .cloned()
+            .ok_or_else(|| PluginError::UnknownStrategy(name.to_string()))?;
+        strategy.recover(self);
+        self.index_rotations();
+        self.sort_airport_legs();
+        Ok(())
+    }
+
+    pub fn run_command(&mut self, name: &str, args: &[&str]) -> Result<String, PluginError> {
+        let command = self
+            .commands
+            .iter()
+            .find(|c| c.name() == name)
+            .cloned()
+            .ok_or_else(|| PluginError::UnknownCommand(name.to_string()))?;
+        Ok(command.run(self, args))
+    }
+}
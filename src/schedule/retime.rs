@@ -0,0 +1,109 @@
+use crate::flight::FlightStatus::{Delayed, Scheduled};
+use crate::flight::{FlightKey, UnscheduledReason};
+use crate::schedule::schedule::Schedule;
+use crate::time::Time;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum RetimeError {
+    UnknownFlight(FlightKey),
+    TooEarly,
+    Frozen(Time),
+    NotReady(Time),
+    AircraftMaintenance,
+    AirportClosed(UnscheduledReason),
+}
+
+impl fmt::Display for RetimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetimeError::UnknownFlight(id) => write!(f, "unknown flight {}", id),
+            RetimeError::TooEarly => write!(f, "flight would depart before the simulation start"),
+            RetimeError::Frozen(until) => write!(f, "inside the freeze window until {}", until),
+            RetimeError::NotReady(at) => write!(f, "aircraft is not ready before {}", at),
+            RetimeError::AircraftMaintenance => write!(f, "aircraft is in maintenance"),
+            RetimeError::AirportClosed(reason) => write!(f, "airport is closed ({:?})", reason),
+        }
+    }
+}
+
+impl std::error::Error for RetimeError {}
+
+impl Schedule {
+    /// Moves a flight by `minutes`. Positive values go through `apply_delay`; negative ones
+    /// advance the flight, which never disturbs the rest of the rotation but must leave the
+    /// aircraft time to turn after its previous leg and stay clear of maintenance, curfews
+    /// and the freeze window.
+    pub fn retime(&mut self, flight_id: FlightKey, minutes: i64) -> Result<(), RetimeError> {
+        let idx = self
+            .flights
+            .iter()
+            .position(|f| f.key() == flight_id)
+            .ok_or_else(|| RetimeError::UnknownFlight(flight_id.clone()))?;
+        if minutes >= 0 {
+            // the flight is known, so only a zero delay is refused, and that one is a no-op
+            let _ = self.apply_delay(flight_id, minutes as u64);
+            return Ok(());
+        }
+
+        let advance = minutes.unsigned_abs();
+        let flight = &self.flights[idx];
+        if self.is_frozen(flight) {
+            return Err(RetimeError::Frozen(self.frozen_until()));
+        }
+        let dep = Time(
+            flight
+                .departure_time
+                .0
+                .checked_sub(advance)
+                .ok_or(RetimeError::TooEarly)?,
+        );
+        let arr = flight.arrival_time - advance;
+        if dep < self.frozen_until() {
+            return Err(RetimeError::Frozen(self.frozen_until()));
+        }
+
+        if let Some(aircraft) = flight
+            .aircraft_id
+            .as_ref()
+            .and_then(|id| self.aircraft.get(id))
+        {
+            let previous = self
+                .rotation(&aircraft.id)
+                .into_iter()
+                .rev()
+                .find(|f| f.key() != flight.key() && f.departure_time < flight.departure_time);
+            let position = previous.map_or((aircraft.initial_location_id.clone(), Time(0)), |p| {
+                (p.destination_id.clone(), p.arrival_time)
+            });
+            let ready = Self::get_ready_time(&self.airports, position.1, &position.0);
+            if previous.is_some() && dep < ready {
+                return Err(RetimeError::NotReady(ready));
+            }
+            if Self::violates_aircraft_maintenance(&aircraft.disruptions, dep, arr)
+                || Self::is_at_wrong_airport(&aircraft.disruptions, dep, Some(&position))
+            {
+                return Err(RetimeError::AircraftMaintenance);
+            }
+        }
+        if let Some(reason) = Self::airport_closure(&self.airports, flight, dep, arr) {
+            return Err(RetimeError::AirportClosed(reason));
+        }
+
+        let flight = &mut self.flights[idx];
+        flight.departure_time = dep;
+        flight.arrival_time = arr;
+        flight.status = match flight.status {
+            Delayed { minutes } if minutes > advance => Delayed {
+                minutes: minutes - advance,
+            },
+            Delayed { .. } => Scheduled,
+            ref status => status.clone(),
+        };
+        self.sort_airport_legs();
+
+        #[cfg(debug_assertions)]
+        self.assert_invariants();
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+use crate::aircraft::{AircraftId, Availability, MaintenanceUsage};
+use crate::flight::{Flight, FlightKey};
+use crate::schedule::schedule::Schedule;
+use crate::time::Time;
+use std::fmt;
+
+/// Flights and maintenance windows beyond the horizon, kept out of the working schedule
+/// until `rollover` brings their day within reach.
+#[derive(Clone, Default)]
+pub struct Deferred {
+    pub flights: Vec<Flight>,
+    pub maintenance: Vec<(AircraftId, Availability)>,
+}
+
+impl Deferred {
+    pub fn is_empty(&self) -> bool {
+        self.flights.is_empty() && self.maintenance.is_empty()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RolloverError {
+    NoDays,
+    /// The clock has not yet passed every departure of the given day.
+    NotCompleted(u64),
+}
+
+impl fmt::Display for RolloverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RolloverError::NoDays => write!(f, "at least one day is needed"),
+            RolloverError::NotCompleted(day) => write!(f, "day {} is not completed yet", day),
+        }
+    }
+}
+
+impl std::error::Error for RolloverError {}
+
+/// What a rollover dropped from the session and brought in from beyond the horizon.
+#[derive(Debug, Default, PartialEq)]
+pub struct RolloverSummary {
+    pub dropped: usize,
+    pub loaded: usize,
+}
+
+/// `time` counted from `cutoff` instead of the old start of day 1.
+fn rebase(time: Time, cutoff: Time) -> Time {
+    Time(time.0.saturating_sub(cutoff.0))
+}
+
+impl Schedule {
+    /// Moves flights and maintenance windows past the horizon into `deferred`, and those
+    /// now within it back into the schedule; the caller reindexes.
+    pub(crate) fn apply_horizon(&mut self) {
+        let Some(days) = self.horizon else {
+            return;
+        };
+        let end = Time(days.saturating_mul(1440));
+
+        let (later, flights) = std::mem::take(&mut self.flights)
+            .into_inner()
+            .into_iter()
+            .partition::<Vec<_>, _>(|f| f.day > days);
+        self.flights = flights.into();
+        for f in &later {
+            self.slots.remove(&f.key());
+        }
+        let (due, deferred) = std::mem::take(&mut self.deferred.flights)
+            .into_iter()
+            .chain(later)
+            .partition::<Vec<_>, _>(|f| f.day <= days);
+        self.flights.extend(due);
+        self.deferred.flights = deferred;
+
+        for ac in self.aircraft.values_mut() {
+            let (later, windows) = std::mem::take(&mut ac.disruptions)
+                .into_iter()
+                .partition::<Vec<_>, _>(|w| w.from >= end);
+            ac.disruptions = windows;
+            self.deferred
+                .maintenance
+                .extend(later.into_iter().map(|w| (ac.id.clone(), w)));
+        }
+        let (due, deferred) = std::mem::take(&mut self.deferred.maintenance)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(_, w)| w.from < end);
+        for (aircraft_id, window) in due {
+            if let Some(ac) = self.aircraft.get_mut(&aircraft_id) {
+                ac.disruptions.push(window);
+            }
+        }
+        self.deferred.maintenance = deferred;
+    }
+
+    /// Drops the first `days` operating days, which must all have departed by the clock,
+    /// and rebases every time so that the next day starts at minute 0 as day 1. Aircraft
+    /// start from wherever the dropped days left them, with the legs flown added to their
+    /// maintenance counters; one still airborne at the cutoff is held until it lands.
+    /// Flights and maintenance the horizon now reaches are brought in, and queued events
+    /// move with the clock. Disruption history and recoveries keep the flight keys and
+    /// times they were recorded with.
+    pub fn rollover(&mut self, days: u64) -> Result<RolloverSummary, RolloverError> {
+        if days == 0 {
+            return Err(RolloverError::NoDays);
+        }
+        let cutoff = Time(days.saturating_mul(1440));
+        if self.clock < cutoff {
+            return Err(RolloverError::NotCompleted(self.clock.day()));
+        }
+        if let Some(f) = self
+            .flights
+            .iter()
+            .find(|f| f.day <= days && f.departure_time > self.clock)
+        {
+            return Err(RolloverError::NotCompleted(f.day));
+        }
+
+        let (dropped, kept) = std::mem::take(&mut self.flights)
+            .into_inner()
+            .into_iter()
+            .partition::<Vec<_>, _>(|f| f.day <= days);
+        for ac in self.aircraft.values_mut() {
+            let legs = dropped
+                .iter()
+                .filter(|f| !f.status.is_unscheduled() && f.aircraft_id.as_ref() == Some(&ac.id))
+                .collect::<Vec<_>>();
+            let Some(last) = legs.last() else {
+                continue;
+            };
+            ac.initial_location_id = last.destination_id.clone();
+            if last.arrival_time > cutoff {
+                ac.disruptions.push(Availability {
+                    from: cutoff,
+                    to: last.arrival_time,
+                    location_id: Some(last.destination_id.clone()),
+                });
+            }
+            if let Some(check) = ac.maintenance.as_mut() {
+                let usage = legs.iter().fold(check.usage(), |usage, f| {
+                    check
+                        .fly(usage, f.departure_time, f.arrival_time, &f.destination_id)
+                        .unwrap_or(MaintenanceUsage {
+                            minutes: usage.minutes + (f.arrival_time - f.departure_time).0,
+                            cycles: usage.cycles + 1,
+                            checked: usage.checked,
+                        })
+                });
+                check.flown_minutes = usage.minutes;
+                check.flown_cycles = usage.cycles;
+                if usage.checked {
+                    check.due_by = None;
+                }
+            }
+        }
+
+        self.flights = kept.into();
+        for f in self.flights.iter_mut() {
+            f.day -= days;
+            f.departure_time = rebase(f.departure_time, cutoff);
+            f.arrival_time = rebase(f.arrival_time, cutoff);
+        }
+        self.deferred.flights.retain(|f| f.day > days);
+        for f in self.deferred.flights.iter_mut() {
+            f.day -= days;
+            f.departure_time = rebase(f.departure_time, cutoff);
+            f.arrival_time = rebase(f.arrival_time, cutoff);
+        }
+        if let Some(baseline) = self.baseline.as_mut() {
+            baseline.retain(|f| f.day > days);
+            for f in baseline.iter_mut() {
+                f.day -= days;
+                f.departure_time = rebase(f.departure_time, cutoff);
+                f.arrival_time = rebase(f.arrival_time, cutoff);
+            }
+        }
+        self.infeasible_at_load = std::mem::take(&mut self.infeasible_at_load)
+            .into_iter()
+            .filter(|key| key.day > days)
+            .map(|key| FlightKey {
+                day: key.day - days,
+                ..key
+            })
+            .collect();
+        self.slots = std::mem::take(&mut self.slots)
+            .into_inner()
+            .into_iter()
+            .filter(|(key, _)| key.day > days)
+            .map(|(key, (dep, arr))| {
+                (
+                    FlightKey {
+                        day: key.day - days,
+                        ..key
+                    },
+                    (rebase(dep, cutoff), rebase(arr, cutoff)),
+                )
+            })
+            .collect();
+
+        for ac in self.aircraft.values_mut() {
+            ac.disruptions.retain(|w| w.to > cutoff);
+            for w in ac.disruptions.iter_mut() {
+                w.from = rebase(w.from, cutoff);
+                w.to = rebase(w.to, cutoff);
+            }
+            if let Some(due) = ac.maintenance.as_mut().and_then(|c| c.due_by.as_mut()) {
+                *due = rebase(*due, cutoff);
+            }
+        }
+        self.deferred.maintenance.retain(|(_, w)| w.to > cutoff);
+        for (_, w) in self.deferred.maintenance.iter_mut() {
+            w.from = rebase(w.from, cutoff);
+            w.to = rebase(w.to, cutoff);
+        }
+        for ap in self.airports.values_mut() {
+            // curfews wrapping midnight repeat daily and only use the time of day
+            ap.disruptions.retain(|c| c.is_wrapping() || c.to > cutoff);
+            for c in ap.disruptions.iter_mut().filter(|c| !c.is_wrapping()) {
+                c.from = rebase(c.from, cutoff);
+                c.to = rebase(c.to, cutoff);
+            }
+        }
+        self.charters.retain(|c| c.to > cutoff);
+        for c in self.charters.iter_mut() {
+            c.from = rebase(c.from, cutoff);
+            c.to = rebase(c.to, cutoff);
+        }
+        self.reserves.retain(|r| r.to > cutoff);
+        for r in self.reserves.iter_mut() {
+            r.from = rebase(r.from, cutoff);
+            r.to = rebase(r.to, cutoff);
+        }
+        self.clock = rebase(self.clock, cutoff);
+        self.rebase_events(cutoff.0);
+
+        let before = self.flights.len();
+        self.apply_horizon();
+        self.reindex();
+        Ok(RolloverSummary {
+            dropped: dropped.len(),
+            loaded: self.flights.len() - before,
+        })
+    }
+}
@@ -1,42 +1,464 @@
-use crate::aircraft::{Aircraft, AircraftId, Availability};
-use crate::airport::{Airport, AirportId, Curfew};
+use crate::aircraft::{
+    Aircraft, AircraftId, Availability, Charter, FleetType, MaintenanceUsage, normalize_windows,
+};
+use crate::airport::{Airport, AirportId, Curfew, RunwayId, normalize_curfews};
+use crate::compensation::CompensationModel;
+use crate::cost::CostModel;
+use crate::crew::{Crew, CrewId, ReserveCrew};
+use crate::delay_code::DelayCodes;
+use crate::error::IrropsError;
 use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
 use crate::flight::UnscheduledReason::{
-    AircraftMaintenance, AirportCurfew, BrokenChain, MaxDelayExceeded,
+    AircraftMaintenance, AirportCurfew, BrokenChain, ConstraintViolated, MaintenanceDue,
+    MaxDelayExceeded, MovementCapacity, OutOfPosition, OutsideOperatingHours, StandCapacity,
+    TurnTimeViolation,
 };
-use crate::flight::{Flight, FlightId, UnscheduledReason};
+use crate::flight::{Flight, FlightId, FlightKey, FlightStatus, Frequency, UnscheduledReason};
+use crate::metrics;
+use crate::metrics::RecoveryOutcome;
+use crate::schedule::anneal::AnnealConfig;
+use crate::schedule::atfm::{AtfmTarget, DelayDistribution};
+use crate::schedule::capacity::MovementBook;
+use crate::schedule::constraint::{Constraint, ConstraintContext, Rule, Violation};
+use crate::schedule::events::Timeline;
+use crate::schedule::plugin::{RecoveryStrategy, ReplCommand};
+use crate::schedule::rollover::Deferred;
+use crate::schedule::shared::Shared;
+use crate::schedule::slots::SlotViolation;
+use crate::schedule::stands::StandBook;
+use crate::schedule::turn_times::TurnTimes;
+use crate::severity::SeverityModel;
 use crate::time::Time;
-use serde::Deserialize;
-use std::collections::HashMap;
+use flate2::read::MultiGzDecoder;
+use schemars::JsonSchema;
+use serde::de::{DeserializeOwned, Error as _, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
+use std::fs::File;
 use std::io;
-use std::io::Error;
+use std::io::{BufReader, Error, Read};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{debug, info, instrument, trace};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Objective {
+    /// Lowest aircraft ID among the feasible ones.
+    #[default]
+    FirstFit,
+    /// Aircraft leaving the largest connection buffers around the flight.
+    Robust,
+    /// Aircraft with the least block time so far, spreading flying across the fleet.
+    Balanced,
+}
+
+/// What delay propagation checks against airport closures when it retimes a flight.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ClosureWindow {
+    /// The whole new block, from new departure to new arrival, must clear the closures
+    /// at both airports: a flight airborne when its origin closes is refused too.
+    #[default]
+    Block,
+    /// Only the movements count: the new departure instant at the origin and the new
+    /// arrival instant at the destination, as curfews on takeoffs and landings do.
+    Instants,
+}
+
+/// How a curfew decides what to cancel in the rotations it breaks.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CurfewSelection {
+    /// Whatever the curfew hits is cancelled, with the legs the aircraft can then no
+    /// longer reach.
+    #[default]
+    Blind,
+    /// A loop flown before the hit leg may be cancelled instead when that lets the hit
+    /// leg and the rest of the rotation fall back towards schedule and clear the curfew,
+    /// whichever weighs less, see `Flight::weight`.
+    Priority,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Strategy {
+    /// Single greedy pass over unscheduled flights in departure order.
+    Greedy(Objective),
+    /// Tail assignment as a min-cost flow over the connection network, see `assign_flow`.
+    Flow,
+    /// Time-boxed simulated annealing minimizing `CostModel`, see `assign_anneal`, then
+    /// charters for whatever is left, see `assign_charters`.
+    Anneal(AnnealConfig),
+}
 
+#[derive(Clone)]
 pub enum DisruptionType {
     Delay {
-        flight: FlightId,
+        flight: FlightKey,
         delay_by: u64,
     },
     Curfew {
         airport: AirportId,
         from: Time,
         to: Time,
+        arrival_tolerance: u64,
+    },
+    /// An air-traffic flow regulation delaying a set of departures at once.
+    Atfm {
+        target: AtfmTarget,
+        delays: DelayDistribution,
+    },
+    /// Every departure from an airport in a window held back, as in a snow event; the
+    /// window runs on to the end of the schedule without `to`.
+    AirportDelay {
+        airport: AirportId,
+        delay_by: u64,
+        from: Time,
+        to: Option<Time>,
+    },
+    /// The movement rate of an airport cut by `percent` in a window, as low visibility or
+    /// a closed runway does; movements are metered instead of cancelled. `runway` is the
+    /// one lost, if any.
+    CapacityReduction {
+        airport: AirportId,
+        runway: Option<RunwayId>,
+        from: Time,
+        to: Time,
+        percent: u32,
     },
 }
 
+/// Times and status of a flight on one side of a disruption.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FlightState {
+    pub departure_time: Time,
+    pub arrival_time: Time,
+    pub status: FlightStatus,
+    pub aircraft_id: Option<AircraftId>,
+}
+
+impl FlightState {
+    pub fn of(flight: &Flight) -> FlightState {
+        FlightState {
+            departure_time: flight.departure_time,
+            arrival_time: flight.arrival_time,
+            status: flight.status.clone(),
+            aircraft_id: flight.aircraft_id.clone(),
+        }
+    }
+}
+
+/// A flight a disruption moved or cancelled, as it was before and after.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlightChange {
+    pub flight: FlightKey,
+    pub before: FlightState,
+    pub after: FlightState,
+}
+
+#[derive(Clone)]
 pub struct DisruptionReport {
     pub kind: DisruptionType,
-    pub affected: Vec<FlightId>,
-    pub unscheduled: Vec<(FlightId, UnscheduledReason)>,
-    pub first_break: Option<(FlightId, UnscheduledReason)>,
+    pub affected: Vec<FlightKey>,
+    pub unscheduled: Vec<(FlightKey, UnscheduledReason)>,
+    /// The earliest flight a constraint broke, ahead of the rotations cancelled after it.
+    pub first_break: Option<(FlightKey, UnscheduledReason)>,
+    /// The break costing the most severity points, counting the rest of the rotation it
+    /// cancelled.
+    pub worst_break: Option<(FlightKey, UnscheduledReason)>,
+    pub compensation: u64,
+    /// Details of the custom rules behind `ConstraintViolated` entries.
+    pub violations: Vec<(FlightKey, Violation)>,
+    /// Curfew-exempt flights left operating inside a closed window, with the closure they
+    /// were waived from.
+    pub exempted: Vec<(FlightKey, UnscheduledReason)>,
+    /// Movements pushed outside their slot tolerance at a coordinated airport.
+    pub slot_violations: Vec<SlotViolation>,
+    /// Delay added to the flights that kept operating.
+    pub delay_minutes: u64,
+    /// Passengers on the delayed and unscheduled flights.
+    pub passengers: u64,
+    /// Score from the schedule's `SeverityModel` when the report was made.
+    pub severity: u64,
+    /// Old and new times and status of the delayed and unscheduled flights.
+    pub changes: Vec<FlightChange>,
 }
 
+impl DisruptionReport {
+    /// Folds in the report of a delay applied on behalf of this disruption.
+    pub(crate) fn absorb(&mut self, delay: DisruptionReport) {
+        for id in delay.affected {
+            if !self.affected.contains(&id) {
+                self.affected.push(id);
+            }
+        }
+        self.unscheduled.extend(delay.unscheduled);
+        self.violations.extend(delay.violations);
+        self.exempted.extend(delay.exempted);
+        self.slot_violations.extend(delay.slot_violations);
+        self.delay_minutes += delay.delay_minutes;
+    }
+
+    pub(crate) fn log(&self) {
+        for (flight_id, reason) in &self.unscheduled {
+            debug!("unscheduled {} ({:?})", flight_id, reason);
+        }
+        info!(
+            "{}: {} delayed, {} unscheduled, EUR {} added exposure, severity {}",
+            match &self.kind {
+                DisruptionType::Delay { flight, delay_by } =>
+                    format!("delay {} +{}", flight, delay_by),
+                DisruptionType::Curfew { airport, .. } => format!("curfew {}", airport),
+                DisruptionType::Atfm { target, delays } => format!("atfm {} {}", target, delays),
+                DisruptionType::AirportDelay {
+                    airport, delay_by, ..
+                } => format!("delay-airport {} +{}", airport, delay_by),
+                DisruptionType::CapacityReduction {
+                    airport,
+                    runway: None,
+                    percent,
+                    ..
+                } => format!("capacity {} -{}%", airport, percent),
+                DisruptionType::CapacityReduction {
+                    airport,
+                    runway: Some(runway),
+                    percent,
+                    ..
+                } => format!("close-runway {} {} -{}%", airport, runway, percent),
+            },
+            self.affected.len(),
+            self.unscheduled.len(),
+            self.compensation,
+            self.severity
+        );
+    }
+}
+
+/// A recovery run kept for the post-mortem report.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecoveryRecord {
+    pub strategy: String,
+    /// Number of disruptions in the history when the recovery ran.
+    pub after_disruptions: usize,
+    pub cost_before: u64,
+    pub cost_after: u64,
+    pub outcome: RecoveryOutcome,
+    /// Reserve crews called out by this recovery.
+    pub callouts: Vec<CrewId>,
+    pub callout_cost: u64,
+    /// Unscheduled flights the recovery left alone inside the freeze window, which ended
+    /// at `frozen_until`.
+    pub frozen: Vec<FlightKey>,
+    pub frozen_until: Time,
+}
+
+#[derive(Clone)]
 pub struct Schedule {
     pub aircraft: HashMap<AircraftId, Aircraft>,
     pub airports: HashMap<AirportId, Airport>,
-    pub flights: Vec<Flight>,
-    flights_index: HashMap<FlightId, usize>,
+    /// Shared with forks until either side changes them.
+    pub flights: Shared<Vec<Flight>>,
+    flights_index: Shared<HashMap<FlightKey, usize>>,
+    /// Positions in `flights` of the legs each aircraft flies, in departure order as of
+    /// the last reassignment; see `rotation`.
+    rotations: Shared<HashMap<AircraftId, Vec<usize>>>,
+    /// Positions in `flights` of the legs touching each airport; see `departures` and
+    /// `arrivals`.
+    airport_legs: Shared<HashMap<AirportId, AirportLegs>>,
+    /// Minimum turn time after each flight in `flights`, by position.
+    turn_times: Shared<TurnTimes>,
     pub last_report: Option<DisruptionReport>,
+    /// Shared with forks until either side records a disruption.
+    pub history: Shared<Vec<DisruptionReport>>,
+    /// Recovery runs recorded through `record_recovery`, in order.
+    pub recoveries: Vec<RecoveryRecord>,
+    pub compensation: CompensationModel,
+    pub cost: CostModel,
+    /// Weights behind the severity of every disruption report.
+    pub severity: SeverityModel,
+    /// Simulation clock.
+    pub clock: Time,
+    /// Minutes after `clock` during which departures can no longer be changed.
+    pub freeze_horizon: u64,
+    /// Longest delay a flight may take before it is unscheduled instead, in minutes.
+    pub max_delay: u64,
+    /// Wet-lease capacity available to cost-based recovery.
+    pub charters: Vec<Charter>,
+    /// IATA codes used when reporting delays and cancellations.
+    pub delay_codes: DelayCodes,
+    /// How delay propagation checks retimed flights against closures.
+    pub closure_window: ClosureWindow,
+    /// How curfews pick the flights to cancel.
+    pub curfew_selection: CurfewSelection,
+    /// Seat count per fleet type, for passenger spill on smaller aircraft.
+    pub seats: HashMap<FleetType, u32>,
+    /// Range per fleet type in km, against flight distances; a tail is never put on a
+    /// longer sector.
+    pub ranges: HashMap<FleetType, u64>,
+    /// Departure and arrival slot of every flight: its times when it entered the schedule.
+    pub slots: Shared<HashMap<FlightKey, (Time, Time)>>,
+    /// Crews named by flights, held to their duty limits.
+    pub crews: Vec<Crew>,
+    /// Standby crews recovery may call out.
+    pub reserves: Vec<ReserveCrew>,
+    /// Operating days kept in the schedule, counted from day 1; anything later waits in
+    /// `deferred`.
+    pub horizon: Option<u64>,
+    /// Flights and maintenance beyond the horizon, brought in by `rollover`.
+    pub deferred: Deferred,
+    /// Commands queued with `at`, waiting for the clock.
+    pub(crate) timeline: Timeline,
+    /// Flights as they stood after the first `assign`, or when the baseline was last
+    /// captured, for measuring how far the day has drifted. Shares the flights it was
+    /// taken from until either changes.
+    pub baseline: Option<Shared<Vec<Flight>>>,
+    /// Flights the scenario loaded with a `ChainViolation`, left out of the invariant
+    /// checks after each change.
+    pub(crate) infeasible_at_load: HashSet<FlightKey>,
+    /// Custom rules registered through `add_constraint`.
+    pub(crate) constraints: Vec<Arc<dyn Constraint>>,
+    /// Plugin recovery strategies registered through `register_strategy`.
+    pub(crate) strategies: Vec<Arc<dyn RecoveryStrategy>>,
+    /// Plugin REPL commands registered through `register_command`.
+    pub(crate) commands: Vec<Arc<dyn ReplCommand>>,
+}
+
+/// Positions in `flights` of the legs departing from and arriving at one airport, in
+/// departure and arrival order respectively, and the longest block time among them.
+#[derive(Clone, Debug, Default)]
+struct AirportLegs {
+    departures: Vec<usize>,
+    arrivals: Vec<usize>,
+    max_block: u64,
+}
+
+impl AirportLegs {
+    fn blocks<'a>(&'a self, flights: &'a [Flight]) -> impl Iterator<Item = u64> + 'a {
+        self.departures
+            .iter()
+            .chain(&self.arrivals)
+            .map(|i| (flights[*i].arrival_time.0).saturating_sub(flights[*i].departure_time.0))
+    }
+
+    fn is_sorted(&self, flights: &[Flight]) -> bool {
+        self.departures
+            .is_sorted_by_key(|i| (flights[*i].departure_time, *i))
+            && self
+                .arrivals
+                .is_sorted_by_key(|i| (flights[*i].arrival_time, *i))
+            && self.blocks(flights).all(|block| block <= self.max_block)
+    }
+
+    fn sort(&mut self, flights: &[Flight]) {
+        self.departures
+            .sort_by_key(|i| (flights[*i].departure_time, *i));
+        self.arrivals
+            .sort_by_key(|i| (flights[*i].arrival_time, *i));
+        self.max_block = self.blocks(flights).max().unwrap_or(0);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FlightLookupError {
+    Unknown(String),
+    /// The flight number operates on several days; the caller has to pick one.
+    Ambiguous(FlightId, Vec<u64>),
+}
+
+impl std::fmt::Display for FlightLookupError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlightLookupError::Unknown(text) => write!(f, "unknown flight {}", text),
+            FlightLookupError::Ambiguous(number, days) => write!(
+                f,
+                "{} operates on days {}; use {}/<day>",
+                number,
+                days.iter()
+                    .map(|d| d.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                number
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FlightLookupError {}
+
+/// The scenario file as written, before ids are indexed.
+#[derive(Default, Deserialize, JsonSchema)]
+pub(crate) struct Scenario {
+    pub(crate) aircraft: Vec<Aircraft>,
+    pub(crate) airports: Vec<Airport>,
+    #[serde(deserialize_with = "expand_flights")]
+    #[schemars(with = "Vec<FlightEntry>")]
+    pub(crate) flights: Vec<Flight>,
+    #[serde(default)]
+    pub(crate) charters: Vec<Charter>,
+    #[serde(default)]
+    pub(crate) rules: Vec<Rule>,
+    #[serde(default)]
+    pub(crate) delay_codes: DelayCodes,
+    #[serde(default)]
+    pub(crate) closure_window: ClosureWindow,
+    #[serde(default)]
+    pub(crate) curfew_selection: CurfewSelection,
+    /// Seat count per fleet type, used for passenger spill when a flight moves to a
+    /// smaller aircraft.
+    #[serde(default)]
+    pub(crate) seats: HashMap<FleetType, u32>,
+    /// Range in km per fleet type; a tail is never assigned a flight with a longer distance.
+    #[serde(default)]
+    pub(crate) ranges: HashMap<FleetType, u64>,
+    #[serde(default)]
+    pub(crate) crews: Vec<Crew>,
+    #[serde(default)]
+    pub(crate) reserves: Vec<ReserveCrew>,
+    /// Operating days loaded into the session; later flights and maintenance windows are
+    /// deferred until rollover reaches them.
+    #[serde(default)]
+    pub(crate) horizon_days: Option<u64>,
+}
+
+/// A flight as written in a scenario file: a single flight, or with a `frequency` a
+/// template whose times are minutes into each day it operates on.
+#[derive(Deserialize, JsonSchema)]
+struct FlightEntry {
+    #[serde(flatten)]
+    flight: Flight,
+    #[serde(default)]
+    frequency: Option<Frequency>,
+}
+
+/// Expands each entry as it is read, so a large `flights` array is never held twice.
+fn expand_flights<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Flight>, D::Error> {
+    struct Entries;
+
+    impl<'de> Visitor<'de> for Entries {
+        type Value = Vec<Flight>;
+
+        fn expecting(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.write_str("an array of flights")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<Flight>, A::Error> {
+            let mut flights = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(entry) = seq.next_element::<FlightEntry>()? {
+                match entry.frequency {
+                    Some(frequency) => {
+                        flights.extend(frequency.expand(&entry.flight).map_err(|e| {
+                            A::Error::custom(format!("flight {}: {}", entry.flight.id, e))
+                        })?)
+                    }
+                    None => flights.push(entry.flight),
+                }
+            }
+            Ok(flights)
+        }
+    }
+
+    deserializer.deserialize_seq(Entries)
 }
 
 #[derive(Debug)]
@@ -68,25 +490,241 @@ impl From<serde_json::Error> for LoadError {
 impl std::error::Error for LoadError {}
 
 impl Schedule {
-    const MAX_DELAY: u64 = 2000;
+    pub const DEFAULT_MAX_DELAY: u64 = 2000;
 
     pub fn new(
         aircraft: HashMap<AircraftId, Aircraft>,
         airports: HashMap<AirportId, Airport>,
-        mut flights: Vec<Flight>,
+        flights: Vec<Flight>,
     ) -> Schedule {
-        flights.sort_by_key(|f| f.departure_time);
-        let flights_index = flights
-            .iter()
-            .enumerate()
-            .map(|(i, v)| (v.id.clone(), i))
-            .collect::<HashMap<FlightId, usize>>();
-        Schedule {
+        let mut schedule = Schedule {
             aircraft,
             airports,
-            flights,
-            flights_index,
+            flights: flights.into(),
+            flights_index: Shared::default(),
+            rotations: Shared::default(),
+            airport_legs: Shared::default(),
+            turn_times: Shared::default(),
             last_report: None,
+            history: Shared::default(),
+            recoveries: vec![],
+            compensation: CompensationModel::default(),
+            cost: CostModel::default(),
+            severity: SeverityModel::default(),
+            clock: Time(0),
+            freeze_horizon: 0,
+            max_delay: Self::DEFAULT_MAX_DELAY,
+            charters: vec![],
+            delay_codes: DelayCodes::default(),
+            closure_window: ClosureWindow::default(),
+            curfew_selection: CurfewSelection::default(),
+            seats: HashMap::new(),
+            ranges: HashMap::new(),
+            slots: Shared::default(),
+            crews: vec![],
+            reserves: vec![],
+            horizon: None,
+            deferred: Deferred::default(),
+            timeline: Timeline::default(),
+            baseline: None,
+            infeasible_at_load: HashSet::new(),
+            constraints: vec![],
+            strategies: vec![],
+            commands: vec![],
+        };
+        schedule.reindex();
+        schedule
+    }
+
+    /// A copy to try things on. Flights, their index and slots are shared with `self`
+    /// until one side changes them, so a what-if that delays a few flights does not copy
+    /// all of them up front.
+    pub fn fork(&self) -> Schedule {
+        self.clone()
+    }
+
+    /// Orders flights by departure and rebuilds the key, airport and turn time indexes,
+    /// and sorts and merges the aircraft and curfew windows; needed whenever flights or
+    /// windows are added. Flights without an operating day get the day of their
+    /// departure, and new flights get their current times as slots.
+    pub(crate) fn reindex(&mut self) {
+        for ac in self.aircraft.values_mut() {
+            normalize_windows(&mut ac.disruptions);
+        }
+        for ap in self.airports.values_mut() {
+            normalize_curfews(&mut ap.disruptions);
+        }
+        self.flights.sort_by_key(|f| f.departure_time);
+        for f in self.flights.iter_mut().filter(|f| f.day == 0) {
+            f.day = f.departure_time.day();
+        }
+        for f in &self.flights {
+            self.slots
+                .entry(f.key())
+                .or_insert((f.departure_time, f.arrival_time));
+        }
+        self.flights_index = self
+            .flights
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (v.key(), i))
+            .collect();
+        let mut airport_legs = HashMap::<AirportId, AirportLegs>::new();
+        for (i, f) in self.flights.iter().enumerate() {
+            airport_legs
+                .entry(f.origin_id.clone())
+                .or_default()
+                .departures
+                .push(i);
+            airport_legs
+                .entry(f.destination_id.clone())
+                .or_default()
+                .arrivals
+                .push(i);
+        }
+        for legs in airport_legs.values_mut() {
+            legs.sort(&self.flights);
+        }
+        self.airport_legs = airport_legs.into();
+        self.turn_times = TurnTimes::new(&self.airports, &self.flights).into();
+        self.index_rotations();
+    }
+
+    /// Puts each airport's departures back in departure order and its arrivals in arrival
+    /// order, and refreshes its longest block; needed whenever flights are retimed.
+    pub(crate) fn sort_airport_legs(&mut self) {
+        // leave a forked schedule sharing the index when nothing moved
+        if self
+            .airport_legs
+            .values()
+            .all(|legs| legs.is_sorted(&self.flights))
+        {
+            return;
+        }
+        for legs in self.airport_legs.values_mut() {
+            legs.sort(&self.flights);
+        }
+    }
+
+    /// Rebuilds the per-aircraft rotations from `flights`; needed whenever flights change
+    /// aircraft other than through `unschedule`.
+    pub(crate) fn index_rotations(&mut self) {
+        let mut rotations = HashMap::<AircraftId, Vec<usize>>::new();
+        for (i, f) in self.flights.iter().enumerate() {
+            if let Some(ac_id) = &f.aircraft_id {
+                rotations.entry(ac_id.clone()).or_default().push(i);
+            }
+        }
+        for chain in rotations.values_mut() {
+            chain.sort_by_key(|i| (self.flights[*i].departure_time, *i));
+        }
+        self.rotations = rotations.into();
+    }
+
+    /// The legs departing from `airport_id`, in departure order.
+    pub fn departures(&self, airport_id: &AirportId) -> Vec<&Flight> {
+        self.airport_legs
+            .get(airport_id)
+            .map_or(&[][..], |legs| &legs.departures)
+            .iter()
+            .map(|i| &self.flights[*i])
+            .collect()
+    }
+
+    /// The legs arriving at `airport_id`, in arrival order.
+    pub fn arrivals(&self, airport_id: &AirportId) -> Vec<&Flight> {
+        self.airport_legs
+            .get(airport_id)
+            .map_or(&[][..], |legs| &legs.arrivals)
+            .iter()
+            .map(|i| &self.flights[*i])
+            .collect()
+    }
+
+    /// Positions in `flights` of the legs departing from `airport_id` while `curfew` is on,
+    /// in departure order. A wrapping curfew repeats every day, so that is all of them.
+    fn departing_within(&self, airport_id: &AirportId, curfew: &Curfew) -> &[usize] {
+        let departures = self
+            .airport_legs
+            .get(airport_id)
+            .map_or(&[][..], |legs| &legs.departures);
+        if curfew.is_wrapping() {
+            return departures;
+        }
+        let start = departures.partition_point(|i| self.flights[*i].departure_time < curfew.from);
+        let end = departures.partition_point(|i| self.flights[*i].departure_time < curfew.to);
+        &departures[start..end]
+    }
+
+    /// Positions in `flights` of the legs at `airport_id` whose flight may overlap `curfew`,
+    /// in ascending order. A wrapping curfew repeats every day, so every leg is a candidate.
+    fn curfew_candidates(&self, airport_id: &AirportId, curfew: &Curfew) -> Vec<usize> {
+        let Some(legs) = self.airport_legs.get(airport_id) else {
+            return vec![];
+        };
+        let mut candidates = if curfew.is_wrapping() {
+            [&legs.departures[..], &legs.arrivals[..]].concat()
+        } else {
+            // a leg overlaps the window when it departs before it closes and lands after it
+            // opens, so no more than the longest block either side of it
+            let earliest = Time(curfew.from.0.saturating_sub(legs.max_block));
+            let latest = Time(curfew.to.0.saturating_add(legs.max_block));
+            let departed = |t: Time| {
+                legs.departures
+                    .partition_point(|i| self.flights[*i].departure_time < t)
+            };
+            let landed = |t: Time| {
+                legs.arrivals
+                    .partition_point(|i| self.flights[*i].arrival_time <= t)
+            };
+            let departures = &legs.departures[departed(earliest)..departed(curfew.to)];
+            let arrivals = &legs.arrivals[landed(curfew.from)..landed(latest)];
+            [departures, arrivals].concat()
+        };
+        candidates.sort();
+        candidates.dedup();
+        candidates
+    }
+
+    /// The legs `aircraft_id` flies, in departure order.
+    pub fn rotation(&self, aircraft_id: &AircraftId) -> Vec<&Flight> {
+        let mut legs = self
+            .rotations
+            .get(aircraft_id)
+            .map(|chain| chain.iter().map(|i| &self.flights[*i]).collect::<Vec<_>>())
+            .unwrap_or_default();
+        // retimed legs may have overtaken each other since the rotation was indexed
+        legs.sort_by_key(|f| f.departure_time);
+        legs
+    }
+
+    pub fn flight(&self, key: &FlightKey) -> Option<&Flight> {
+        self.flights_index.get(key).map(|idx| &self.flights[*idx])
+    }
+
+    /// Resolves `FL-101/2`, or a bare `FL-101` if that number operates on a single day.
+    pub fn find_flight(&self, text: &str) -> Result<FlightKey, FlightLookupError> {
+        if let Some((number, day)) = text.rsplit_once('/')
+            && let Ok(day) = day.parse::<u64>()
+        {
+            let key = FlightKey::new(number, day);
+            return match self.flights_index.contains_key(&key) {
+                true => Ok(key),
+                false => Err(FlightLookupError::Unknown(text.to_string())),
+            };
+        }
+        let mut days = self
+            .flights
+            .iter()
+            .filter(|f| *f.id == *text)
+            .map(|f| f.day)
+            .collect::<Vec<_>>();
+        days.sort();
+        days.dedup();
+        match days[..] {
+            [] => Err(FlightLookupError::Unknown(text.to_string())),
+            [day] => Ok(FlightKey::new(text, day)),
+            _ => Err(FlightLookupError::Ambiguous(text.into(), days)),
         }
     }
 
@@ -94,16 +732,47 @@ impl Schedule {
         self.last_report.as_ref()
     }
 
+    /// End of the freeze window. Flights departing before it are boarding, pushing back
+    /// or already gone, so recovery must leave their aircraft and times alone.
+    pub fn frozen_until(&self) -> Time {
+        self.clock + self.freeze_horizon
+    }
+
+    pub fn is_frozen(&self, flight: &Flight) -> bool {
+        flight.departure_time < self.frozen_until()
+    }
+
+    /// Unscheduled flights that recovery skipped because they are inside the freeze window.
+    pub fn frozen_unscheduled(&self) -> Vec<FlightKey> {
+        self.flights
+            .iter()
+            .filter(|f| f.status.is_unscheduled() && self.is_frozen(f))
+            .map(|f| f.key())
+            .collect()
+    }
+
+    /// Deserializes straight from a buffered reader instead of a String copy of the file.
+    /// `.gz` and `.zst` files are decompressed on the fly as they are read.
+    pub(crate) fn read_scenario<T: DeserializeOwned>(path: &str) -> Result<T, LoadError> {
+        let file = BufReader::new(File::open(path)?);
+        let reader: Box<dyn Read> = match Path::new(path).extension().and_then(|e| e.to_str()) {
+            Some("gz") => Box::new(MultiGzDecoder::new(file)),
+            Some("zst") => Box::new(zstd::Decoder::with_buffer(file)?),
+            _ => Box::new(file),
+        };
+        Ok(serde_json::from_reader(BufReader::new(reader))?)
+    }
+
     pub fn load_from_file(path: &str) -> Result<Self, LoadError> {
-        let data = std::fs::read_to_string(path)?;
-        #[derive(Deserialize)]
-        struct RawData {
-            aircraft: Vec<Aircraft>,
-            airports: Vec<Airport>,
-            flights: Vec<Flight>,
-        }
-        let raw: RawData = serde_json::from_str(&data)?;
+        Ok(Self::from_scenario(Self::read_scenario(path)?))
+    }
 
+    /// Loads a scenario already in memory, e.g. received over the network.
+    pub fn load_from_slice(json: &[u8]) -> Result<Self, LoadError> {
+        Ok(Self::from_scenario(serde_json::from_slice(json)?))
+    }
+
+    pub(crate) fn from_scenario(raw: Scenario) -> Self {
         let ac_map = raw
             .aircraft
             .into_iter()
@@ -116,59 +785,295 @@ impl Schedule {
             .map(|a| (a.id.clone(), a))
             .collect();
 
-        Ok(Schedule::new(ac_map, ap_map, raw.flights))
+        let mut schedule = Schedule::new(ac_map, ap_map, raw.flights);
+        schedule.charters = raw.charters;
+        schedule.delay_codes = raw.delay_codes;
+        schedule.closure_window = raw.closure_window;
+        schedule.curfew_selection = raw.curfew_selection;
+        schedule.seats = raw.seats;
+        schedule.ranges = raw.ranges;
+        schedule.crews = raw.crews;
+        schedule.reserves = raw.reserves;
+        schedule.horizon = raw.horizon_days;
+        schedule.apply_horizon();
+        schedule.reindex();
+        raw.rules
+            .into_iter()
+            .for_each(|rule| schedule.add_constraint(rule));
+        schedule.infeasible_at_load = schedule
+            .chain_violations()
+            .iter()
+            .map(|v| v.flight().clone())
+            .collect();
+        schedule
     }
 
-    fn unschedule(&mut self, flight_id: &FlightId, reason: UnscheduledReason) {
-        if let Some(idx) = self.flights_index.get(flight_id) {
-            self.flights[*idx].status = Unscheduled(reason);
-            self.flights[*idx].aircraft_id = None;
+    fn unschedule(&mut self, flight_id: &FlightKey, reason: UnscheduledReason) {
+        if let Some(&idx) = self.flights_index.get(flight_id) {
+            self.flights[idx].status = Unscheduled(reason);
+            if let Some(ac_id) = self.flights[idx].aircraft_id.take()
+                && let Some(chain) = self.rotations.get_mut(&ac_id)
+            {
+                chain.retain(|i| *i != idx);
+            }
         }
     }
 
-    fn is_at_wrong_airport(
+    pub(crate) fn is_at_wrong_airport(
         disruptions: &[Availability],
         departure_time: Time,
         ready_at: Option<&(AirportId, Time)>,
     ) -> bool {
-        ready_at
-            .map(|(location_id, arrival_time)| {
-                disruptions
-                    .iter()
-                    .filter(|d| {
-                        d.from >= *arrival_time && d.to <= departure_time && d.location_id.is_some()
-                    })
-                    .any(|d| Some(location_id.clone()) != d.location_id)
-            })
-            .unwrap_or(false)
+        Self::required_position(disruptions, departure_time, ready_at).is_some()
     }
 
-    fn is_airport_closed(
+    /// Airport a maintenance window between landing at `ready_at` and `departure_time`
+    /// needs the aircraft at, when it sits somewhere else.
+    pub(crate) fn required_position(
+        disruptions: &[Availability],
+        departure_time: Time,
+        ready_at: Option<&(AirportId, Time)>,
+    ) -> Option<AirportId> {
+        let (location_id, arrival_time) = ready_at?;
+        let first = disruptions.partition_point(|d| d.from < *arrival_time);
+        disruptions[first..]
+            .iter()
+            .take_while(|d| d.to <= departure_time)
+            .find_map(|d| d.location_id.clone().filter(|l| l != location_id))
+    }
+
+    /// Whether a flight operating over `flight` (departure to arrival) runs into the curfew
+    /// `window`. The whole block counts, not just the departure and arrival instants; a flight
+    /// `arriving` at the airport may land within the curfew's arrival tolerance.
+    pub(crate) fn is_curfew_violated(
+        flight: &(Time, Time),
+        window: &Curfew,
+        arriving: bool,
+    ) -> bool {
+        if arriving {
+            !window.arrival_windows(*flight).is_empty()
+        } else {
+            !window.windows(*flight).is_empty()
+        }
+    }
+
+    /// Why the airports at either end cannot handle a flight operating over
+    /// `dep_time..arr_time`, if they cannot. Standing operating hours take precedence over
+    /// ad-hoc curfews so `explain` can tell the two apart. Curfew-exempt flights are never
+    /// refused.
+    pub(crate) fn airport_closure(
         airports: &HashMap<AirportId, Airport>,
         flight: &Flight,
         dep_time: Time,
         arr_time: Time,
-    ) -> bool {
-        let orig_closed = airports.get(&flight.origin_id).map_or(false, |ap| {
-            ap.disruptions
-                .iter()
-                .any(|d| d.from <= dep_time && d.to >= dep_time)
-        });
-        let dest_closed = airports.get(&flight.destination_id).map_or(false, |ap| {
+    ) -> Option<UnscheduledReason> {
+        if flight.curfew_exempt {
+            return None;
+        }
+        Self::waived_closure(airports, flight, dep_time, arr_time)
+    }
+
+    /// The closure `airport_closure` would report if the flight were not exempt.
+    pub(crate) fn waived_closure(
+        airports: &HashMap<AirportId, Airport>,
+        flight: &Flight,
+        dep_time: Time,
+        arr_time: Time,
+    ) -> Option<UnscheduledReason> {
+        Self::closure_at(
+            airports,
+            [
+                (&flight.origin_id, (dep_time, arr_time), false),
+                (&flight.destination_id, (dep_time, arr_time), true),
+            ],
+        )
+    }
+
+    /// Closure met by a flight delay propagation moves to `dep_time..arr_time`, judged by
+    /// `window`.
+    pub(crate) fn propagation_closure(
+        airports: &HashMap<AirportId, Airport>,
+        window: ClosureWindow,
+        flight: &Flight,
+        dep_time: Time,
+        arr_time: Time,
+    ) -> Option<UnscheduledReason> {
+        if flight.curfew_exempt {
+            return None;
+        }
+        Self::waived_propagation_closure(airports, window, flight, dep_time, arr_time)
+    }
+
+    /// The closure `propagation_closure` would report if the flight were not exempt.
+    fn waived_propagation_closure(
+        airports: &HashMap<AirportId, Airport>,
+        window: ClosureWindow,
+        flight: &Flight,
+        dep_time: Time,
+        arr_time: Time,
+    ) -> Option<UnscheduledReason> {
+        match window {
+            ClosureWindow::Block => Self::waived_closure(airports, flight, dep_time, arr_time),
+            ClosureWindow::Instants => Self::closure_at(
+                airports,
+                [
+                    (&flight.origin_id, (dep_time, dep_time + 1), false),
+                    (&flight.destination_id, (arr_time, arr_time + 1), true),
+                ],
+            ),
+        }
+    }
+
+    /// Why any of the airports is closed over its span, the flight `arriving` there or not.
+    fn closure_at(
+        airports: &HashMap<AirportId, Airport>,
+        ends: [(&AirportId, (Time, Time), bool); 2],
+    ) -> Option<UnscheduledReason> {
+        let ends = ends
+            .into_iter()
+            .filter_map(|(id, span, arriving)| airports.get(id).map(|ap| (ap, span, arriving)))
+            .collect::<Vec<_>>();
+        if ends.iter().any(|(ap, span, _)| {
+            ap.operating_hours
+                .is_some_and(|h| !h.closures(*span).is_empty())
+        }) {
+            Some(OutsideOperatingHours)
+        } else if ends.iter().any(|(ap, span, arriving)| {
             ap.disruptions
                 .iter()
-                .any(|d| d.from <= arr_time && d.to >= arr_time)
-        });
-        orig_closed || dest_closed
+                .any(|c| Self::is_curfew_violated(span, c, *arriving))
+        }) {
+            Some(AirportCurfew)
+        } else {
+            None
+        }
     }
 
-    fn violates_aircraft_maintenance(disruptions: &[Availability], dep: Time, arr: Time) -> bool {
-        disruptions
+    pub(crate) fn is_airport_closed(
+        airports: &HashMap<AirportId, Airport>,
+        flight: &Flight,
+        dep_time: Time,
+        arr_time: Time,
+    ) -> bool {
+        Self::airport_closure(airports, flight, dep_time, arr_time).is_some()
+    }
+
+    /// Whether the airport at either end of the flight cannot accept `fleet_type`.
+    pub(crate) fn is_type_restricted(
+        airports: &HashMap<AirportId, Airport>,
+        fleet_type: Option<&FleetType>,
+        flight: &Flight,
+    ) -> bool {
+        [&flight.origin_id, &flight.destination_id]
+            .into_iter()
+            .filter_map(|id| airports.get(id))
+            .any(|ap| !ap.accepts(fleet_type))
+    }
+
+    /// Whether the flight is longer than the range of `fleet_type`; flights without a
+    /// distance and types without a range always fit.
+    pub(crate) fn is_out_of_range(
+        ranges: &HashMap<FleetType, u64>,
+        fleet_type: Option<&FleetType>,
+        flight: &Flight,
+    ) -> bool {
+        let range = fleet_type.and_then(|t| ranges.get(t));
+        matches!((flight.distance, range), (Some(distance), Some(range)) if distance > *range)
+    }
+
+    /// Whether the flight is an ETOPS sector for an aircraft without the certification.
+    pub(crate) fn lacks_etops(certified: bool, flight: &Flight) -> bool {
+        flight.etops && !certified
+    }
+
+    /// Operating ETOPS sectors flown by an aircraft without the certification.
+    pub fn etops_violations(&self) -> Vec<(FlightKey, AircraftId)> {
+        self.flights
             .iter()
-            .any(|d| Time::is_overlapping(&(dep, arr), &(d.from, d.to)))
+            .filter(|f| !f.status.is_unscheduled())
+            .filter_map(|f| {
+                let aircraft = f
+                    .aircraft_id
+                    .as_ref()
+                    .and_then(|id| self.aircraft.get(id))?;
+                Self::lacks_etops(aircraft.etops, f).then(|| (f.key(), aircraft.id.clone()))
+            })
+            .collect()
+    }
+
+    /// Operating flights flown by a fleet type that one of their airports does not accept.
+    pub fn type_restriction_violations(&self) -> Vec<(FlightKey, FleetType)> {
+        self.flights
+            .iter()
+            .filter(|f| !f.status.is_unscheduled())
+            .filter_map(|f| {
+                let fleet_type = f
+                    .aircraft_id
+                    .as_ref()
+                    .and_then(|id| self.aircraft.get(id))
+                    .and_then(|ac| ac.fleet_type.as_ref())?;
+                Self::is_type_restricted(&self.airports, Some(fleet_type), f)
+                    .then(|| (f.key(), fleet_type.clone()))
+            })
+            .collect()
+    }
+
+    /// Operating flights flown by a fleet type without the range for them, with that range.
+    pub fn range_violations(&self) -> Vec<(FlightKey, FleetType, u64)> {
+        self.flights
+            .iter()
+            .filter(|f| !f.status.is_unscheduled())
+            .filter_map(|f| {
+                let fleet_type = f
+                    .aircraft_id
+                    .as_ref()
+                    .and_then(|id| self.aircraft.get(id))
+                    .and_then(|ac| ac.fleet_type.as_ref())?;
+                Self::is_out_of_range(&self.ranges, Some(fleet_type), f)
+                    .then(|| (f.key(), fleet_type.clone(), self.ranges[fleet_type]))
+            })
+            .collect()
+    }
+
+    /// Operating flights that an airport closure at either end forbids, e.g. pre-assigned
+    /// flights loaded from a scenario outside operating hours.
+    pub fn closure_violations(&self) -> Vec<(FlightKey, UnscheduledReason)> {
+        self.flights
+            .iter()
+            .filter(|f| !f.status.is_unscheduled())
+            .filter_map(|f| {
+                Self::airport_closure(&self.airports, f, f.departure_time, f.arrival_time)
+                    .map(|reason| (f.key(), reason))
+            })
+            .collect()
+    }
+
+    /// Curfew-exempt operating flights that an airport closure would otherwise forbid.
+    pub fn closure_waivers(&self) -> Vec<(FlightKey, UnscheduledReason)> {
+        self.flights
+            .iter()
+            .filter(|f| f.curfew_exempt && !f.status.is_unscheduled())
+            .filter_map(|f| {
+                Self::waived_closure(&self.airports, f, f.departure_time, f.arrival_time)
+                    .map(|reason| (f.key(), reason))
+            })
+            .collect()
+    }
+
+    /// Whether `dep..arr` overlaps one of the normalized `disruptions`: only the first
+    /// window ending after `dep` can.
+    pub(crate) fn violates_aircraft_maintenance(
+        disruptions: &[Availability],
+        dep: Time,
+        arr: Time,
+    ) -> bool {
+        let next = disruptions.partition_point(|d| d.to <= dep);
+        disruptions
+            .get(next)
+            .is_some_and(|d| Time::is_overlapping(&(dep, arr), &(d.from, d.to)))
     }
 
-    fn get_ready_time(
+    pub(crate) fn get_ready_time(
         airports: &HashMap<AirportId, Airport>,
         arrival_time: Time,
         airport_id: &AirportId,
@@ -176,48 +1081,193 @@ impl Schedule {
         arrival_time + airports.get(airport_id).map(|x| x.mtt).unwrap_or(0)
     }
 
+    /// Block minutes of `flight` when it departs at `departure` instead, following the
+    /// block bands of its origin.
+    pub(crate) fn shifted_block(
+        airports: &HashMap<AirportId, Airport>,
+        flight: &Flight,
+        departure: Time,
+    ) -> u64 {
+        let block = (flight.arrival_time - flight.departure_time).0;
+        match airports.get(&flight.origin_id) {
+            Some(origin) => {
+                block.saturating_sub(origin.extra_block(flight.departure_time))
+                    + origin.extra_block(departure)
+            }
+            None => block,
+        }
+    }
+
     fn compute_shifted_times(
         airports: &HashMap<AirportId, Airport>,
         flight: &Flight,
-        prev_arrival: Time,
+        ready_at: Time,
     ) -> (Time, Time, bool) {
-        let len = flight.arrival_time - flight.departure_time;
-        let ready_at = Self::get_ready_time(airports, prev_arrival, &flight.origin_id);
         let dep_time = ready_at.max(flight.departure_time);
-        let arr_time = dep_time + len;
+        let arr_time = dep_time + Self::shifted_block(airports, flight, dep_time);
         let is_overlapping = flight.departure_time < ready_at;
         (dep_time, arr_time, is_overlapping)
     }
 
-    pub fn assign(&mut self) {
+    /// Smallest slack the aircraft would keep on either side of the flight: after its
+    /// current ready time and before the next interval it is already busy with.
+    fn connection_buffer(
+        airports: &HashMap<AirportId, Airport>,
+        flight: &Flight,
+        ready_at: Option<&(AirportId, Time)>,
+        busy: Option<&Vec<(Time, Time)>>,
+    ) -> u64 {
+        let before = ready_at.map_or(u64::MAX, |(_, ready)| {
+            flight.departure_time.0.saturating_sub(ready.0)
+        });
+        let turned_at = Self::get_ready_time(airports, flight.arrival_time, &flight.destination_id);
+        let after = busy
+            .into_iter()
+            .flatten()
+            .filter(|(from, _)| *from >= turned_at)
+            .map(|(from, _)| (*from - turned_at).0)
+            .min()
+            .unwrap_or(u64::MAX);
+        before.min(after)
+    }
+
+    /// Recovery objective: `CostModel` over all flights plus hired charter capacity.
+    pub fn total_cost(&self) -> u64 {
+        self.cost.evaluate(&self.compensation, &self.flights)
+            + self.charter_cost()
+            + self.spill_cost()
+            + self.callout_cost()
+            + self.broken_connection_cost()
+    }
+
+    /// Maintenance counters at the end of the aircraft's current rotation, or `None` when it
+    /// has no due limits or already busts one.
+    pub(crate) fn maintenance_usage(&self, aircraft: &Aircraft) -> Option<MaintenanceUsage> {
+        let check = aircraft.maintenance.as_ref()?;
+        let mut legs = self
+            .flights
+            .iter()
+            .filter(|f| !f.status.is_unscheduled() && f.aircraft_id.as_ref() == Some(&aircraft.id))
+            .collect::<Vec<_>>();
+        legs.sort_by_key(|f| f.departure_time);
+        check
+            .walk(
+                legs.iter()
+                    .map(|f| (f.departure_time, f.arrival_time, &f.destination_id)),
+            )
+            .ok()
+    }
+
+    /// Keeps what a recovery run with `strategy` changed, given the flights and total cost
+    /// from before it, so the session can be reviewed later.
+    pub fn record_recovery(&mut self, strategy: &str, flights_before: &[Flight], cost_before: u64) {
+        let callouts = self
+            .callouts()
+            .into_iter()
+            .map(|c| c.crew_id)
+            .filter(|crew_id| {
+                !flights_before
+                    .iter()
+                    .any(|f| !f.status.is_unscheduled() && f.crew_id.as_ref() == Some(crew_id))
+            })
+            .collect::<Vec<_>>();
+        self.recoveries.push(RecoveryRecord {
+            strategy: strategy.to_string(),
+            after_disruptions: self.history.len(),
+            cost_before,
+            cost_after: self.total_cost(),
+            outcome: metrics::compare_recovery(flights_before, &self.flights),
+            callout_cost: callouts.len() as u64 * self.cost.reserve_callout,
+            callouts,
+            frozen: self.frozen_unscheduled(),
+            frozen_until: self.frozen_until(),
+        });
+    }
+
+    /// Assigns the unscheduled flights first fit; the first run captures the baseline.
+    pub fn assign(&mut self) {
+        self.assign_with(Objective::FirstFit);
+        if self.baseline.is_none() {
+            self.capture_baseline();
+        }
+    }
+
+    /// Takes the flights as they stand now as the baseline for `compare baseline`.
+    pub fn capture_baseline(&mut self) {
+        self.baseline = Some(self.flights.clone());
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    pub fn recover(&mut self, strategy: Strategy) {
+        match strategy {
+            Strategy::Greedy(objective) => self.assign_with(objective),
+            Strategy::Flow => self.assign_flow(),
+            Strategy::Anneal(config) => {
+                self.assign_anneal(&config);
+                self.assign_charters();
+            }
+        }
+        self.call_reserves();
+    }
+
+    /// Assigns unscheduled flights, choosing among feasible aircraft per `objective`.
+    #[instrument(name = "assign", level = "debug", skip(self), fields(unscheduled = self.unscheduled_count()))]
+    pub fn assign_with(&mut self, objective: Objective) {
+        let unscheduled_before = self.unscheduled_count();
+        let frozen_until = self.frozen_until();
         let mut sorted_ids = self.aircraft.keys().collect::<Vec<&AircraftId>>();
         sorted_ids.sort();
 
-        let mut current_locations: HashMap<AircraftId, (AirportId, Time)> = self
+        // where each aircraft is and when it is ready there, next to when it landed
+        let mut current_locations: HashMap<AircraftId, ((AirportId, Time), Time)> = self
             .aircraft
             .iter()
-            .map(|(id, ac)| (id.clone(), (ac.initial_location_id.clone(), Time(0))))
+            .map(|(id, ac)| {
+                (
+                    id.clone(),
+                    ((ac.initial_location_id.clone(), Time(0)), Time(0)),
+                )
+            })
             .collect();
 
+        let turns = &self.turn_times;
         self.flights
             .iter()
-            .filter(|f| !f.status.is_unscheduled())
-            .for_each(|f| {
+            .enumerate()
+            .filter(|(_, f)| !f.status.is_unscheduled())
+            .for_each(|(i, f)| {
                 if let Some(ac_id) = &f.aircraft_id {
                     current_locations.insert(
                         ac_id.clone(),
                         (
-                            f.destination_id.clone(),
-                            Self::get_ready_time(&self.airports, f.arrival_time, &f.destination_id),
+                            (
+                                f.destination_id.clone(),
+                                turns.ready_after(i, f.arrival_time),
+                            ),
+                            f.arrival_time,
                         ),
                     );
                 }
             });
 
+        // maintenance counters at the end of each current rotation, `None` once busted
+        let mut maintenance_usage = self
+            .aircraft
+            .values()
+            .filter(|ac| ac.maintenance.is_some())
+            .map(|ac| (ac.id.clone(), self.maintenance_usage(ac)))
+            .collect::<HashMap<AircraftId, Option<MaintenanceUsage>>>();
+
+        // block minutes flown per aircraft, kept up to date for the balanced objective
+        let mut block_minutes = metrics::utilization(self.aircraft.keys(), &self.flights)
+            .into_iter()
+            .map(|u| (u.aircraft_id, u.block_minutes))
+            .collect::<HashMap<AircraftId, u64>>();
+
         // collect aircraft per airport, sorted by aircraft name
         let mut aircraft_by_airport = HashMap::<AirportId, Vec<&AircraftId>>::new();
         sorted_ids.iter().for_each(|ac_id| {
-            if let Some(ap_id) = current_locations.get(*ac_id).map(|x| x.0.clone()) {
+            if let Some(ap_id) = current_locations.get(*ac_id).map(|x| x.0.0.clone()) {
                 aircraft_by_airport
                     .entry(ap_id.clone())
                     .or_default()
@@ -229,48 +1279,55 @@ impl Schedule {
         let mut busy = HashMap::<AircraftId, Vec<(Time, Time)>>::new();
         self.flights
             .iter()
-            .map(|f| {
+            .enumerate()
+            .map(|(i, f)| {
                 (
                     f.aircraft_id.as_ref(),
                     f.departure_time,
-                    Self::get_ready_time(&self.airports, f.arrival_time, &f.destination_id),
+                    turns.ready_after(i, f.arrival_time),
                 )
             })
             .filter_map(|(maybe_id, dep, arr)| maybe_id.map(|id| (id.clone(), (dep, arr))))
             .for_each(|(id, val)| busy.entry(id).or_default().push(val));
 
+        // aircraft on the ground at airports with a stand limit
+        let mut stands = StandBook::new(self);
+        // movements at airports metered by a capacity reduction
+        let mut movements = MovementBook::new(self);
+
         self.flights
             .iter_mut()
-            .filter(|flight| flight.status.is_unscheduled())
-            .for_each(|flight| {
+            .enumerate()
+            .filter(|(_, flight)| flight.status.is_unscheduled())
+            .filter(|(_, flight)| flight.departure_time >= frozen_until)
+            .for_each(|(i, flight)| {
                 // collect candidates at the origin airport that are not disrupted
                 let chosen_aircraft =
                     aircraft_by_airport
                         .get(&flight.origin_id)
                         .and_then(|ac_ids| {
-                            ac_ids
+                            let mut candidates = ac_ids
                                 .iter()
                                 .filter_map(|ac_id| self.aircraft.get(*ac_id))
                                 // filter aircraft at the origin airport that are not disrupted
                                 .filter(|a| {
-                                    a.disruptions.iter().all(|d| {
-                                        !Time::is_overlapping(
-                                            &(flight.departure_time, flight.arrival_time),
-                                            &(d.from, d.to),
-                                        )
-                                    })
+                                    !Self::violates_aircraft_maintenance(
+                                        &a.disruptions,
+                                        flight.departure_time,
+                                        flight.arrival_time,
+                                    )
                                 })
                                 // filter aircraft that have maintenance window ending before the flight and are at the proper airport
                                 .filter(|a| {
                                     !Self::is_at_wrong_airport(
                                         &a.disruptions,
                                         flight.departure_time,
-                                        current_locations.get(&a.id),
+                                        current_locations.get(&a.id).map(|(position, _)| position),
                                     )
                                 })
                                 // filter out busy ones
                                 .filter(|ac| {
-                                    busy.get(&ac.id).map_or(true, |intervals| {
+                                    busy.get(&ac.id).is_none_or(|intervals| {
                                         intervals.iter().all(|(from, to)| {
                                             !Time::is_overlapping(
                                                 &(flight.departure_time, flight.arrival_time),
@@ -279,39 +1336,134 @@ impl Schedule {
                                         })
                                     })
                                 })
-                                // filter out busy due to curfew
-                                .find(|_| {
-                                    let origin_open =
-                                        self.airports.get(&flight.origin_id).map_or(true, |ap| {
-                                            !ap.disruptions.iter().any(|d| {
-                                                d.from <= flight.departure_time
-                                                    && d.to >= flight.departure_time
-                                            })
-                                        });
-                                    let destination_open = self
-                                        .airports
-                                        .get(&flight.destination_id)
-                                        .map_or(true, |ap| {
-                                            !ap.disruptions.iter().any(|d| {
-                                                d.from <= flight.arrival_time
-                                                    && d.to >= flight.arrival_time
+                                // filter out fleet types either airport cannot accept
+                                .filter(|a| {
+                                    !Self::is_type_restricted(
+                                        &self.airports,
+                                        a.fleet_type.as_ref(),
+                                        flight,
+                                    )
+                                })
+                                // and those without the range or ETOPS for the sector
+                                .filter(|a| {
+                                    !Self::is_out_of_range(
+                                        &self.ranges,
+                                        a.fleet_type.as_ref(),
+                                        flight,
+                                    ) && !Self::lacks_etops(a.etops, flight)
+                                })
+                                // filter out those breaking a custom rule
+                                .filter(|a| {
+                                    Self::custom_violation(
+                                        &self.constraints,
+                                        flight,
+                                        &ConstraintContext {
+                                            aircraft: a,
+                                            departure: flight.departure_time,
+                                            arrival: flight.arrival_time,
+                                            airports: &self.airports,
+                                        },
+                                    )
+                                    .is_none()
+                                })
+                                // filter out those that would fly past a maintenance limit
+                                .filter(|a| {
+                                    a.maintenance.as_ref().is_none_or(|check| {
+                                        maintenance_usage
+                                            .get(&a.id)
+                                            .copied()
+                                            .flatten()
+                                            .and_then(|usage| {
+                                                check.fly(
+                                                    usage,
+                                                    flight.departure_time,
+                                                    flight.arrival_time,
+                                                    &flight.destination_id,
+                                                )
                                             })
-                                        });
-                                    origin_open && destination_open
+                                            .is_some()
+                                    })
                                 })
+                                // filter out busy due to curfew
+                                .filter(|_| {
+                                    !Self::is_airport_closed(
+                                        &self.airports,
+                                        flight,
+                                        flight.departure_time,
+                                        flight.arrival_time,
+                                    )
+                                });
+                            match objective {
+                                Objective::FirstFit => candidates.next(),
+                                Objective::Robust => candidates.max_by_key(|a| {
+                                    (
+                                        Self::connection_buffer(
+                                            &self.airports,
+                                            flight,
+                                            current_locations
+                                                .get(&a.id)
+                                                .map(|(position, _)| position),
+                                            busy.get(&a.id),
+                                        ),
+                                        Reverse(&a.id),
+                                    )
+                                }),
+                                Objective::Balanced => candidates
+                                    .min_by_key(|a| (block_minutes.get(&a.id).copied(), &a.id)),
+                            }
                         });
 
-                if let Some(aircraft) = chosen_aircraft {
+                // the destination needs a free stand from the arrival until the aircraft
+                // leaves again, if it already has a later leg
+                let next_departure = chosen_aircraft.and_then(|a| {
+                    busy.get(&a.id)
+                        .into_iter()
+                        .flatten()
+                        .map(|(from, _)| *from)
+                        .filter(|from| *from >= flight.arrival_time)
+                        .min()
+                });
+                let stands_full = chosen_aircraft.is_some()
+                    && stands.is_full(&flight.destination_id, flight.arrival_time, next_departure);
+                let metered_out = chosen_aircraft.is_some()
+                    && (movements.is_full(&flight.origin_id, flight.departure_time)
+                        || movements.is_full(&flight.destination_id, flight.arrival_time));
+
+                if let Some(aircraft) = chosen_aircraft.filter(|_| !stands_full && !metered_out) {
+                    trace!("assign {} -> {}", flight.key(), aircraft.id);
                     flight.aircraft_id = Some(aircraft.id.clone());
                     flight.status = Scheduled;
-                    let mtt = self
-                        .airports
-                        .get(&flight.destination_id)
-                        .map(|ap| ap.mtt)
-                        .unwrap_or(0);
-                    busy.entry(aircraft.id.clone())
-                        .or_default()
-                        .push((flight.departure_time, flight.arrival_time + mtt));
+                    *block_minutes.entry(aircraft.id.clone()).or_default() +=
+                        flight.arrival_time.0 - flight.departure_time.0;
+                    if let Some(check) = &aircraft.maintenance {
+                        let usage = maintenance_usage
+                            .get(&aircraft.id)
+                            .copied()
+                            .flatten()
+                            .and_then(|usage| {
+                                check.fly(
+                                    usage,
+                                    flight.departure_time,
+                                    flight.arrival_time,
+                                    &flight.destination_id,
+                                )
+                            });
+                        maintenance_usage.insert(aircraft.id.clone(), usage);
+                    }
+                    busy.entry(aircraft.id.clone()).or_default().push((
+                        flight.departure_time,
+                        turns.ready_after(i, flight.arrival_time),
+                    ));
+                    stands.fly(
+                        &aircraft.id,
+                        (&flight.origin_id, flight.departure_time),
+                        (&flight.destination_id, flight.arrival_time),
+                        next_departure,
+                    );
+                    movements.fly(
+                        (&flight.origin_id, flight.departure_time),
+                        (&flight.destination_id, flight.arrival_time),
+                    );
                     aircraft_by_airport
                         .entry(flight.destination_id.clone())
                         .and_modify(|val| {
@@ -325,22 +1477,107 @@ impl Schedule {
                     current_locations.insert(
                         aircraft.id.clone(),
                         (
-                            flight.destination_id.clone(),
-                            Self::get_ready_time(
-                                &self.airports,
-                                flight.arrival_time,
-                                &flight.destination_id,
+                            (
+                                flight.destination_id.clone(),
+                                turns.ready_after(i, flight.arrival_time),
                             ),
+                            flight.arrival_time,
                         ),
                     );
+                } else if stands_full {
+                    trace!(
+                        "assign {} refused: no free stand at {}",
+                        flight.key(),
+                        flight.destination_id
+                    );
+                    flight.status = Unscheduled(StandCapacity);
+                } else if metered_out {
+                    trace!(
+                        "assign {} refused: movement rate reduced at {} or {}",
+                        flight.key(),
+                        flight.origin_id,
+                        flight.destination_id
+                    );
+                    flight.status = Unscheduled(MovementCapacity);
+                } else if !Self::is_airport_closed(
+                    &self.airports,
+                    flight,
+                    flight.departure_time,
+                    flight.arrival_time,
+                ) && let Some((expected, actual)) = aircraft_by_airport
+                    .get(&flight.origin_id)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|ac_id| self.aircraft.get(*ac_id))
+                    .find_map(|a| {
+                        let position = current_locations.get(&a.id).map(|(position, _)| position);
+                        Self::required_position(&a.disruptions, flight.departure_time, position)
+                            .zip(position.map(|(location, _)| location.clone()))
+                    })
+                {
+                    // an aircraft is on the ground here but due for maintenance elsewhere
+                    trace!(
+                        "assign {} refused: aircraft needed at {} instead",
+                        flight.key(),
+                        expected
+                    );
+                    flight.status = Unscheduled(OutOfPosition { expected, actual });
+                } else if !Self::is_airport_closed(
+                    &self.airports,
+                    flight,
+                    flight.departure_time,
+                    flight.arrival_time,
+                ) && let Some((required, available)) = aircraft_by_airport
+                    .get(&flight.origin_id)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|ac_id| current_locations.get(*ac_id))
+                    .find_map(|((_, ready), landed)| {
+                        // landed before departure, but not turned around in time; the turn
+                        // it needs is the one it got, stretched by ground handling
+                        (*landed <= flight.departure_time && *ready > flight.departure_time)
+                            .then(|| ((*ready - *landed).0, (flight.departure_time - *landed).0))
+                    })
+                {
+                    trace!(
+                        "assign {} refused: {} of {} min turn time",
+                        flight.key(),
+                        available,
+                        required
+                    );
+                    flight.status = Unscheduled(TurnTimeViolation {
+                        required,
+                        available,
+                    });
                 }
             });
 
+        info!(
+            "assign: {} of {} unscheduled flights assigned",
+            unscheduled_before - self.unscheduled_count(),
+            unscheduled_before
+        );
+        self.index_rotations();
+
         #[cfg(debug_assertions)]
         self.assert_invariants();
     }
 
-    pub fn apply_delay(&mut self, flight_id: FlightId, shift: u64) {
+    fn unscheduled_count(&self) -> usize {
+        self.flights
+            .iter()
+            .filter(|f| f.status.is_unscheduled())
+            .count()
+    }
+
+    /// Delays a flight by `shift` minutes, propagating down its rotation, and returns the
+    /// report recorded for it.
+    #[instrument(level = "debug", skip(self), fields(flight = %flight_id))]
+    pub fn apply_delay(
+        &mut self,
+        flight_id: FlightKey,
+        shift: u64,
+    ) -> Result<&DisruptionReport, IrropsError> {
         let mut report = DisruptionReport {
             kind: DisruptionType::Delay {
                 flight: flight_id.clone(),
@@ -349,259 +1586,866 @@ impl Schedule {
             affected: vec![],
             unscheduled: vec![],
             first_break: None,
+            worst_break: None,
+            compensation: 0,
+            violations: vec![],
+            exempted: vec![],
+            slot_violations: vec![],
+            delay_minutes: 0,
+            passengers: 0,
+            severity: 0,
+            changes: vec![],
         };
 
+        if !self.flights_index.contains_key(&flight_id) {
+            return Err(IrropsError::UnknownFlight(flight_id));
+        }
         if shift == 0 {
-            return;
+            return Err(IrropsError::ZeroDelay(flight_id));
         }
+        // only the triggering flight and the rest of its rotation can change
+        let trigger = self.flights_index[&flight_id];
+        let mut touched = self.flights[trigger]
+            .aircraft_id
+            .as_ref()
+            .and_then(|ac_id| self.rotations.get(ac_id))
+            .cloned()
+            .unwrap_or_default();
+        touched.push(trigger);
+        touched.sort();
+        touched.dedup();
+        let states_before = self.states_of(&touched);
+        let slots_before = self.slot_violations_of(touched.iter().map(|i| &self.flights[*i]));
 
         // lookup flight & aircraft
         let idx = self.flights_index.get(&flight_id);
         let flight_aircraft =
-            idx.and_then(|i| Some((i, self.flights[*i].aircraft_id.as_ref().map(|x| x.clone()))));
+            idx.map(|i| (i, self.flights[*i].aircraft_id.as_ref().map(|x| x.clone())));
 
         if let Some((f_id, ac_id)) = flight_aircraft {
             let empty_ac_vec = vec![];
-            let ac_disruptions = ac_id
-                .as_ref()
-                .and_then(|i| self.aircraft.get(i))
+            let aircraft = ac_id.as_ref().and_then(|i| self.aircraft.get(i));
+            let ac_disruptions = aircraft
                 .map(|a| a.disruptions.as_slice())
                 .unwrap_or(&empty_ac_vec);
+            // custom rules judge the flight at the given times on its aircraft
+            let violation = |flight: &Flight, departure: Time, arrival: Time| {
+                aircraft.and_then(|aircraft| {
+                    Self::custom_violation(
+                        &self.constraints,
+                        flight,
+                        &ConstraintContext {
+                            aircraft,
+                            departure,
+                            arrival,
+                            airports: &self.airports,
+                        },
+                    )
+                })
+            };
 
             let mut is_broken = false;
+            let orig_dep_time = self.flights[*f_id].departure_time;
+            // a flight pushed again keeps the delay it already had
+            let own = match self.flights[*f_id].status {
+                Delayed { minutes } => minutes,
+                _ => 0,
+            };
 
-            // apply delay to triggering flight
-            if shift > Self::MAX_DELAY {
-                report
-                    .unscheduled
-                    .push((self.flights[*f_id].id.clone(), MaxDelayExceeded));
-                is_broken = true;
-            } else {
-                let orig_dep_time = self.flights[*f_id].departure_time;
-                self.flights[*f_id].departure_time += shift;
-                self.flights[*f_id].arrival_time += shift;
+            // apply delay to triggering flight; a shift running past the end of time is
+            // beyond any max delay too
+            let retimed = self.flights[*f_id]
+                .departure_time
+                .checked_add(shift)
+                .and_then(|departure| {
+                    let block =
+                        Self::shifted_block(&self.airports, &self.flights[*f_id], departure);
+                    departure
+                        .checked_add(block)
+                        .map(|arrival| (departure, arrival))
+                });
+            let retimed = retimed.filter(|_| own.saturating_add(shift) <= self.max_delay);
+            if let Some((departure, arrival)) = retimed {
+                self.flights[*f_id].departure_time = departure;
+                self.flights[*f_id].arrival_time = arrival;
                 let shifted_arr_time = self.flights[*f_id].arrival_time;
                 if Self::violates_aircraft_maintenance(
-                    &ac_disruptions,
+                    ac_disruptions,
                     orig_dep_time,
                     shifted_arr_time,
                 ) {
                     report
                         .unscheduled
-                        .push((self.flights[*f_id].id.clone(), AircraftMaintenance));
+                        .push((self.flights[*f_id].key(), AircraftMaintenance));
                     is_broken = true;
-                } else if Self::is_airport_closed(
+                } else if let Some(reason) = Self::propagation_closure(
                     &self.airports,
+                    self.closure_window,
                     &self.flights[*f_id],
-                    orig_dep_time,
+                    self.flights[*f_id].departure_time,
                     shifted_arr_time,
                 ) {
-                    report
-                        .unscheduled
-                        .push((self.flights[*f_id].id.clone(), AirportCurfew));
+                    report.unscheduled.push((self.flights[*f_id].key(), reason));
+                    is_broken = true;
+                } else if let Some(v) = violation(
+                    &self.flights[*f_id],
+                    self.flights[*f_id].departure_time,
+                    shifted_arr_time,
+                ) {
+                    let id = self.flights[*f_id].key();
+                    report.unscheduled.push((id.clone(), ConstraintViolated));
+                    report.violations.push((id, v));
                     is_broken = true;
                 } else {
-                    self.flights[*f_id].status = Delayed { minutes: shift };
-                    report.affected.push(self.flights[*f_id].id.clone());
+                    self.flights[*f_id].status = Delayed {
+                        minutes: own + shift,
+                    };
+                    report.affected.push(self.flights[*f_id].key());
                 }
+            } else {
+                report
+                    .unscheduled
+                    .push((self.flights[*f_id].key(), MaxDelayExceeded));
+                is_broken = true;
             }
 
             // propagate delay along aircraft chain
             if let Some(ac_id) = ac_id {
-                let mut prev_arrival_time = self.flights[*f_id].arrival_time;
-                let mut prev_destination_id = self.flights[*f_id].destination_id.clone();
-
-                for flight in self.flights.iter_mut().skip(*f_id + 1).filter(|f| {
-                    f.aircraft_id
-                        .as_ref()
-                        .map(|x| **x == *ac_id)
-                        .unwrap_or(false)
-                }) {
-                    if is_broken {
-                        report.unscheduled.push((flight.id.clone(), BrokenChain));
+                // walk the rotation in departure order, placing the triggering flight at its
+                // original slot: recovery may retime flights without re-sorting `flights`
+                let mut chain = self.rotations.get(&ac_id).cloned().unwrap_or_default();
+                chain.sort_by_key(|i| match i == f_id {
+                    true => (orig_dep_time, *i),
+                    false => (self.flights[*i].departure_time, *i),
+                });
+                let pos = chain.iter().position(|i| i == f_id).unwrap_or(0);
+
+                // a conflict already present upstream of the triggering flight (wrong
+                // airport or not turned around in time) is where propagation really starts
+                let start = (1..pos)
+                    .find(|k| {
+                        let prev = &self.flights[chain[k - 1]];
+                        let flight = &self.flights[chain[*k]];
+                        flight.origin_id != prev.destination_id
+                            || flight.departure_time
+                                < self.turn_times.ready_after(chain[k - 1], prev.arrival_time)
+                    })
+                    .unwrap_or(pos);
+
+                let trigger_broken = is_broken;
+                is_broken = false;
+                let mut prev = (start > 0).then(|| {
+                    let prev = &self.flights[chain[start - 1]];
+                    (prev.arrival_time, prev.destination_id.clone())
+                });
+                for (k, &i) in chain.iter().enumerate().skip(start) {
+                    // ground handling may stretch the turn past the minimum turn time
+                    let ready_at = prev.as_ref().map(|(arrival, _)| {
+                        self.turn_ready_time(&self.flights[i].origin_id, *arrival, Some(&ac_id))
+                    });
+                    let flight = &self.flights[i];
+                    if k == pos && trigger_broken {
+                        is_broken = true;
                         continue;
                     }
+                    if is_broken {
+                        // the aircraft waits where its last operating leg landed; a leg
+                        // departing from there picks the rotation up again
+                        let location = prev
+                            .as_ref()
+                            .map(|(_, destination_id)| destination_id)
+                            .or(aircraft.map(|ac| &ac.initial_location_id));
+                        if location != Some(&flight.origin_id) {
+                            report.unscheduled.push((flight.key(), BrokenChain));
+                            continue;
+                        }
+                        is_broken = false;
+                    }
+                    let (Some((prev_arrival_time, prev_destination_id)), Some(ready_at)) =
+                        (prev.clone(), ready_at)
+                    else {
+                        prev = Some((flight.arrival_time, flight.destination_id.clone()));
+                        continue;
+                    };
 
                     let (dep_time, arr_time, is_overlapping) =
-                        Self::compute_shifted_times(&self.airports, flight, prev_arrival_time);
+                        Self::compute_shifted_times(&self.airports, flight, ready_at);
+                    // a leg pushed further keeps the delay it already had, the triggering
+                    // flight's shift included
+                    let delayed = match flight.status {
+                        Delayed { minutes } => minutes,
+                        _ => 0,
+                    } + (dep_time - flight.departure_time).0;
                     let is_ac_disrupted = Self::violates_aircraft_maintenance(
-                        &ac_disruptions,
+                        ac_disruptions,
                         flight.departure_time,
                         arr_time,
                     );
-                    let is_at_wrong_airport = Self::is_at_wrong_airport(
+                    let required_position = Self::required_position(
                         ac_disruptions,
                         flight.departure_time,
                         Some(&(prev_destination_id.clone(), prev_arrival_time)),
                     );
 
-                    if is_ac_disrupted || is_at_wrong_airport {
-                        report
-                            .unscheduled
-                            .push((flight.id.clone(), AircraftMaintenance));
+                    if flight.origin_id != prev_destination_id {
+                        report.unscheduled.push((flight.key(), BrokenChain));
+                        is_broken = true;
+                    } else if is_ac_disrupted {
+                        report.unscheduled.push((flight.key(), AircraftMaintenance));
+                        is_broken = true;
+                    } else if let Some(expected) = required_position {
+                        report.unscheduled.push((
+                            flight.key(),
+                            OutOfPosition {
+                                expected,
+                                actual: prev_destination_id.clone(),
+                            },
+                        ));
                         is_broken = true;
-                    } else if Self::is_airport_closed(&self.airports, &flight, dep_time, arr_time) {
-                        report.unscheduled.push((flight.id.clone(), AirportCurfew));
+                    } else if let Some(reason) = Self::propagation_closure(
+                        &self.airports,
+                        self.closure_window,
+                        flight,
+                        dep_time,
+                        arr_time,
+                    ) {
+                        report.unscheduled.push((flight.key(), reason));
+                        is_broken = true;
+                    } else if let Some(v) = violation(flight, dep_time, arr_time) {
+                        report.unscheduled.push((flight.key(), ConstraintViolated));
+                        report.violations.push((flight.key(), v));
                         is_broken = true;
-                    } else if dep_time - flight.departure_time > Time(Self::MAX_DELAY) {
-                        report
-                            .unscheduled
-                            .push((flight.id.clone(), MaxDelayExceeded));
+                    } else if delayed > self.max_delay {
+                        report.unscheduled.push((flight.key(), MaxDelayExceeded));
+                        is_broken = true;
+                    } else if is_overlapping
+                        && k != pos
+                        && self.is_metered_out(flight, dep_time, arr_time)
+                    {
+                        report.unscheduled.push((flight.key(), MovementCapacity));
                         is_broken = true;
                     } else if is_overlapping {
-                        flight.status = Delayed {
-                            minutes: (dep_time - flight.departure_time).0,
-                        };
+                        let flight = &mut self.flights[i];
+                        flight.status = Delayed { minutes: delayed };
                         flight.departure_time = dep_time;
                         flight.arrival_time = arr_time;
-                        prev_arrival_time = flight.arrival_time;
-                        prev_destination_id = flight.destination_id.clone();
-                        report.affected.push(flight.id.clone());
-                    } else {
+                        prev = Some((flight.arrival_time, flight.destination_id.clone()));
+                        if k != pos {
+                            report.affected.push(flight.key());
+                        }
+                    } else if k > pos {
                         break;
+                    } else {
+                        prev = Some((flight.arrival_time, flight.destination_id.clone()));
+                    }
+                }
+
+                // the shifted rotation may now reach a maintenance base too late
+                if let Some(check) = self
+                    .aircraft
+                    .get(&ac_id)
+                    .and_then(|ac| ac.maintenance.as_ref())
+                {
+                    let mut legs = chain
+                        .iter()
+                        .map(|i| &self.flights[*i])
+                        .filter(|f| report.unscheduled.iter().all(|(u, _)| *u != f.key()))
+                        .collect::<Vec<_>>();
+                    legs.sort_by_key(|f| f.departure_time);
+                    if let Err(bust) = check.walk(
+                        legs.iter()
+                            .map(|f| (f.departure_time, f.arrival_time, &f.destination_id)),
+                    ) {
+                        report.unscheduled.push((legs[bust].key(), MaintenanceDue));
+                        for f in &legs[bust + 1..] {
+                            report.unscheduled.push((f.key(), BrokenChain));
+                        }
                     }
                 }
             }
         }
+        report
+            .affected
+            .retain(|id| report.unscheduled.iter().all(|(u, _)| u != id));
         report.unscheduled.iter().for_each(|(f_id, reason)| {
-            self.unschedule(f_id, *reason);
+            self.unschedule(f_id, reason.clone());
         });
-        report.first_break = report.unscheduled.first().cloned();
-
-        self.last_report = Some(report);
+        report.exempted = report
+            .affected
+            .iter()
+            .filter_map(|id| self.flight(id))
+            .filter(|f| f.curfew_exempt)
+            .filter_map(|f| {
+                Self::waived_propagation_closure(
+                    &self.airports,
+                    self.closure_window,
+                    f,
+                    f.departure_time,
+                    f.arrival_time,
+                )
+                .map(|reason| (f.key(), reason))
+            })
+            .collect();
+        report.slot_violations = self
+            .slot_violations_of(touched.iter().map(|i| &self.flights[*i]))
+            .into_iter()
+            .filter(|v| {
+                !slots_before
+                    .iter()
+                    .any(|b| b.flight == v.flight && b.airport_id == v.airport_id)
+            })
+            .collect();
+        self.sort_airport_legs();
+        report.delay_minutes = report
+            .affected
+            .iter()
+            .filter_map(|id| Some((self.flight(id)?, states_before.get(id)?)))
+            .map(|(flight, before)| (flight.departure_time - before.departure_time).0)
+            .sum();
+        self.record_changes(&mut report, &states_before);
+        self.score(&mut report);
+        report.log();
 
         #[cfg(debug_assertions)]
         self.assert_invariants();
+
+        self.history.push(report.clone());
+        Ok(self.last_report.insert(report))
+    }
+
+    /// Flights a new curfew would unschedule, together with the reason: a flight the curfew
+    /// hits is `AirportCurfew`, and the legs after it that the aircraft can no longer reach
+    /// are `BrokenChain`. A later leg departing from where the aircraft last landed resumes
+    /// the rotation, so skipping a broken loop does not cancel the rest of the day. The
+    /// airport's existing curfews are checked along with the new one.
+    pub(crate) fn curfew_breaks(
+        &self,
+        airport_id: &AirportId,
+        curfew: &Curfew,
+    ) -> Vec<(FlightKey, UnscheduledReason)> {
+        if !self.airports.contains_key(airport_id) {
+            return vec![];
+        }
+        let hits = |f: &Flight| {
+            self.is_curfew_hit(airport_id, curfew, f, f.departure_time, f.arrival_time)
+        };
+
+        // only the rotations of aircraft flying a leg the curfew hits can break
+        let mut hit_aircraft = self
+            .curfew_candidates(airport_id, curfew)
+            .into_iter()
+            .map(|i| &self.flights[i])
+            .filter(|f| !f.status.is_unscheduled() && hits(f))
+            .filter_map(|f| f.aircraft_id.as_ref())
+            .collect::<Vec<_>>();
+        hit_aircraft.sort();
+        hit_aircraft.dedup();
+        let mut legs = hit_aircraft
+            .into_iter()
+            .filter_map(|ac_id| self.rotations.get(ac_id))
+            .flatten()
+            .filter(|i| !self.flights[**i].status.is_unscheduled())
+            .map(|i| (self.flights[*i].departure_time, *i))
+            .collect::<Vec<_>>();
+        legs.sort();
+        let legs = legs.into_iter().map(|(_, i)| &self.flights[i]);
+
+        let mut breaks = vec![];
+        // per broken aircraft, where and when its last operating leg lands
+        let mut broken: HashMap<&AircraftId, Option<(AirportId, Time)>> = HashMap::new();
+        let mut last: HashMap<&AircraftId, (AirportId, Time)> = HashMap::new();
+        for f in legs {
+            let Some(ac_id) = &f.aircraft_id else {
+                continue;
+            };
+            if let Some(landed) = broken.get(ac_id) {
+                let location = landed.as_ref().map_or_else(
+                    || self.aircraft.get(ac_id).map(|ac| &ac.initial_location_id),
+                    |(location, _)| Some(location),
+                );
+                let resumes = location == Some(&f.origin_id)
+                    && !Self::is_at_wrong_airport(
+                        self.aircraft
+                            .get(ac_id)
+                            .map_or(&[][..], |ac| ac.disruptions.as_slice()),
+                        f.departure_time,
+                        landed.as_ref(),
+                    );
+                if !resumes {
+                    breaks.push((f.key(), BrokenChain));
+                    continue;
+                }
+            }
+            if hits(f) {
+                breaks.push((f.key(), AirportCurfew));
+                broken.insert(ac_id, last.get(ac_id).cloned());
+                continue;
+            }
+            broken.remove(ac_id);
+            last.insert(ac_id, (f.destination_id.clone(), f.arrival_time));
+        }
+        breaks
+    }
+
+    /// Whether `flight` operating over `dep..arr` runs into `curfew` or another curfew of
+    /// the airport; curfew-exempt flights never do.
+    pub(crate) fn is_curfew_hit(
+        &self,
+        airport_id: &AirportId,
+        curfew: &Curfew,
+        flight: &Flight,
+        dep: Time,
+        arr: Time,
+    ) -> bool {
+        let Some(airport) = self.airports.get(airport_id) else {
+            return false;
+        };
+        !flight.curfew_exempt
+            && (*flight.origin_id == **airport_id || *flight.destination_id == **airport_id)
+            && airport
+                .disruptions
+                .iter()
+                .chain(std::iter::once(curfew))
+                .any(|c| {
+                    Self::is_curfew_violated(&(dep, arr), c, *flight.origin_id != **airport_id)
+                })
+    }
+
+    /// Counts the passengers hit by a finished report and scores its severity.
+    pub(crate) fn score(&self, report: &mut DisruptionReport) {
+        report.passengers = report
+            .affected
+            .iter()
+            .chain(report.unscheduled.iter().map(|(id, _)| id))
+            .filter_map(|id| self.flight(id))
+            .map(|f| f.passengers as u64)
+            .sum();
+        report.severity = self.severity.score(report);
+        self.rank_breaks(report);
+    }
+
+    /// Picks the report's first and worst breaks. Flights cancelled for a broken chain
+    /// follow the break before them on their aircraft, so the first break is the earliest
+    /// of the others, and the worst is the one whose flights and the chain behind them
+    /// weigh most in unscheduled and passenger points.
+    fn rank_breaks(&self, report: &mut DisruptionReport) {
+        let departure = |id: &FlightKey| self.flight(id).map(|f| f.departure_time);
+        let aircraft = |id: &FlightKey| {
+            report
+                .changes
+                .iter()
+                .find(|c| c.flight == *id)
+                .and_then(|c| c.before.aircraft_id.clone())
+        };
+        let mut breaks = report.unscheduled.clone();
+        breaks.sort_by_key(|(id, _)| departure(id));
+        let (roots, chained) = breaks
+            .iter()
+            .partition::<Vec<_>, _>(|(_, reason)| *reason != BrokenChain);
+        report.first_break = roots.first().or(chained.first()).cloned().cloned();
+
+        let points = |id: &FlightKey| {
+            self.severity.unscheduled
+                + self.severity.passenger * self.flight(id).map_or(0, |f| f.passengers as u64)
+        };
+        let mut weights = roots.iter().map(|(id, _)| points(id)).collect::<Vec<_>>();
+        for (id, _) in &chained {
+            let root = roots.iter().rposition(|(root, _)| {
+                departure(root) <= departure(id)
+                    && aircraft(root).is_some()
+                    && aircraft(root) == aircraft(id)
+            });
+            if let Some(root) = root {
+                weights[root] += points(id);
+            }
+        }
+        report.worst_break = roots
+            .iter()
+            .zip(weights)
+            .rev()
+            .max_by_key(|(_, weight)| *weight)
+            .map(|(b, _)| (*b).clone())
+            .or(report.first_break.clone());
+    }
+
+    /// Fills in `changes` for the report's flights from how they stood in `before`, and
+    /// the compensation exposure the disruption added over the flights in it.
+    pub(crate) fn record_changes(
+        &self,
+        report: &mut DisruptionReport,
+        before: &HashMap<FlightKey, FlightState>,
+    ) {
+        report.changes = report
+            .affected
+            .iter()
+            .chain(report.unscheduled.iter().map(|(id, _)| id))
+            .filter_map(|id| {
+                let old = before.get(id)?;
+                let new = self.flight(id)?;
+                Some(FlightChange {
+                    flight: id.clone(),
+                    before: old.clone(),
+                    after: FlightState::of(new),
+                })
+            })
+            .filter(|change| change.before != change.after)
+            .collect();
+        let (exposure_before, exposure_after) = before
+            .iter()
+            .filter_map(|(id, state)| self.flight(id).map(|flight| (flight, state)))
+            .fold((0, 0), |(old, new), (flight, state)| {
+                (
+                    old + self.compensation.exposure_in(flight, &state.status),
+                    new + self.compensation.flight_exposure(flight),
+                )
+            });
+        report.compensation = exposure_after.saturating_sub(exposure_before);
+    }
+
+    /// States of the flights at `positions`, keyed by flight, taken before a change so
+    /// `record_changes` only has to look at those.
+    pub(crate) fn states_of(&self, positions: &[usize]) -> HashMap<FlightKey, FlightState> {
+        positions
+            .iter()
+            .map(|i| (self.flights[*i].key(), FlightState::of(&self.flights[*i])))
+            .collect()
+    }
+
+    /// Curfew-exempt operating flights at the airport that a new curfew overlaps.
+    fn curfew_waivers(
+        &self,
+        airport_id: &AirportId,
+        curfew: &Curfew,
+    ) -> Vec<(FlightKey, UnscheduledReason)> {
+        self.curfew_candidates(airport_id, curfew)
+            .into_iter()
+            .map(|i| &self.flights[i])
+            .filter(|f| !f.status.is_unscheduled() && f.curfew_exempt)
+            .filter(|f| {
+                Self::is_curfew_violated(
+                    &(f.departure_time, f.arrival_time),
+                    curfew,
+                    *f.origin_id != **airport_id,
+                )
+            })
+            .map(|f| (f.key(), AirportCurfew))
+            .collect()
+    }
+
+    /// What `apply_curfew` would do, without touching the schedule or its history.
+    pub fn preview_curfew(&self, airport_id: AirportId, from: Time, to: Time) -> DisruptionReport {
+        self.preview_curfew_with(
+            airport_id,
+            Curfew {
+                from,
+                to,
+                arrival_tolerance: 0,
+            },
+        )
+    }
+
+    /// What `apply_curfew_with` would do, without touching the schedule or its history.
+    pub fn preview_curfew_with(&self, airport_id: AirportId, curfew: Curfew) -> DisruptionReport {
+        let plan = self.curfew_plan(&airport_id, &curfew);
+        let unscheduled = plan.unscheduled;
+        let exempted = self.curfew_waivers(&airport_id, &curfew);
+        let Curfew {
+            from,
+            to,
+            arrival_tolerance,
+        } = curfew;
+        let compensation = unscheduled
+            .iter()
+            .filter_map(|(f_id, _)| self.flights_index.get(f_id))
+            .map(|idx| {
+                let flight = &self.flights[*idx];
+                (self.compensation.rate(flight) * flight.passengers as u64)
+                    .saturating_sub(self.compensation.flight_exposure(flight))
+            })
+            .sum();
+        let retimed = plan.retimed.iter().filter_map(|(key, dep, arr)| {
+            let flight = self.flight(key)?;
+            let (scheduled, _) = flight.scheduled_times();
+            let status = match (*dep - scheduled).0 {
+                0 => Scheduled,
+                minutes => Delayed { minutes },
+            };
+            Some((flight, *dep, *arr, status))
+        });
+        let cancelled = unscheduled.iter().filter_map(|(key, reason)| {
+            let flight = self.flight(key)?;
+            let status = Unscheduled(reason.clone());
+            Some((flight, flight.departure_time, flight.arrival_time, status))
+        });
+        let changes = retimed
+            .chain(cancelled)
+            .map(
+                |(flight, departure_time, arrival_time, status)| FlightChange {
+                    flight: flight.key(),
+                    before: FlightState::of(flight),
+                    after: FlightState {
+                        departure_time,
+                        arrival_time,
+                        aircraft_id: match status {
+                            Unscheduled(_) => None,
+                            _ => flight.aircraft_id.clone(),
+                        },
+                        status,
+                    },
+                },
+            )
+            .collect();
+        let mut report = DisruptionReport {
+            kind: DisruptionType::Curfew {
+                airport: airport_id,
+                from,
+                to,
+                arrival_tolerance,
+            },
+            affected: plan.retimed.into_iter().map(|(key, ..)| key).collect(),
+            first_break: None,
+            worst_break: None,
+            unscheduled,
+            compensation,
+            violations: vec![],
+            exempted,
+            slot_violations: vec![],
+            delay_minutes: 0,
+            passengers: 0,
+            severity: 0,
+            changes,
+        };
+        self.score(&mut report);
+        report
+    }
+
+    pub fn apply_curfew(
+        &mut self,
+        airport_id: AirportId,
+        from: Time,
+        to: Time,
+    ) -> Result<&DisruptionReport, IrropsError> {
+        self.apply_curfew_with(
+            airport_id,
+            Curfew {
+                from,
+                to,
+                arrival_tolerance: 0,
+            },
+        )
     }
 
-    pub fn apply_curfew(&mut self, airport_id: AirportId, from: Time, to: Time) {
+    /// Closes the airport for `curfew`, unscheduling the flights it hits and the rest of
+    /// their rotations; arrivals within its tolerance keep operating.
+    #[instrument(name = "apply_curfew", level = "debug", skip_all, fields(airport = %airport_id, from = %curfew.from, to = %curfew.to))]
+    pub fn apply_curfew_with(
+        &mut self,
+        airport_id: AirportId,
+        curfew: Curfew,
+    ) -> Result<&DisruptionReport, IrropsError> {
+        if !self.airports.contains_key(&airport_id) {
+            return Err(IrropsError::UnknownAirport(airport_id));
+        }
+        if curfew.from == curfew.to {
+            return Err(IrropsError::EmptyCurfew(airport_id));
+        }
+        let Curfew {
+            from,
+            to,
+            arrival_tolerance,
+        } = curfew;
         let mut report = DisruptionReport {
             kind: DisruptionType::Curfew {
                 airport: airport_id.clone(),
                 from,
                 to,
+                arrival_tolerance,
             },
             affected: vec![],
             unscheduled: vec![],
             first_break: None,
+            worst_break: None,
+            compensation: 0,
+            violations: vec![],
+            exempted: vec![],
+            slot_violations: vec![],
+            delay_minutes: 0,
+            passengers: 0,
+            severity: 0,
+            changes: vec![],
         };
 
-        let maybe_airport = self.airports.get_mut(&airport_id);
-        if let Some(airport) = maybe_airport {
-            airport.disruptions.push(Curfew { from, to });
-
-            let broken = self
-                .flights
-                .iter()
-                .filter(|f| !f.status.is_unscheduled())
-                .filter(|f| *f.origin_id == *airport_id || *f.destination_id == *airport_id)
-                .filter(|f| {
-                    airport.disruptions.iter().any(|Curfew { from, to }| {
-                        Time::is_overlapping(&(f.departure_time, f.arrival_time), &(*from, *to))
-                    })
-                })
-                .fold(HashMap::new(), |mut acc: HashMap<AircraftId, Time>, f| {
-                    if let Some(ac_id) = f.aircraft_id.clone() {
-                        acc.entry(ac_id).or_insert(f.departure_time);
-                    }
-                    acc
-                });
-
-            let mut counter: HashMap<AircraftId, usize> = HashMap::new();
-            self.flights
-                .iter_mut()
-                .filter(|f| !f.status.is_unscheduled())
-                .for_each(|f| {
-                    if let Some(ac_id) = &f.aircraft_id {
-                        let broken_time = broken.get(ac_id);
-                        if let Some(time) = broken_time {
-                            if f.departure_time >= *time {
-                                counter
-                                    .entry(ac_id.clone())
-                                    .and_modify(|e| *e += 1)
-                                    .or_insert(0);
-                                report.unscheduled.push((
-                                    f.id.clone(),
-                                    if counter.get(&ac_id.clone()).map_or(true, |x| *x == 0) {
-                                        AirportCurfew
-                                    } else {
-                                        BrokenChain
-                                    },
-                                ));
-                            }
-                        }
-                    }
-                })
+        let plan = self.curfew_plan(&airport_id, &curfew);
+        let touched = plan
+            .unscheduled
+            .iter()
+            .map(|(key, _)| key)
+            .chain(plan.retimed.iter().map(|(key, _, _)| key))
+            .filter_map(|key| self.flights_index.get(key).copied())
+            .collect::<Vec<_>>();
+        let states_before = self.states_of(&touched);
+        report.unscheduled = plan.unscheduled;
+        report.exempted = self.curfew_waivers(&airport_id, &curfew);
+        if let Some(airport) = self.airports.get_mut(&airport_id) {
+            airport.add_curfew(curfew);
         }
         report.unscheduled.iter().for_each(|(f_id, reason)| {
-            self.unschedule(f_id, *reason);
+            self.unschedule(f_id, reason.clone());
         });
-        report.first_break = report.unscheduled.first().cloned();
-
-        self.last_report = Some(report);
+        for (key, dep, arr) in plan.retimed {
+            self.move_back(&key, dep, arr);
+            report.affected.push(key);
+        }
+        self.sort_airport_legs();
+        self.record_changes(&mut report, &states_before);
+        self.score(&mut report);
+        report.log();
 
         #[cfg(debug_assertions)]
         self.assert_invariants();
+
+        self.history.push(report.clone());
+        Ok(self.last_report.insert(report))
     }
 
-    #[cfg(debug_assertions)]
-    fn assert_invariants(&self) {
-        debug_assert!(
-            self.flights.iter().all(|f| {
-                match &f.status {
-                    Unscheduled(_) => f.aircraft_id.is_none(),
-                    Scheduled | Delayed { .. } => f.aircraft_id.is_some(),
-                }
-            }),
-            "Status <-> aircraft_id invariant violated"
-        );
+    /// Closes the airport for `curfew` the way controllers handle short closures: flights
+    /// departing from it inside the window are delayed to the moment it reopens, with the
+    /// delay propagated down their rotations as `apply_delay` does. Only what cannot be
+    /// pushed, over max delay or into another closure, and whatever else the curfew hits
+    /// are unscheduled. Everything lands in a single curfew report, which is returned.
+    #[instrument(level = "debug", skip_all, fields(airport = %airport_id, from = %curfew.from, to = %curfew.to))]
+    pub fn push_curfew(
+        &mut self,
+        airport_id: AirportId,
+        curfew: Curfew,
+    ) -> Result<&DisruptionReport, IrropsError> {
+        if !self.airports.contains_key(&airport_id) {
+            return Err(IrropsError::UnknownAirport(airport_id));
+        }
+        if curfew.from == curfew.to {
+            return Err(IrropsError::EmptyCurfew(airport_id));
+        }
+        let Curfew {
+            from,
+            to,
+            arrival_tolerance,
+        } = curfew;
+        let mut report = DisruptionReport {
+            kind: DisruptionType::Curfew {
+                airport: airport_id.clone(),
+                from,
+                to,
+                arrival_tolerance,
+            },
+            affected: vec![],
+            unscheduled: vec![],
+            first_break: None,
+            worst_break: None,
+            compensation: 0,
+            violations: vec![],
+            exempted: vec![],
+            slot_violations: vec![],
+            delay_minutes: 0,
+            passengers: 0,
+            severity: 0,
+            changes: vec![],
+        };
 
-        debug_assert!(
-            self.flights.iter().all(|f| {
-                match &f.status {
-                    Delayed { minutes } => *minutes > 0,
-                    _ => true,
+        // the pushes bring the states of the flights they change
+        let mut states_before = HashMap::new();
+        report.exempted = self.curfew_waivers(&airport_id, &curfew);
+        if let Some(airport) = self.airports.get_mut(&airport_id) {
+            airport.add_curfew(curfew.clone());
+        }
+
+        // each push moves the flight out of the window or unschedules it, so this ends
+        let departures = self.departing_within(&airport_id, &curfew).to_vec();
+        while let Some((key, push)) = departures
+            .iter()
+            .map(|i| &self.flights[*i])
+            .filter(|f| !f.status.is_unscheduled() && !f.curfew_exempt)
+            .find_map(|f| {
+                curfew
+                    .windows((f.departure_time, f.departure_time + 1))
+                    .first()
+                    .map(|(_, reopen)| (f.key(), (*reopen - f.departure_time).0))
+            })
+        {
+            if self.apply_delay(key, push).is_ok()
+                && let Some(delay) = self.history.pop()
+            {
+                for change in &delay.changes {
+                    states_before
+                        .entry(change.flight.clone())
+                        .or_insert_with(|| change.before.clone());
                 }
-            }),
-            "Delay > 0 invariant violated"
-        );
+                report.absorb(delay);
+            }
+        }
 
-        let mut flight_by_aircraft: HashMap<AircraftId, Vec<&Flight>> = HashMap::new();
-        for flight in &self.flights {
-            if let Some(ac_id) = &flight.aircraft_id {
-                flight_by_aircraft
-                    .entry(ac_id.clone())
-                    .or_default()
-                    .push(flight);
+        let plan = self.curfew_plan(&airport_id, &curfew);
+        for key in plan
+            .unscheduled
+            .iter()
+            .map(|(key, _)| key)
+            .chain(plan.retimed.iter().map(|(key, _, _)| key))
+        {
+            if let Some(flight) = self.flight(key) {
+                states_before
+                    .entry(key.clone())
+                    .or_insert_with(|| FlightState::of(flight));
             }
         }
-        for (ac_id, mut flights) in flight_by_aircraft.into_iter() {
-            flights.sort_by_key(|f| f.departure_time);
-            debug_assert!(
-                flights
-                    .windows(2)
-                    .all(|fs| { fs[0].destination_id == fs[1].origin_id }),
-                "Pref destination <-> next origin location continuity violated"
-            );
-            debug_assert!(
-                flights.windows(2).all(|fs| {
-                    let mtt = self
-                        .airports
-                        .get(&fs[0].destination_id)
-                        .map(|a| a.mtt)
-                        .unwrap();
-                    fs[1].departure_time >= fs[0].arrival_time + mtt
-                }),
-                "Pref destination <-> next origin temporal continuity violated"
-            );
-
-            if let Some(flight) = flights.first() {
-                debug_assert_eq!(
-                    flight.origin_id,
-                    self.aircraft.get(&ac_id).unwrap().initial_location_id,
-                    "First flight origin <-> aircraft initial location violated"
-                );
+        plan.unscheduled.iter().for_each(|(f_id, reason)| {
+            self.unschedule(f_id, reason.clone());
+        });
+        for (key, dep, arr) in plan.retimed {
+            self.move_back(&key, dep, arr);
+            if !report.affected.contains(&key) {
+                report.affected.push(key);
             }
         }
+        self.sort_airport_legs();
+        report.unscheduled.extend(plan.unscheduled);
+        report
+            .affected
+            .retain(|id| report.unscheduled.iter().all(|(u, _)| u != id));
+        self.record_changes(&mut report, &states_before);
+        self.score(&mut report);
+        report.log();
+
+        #[cfg(debug_assertions)]
+        self.assert_invariants();
+
+        self.history.push(report.clone());
+        Ok(self.last_report.insert(report))
+    }
+
+    #[cfg(debug_assertions)]
+    pub(crate) fn assert_invariants(&self) {
+        let violations = self.audit();
+        debug_assert!(
+            violations.is_empty(),
+            "Schedule invariants violated: {:?}",
+            violations
+        );
+        let indexed = self
+            .rotations
+            .iter()
+            .flat_map(|(ac_id, chain)| chain.iter().map(move |i| (ac_id, *i)))
+            .collect::<HashSet<_>>();
+        let flown = self
+            .flights
+            .iter()
+            .enumerate()
+            .filter_map(|(i, f)| f.aircraft_id.as_ref().map(|ac_id| (ac_id, i)))
+            .collect::<HashSet<_>>();
+        debug_assert!(indexed == flown, "Rotation index out of date");
+        debug_assert!(
+            self.airport_legs
+                .values()
+                .all(|legs| legs.is_sorted(&self.flights)),
+            "Airport legs out of date"
+        );
     }
 }
@@ -0,0 +1,227 @@
+use crate::aircraft::{AircraftId, FleetType};
+use crate::airport::AirportId;
+use crate::flight::FlightId;
+use crate::schedule::schedule::Schedule;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// Something `find` turned up.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Hit {
+    /// A flight number with the days it operates on.
+    Flight {
+        number: FlightId,
+        route: String,
+        days: Vec<u64>,
+    },
+    /// Flights between two airports, written `KRK-WAW`.
+    Route { route: String, flights: usize },
+    Aircraft {
+        id: AircraftId,
+        fleet_type: Option<FleetType>,
+        flights: usize,
+    },
+}
+
+impl Hit {
+    pub fn name(&self) -> &str {
+        match self {
+            Hit::Flight { number, .. } => number,
+            Hit::Route { route, .. } => route,
+            Hit::Aircraft { id, .. } => id,
+        }
+    }
+}
+
+/// A hit and how closely it matched; lower scores are better, 0 being exact.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchHit {
+    pub hit: Hit,
+    pub score: usize,
+}
+
+impl Schedule {
+    /// Flight numbers, routes and aircraft resembling `text`, best first. Case and
+    /// punctuation are ignored, so `fl101` finds `FL-101` and `krk waw` finds `KRK-WAW`;
+    /// a name matches exactly, by prefix, by substring, with the typed characters in
+    /// order, or with a typo or two.
+    pub fn search(&self, text: &str) -> Vec<SearchHit> {
+        let query = normalize(text);
+        if query.is_empty() {
+            return vec![];
+        }
+        let mut numbers: BTreeMap<FlightId, (String, Vec<u64>)> = BTreeMap::new();
+        let mut routes: BTreeMap<String, usize> = BTreeMap::new();
+        let mut flown: BTreeMap<AircraftId, usize> = BTreeMap::new();
+        for f in &self.flights {
+            let route = format!("{}-{}", f.origin_id, f.destination_id);
+            numbers
+                .entry(f.id.clone())
+                .or_insert_with(|| (route.clone(), vec![]))
+                .1
+                .push(f.day);
+            *routes.entry(route).or_default() += 1;
+            if let Some(id) = &f.aircraft_id {
+                *flown.entry(id.clone()).or_default() += 1;
+            }
+        }
+
+        let flights = numbers.into_iter().map(|(number, (route, mut days))| {
+            days.sort();
+            days.dedup();
+            Hit::Flight {
+                number,
+                route,
+                days,
+            }
+        });
+        let routes = routes
+            .into_iter()
+            .map(|(route, flights)| Hit::Route { route, flights });
+        let aircraft = self.aircraft.values().map(|a| Hit::Aircraft {
+            id: a.id.clone(),
+            fleet_type: a.fleet_type.clone(),
+            flights: flown.get(&a.id).copied().unwrap_or(0),
+        });
+
+        let mut hits = flights
+            .chain(routes)
+            .chain(aircraft)
+            .filter_map(|hit| {
+                score(&query, &normalize(hit.name())).map(|score| SearchHit { hit, score })
+            })
+            .collect::<Vec<_>>();
+        hits.sort_by(|a, b| {
+            (a.score, a.hit.name().len(), a.hit.name()).cmp(&(
+                b.score,
+                b.hit.name().len(),
+                b.hit.name(),
+            ))
+        });
+        hits
+    }
+}
+
+/// How many ids a "did you mean" offers at most.
+const SUGGESTIONS: usize = 3;
+
+impl Schedule {
+    /// Flights resembling an unknown `FL-10` or `FL-10/2`, closest first, for a "did you
+    /// mean" hint; the day typed is kept.
+    pub fn suggest_flights(&self, text: &str) -> Vec<String> {
+        let (number, day) = match text.rsplit_once('/') {
+            Some((number, day)) if day.parse::<u64>().is_ok() => (number, Some(day)),
+            _ => (text, None),
+        };
+        closest(number, self.flights.iter().map(|f| &*f.id))
+            .into_iter()
+            .map(|closest| match day {
+                Some(day) => format!("{}/{}", closest, day),
+                None => closest.to_string(),
+            })
+            .collect()
+    }
+
+    pub fn suggest_airports(&self, text: &str) -> Vec<AirportId> {
+        closest(text, self.airports.keys().map(|id| &**id))
+            .into_iter()
+            .map(AirportId::from)
+            .collect()
+    }
+
+    pub fn suggest_aircraft(&self, text: &str) -> Vec<AircraftId> {
+        closest(text, self.aircraft.keys().map(|id| &**id))
+            .into_iter()
+            .map(AircraftId::from)
+            .collect()
+    }
+}
+
+/// `message`, followed by the ids offered instead when there are any.
+pub fn did_you_mean(message: String, suggestions: &[impl Display]) -> String {
+    let names = suggestions
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    match names.split_last() {
+        None => message,
+        Some((last, [])) => format!("{}; did you mean {}?", message, last),
+        Some((last, rest)) => format!("{}; did you mean {} or {}?", message, rest.join(", "), last),
+    }
+}
+
+/// The few names among `names` that `text` most resembles, closest first, leaving out
+/// `text` itself.
+fn closest<'a>(text: &str, names: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let query = normalize(text);
+    if query.is_empty() {
+        return vec![];
+    }
+    let mut matches = names
+        .into_iter()
+        .filter(|name| *name != text)
+        .filter_map(|name| score(&query, &normalize(name)).map(|score| (score, name.len(), name)))
+        .collect::<Vec<_>>();
+    matches.sort();
+    matches.dedup();
+    matches
+        .into_iter()
+        .take(SUGGESTIONS)
+        .map(|(_, _, name)| name)
+        .collect()
+}
+
+/// Lowercase letters and digits only.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// How well `candidate` matches `query`, both normalized, or `None` when it does not.
+fn score(query: &str, candidate: &str) -> Option<usize> {
+    if candidate == query {
+        return Some(0);
+    }
+    if candidate.starts_with(query) {
+        return Some(1);
+    }
+    if candidate.contains(query) {
+        return Some(2);
+    }
+    let mut rest = candidate.chars();
+    if query.chars().all(|q| rest.any(|c| c == q)) {
+        return Some(3);
+    }
+    // a typo per four characters typed, from three on, against the whole name or its start
+    let allowed = (query.chars().count() + 1) / 4;
+    let start = candidate
+        .chars()
+        .take(query.chars().count())
+        .collect::<String>();
+    let distance = edit_distance(query, candidate).min(edit_distance(query, &start));
+    (allowed > 0 && distance <= allowed).then_some(3 + distance)
+}
+
+/// Edits turning `a` into `b`: insertions, deletions, substitutions and swaps of two
+/// neighbouring characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+    // rows[i][j] is the distance between the first i characters of a and j of b
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i];
+        for j in 1..=b.len() {
+            let mut distance = (rows[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]))
+                .min(rows[i - 1][j] + 1)
+                .min(row[j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            row.push(distance);
+        }
+        rows.push(row);
+    }
+    rows[a.len()][b.len()]
+}
@@ -0,0 +1,122 @@
+use crate::aircraft::AircraftId;
+use crate::flight::{Flight, FlightKey};
+use crate::schedule::schedule::Schedule;
+use std::collections::BTreeMap;
+
+pub struct Connection {
+    pub inbound: FlightKey,
+    pub outbound: FlightKey,
+    pub buffer: u64,
+}
+
+pub struct RotationSlack {
+    pub aircraft_id: AircraftId,
+    pub legs: usize,
+    pub connections: Vec<Connection>,
+    pub min_buffer: Option<u64>,
+    /// Largest delay on the first leg that propagates without breaking the chain.
+    pub absorbable: u64,
+    /// Leg that caps `absorbable`, if any leg is tighter than the max delay limit.
+    pub limited_by: Option<FlightKey>,
+}
+
+impl Schedule {
+    /// Largest shift a single leg can take before it hits maintenance, a curfew or
+    /// the max delay limit, mirroring the checks done by `apply_delay`.
+    fn leg_tolerance(&self, flight: &Flight) -> u64 {
+        let mut tolerance = self.max_delay;
+
+        let disruptions = flight
+            .aircraft_id
+            .as_ref()
+            .and_then(|id| self.aircraft.get(id))
+            .map(|a| a.disruptions.as_slice())
+            .unwrap_or_default();
+        for d in disruptions.iter().filter(|d| d.to > flight.departure_time) {
+            // the extended window [dep, arr + s] starts to overlap once arr + s > from
+            tolerance = tolerance.min(d.from.0.saturating_sub(flight.arrival_time.0));
+        }
+
+        // closures at either end must not overlap the shifted block [dep + s, arr + s]
+        let span = (
+            flight.departure_time,
+            flight.arrival_time + self.max_delay + 1,
+        );
+        for (from, _) in self
+            .airports
+            .get(&flight.origin_id)
+            .into_iter()
+            .flat_map(|ap| ap.closed_windows(span))
+            .chain(
+                self.airports
+                    .get(&flight.destination_id)
+                    .into_iter()
+                    .flat_map(|ap| ap.arrival_closed_windows(span)),
+            )
+            .filter(|_| !flight.curfew_exempt)
+        {
+            tolerance = tolerance.min(from.0.saturating_sub(flight.arrival_time.0));
+        }
+
+        tolerance
+    }
+
+    pub fn rotations(&self) -> BTreeMap<AircraftId, Vec<&Flight>> {
+        let mut rotations = BTreeMap::<AircraftId, Vec<&Flight>>::new();
+        for flight in &self.flights {
+            if let Some(ac_id) = &flight.aircraft_id {
+                rotations.entry(ac_id.clone()).or_default().push(flight);
+            }
+        }
+        rotations
+            .values_mut()
+            .for_each(|flights| flights.sort_by_key(|f| f.departure_time));
+        rotations
+    }
+
+    pub fn slack(&self) -> Vec<RotationSlack> {
+        self.rotations()
+            .into_iter()
+            .map(|(aircraft_id, flights)| {
+                let connections = flights
+                    .windows(2)
+                    .map(|fs| {
+                        let ready_at = Self::get_ready_time(
+                            &self.airports,
+                            fs[0].arrival_time,
+                            &fs[0].destination_id,
+                        );
+                        Connection {
+                            inbound: fs[0].key(),
+                            outbound: fs[1].key(),
+                            buffer: fs[1].departure_time.0.saturating_sub(ready_at.0),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut absorbable = self.max_delay;
+                let mut limited_by = None;
+                let mut cumulative_buffer = 0;
+                for (i, flight) in flights.iter().enumerate() {
+                    if i > 0 {
+                        cumulative_buffer += connections[i - 1].buffer;
+                    }
+                    let limit = self.leg_tolerance(flight) + cumulative_buffer;
+                    if limit < absorbable {
+                        absorbable = limit;
+                        limited_by = Some(flight.key());
+                    }
+                }
+
+                RotationSlack {
+                    aircraft_id,
+                    legs: flights.len(),
+                    min_buffer: connections.iter().map(|c| c.buffer).min(),
+                    connections,
+                    absorbable,
+                    limited_by,
+                }
+            })
+            .collect()
+    }
+}
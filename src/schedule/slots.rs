@@ -0,0 +1,81 @@
+use crate::airport::AirportId;
+use crate::flight::{Flight, FlightKey};
+use crate::schedule::schedule::Schedule;
+use crate::time::Time;
+
+/// A departure or arrival moved outside its slot tolerance at a coordinated airport.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlotViolation {
+    pub flight: FlightKey,
+    pub airport_id: AirportId,
+    pub slot: Time,
+    pub actual: Time,
+    /// Whether the airport still has capacity to slot the movement at its new hour.
+    pub new_slot: bool,
+}
+
+impl Schedule {
+    /// Minutes the flight now departs after the time it entered the schedule with,
+    /// negative when moved earlier.
+    pub fn plan_delta(&self, flight: &Flight) -> Option<i64> {
+        let (departure, _) = self.slots.get(&flight.key())?;
+        Some(flight.departure_time.0 as i64 - departure.0 as i64)
+    }
+
+    /// Movements of operating flights outside their slot tolerance, in departure order.
+    pub fn slot_violations(&self) -> Vec<SlotViolation> {
+        self.slot_violations_of(self.flights.iter())
+    }
+
+    /// Movements of those of `flights` that operate outside their slot tolerance.
+    pub(crate) fn slot_violations_of<'a>(
+        &self,
+        flights: impl IntoIterator<Item = &'a Flight>,
+    ) -> Vec<SlotViolation> {
+        let mut violations = vec![];
+        for f in flights.into_iter().filter(|f| !f.status.is_unscheduled()) {
+            let Some((departure, arrival)) = self.slots.get(&f.key()) else {
+                continue;
+            };
+            for (airport_id, slot, actual) in [
+                (&f.origin_id, *departure, f.departure_time),
+                (&f.destination_id, *arrival, f.arrival_time),
+            ] {
+                let Some(tolerance) = self.airports.get(airport_id).and_then(|a| a.slot_tolerance)
+                else {
+                    continue;
+                };
+                if slot.0.abs_diff(actual.0) > tolerance {
+                    violations.push(SlotViolation {
+                        flight: f.key(),
+                        airport_id: airport_id.clone(),
+                        slot,
+                        actual,
+                        new_slot: self.has_free_slot(airport_id, &f.key(), actual),
+                    });
+                }
+            }
+        }
+        violations
+    }
+
+    /// Whether the movements slotted at the airport in the clock hour of `at`, other than
+    /// those of `flight`, leave room for one more.
+    fn has_free_slot(&self, airport_id: &AirportId, flight: &FlightKey, at: Time) -> bool {
+        let Some(capacity) = self.airports.get(airport_id).and_then(|a| a.slots_per_hour) else {
+            return false;
+        };
+        let hour = at.0 / 60;
+        let taken = self
+            .flights
+            .iter()
+            .filter(|f| f.key() != *flight && !f.status.is_unscheduled())
+            .filter_map(|f| self.slots.get(&f.key()).map(|slots| (f, slots)))
+            .map(|(f, (departure, arrival))| {
+                (f.origin_id == *airport_id && departure.0 / 60 == hour) as u32
+                    + (f.destination_id == *airport_id && arrival.0 / 60 == hour) as u32
+            })
+            .sum::<u32>();
+        taken < capacity
+    }
+}
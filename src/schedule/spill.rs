@@ -0,0 +1,44 @@
+use crate::aircraft::AircraftId;
+use crate::flight::{Flight, FlightKey};
+use crate::schedule::schedule::Schedule;
+
+impl Schedule {
+    /// Seats on an aircraft or charter, when its fleet type has a seat count in `seats`.
+    pub fn seats(&self, aircraft_id: &AircraftId) -> Option<u32> {
+        let fleet_type = match self.aircraft.get(aircraft_id) {
+            Some(aircraft) => aircraft.fleet_type.as_ref(),
+            None => self
+                .charters
+                .iter()
+                .find(|c| c.id == *aircraft_id)
+                .and_then(|c| c.fleet_type.as_ref()),
+        }?;
+        self.seats.get(fleet_type).copied()
+    }
+
+    /// Passengers booked on `flight` that would not fit on `aircraft_id`; none when its
+    /// seat count is unknown.
+    pub fn spill_on(&self, flight: &Flight, aircraft_id: &AircraftId) -> u32 {
+        self.seats(aircraft_id)
+            .map_or(0, |seats| flight.passengers.saturating_sub(seats))
+    }
+
+    /// Operating flights downgauged below their passenger count, with the spill.
+    pub fn spills(&self) -> Vec<(FlightKey, u32)> {
+        self.flights
+            .iter()
+            .filter(|f| !f.status.is_unscheduled())
+            .filter_map(|f| {
+                let spill = self.spill_on(f, f.aircraft_id.as_ref()?);
+                (spill > 0).then(|| (f.key(), spill))
+            })
+            .collect()
+    }
+
+    pub fn spill_cost(&self) -> u64 {
+        self.spills()
+            .iter()
+            .map(|(_, spill)| *spill as u64 * self.cost.spilled_passenger)
+            .sum()
+    }
+}
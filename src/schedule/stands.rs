@@ -0,0 +1,286 @@
+use crate::aircraft::AircraftId;
+use crate::airport::AirportId;
+use crate::schedule::schedule::Schedule;
+use crate::time::Time;
+use std::collections::HashMap;
+
+/// An aircraft parked at an airport from its arrival until its next departure, if any.
+#[derive(Clone)]
+pub struct GroundSpell {
+    pub aircraft_id: AircraftId,
+    pub from: Time,
+    pub to: Option<Time>,
+}
+
+/// A stretch of time with more aircraft on the ground than the airport has stands.
+pub struct StandOverflow {
+    pub airport_id: AirportId,
+    pub from: Time,
+    pub to: Option<Time>,
+    pub peak: usize,
+    pub stands: u32,
+}
+
+impl Schedule {
+    /// Ground spells at an airport, including aircraft that start the day parked there.
+    pub fn ground_spells(&self, airport_id: &AirportId) -> Vec<GroundSpell> {
+        let mut spells = StandBook::of(self, |id| id == airport_id)
+            .airports
+            .remove(airport_id)
+            .map(|(_, spells)| spells)
+            .unwrap_or_default();
+        spells.sort_by_key(|s| (s.from, s.aircraft_id.clone()));
+        spells
+    }
+
+    /// Number of aircraft on the ground at an airport from each listed time onwards.
+    pub fn ground_occupancy(&self, airport_id: &AirportId) -> Vec<(Time, usize)> {
+        let mut events = self
+            .ground_spells(airport_id)
+            .iter()
+            .flat_map(|s| {
+                Some((s.from, 1i64))
+                    .into_iter()
+                    .chain(s.to.map(|to| (to, -1)))
+            })
+            .collect::<Vec<_>>();
+        // a departure frees its stand for an arrival in the same minute
+        events.sort();
+
+        let mut steps = Vec::<(Time, usize)>::new();
+        let mut count = 0;
+        for (time, change) in events {
+            count += change;
+            match steps.last_mut() {
+                Some((last, c)) if *last == time => *c = count as usize,
+                _ => steps.push((time, count as usize)),
+            }
+        }
+        steps
+    }
+
+    /// Every stretch during which an airport holds more aircraft than it has stands.
+    pub fn stand_overflows(&self) -> Vec<StandOverflow> {
+        let mut airports = self
+            .airports
+            .values()
+            .filter_map(|ap| ap.stands.map(|stands| (ap.id.clone(), stands)))
+            .collect::<Vec<_>>();
+        airports.sort();
+
+        let mut overflows = Vec::new();
+        for (airport_id, stands) in airports {
+            let mut current: Option<StandOverflow> = None;
+            for (time, count) in self.ground_occupancy(&airport_id) {
+                match current.as_mut() {
+                    Some(o) if count > stands as usize => o.peak = o.peak.max(count),
+                    Some(o) => {
+                        o.to = Some(time);
+                        overflows.extend(current.take());
+                    }
+                    None if count > stands as usize => {
+                        current = Some(StandOverflow {
+                            airport_id: airport_id.clone(),
+                            from: time,
+                            to: None,
+                            peak: count,
+                            stands,
+                        })
+                    }
+                    None => {}
+                }
+            }
+            overflows.extend(current);
+        }
+        overflows
+    }
+}
+
+/// Where one aircraft starting the day at `initial` is parked around the legs it flies,
+/// given in order as (departure, destination, arrival).
+fn spells_of<'a>(
+    aircraft_id: &AircraftId,
+    initial: &'a AirportId,
+    legs: impl IntoIterator<Item = (Time, &'a AirportId, Time)>,
+) -> Vec<(&'a AirportId, GroundSpell)> {
+    let mut spells = vec![];
+    let (mut location, mut from) = (initial, Time(0));
+    for (departure, destination, arrival) in legs {
+        spells.push((
+            location,
+            GroundSpell {
+                aircraft_id: aircraft_id.clone(),
+                from,
+                to: Some(departure),
+            },
+        ));
+        (location, from) = (destination, arrival);
+    }
+    spells.push((
+        location,
+        GroundSpell {
+            aircraft_id: aircraft_id.clone(),
+            from,
+            to: None,
+        },
+    ));
+    spells
+}
+
+/// Ground spells at the airports with a stand limit, kept up to date while a recovery
+/// pass places aircraft so it never parks more of them than there are stands.
+#[derive(Default)]
+pub(crate) struct StandBook {
+    airports: HashMap<AirportId, (u32, Vec<GroundSpell>)>,
+}
+
+impl StandBook {
+    /// The ground spells of the operating legs of `schedule` at the airports `keep` picks.
+    fn of(schedule: &Schedule, keep: impl Fn(&AirportId) -> bool) -> StandBook {
+        let rotations = schedule.rotations();
+        let mut book = StandBook::default();
+        for (ac_id, aircraft) in &schedule.aircraft {
+            let legs = rotations
+                .get(ac_id)
+                .into_iter()
+                .flatten()
+                .filter(|f| !f.status.is_unscheduled())
+                .map(|f| (f.departure_time, &f.destination_id, f.arrival_time));
+            for (airport_id, spell) in spells_of(ac_id, &aircraft.initial_location_id, legs) {
+                if keep(airport_id) {
+                    book.airports
+                        .entry(airport_id.clone())
+                        .or_insert((0, vec![]))
+                        .1
+                        .push(spell);
+                }
+            }
+        }
+        for (airport_id, (stands, _)) in book.airports.iter_mut() {
+            *stands = schedule
+                .airports
+                .get(airport_id)
+                .and_then(|ap| ap.stands)
+                .unwrap_or(u32::MAX);
+        }
+        book
+    }
+
+    /// Spells of the operating legs of `schedule` at the airports with a stand limit.
+    pub(crate) fn new(schedule: &Schedule) -> StandBook {
+        StandBook::of(schedule, |id| {
+            schedule
+                .airports
+                .get(id)
+                .is_some_and(|ap| ap.stands.is_some())
+        })
+    }
+
+    /// Spells of the given rotations instead, each leg as (flight position, departure,
+    /// arrival), as a search holds them before writing them back.
+    pub(crate) fn of_rotations<'a>(
+        schedule: &'a Schedule,
+        rotations: impl IntoIterator<Item = (&'a AircraftId, Vec<(usize, Time, Time)>)>,
+    ) -> StandBook {
+        let mut book = StandBook::default();
+        for (ac_id, legs) in rotations {
+            let Some(aircraft) = schedule.aircraft.get(ac_id) else {
+                continue;
+            };
+            let legs = legs
+                .into_iter()
+                .map(|(f, dep, arr)| (dep, &schedule.flights[f].destination_id, arr));
+            for (airport_id, spell) in spells_of(ac_id, &aircraft.initial_location_id, legs) {
+                if let Some(stands) = schedule.airports.get(airport_id).and_then(|ap| ap.stands) {
+                    book.airports
+                        .entry(airport_id.clone())
+                        .or_insert((stands, vec![]))
+                        .1
+                        .push(spell);
+                }
+            }
+        }
+        book
+    }
+
+    /// Whether no airport limits its stands.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.airports.is_empty()
+    }
+
+    /// Whether parking one more aircraft at `airport_id` from `from` until `to`, or for
+    /// good, would take more stands than the airport has.
+    pub(crate) fn is_full(&self, airport_id: &AirportId, from: Time, to: Option<Time>) -> bool {
+        let Some((stands, spells)) = self.airports.get(airport_id) else {
+            return false;
+        };
+        let until = to.unwrap_or(Time(u64::MAX));
+        // the count only rises where a spell starts, so those are the times to check
+        std::iter::once(from)
+            .chain(
+                spells
+                    .iter()
+                    .map(|s| s.from)
+                    .filter(|t| *t > from && *t < until),
+            )
+            .any(|t| {
+                spells
+                    .iter()
+                    .filter(|s| s.from <= t && s.to.is_none_or(|to| t < to))
+                    .count()
+                    >= *stands as usize
+            })
+    }
+
+    /// Moves `aircraft_id` off the stand it holds at `origin` at `departure` onto one at
+    /// `destination` from `arrival` until its `next` departure, if it has one.
+    pub(crate) fn fly(
+        &mut self,
+        aircraft_id: &AircraftId,
+        (origin, departure): (&AirportId, Time),
+        (destination, arrival): (&AirportId, Time),
+        next: Option<Time>,
+    ) {
+        if let Some((_, spells)) = self.airports.get_mut(origin)
+            && let Some(spell) = spells.iter_mut().find(|s| {
+                s.aircraft_id == *aircraft_id
+                    && s.from <= departure
+                    && s.to.is_none_or(|to| departure < to)
+            })
+        {
+            spell.to = Some(departure);
+        }
+        if let Some((_, spells)) = self.airports.get_mut(destination) {
+            spells.push(GroundSpell {
+                aircraft_id: aircraft_id.clone(),
+                from: arrival,
+                to: next,
+            });
+        }
+    }
+
+    /// Aircraft parked beyond the stands at the worst moment of each airport, summed.
+    pub(crate) fn excess(&self) -> usize {
+        self.airports
+            .values()
+            .map(|(stands, spells)| {
+                let mut events = spells
+                    .iter()
+                    .flat_map(|s| {
+                        Some((s.from, 1i64))
+                            .into_iter()
+                            .chain(s.to.map(|to| (to, -1)))
+                    })
+                    .collect::<Vec<_>>();
+                events.sort();
+                let mut count = 0i64;
+                let mut peak = 0;
+                for (_, change) in events {
+                    count += change;
+                    peak = peak.max(count);
+                }
+                (peak as usize).saturating_sub(*stands as usize)
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,82 @@
+use crate::airport::AirportId;
+use crate::flight::FlightKey;
+use crate::schedule::schedule::Schedule;
+use std::collections::{BTreeMap, HashMap};
+
+/// Passengers left overnight at an airport on one operating day, with the flights that
+/// failed them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stranding {
+    pub airport_id: AirportId,
+    pub day: u64,
+    pub passengers: u32,
+    pub flights: Vec<FlightKey>,
+}
+
+impl Schedule {
+    /// Passengers of cancelled flights, and of flights pushed past their operating day,
+    /// who find no seat on another operating flight between the same airports leaving
+    /// that day after their scheduled departure, by origin and day. Spare seats go to the
+    /// earliest disrupted flight first; an aircraft without a seat count takes everyone.
+    pub fn strandings(&self) -> Vec<Stranding> {
+        let mut spare = self
+            .flights
+            .iter()
+            .filter(|f| !f.status.is_unscheduled())
+            .map(|f| {
+                let seats = f.aircraft_id.as_ref().and_then(|id| self.seats(id));
+                (f.key(), seats.map(|s| s.saturating_sub(f.passengers)))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut disrupted = self
+            .flights
+            .iter()
+            .filter(|f| f.passengers > 0)
+            .filter(|f| f.status.is_unscheduled() || f.departure_time.day() > f.day)
+            .collect::<Vec<_>>();
+        disrupted.sort_by_key(|f| (f.departure_time, f.key()));
+
+        let mut strandings: BTreeMap<(AirportId, u64), Stranding> = BTreeMap::new();
+        for flight in disrupted {
+            let mut alternatives = self
+                .flights
+                .iter()
+                .filter(|f| f.key() != flight.key() && !f.status.is_unscheduled())
+                .filter(|f| f.origin_id == flight.origin_id)
+                .filter(|f| f.destination_id == flight.destination_id)
+                .filter(|f| f.departure_time.day() == flight.day)
+                .filter(|f| f.departure_time >= flight.scheduled_times().0)
+                .collect::<Vec<_>>();
+            alternatives.sort_by_key(|f| f.departure_time);
+
+            let mut left = flight.passengers;
+            for alternative in alternatives {
+                match spare.get_mut(&alternative.key()) {
+                    Some(Some(seats)) => {
+                        let taken = (*seats).min(left);
+                        *seats -= taken;
+                        left -= taken;
+                    }
+                    _ => left = 0,
+                }
+                if left == 0 {
+                    break;
+                }
+            }
+            if left > 0 {
+                let stranding = strandings
+                    .entry((flight.origin_id.clone(), flight.day))
+                    .or_insert_with(|| Stranding {
+                        airport_id: flight.origin_id.clone(),
+                        day: flight.day,
+                        passengers: 0,
+                        flights: vec![],
+                    });
+                stranding.passengers += left;
+                stranding.flights.push(flight.key());
+            }
+        }
+        strandings.into_values().collect()
+    }
+}
@@ -0,0 +1,55 @@
+use crate::flight::FlightStatus::{Delayed, Scheduled};
+use crate::schedule::schedule::{DisruptionType, Schedule};
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+/// PLANE_1 flies KRK-WAW at 100 and back at 200, then KRK-WAW again at 300; PLANE_2
+/// leaves KRK at 150 and PLANE_3 leaves WAW at 120.
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "KRK"), ("PLANE_3", "WAW")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 160, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 200, 260, Some("PLANE_1")),
+            ("FLIGHT_3", "KRK", "WAW", 300, 360, Some("PLANE_1")),
+            ("FLIGHT_4", "KRK", "WAW", 150, 210, Some("PLANE_2")),
+            ("FLIGHT_5", "WAW", "KRK", 120, 180, Some("PLANE_3")),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+fn status(schedule: &Schedule, flight: &str) -> crate::flight::FlightStatus {
+    schedule.flight(&key(flight)).unwrap().status.clone()
+}
+
+#[test]
+fn test_delay_airport_one_report_with_knock_on() {
+    let mut schedule = build();
+    schedule.delay_airport(id("KRK"), 90, Time(0), None);
+
+    assert_eq!(1, schedule.history.len());
+    let report = schedule.last_report().unwrap();
+    assert!(matches!(report.kind, DisruptionType::AirportDelay { .. }));
+    assert_eq!(Delayed { minutes: 90 }, status(&schedule, "FLIGHT_1"));
+    assert_eq!(Delayed { minutes: 90 }, status(&schedule, "FLIGHT_4"));
+    // FLIGHT_1 lands at 250, so FLIGHT_2 leaves WAW at 280
+    assert_eq!(Delayed { minutes: 80 }, status(&schedule, "FLIGHT_2"));
+    // FLIGHT_3 already moved to 370 by the rotation, then held to its own 390
+    assert_eq!(Delayed { minutes: 90 }, status(&schedule, "FLIGHT_3"));
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_5"));
+    assert_eq!(4, report.affected.len());
+    assert_eq!(90 + 90 + 80 + 90, report.delay_minutes);
+}
+
+#[test]
+fn test_delay_airport_window() {
+    let mut schedule = build();
+    schedule.delay_airport(id("KRK"), 30, Time(120), Some(Time(300)));
+
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_1"));
+    assert_eq!(Delayed { minutes: 30 }, status(&schedule, "FLIGHT_4"));
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_3"));
+}
@@ -0,0 +1,72 @@
+use crate::alert::{Alert, AlertRules};
+use crate::config::Config;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{key, scenario};
+
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW", "GDN"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 230, 330, Some("PLANE_1")),
+            ("FLIGHT_3", "GDN", "KRK", 400, 500, None),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_alerts_follow_schedule_health() {
+    let mut schedule = build();
+    let rules = AlertRules {
+        max_unscheduled: Some(0),
+        max_flight_delay: Some(180),
+        max_delay_minutes: Some(300),
+        ..AlertRules::default()
+    };
+    assert_eq!(
+        vec![Alert {
+            rule: "max_unscheduled",
+            message: "1 flights unscheduled (limit 0)".to_string(),
+        }],
+        rules.evaluate(&schedule)
+    );
+
+    schedule.apply_delay(key("FLIGHT_1"), 200).unwrap();
+    assert_eq!(
+        vec!["max_unscheduled", "max_flight_delay", "max_delay_minutes"],
+        rules
+            .evaluate(&schedule)
+            .iter()
+            .map(|a| a.rule)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        "FLIGHT_1/1 delayed by 200 min (limit 180)",
+        rules.evaluate(&schedule)[1].message
+    );
+    assert!(AlertRules::default().evaluate(&schedule).is_empty());
+}
+
+#[test]
+fn test_alert_rules_from_config() {
+    let config = Config::parse(
+        r#"
+        [alerts]
+        max_unscheduled = 10
+        max_flight_delay = 180
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        AlertRules {
+            max_unscheduled: Some(10),
+            max_flight_delay: Some(180),
+            ..AlertRules::default()
+        },
+        config.alerts
+    );
+    assert!(Config::parse("[alerts]\nmax_cancelled = 1").is_err());
+}
@@ -0,0 +1,70 @@
+use crate::flight::FlightStatus::{Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::anneal::AnnealConfig;
+use crate::schedule::schedule::{Schedule, Strategy};
+use crate::schedule::tests::utils::{id, scenario};
+use std::time::Duration;
+
+fn build() -> Schedule {
+    let mut schedule = scenario(
+        &["KRK", "WAW", "GDN"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, None),
+            ("FLIGHT_2", "KRK", "GDN", 150, 250, None),
+            ("FLIGHT_3", "GDN", "KRK", 300, 400, None),
+            ("FLIGHT_4", "KRK", "GDN", 450, 550, None),
+        ],
+    )
+    .build()
+    .unwrap();
+    schedule.assign();
+    schedule
+}
+
+fn config(seed: u64) -> AnnealConfig {
+    AnnealConfig {
+        seed,
+        time_limit: Duration::from_secs(60),
+        max_iterations: Some(5000),
+    }
+}
+
+#[test]
+fn test_anneal_escapes_greedy_dead_end() {
+    let mut schedule = build();
+    let greedy_cost = schedule
+        .cost
+        .evaluate(&schedule.compensation, &schedule.flights);
+    assert_eq!(Some(id("PLANE_1")), schedule.flights[0].aircraft_id);
+
+    schedule.recover(Strategy::Anneal(config(42)));
+
+    let anneal_cost = schedule
+        .cost
+        .evaluate(&schedule.compensation, &schedule.flights);
+    assert!(anneal_cost < greedy_cost);
+    assert_eq!(Unscheduled(Waiting), schedule.flights[0].status);
+    assert!(schedule.flights[1..].iter().all(|f| f.status == Scheduled));
+}
+
+#[test]
+fn test_anneal_is_reproducible_for_seed() {
+    let mut first = build();
+    first.recover(Strategy::Anneal(config(7)));
+    let mut second = build();
+    second.recover(Strategy::Anneal(config(7)));
+
+    assert_eq!(
+        first
+            .flights
+            .iter()
+            .map(|f| (f.aircraft_id.clone(), f.departure_time))
+            .collect::<Vec<_>>(),
+        second
+            .flights
+            .iter()
+            .map(|f| (f.aircraft_id.clone(), f.departure_time))
+            .collect::<Vec<_>>()
+    );
+}
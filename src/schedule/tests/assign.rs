@@ -1,7 +1,7 @@
 use crate::flight::FlightStatus::{Scheduled, Unscheduled};
 use crate::flight::UnscheduledReason::{AircraftMaintenance, BrokenChain, Waiting};
-use crate::schedule::schedule::Schedule;
-use crate::schedule::tests::utils::{add_aircraft, add_airport, add_flight, availability, id};
+use crate::schedule::schedule::{Objective, Schedule};
+use crate::schedule::tests::utils::{add_aircraft, add_airport, add_flight, availability, id, key};
 use crate::time::Time;
 use std::collections::HashMap;
 
@@ -353,7 +353,7 @@ fn test_recovery_after_disruption() {
 
     let mut schedule = Schedule::new(aircraft, airports, flights);
     schedule.assign();
-    schedule.apply_delay(id("FLIGHT_1"), 400);
+    schedule.apply_delay(key("FLIGHT_1"), 400).unwrap();
 
     assert_eq!(None, schedule.flights[0].aircraft_id);
     assert_eq!(Time(200) + 400, schedule.flights[0].departure_time);
@@ -377,3 +377,89 @@ fn test_recovery_after_disruption() {
     assert_eq!(Time(2000), schedule.flights[1].arrival_time);
     assert_eq!(Scheduled, schedule.flights[1].status);
 }
+
+#[test]
+fn test_assign_robust_objective_prefers_largest_buffer() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+    add_airport(&mut airports, "GDN", 30, vec![]);
+
+    add_aircraft(&mut aircraft, "PLANE_1", "WAW", vec![]);
+    add_aircraft(&mut aircraft, "PLANE_2", "KRK", vec![]);
+
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "WAW",
+        "KRK",
+        0,
+        60,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        "KRK",
+        "GDN",
+        100,
+        200,
+        None,
+        Unscheduled(Waiting),
+    );
+
+    let mut first_fit = Schedule::new(aircraft.clone(), airports.clone(), flights.clone());
+    first_fit.assign_with(Objective::FirstFit);
+    // PLANE_1 is ready at 90, leaving a 10 minute buffer
+    assert_eq!(Some(id("PLANE_1")), first_fit.flights[1].aircraft_id);
+
+    let mut robust = Schedule::new(aircraft, airports, flights);
+    robust.assign_with(Objective::Robust);
+    assert_eq!(Some(id("PLANE_2")), robust.flights[1].aircraft_id);
+}
+
+#[test]
+fn test_assign_balanced_objective_prefers_least_flown() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+    add_airport(&mut airports, "GDN", 30, vec![]);
+
+    add_aircraft(&mut aircraft, "PLANE_1", "KRK", vec![]);
+    add_aircraft(&mut aircraft, "PLANE_2", "KRK", vec![]);
+
+    for (flight_id, origin, destination, dep, arr) in [
+        ("FLIGHT_1", "KRK", "WAW", 100, 200),
+        ("FLIGHT_2", "WAW", "KRK", 300, 400),
+        ("FLIGHT_3", "KRK", "GDN", 500, 600),
+    ] {
+        add_flight(
+            &mut flights,
+            flight_id,
+            origin,
+            destination,
+            dep,
+            arr,
+            None,
+            Unscheduled(Waiting),
+        );
+    }
+
+    let mut first_fit = Schedule::new(aircraft.clone(), airports.clone(), flights.clone());
+    first_fit.assign_with(Objective::FirstFit);
+    assert_eq!(Some(id("PLANE_1")), first_fit.flights[2].aircraft_id);
+
+    // PLANE_1 is back at KRK with 200 block minutes, PLANE_2 has not flown
+    let mut balanced = Schedule::new(aircraft, airports, flights);
+    balanced.assign_with(Objective::Balanced);
+    assert_eq!(Some(id("PLANE_1")), balanced.flights[0].aircraft_id);
+    assert_eq!(Some(id("PLANE_1")), balanced.flights[1].aircraft_id);
+    assert_eq!(Some(id("PLANE_2")), balanced.flights[2].aircraft_id);
+}
@@ -0,0 +1,115 @@
+use crate::flight::FlightStatus::{Delayed, Unscheduled};
+use crate::flight::UnscheduledReason::MaxDelayExceeded;
+use crate::schedule::atfm::{AtfmTarget, DelayDistribution};
+use crate::schedule::schedule::{DisruptionType, Schedule};
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+/// Three departures from KRK at 100, 110 and 120 on separate aircraft; PLANE_1 flies
+/// FLIGHT_4 back from WAW at 200 after FLIGHT_1.
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "KRK"), ("PLANE_3", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 160, Some("PLANE_1")),
+            ("FLIGHT_2", "KRK", "WAW", 110, 170, Some("PLANE_2")),
+            ("FLIGHT_3", "KRK", "WAW", 120, 180, Some("PLANE_3")),
+            ("FLIGHT_4", "WAW", "KRK", 200, 260, Some("PLANE_1")),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+fn departure(schedule: &Schedule, flight: &str) -> Time {
+    schedule.flight(&key(flight)).unwrap().departure_time
+}
+
+#[test]
+fn test_atfm_fixed_one_report() {
+    let mut schedule = build();
+    schedule.apply_atfm(
+        AtfmTarget::Airport {
+            airport: id("KRK"),
+            from: Time(100),
+            to: Time(120),
+        },
+        DelayDistribution::Fixed(60),
+    );
+
+    assert_eq!(1, schedule.history.len());
+    let report = schedule.last_report().unwrap();
+    assert!(matches!(report.kind, DisruptionType::Atfm { .. }));
+    // FLIGHT_4 picks up FLIGHT_1's delay down the rotation; FLIGHT_3 is outside the window
+    assert_eq!(
+        vec![key("FLIGHT_1"), key("FLIGHT_4"), key("FLIGHT_2")],
+        report.affected
+    );
+    assert_eq!(60 + 50 + 60, report.delay_minutes);
+    assert_eq!(Time(120), departure(&schedule, "FLIGHT_3"));
+}
+
+#[test]
+fn test_atfm_rate() {
+    let mut schedule = build();
+    schedule.apply_atfm(
+        AtfmTarget::Airport {
+            airport: id("KRK"),
+            from: Time(0),
+            to: Time(200),
+        },
+        DelayDistribution::Rate(2),
+    );
+
+    // one departure every 30 min from 100
+    assert_eq!(Time(100), departure(&schedule, "FLIGHT_1"));
+    assert_eq!(Time(130), departure(&schedule, "FLIGHT_2"));
+    assert_eq!(Time(160), departure(&schedule, "FLIGHT_3"));
+    assert_eq!(
+        Delayed { minutes: 20 },
+        schedule.flight(&key("FLIGHT_2")).unwrap().status
+    );
+}
+
+#[test]
+fn test_atfm_listed_flights_uniform() {
+    let mut schedule = build();
+    let delays = DelayDistribution::Uniform {
+        min: 10,
+        max: 40,
+        seed: 7,
+    };
+    let mut other = schedule.clone();
+    let target = AtfmTarget::Flights(vec![key("FLIGHT_2"), key("FLIGHT_3")]);
+    schedule.apply_atfm(target.clone(), delays.clone());
+    other.apply_atfm(target, delays);
+
+    for flight in ["FLIGHT_2", "FLIGHT_3"] {
+        let pushed = (departure(&schedule, flight) - departure(&build(), flight)).0;
+        assert!((10..=40).contains(&pushed));
+        assert_eq!(departure(&other, flight), departure(&schedule, flight));
+    }
+    assert_eq!(Time(100), departure(&schedule, "FLIGHT_1"));
+}
+
+#[test]
+fn test_atfm_past_max_delay() {
+    let mut schedule = build();
+    schedule.max_delay = 30;
+    schedule.apply_atfm(
+        AtfmTarget::Flights(vec![key("FLIGHT_2")]),
+        DelayDistribution::Fixed(45),
+    );
+    let report = schedule.last_report().unwrap();
+
+    assert_eq!(
+        vec![(key("FLIGHT_2"), MaxDelayExceeded)],
+        report.unscheduled
+    );
+    assert!(report.affected.is_empty());
+    assert_eq!(
+        Unscheduled(MaxDelayExceeded),
+        schedule.flight(&key("FLIGHT_2")).unwrap().status
+    );
+}
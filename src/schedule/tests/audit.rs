@@ -0,0 +1,73 @@
+use crate::schedule::audit::InvariantViolation;
+use crate::schedule::feasibility::ChainViolation;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+fn schedule() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 160, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 200, 260, Some("PLANE_1")),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_audit_of_intact_schedule() {
+    let mut schedule = schedule();
+    assert!(schedule.audit().is_empty());
+
+    schedule.apply_delay(key("FLIGHT_1"), 20).unwrap();
+    assert!(schedule.audit().is_empty());
+}
+
+#[test]
+fn test_audit_after_manual_mutation() {
+    let mut schedule = schedule();
+    schedule.flights[1].departure_time = Time(170);
+    schedule.flights[0].origin_id = id("WAW");
+
+    assert_eq!(
+        vec![
+            InvariantViolation::Chain(ChainViolation::Continuity {
+                flight: key("FLIGHT_1"),
+                expected: id("KRK"),
+                actual: id("WAW"),
+            }),
+            InvariantViolation::Chain(ChainViolation::TurnTime {
+                flight: key("FLIGHT_2"),
+                required: 30,
+                available: 10,
+            }),
+        ],
+        schedule.audit()
+    );
+}
+
+#[test]
+fn test_audit_reports_stale_index() {
+    let mut schedule = schedule();
+    let mut flight = schedule.flights[1].clone();
+    flight.id = id("FLIGHT_3");
+    flight.departure_time = Time(300);
+    flight.arrival_time = Time(360);
+    flight.origin_id = id("KRK");
+    flight.destination_id = id("WAW");
+    schedule.flights.insert(0, flight);
+
+    let violations = schedule.audit();
+    assert_eq!(3, violations.len());
+    assert!(
+        violations
+            .iter()
+            .all(|v| matches!(v, InvariantViolation::StaleIndex(_)))
+    );
+
+    schedule.reindex();
+    assert!(schedule.audit().is_empty());
+}
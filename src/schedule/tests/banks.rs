@@ -0,0 +1,106 @@
+use crate::airport::Airport;
+use crate::airport::{Bank, OperatingHours};
+use crate::flight::FlightStatus::Unscheduled;
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{key, scenario};
+
+/// WAW is a hub with one bank: arrivals 08:00-09:00 feed departures 09:30-10:30.
+/// FLIGHT_1 (KRK) and FLIGHT_2 (GDN) arrive in the bank, FLIGHT_3 (to KRK) and
+/// FLIGHT_4 (to GDN) leave in it; FLIGHT_5 leaves after the bank.
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "GDN"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "GDN"), ("PLANE_3", "WAW")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 430, 490, Some("PLANE_1")),
+            ("FLIGHT_2", "GDN", "WAW", 450, 520, Some("PLANE_2")),
+            ("FLIGHT_3", "WAW", "KRK", 580, 640, Some("PLANE_1")),
+            ("FLIGHT_4", "WAW", "GDN", 600, 670, Some("PLANE_2")),
+            ("FLIGHT_5", "WAW", "KRK", 720, 780, Some("PLANE_3")),
+        ],
+    )
+    .airport(Airport {
+        banks: vec![Bank {
+            arrivals: OperatingHours {
+                open: 480,
+                close: 540,
+            },
+            departures: OperatingHours {
+                open: 570,
+                close: 630,
+            },
+            min_connection: 45,
+        }],
+        ..Airport::new("WAW", 30)
+    })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_bank_connections_planned() {
+    let schedule = build();
+    let connections = schedule.bank_connections();
+
+    assert_eq!(
+        vec![
+            (key("FLIGHT_1"), key("FLIGHT_3")),
+            (key("FLIGHT_1"), key("FLIGHT_4")),
+            (key("FLIGHT_2"), key("FLIGHT_3")),
+            (key("FLIGHT_2"), key("FLIGHT_4")),
+        ],
+        connections
+            .iter()
+            .map(|c| (c.arrival.clone(), c.departure.clone()))
+            .collect::<Vec<_>>()
+    );
+    assert!(connections.iter().all(|c| c.protected));
+    assert_eq!(100.0, schedule.bank_integrity()[0].pct());
+}
+
+#[test]
+fn test_delay_breaks_bank_connections() {
+    let mut schedule = build();
+    schedule.apply_delay(key("FLIGHT_2"), 40).unwrap();
+    let integrity = schedule.bank_integrity();
+
+    // FLIGHT_2 lands at 560, short of 45 min to FLIGHT_3 at 580 and FLIGHT_4 at 600
+    assert_eq!(4, integrity[0].planned);
+    assert_eq!(
+        vec![
+            (key("FLIGHT_2"), key("FLIGHT_3")),
+            (key("FLIGHT_2"), key("FLIGHT_4"))
+        ],
+        schedule
+            .bank_connections()
+            .into_iter()
+            .filter(|c| !c.protected)
+            .map(|c| (c.arrival, c.departure))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(2, integrity[0].protected);
+}
+
+#[test]
+fn test_cancelled_bank_flight_costs_its_connections() {
+    let mut schedule = build();
+    let cost_before = schedule.total_cost();
+    let index = schedule
+        .flights
+        .iter()
+        .position(|f| f.key() == key("FLIGHT_1"))
+        .unwrap();
+    schedule.flights[index].status = Unscheduled(Waiting);
+
+    let broken = 2 * schedule.cost.broken_connection;
+    assert_eq!(broken, schedule.broken_connection_cost());
+    assert_eq!(
+        cost_before + schedule.cost.cancellation + broken,
+        schedule.total_cost()
+    );
+    assert_eq!(
+        schedule.cost.cancellation + broken,
+        schedule.cancellation_costs()[index]
+    );
+}
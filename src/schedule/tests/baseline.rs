@@ -0,0 +1,57 @@
+use crate::flight::FlightStatus::Scheduled;
+use crate::metrics::compare_baseline;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+/// PLANE_1 flies FLIGHT_1 KRK-WAW and FLIGHT_2 back; FLIGHT_3 KRK-GDN waits for PLANE_2.
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW", "GDN"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 160, None),
+            ("FLIGHT_2", "WAW", "KRK", 200, 260, None),
+            ("FLIGHT_3", "KRK", "GDN", 300, 360, None),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_baseline_captured_by_first_assign() {
+    let mut schedule = build();
+    assert!(schedule.baseline.is_none());
+
+    schedule.assign();
+    let baseline = schedule.baseline.as_ref().unwrap();
+    assert!(baseline.iter().all(|f| f.status == Scheduled));
+
+    schedule.apply_delay(key("FLIGHT_1"), 30).unwrap();
+    schedule.assign();
+    let baseline = schedule.baseline.as_ref().unwrap();
+    assert_eq!(Time(100), baseline[0].departure_time);
+}
+
+#[test]
+fn test_baseline_drift() {
+    let mut schedule = build();
+    schedule.assign();
+    schedule.apply_delay(key("FLIGHT_1"), 70).unwrap();
+    schedule
+        .apply_curfew(id("GDN"), Time(350), Time(400))
+        .unwrap();
+
+    let drift = compare_baseline(schedule.baseline.as_ref().unwrap(), &schedule.flights);
+    assert_eq!(2, drift.flights_moved);
+    assert_eq!(130, drift.drift_minutes);
+    assert_eq!(65.0, drift.average_drift());
+    assert_eq!(0, drift.aircraft_changed);
+    assert_eq!(vec![key("FLIGHT_3")], drift.flights_lost);
+
+    schedule.capture_baseline();
+    let drift = compare_baseline(schedule.baseline.as_ref().unwrap(), &schedule.flights);
+    assert_eq!(0, drift.flights_moved);
+    assert!(drift.flights_lost.is_empty());
+}
@@ -0,0 +1,64 @@
+use crate::airport::Airport;
+use crate::airport::{BlockBand, OperatingHours};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{key, scenario};
+use crate::time::Time;
+
+/// Departures from KRK between 17:00 and 21:00 take 15 minutes longer. PLANE_1 flies
+/// FLIGHT_1 (WAW-KRK, 900-960) and FLIGHT_2 (KRK-WAW, 990-1050, 60 min block).
+fn build() -> Schedule {
+    scenario(
+        &["WAW"],
+        &[("PLANE_1", "WAW")],
+        &[
+            ("FLIGHT_1", "WAW", "KRK", 900, 960, Some("PLANE_1")),
+            ("FLIGHT_2", "KRK", "WAW", 990, 1050, Some("PLANE_1")),
+        ],
+    )
+    .airport(Airport {
+        block_bands: vec![BlockBand {
+            hours: OperatingHours {
+                open: 1020,
+                close: 1260,
+            },
+            extra: 15,
+        }],
+        ..Airport::new("KRK", 30)
+    })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_propagated_delay_into_slower_band() {
+    let mut schedule = build();
+    schedule.apply_delay(key("FLIGHT_1"), 60).unwrap();
+    let flight = schedule.flight(&key("FLIGHT_2")).unwrap();
+
+    // ready at 1050, inside the evening band
+    assert_eq!(Time(1050), flight.departure_time);
+    assert_eq!(Time(1125), flight.arrival_time);
+}
+
+#[test]
+fn test_delay_out_of_slower_band() {
+    let mut schedule = build();
+    schedule.apply_delay(key("FLIGHT_2"), 60).unwrap();
+    schedule.apply_delay(key("FLIGHT_2"), 240).unwrap();
+    let flight = schedule.flight(&key("FLIGHT_2")).unwrap();
+
+    // 1050 falls in the band with 75 min block, 1290 is past it again
+    assert_eq!(Time(1290), flight.departure_time);
+    assert_eq!(Time(1350), flight.arrival_time);
+}
+
+#[test]
+fn test_delay_within_band_keeps_block() {
+    let mut schedule = build();
+    schedule.apply_delay(key("FLIGHT_2"), 60).unwrap();
+    schedule.apply_delay(key("FLIGHT_2"), 30).unwrap();
+    let flight = schedule.flight(&key("FLIGHT_2")).unwrap();
+
+    assert_eq!(Time(1080), flight.departure_time);
+    assert_eq!(Time(1155), flight.arrival_time);
+}
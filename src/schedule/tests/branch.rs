@@ -0,0 +1,93 @@
+use crate::branch::{BranchError, Branches, MAIN};
+use crate::flight::FlightStatus::{Delayed, Scheduled};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{key, scenario};
+
+/// PLANE_1 flies FLIGHT_1 (KRK-WAW, 100-200) and FLIGHT_2 (WAW-KRK, 250-350).
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 250, 350, Some("PLANE_1")),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_branches_keep_their_own_history() {
+    let mut schedule = build();
+    let mut branches = Branches::default();
+    schedule.apply_delay(key("FLIGHT_1"), 10).unwrap();
+
+    branches.create("long", &schedule).unwrap();
+    schedule.apply_delay(key("FLIGHT_2"), 5).unwrap();
+    branches.switch("long", &mut schedule).unwrap();
+
+    assert_eq!("long", branches.current());
+    assert_eq!(1, schedule.history.len());
+    assert_eq!(Scheduled, schedule.flight(&key("FLIGHT_2")).unwrap().status);
+
+    schedule.apply_delay(key("FLIGHT_2"), 60).unwrap();
+    branches.switch(MAIN, &mut schedule).unwrap();
+
+    assert_eq!(2, schedule.history.len());
+    assert_eq!(
+        Delayed { minutes: 5 },
+        schedule.flight(&key("FLIGHT_2")).unwrap().status
+    );
+
+    branches.switch("long", &mut schedule).unwrap();
+    assert_eq!(
+        Delayed { minutes: 60 },
+        schedule.flight(&key("FLIGHT_2")).unwrap().status
+    );
+}
+
+#[test]
+fn test_branch_list() {
+    let mut schedule = build();
+    let mut branches = Branches::default();
+    schedule.apply_delay(key("FLIGHT_1"), 10).unwrap();
+    branches.create("alt", &schedule).unwrap();
+    schedule.apply_delay(key("FLIGHT_2"), 5).unwrap();
+
+    let list = branches.list(&schedule);
+
+    assert_eq!(
+        vec![("alt", false, Some(MAIN), 1, 1), (MAIN, true, None, 0, 2)],
+        list.iter()
+            .map(|b| (
+                b.name.as_str(),
+                b.current,
+                b.parent.as_deref(),
+                b.forked_at,
+                b.disruptions
+            ))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_branch_errors() {
+    let mut schedule = build();
+    let mut branches = Branches::default();
+    branches.create("alt", &schedule).unwrap();
+
+    assert_eq!(
+        Err(BranchError::Exists("alt".to_string())),
+        branches.create("alt", &schedule)
+    );
+    assert_eq!(
+        Err(BranchError::Unknown("nope".to_string())),
+        branches.switch("nope", &mut schedule)
+    );
+    assert_eq!(
+        Err(BranchError::AlreadyOn(MAIN.to_string())),
+        branches.switch(MAIN, &mut schedule)
+    );
+    assert_eq!(MAIN, branches.current());
+}
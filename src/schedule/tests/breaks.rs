@@ -0,0 +1,69 @@
+use crate::flight::UnscheduledReason::{AirportCurfew, BrokenChain};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+/// PLANE_1 lands FLIGHT_1 (10 pax) at WAW at 160 and flies FLIGHT_2 (100 pax) back,
+/// then FLIGHT_3 out again; PLANE_2 lands FLIGHT_4 (50 pax) at WAW at 155, departing earlier at
+/// 60, with nothing after it.
+fn build() -> Schedule {
+    let mut schedule = scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 160, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 200, 260, Some("PLANE_1")),
+            ("FLIGHT_3", "KRK", "WAW", 300, 360, Some("PLANE_1")),
+            ("FLIGHT_4", "KRK", "WAW", 60, 155, Some("PLANE_2")),
+        ],
+    )
+    .build()
+    .unwrap();
+    for (flight, passengers) in schedule.flights.iter_mut().zip([10, 100, 20, 50]) {
+        flight.passengers = passengers;
+    }
+    schedule
+}
+
+#[test]
+fn test_first_break_is_earliest_cause() {
+    let mut schedule = build();
+    schedule
+        .apply_curfew(id("WAW"), Time(150), Time(170))
+        .unwrap();
+    let report = schedule.last_report().unwrap();
+
+    assert_eq!(3, report.unscheduled.len());
+    assert_eq!(Some((key("FLIGHT_4"), AirportCurfew)), report.first_break);
+}
+
+#[test]
+fn test_worst_break_counts_its_chain() {
+    let mut schedule = build();
+    let preview = schedule.preview_curfew(id("WAW"), Time(150), Time(170));
+    schedule
+        .apply_curfew(id("WAW"), Time(150), Time(170))
+        .unwrap();
+    let report = schedule.last_report().unwrap();
+
+    assert_eq!(Some((key("FLIGHT_1"), AirportCurfew)), report.worst_break);
+    assert_eq!(report.worst_break, preview.worst_break);
+    assert!(report.unscheduled.contains(&(key("FLIGHT_2"), BrokenChain)));
+}
+
+#[test]
+fn test_worst_break_by_passengers_alone() {
+    let mut schedule = build();
+    schedule.severity.unscheduled = 0;
+    schedule
+        .flights
+        .iter_mut()
+        .filter(|f| f.key() == key("FLIGHT_4"))
+        .for_each(|f| f.passengers = 500);
+    schedule
+        .apply_curfew(id("WAW"), Time(150), Time(170))
+        .unwrap();
+
+    let report = schedule.last_report().unwrap();
+    assert_eq!(Some((key("FLIGHT_4"), AirportCurfew)), report.worst_break);
+}
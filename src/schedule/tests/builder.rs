@@ -0,0 +1,95 @@
+use crate::aircraft::Aircraft;
+use crate::airport::Airport;
+use crate::flight::Flight;
+use crate::flight::FlightStatus::{Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::builder::ScheduleBuilder;
+use crate::schedule::constraint::Rule;
+use crate::schedule::tests::utils::{curfew, id, key};
+use crate::schedule::validate::load_warnings;
+use crate::time::Time;
+
+fn airports() -> ScheduleBuilder {
+    ScheduleBuilder::new()
+        .airport(Airport::new("KRK", 30))
+        .airport(Airport {
+            disruptions: vec![curfew(0, 60)],
+            ..Airport::new("WAW", 45)
+        })
+}
+
+#[test]
+fn test_build_schedule() {
+    let schedule = airports()
+        .aircraft(Aircraft::new("PLANE_1", "KRK"))
+        .flight(Flight::new("FLIGHT_1", "KRK", "WAW", Time(100), Time(160)).assigned("PLANE_1"))
+        .flight(Flight::new(
+            "FLIGHT_2",
+            "WAW",
+            "KRK",
+            Time(1600),
+            Time(1660),
+        ))
+        .rule(Rule::AvoidAirport {
+            aircraft_id: id("PLANE_1"),
+            airport_id: id("KRK"),
+        })
+        .build()
+        .unwrap();
+
+    assert_eq!(2, schedule.airports.len());
+    assert_eq!(45, schedule.airports[&id("WAW")].mtt);
+    assert_eq!(1, schedule.airports[&id("WAW")].disruptions.len());
+    assert_eq!(
+        id("KRK"),
+        schedule.aircraft[&id("PLANE_1")].initial_location_id
+    );
+    assert_eq!(1, schedule.constraints.len());
+
+    let flight_1 = schedule.flight(&key("FLIGHT_1")).unwrap();
+    assert_eq!(Scheduled, flight_1.status);
+    assert_eq!(Some(id("PLANE_1")), flight_1.aircraft_id);
+    let flight_2 = schedule.flight(&crate::flight::FlightKey::new("FLIGHT_2", 2));
+    assert_eq!(Unscheduled(Waiting), flight_2.unwrap().status);
+}
+
+#[test]
+fn test_build_reports_errors() {
+    let errors = airports()
+        .airport(Airport::new("KRK", 20))
+        .aircraft(Aircraft::new("PLANE_1", "GDN"))
+        .flight(Flight::new("FLIGHT_1", "KRK", "WAW", Time(160), Time(100)).assigned("PLANE_2"))
+        .build()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        vec![
+            "duplicate airport id KRK",
+            "aircraft PLANE_1 refers to unknown airport GDN",
+            "flight FLIGHT_1 ends at DAY1 01:40 before it starts at DAY1 02:40",
+            "flight FLIGHT_1 refers to unknown aircraft PLANE_2",
+        ],
+        errors
+            .iter()
+            .map(|issue| issue.message.as_str())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_build_leaves_infeasible_rotation_to_warnings() {
+    let schedule = airports()
+        .aircraft(Aircraft::new("PLANE_1", "WAW"))
+        .flight(Flight::new("FLIGHT_1", "KRK", "WAW", Time(100), Time(160)).assigned("PLANE_1"))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        vec!["flight FLIGHT_1/1 departs KRK but its aircraft is at WAW"],
+        load_warnings(&schedule)
+            .iter()
+            .map(|issue| issue.message.as_str())
+            .collect::<Vec<_>>()
+    );
+}
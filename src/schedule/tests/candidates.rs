@@ -0,0 +1,85 @@
+use crate::aircraft::Aircraft;
+use crate::airport::Airport;
+use crate::flight::UnscheduledReason::AirportCurfew;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{availability, curfew, id, key, scenario};
+
+/// FLIGHT_2 (KRK-WAW, 300-400) is unassigned; PLANE_1 is free at KRK, PLANE_2 is busy
+/// flying FLIGHT_1 until 350, PLANE_3 is in maintenance, PLANE_4 starts at GDN and
+/// PLANE_5 is a B744 that WAW does not accept.
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "GDN"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "GDN"), ("PLANE_4", "GDN")],
+        &[
+            ("FLIGHT_1", "GDN", "KRK", 200, 320, Some("PLANE_2")),
+            ("FLIGHT_2", "KRK", "WAW", 300, 400, None),
+        ],
+    )
+    .airport(Airport {
+        restricted_types: vec![id("B744")],
+        ..Airport::new("WAW", 30)
+    })
+    .aircraft(Aircraft {
+        disruptions: vec![availability(250, 320, None)],
+        ..Aircraft::new("PLANE_3", "KRK")
+    })
+    .aircraft(Aircraft {
+        fleet_type: Some(id("B744")),
+        ..Aircraft::new("PLANE_5", "KRK")
+    })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_candidates_verdicts() {
+    let schedule = build();
+    let candidates = schedule.candidates(&key("FLIGHT_2"));
+
+    assert_eq!(
+        vec!["PLANE_1", "PLANE_2", "PLANE_3", "PLANE_4", "PLANE_5"],
+        candidates
+            .iter()
+            .map(|c| c.aircraft_id.as_ref())
+            .collect::<Vec<_>>()
+    );
+    assert!(candidates[0].is_feasible());
+    assert_eq!(None, candidates[1].wrong_location);
+    assert_eq!(Some(key("FLIGHT_1")), candidates[1].busy_with);
+    assert!(candidates[2].maintenance);
+    assert_eq!(Some(id("GDN")), candidates[3].wrong_location);
+    assert_eq!(Some(id("B744")), candidates[4].restricted_type);
+    assert!(candidates[1..].iter().all(|c| !c.is_feasible()));
+}
+
+#[test]
+fn test_candidates_ignore_current_assignment() {
+    let schedule = build();
+    let candidates = schedule.candidates(&key("FLIGHT_1"));
+
+    assert!(candidates[1].is_feasible());
+    assert_eq!(Some(id("KRK")), candidates[0].wrong_location);
+}
+
+#[test]
+fn test_candidates_report_curfew_for_every_aircraft() {
+    let mut schedule = build();
+    schedule
+        .airports
+        .get_mut(&id("WAW"))
+        .unwrap()
+        .disruptions
+        .push(curfew(380, 420));
+    let candidates = schedule.candidates(&key("FLIGHT_2"));
+
+    assert!(candidates.iter().all(|c| c.curfew == Some(AirportCurfew)));
+    assert!(!candidates[0].is_feasible());
+}
+
+#[test]
+fn test_candidates_unknown_flight() {
+    let schedule = build();
+
+    assert!(schedule.candidates(&key("FLIGHT_9")).is_empty());
+}
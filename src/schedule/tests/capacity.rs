@@ -0,0 +1,286 @@
+use crate::airport::Airport;
+use crate::airport::{CapacityReduction, Runway};
+use crate::error::IrropsError;
+use crate::flight::FlightStatus::{self, Delayed, Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::{MovementCapacity, Waiting};
+use crate::schedule::anneal::AnnealConfig;
+use crate::schedule::schedule::{DisruptionType, Schedule, Strategy};
+use crate::schedule::tests::utils::{add_aircraft, add_flight, id, key, scenario};
+use crate::time::Time;
+
+/// Four departures from GDN at 600, 605, 610 and 640 on their own aircraft, and PLANE_5
+/// landing there at 620 from WAW. GDN slots four movements an hour.
+fn build() -> Schedule {
+    scenario(
+        &["WAW"],
+        &[
+            ("PLANE_1", "GDN"),
+            ("PLANE_2", "GDN"),
+            ("PLANE_3", "GDN"),
+            ("PLANE_4", "GDN"),
+            ("PLANE_5", "WAW"),
+        ],
+        &[
+            ("FLIGHT_1", "GDN", "WAW", 600, 660, Some("PLANE_1")),
+            ("FLIGHT_2", "GDN", "WAW", 605, 665, Some("PLANE_2")),
+            ("FLIGHT_3", "GDN", "WAW", 610, 670, Some("PLANE_3")),
+            ("FLIGHT_4", "GDN", "WAW", 640, 700, Some("PLANE_4")),
+            ("FLIGHT_5", "WAW", "GDN", 560, 620, Some("PLANE_5")),
+        ],
+    )
+    .airport(Airport {
+        slots_per_hour: Some(4),
+        ..Airport::new("GDN", 30)
+    })
+    .build()
+    .unwrap()
+}
+
+fn status(schedule: &Schedule, flight: &str) -> FlightStatus {
+    schedule.flight(&key(flight)).unwrap().status.clone()
+}
+
+#[test]
+fn test_capacity_reduction_meters_movements() {
+    let mut schedule = build();
+    // half of four an hour leaves one movement every 30 minutes
+    let report = schedule
+        .apply_capacity_reduction(id("GDN"), Time(600), Time(700), 50)
+        .unwrap();
+    assert!(matches!(
+        report.kind,
+        DisruptionType::CapacityReduction { percent: 50, .. }
+    ));
+    assert!(report.unscheduled.is_empty());
+    assert_eq!(25 + 50 + 70 + 80, report.delay_minutes);
+
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_1"));
+    assert_eq!(Delayed { minutes: 25 }, status(&schedule, "FLIGHT_2"));
+    assert_eq!(Delayed { minutes: 50 }, status(&schedule, "FLIGHT_3"));
+    // the arrival gets the 690 slot and is held at WAW to land then
+    assert_eq!(Delayed { minutes: 70 }, status(&schedule, "FLIGHT_5"));
+    assert_eq!(
+        Time(690),
+        schedule.flight(&key("FLIGHT_5")).unwrap().arrival_time
+    );
+    // the queue runs past the end of the window
+    assert_eq!(Delayed { minutes: 80 }, status(&schedule, "FLIGHT_4"));
+    assert_eq!(1, schedule.history.len());
+    assert_eq!(
+        vec![CapacityReduction {
+            from: Time(600),
+            to: Time(700),
+            rate: 2,
+        }],
+        schedule.airports["GDN"].reductions
+    );
+}
+
+/// `build` with GDN halved over 600-700 and two more aircraft there for FLIGHT_6 at 615,
+/// between the metered 600 and 630 take-offs, and FLIGHT_7 at 705, past the window.
+fn reduced() -> Schedule {
+    let mut schedule = build();
+    schedule
+        .apply_capacity_reduction(id("GDN"), Time(600), Time(700), 50)
+        .unwrap();
+    add_aircraft(&mut schedule.aircraft, "PLANE_6", "GDN", vec![]);
+    add_aircraft(&mut schedule.aircraft, "PLANE_7", "GDN", vec![]);
+    for (flight, dep) in [("FLIGHT_6", 615), ("FLIGHT_7", 705)] {
+        add_flight(
+            &mut schedule.flights,
+            flight,
+            "GDN",
+            "WAW",
+            dep,
+            dep + 60,
+            None,
+            Unscheduled(Waiting),
+        );
+    }
+    schedule.reindex();
+    schedule
+}
+
+fn assert_metered(schedule: &Schedule) {
+    for f in schedule
+        .flights
+        .iter()
+        .filter(|f| !f.status.is_unscheduled())
+    {
+        assert!(
+            !schedule.is_metered_out(f, f.departure_time, f.arrival_time),
+            "{} is metered out",
+            f.id
+        );
+    }
+}
+
+#[test]
+fn test_assign_keeps_to_capacity_reduction() {
+    let mut schedule = reduced();
+    schedule.assign();
+
+    assert_eq!(Unscheduled(MovementCapacity), status(&schedule, "FLIGHT_6"));
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_7"));
+}
+
+#[test]
+fn test_flow_keeps_to_capacity_reduction() {
+    let mut schedule = reduced();
+    schedule.recover(Strategy::Flow);
+
+    assert_eq!(Unscheduled(MovementCapacity), status(&schedule, "FLIGHT_6"));
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_7"));
+    assert_metered(&schedule);
+}
+
+#[test]
+fn test_anneal_keeps_to_capacity_reduction() {
+    let mut schedule = reduced();
+    schedule.recover(Strategy::Anneal(AnnealConfig {
+        max_iterations: Some(5_000),
+        ..Default::default()
+    }));
+
+    assert_ne!(Scheduled, status(&schedule, "FLIGHT_6"));
+    assert_metered(&schedule);
+}
+
+#[test]
+fn test_delay_propagation_keeps_to_capacity_reduction() {
+    let mut schedule = build();
+    schedule.airports.get_mut("GDN").unwrap().reductions = vec![CapacityReduction {
+        from: Time(700),
+        to: Time(900),
+        rate: 2,
+    }];
+    for (flight, plane, dep, arr) in [
+        ("FLIGHT_6", "PLANE_1", 700, 760),
+        ("FLIGHT_7", "PLANE_2", 720, 800),
+    ] {
+        add_flight(
+            &mut schedule.flights,
+            flight,
+            "WAW",
+            "GDN",
+            dep,
+            arr,
+            Some(plane),
+            Scheduled,
+        );
+    }
+    schedule.reindex();
+
+    // FLIGHT_6 would now land at 810, ten minutes from FLIGHT_7 at 800
+    let report = schedule.apply_delay(key("FLIGHT_1"), 60).unwrap();
+    assert_eq!(
+        vec![(key("FLIGHT_6"), MovementCapacity)],
+        report.unscheduled
+    );
+    assert_eq!(Delayed { minutes: 60 }, status(&schedule, "FLIGHT_1"));
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_7"));
+}
+
+#[test]
+fn test_capacity_reduction_rate_from_busiest_hour() {
+    let mut schedule = build();
+    schedule.airports.get_mut("GDN").unwrap().slots_per_hour = None;
+    // five movements in GDN's busiest hour; a quarter off leaves three, one every 20
+    // minutes, applied only to departures before 620
+    schedule
+        .apply_capacity_reduction(id("GDN"), Time(600), Time(620), 25)
+        .unwrap();
+
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_1"));
+    assert_eq!(Delayed { minutes: 15 }, status(&schedule, "FLIGHT_2"));
+    assert_eq!(Delayed { minutes: 30 }, status(&schedule, "FLIGHT_3"));
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_4"));
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_5"));
+}
+
+#[test]
+fn test_capacity_reduction_rejects() {
+    let mut schedule = build();
+    assert_eq!(
+        Some(IrropsError::UnknownAirport(id("KRK"))),
+        schedule
+            .apply_capacity_reduction(id("KRK"), Time(600), Time(700), 50)
+            .err()
+    );
+    for percent in [0, 100] {
+        assert_eq!(
+            Some(IrropsError::InvalidReduction(percent)),
+            schedule
+                .apply_capacity_reduction(id("GDN"), Time(600), Time(700), percent)
+                .err()
+        );
+    }
+    for (from, to) in [(600, 600), (700, 600)] {
+        assert_eq!(
+            Some(IrropsError::EmptyReduction(id("GDN"))),
+            schedule
+                .apply_capacity_reduction(id("GDN"), Time(from), Time(to), 50)
+                .err()
+        );
+    }
+    assert!(schedule.history.is_empty());
+    assert!(schedule.airports["GDN"].reductions.is_empty());
+}
+
+#[test]
+fn test_close_runway_leaves_the_others() {
+    let mut schedule = build();
+    schedule.airports.get_mut("GDN").unwrap().runways = vec![
+        Runway {
+            id: id("11/29"),
+            movements_per_hour: 3,
+        },
+        Runway {
+            id: id("15/33"),
+            movements_per_hour: 1,
+        },
+    ];
+    // 15/33 alone takes one movement an hour
+    let report = schedule
+        .close_runway(id("GDN"), id("11/29"), Time(600), Time(606))
+        .unwrap();
+    let DisruptionType::CapacityReduction {
+        runway, percent, ..
+    } = &report.kind
+    else {
+        panic!("expected a capacity reduction");
+    };
+    assert_eq!((Some(id("11/29")), 75), (runway.clone(), *percent));
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_1"));
+    assert_eq!(Delayed { minutes: 55 }, status(&schedule, "FLIGHT_2"));
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_3"));
+    assert!(schedule.airports["GDN"].disruptions.is_empty());
+
+    assert_eq!(
+        Some(IrropsError::UnknownRunway(id("GDN"), id("07/25"))),
+        schedule
+            .close_runway(id("GDN"), id("07/25"), Time(600), Time(606))
+            .err()
+    );
+    assert_eq!(
+        Some(IrropsError::EmptyReduction(id("GDN"))),
+        schedule
+            .close_runway(id("GDN"), id("11/29"), Time(606), Time(600))
+            .err()
+    );
+}
+
+#[test]
+fn test_close_only_runway_closes_airport() {
+    let mut schedule = build();
+    schedule.airports.get_mut("GDN").unwrap().runways = vec![Runway {
+        id: id("11/29"),
+        movements_per_hour: 20,
+    }];
+    let report = schedule
+        .close_runway(id("GDN"), id("11/29"), Time(590), Time(606))
+        .unwrap();
+    assert!(matches!(report.kind, DisruptionType::Curfew { .. }));
+    assert_eq!(1, schedule.airports["GDN"].disruptions.len());
+    assert!(status(&schedule, "FLIGHT_1").is_unscheduled());
+}
@@ -0,0 +1,83 @@
+use crate::flight::FlightStatus::{Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::{AirportCurfew, BrokenChain, MaxDelayExceeded};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+/// PLANE_1 flies KRK-WAW (FLIGHT_1), then the WAW-GDN-WAW loop (FLIGHT_2, FLIGHT_3) and
+/// WAW-KRK home (FLIGHT_4).
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW", "GDN"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "GDN", 300, 400, Some("PLANE_1")),
+            ("FLIGHT_3", "GDN", "WAW", 500, 600, Some("PLANE_1")),
+            ("FLIGHT_4", "WAW", "KRK", 700, 800, Some("PLANE_1")),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_curfew_skips_broken_loop() {
+    let mut schedule = build();
+    schedule
+        .apply_curfew(id("GDN"), Time(350), Time(450))
+        .unwrap();
+
+    assert_eq!(
+        vec![
+            (key("FLIGHT_2"), AirportCurfew),
+            (key("FLIGHT_3"), BrokenChain)
+        ],
+        schedule.last_report().unwrap().unscheduled
+    );
+    assert_eq!(Scheduled, schedule.flight(&key("FLIGHT_4")).unwrap().status);
+    assert_eq!(
+        Some(id("PLANE_1")),
+        schedule.flight(&key("FLIGHT_4")).unwrap().aircraft_id
+    );
+}
+
+#[test]
+fn test_delay_skips_broken_loop() {
+    let mut schedule = build();
+    schedule.max_delay = 50;
+    schedule.apply_delay(key("FLIGHT_2"), 100).unwrap();
+
+    assert_eq!(
+        vec![
+            (key("FLIGHT_2"), MaxDelayExceeded),
+            (key("FLIGHT_3"), BrokenChain)
+        ],
+        schedule.last_report().unwrap().unscheduled
+    );
+    let flight_4 = schedule.flight(&key("FLIGHT_4")).unwrap();
+    assert_eq!(Scheduled, flight_4.status);
+    assert_eq!(Time(700), flight_4.departure_time);
+}
+
+#[test]
+fn test_chain_stays_broken_away_from_the_aircraft() {
+    let mut schedule = build();
+    schedule.max_delay = 50;
+    schedule.apply_delay(key("FLIGHT_1"), 100).unwrap();
+
+    // the aircraft never leaves KRK, and nothing else departs from there
+    assert_eq!(
+        vec![
+            Unscheduled(MaxDelayExceeded),
+            Unscheduled(BrokenChain),
+            Unscheduled(BrokenChain),
+            Unscheduled(BrokenChain)
+        ],
+        schedule
+            .flights
+            .iter()
+            .map(|f| f.status.clone())
+            .collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,81 @@
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::{AirportCurfew, BrokenChain};
+use crate::schedule::schedule::{FlightChange, FlightState, Schedule};
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+/// PLANE_1 flies KRK-WAW at 100-160 and back at 200-260.
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 160, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 200, 260, Some("PLANE_1")),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_changes_record_propagated_delay() {
+    let mut schedule = build();
+    schedule.apply_delay(key("FLIGHT_1"), 60).unwrap();
+
+    assert_eq!(
+        vec![
+            FlightChange {
+                flight: key("FLIGHT_1"),
+                before: FlightState {
+                    departure_time: Time(100),
+                    arrival_time: Time(160),
+                    status: Scheduled,
+                    aircraft_id: Some(id("PLANE_1")),
+                },
+                after: FlightState {
+                    departure_time: Time(160),
+                    arrival_time: Time(220),
+                    status: Delayed { minutes: 60 },
+                    aircraft_id: Some(id("PLANE_1")),
+                },
+            },
+            FlightChange {
+                flight: key("FLIGHT_2"),
+                before: FlightState {
+                    departure_time: Time(200),
+                    arrival_time: Time(260),
+                    status: Scheduled,
+                    aircraft_id: Some(id("PLANE_1")),
+                },
+                after: FlightState {
+                    departure_time: Time(250),
+                    arrival_time: Time(310),
+                    status: Delayed { minutes: 50 },
+                    aircraft_id: Some(id("PLANE_1")),
+                },
+            },
+        ],
+        schedule.last_report().unwrap().changes
+    );
+}
+
+#[test]
+fn test_changes_record_curfew_cancellations() {
+    let mut schedule = build();
+    let preview = schedule.preview_curfew(id("WAW"), Time(150), Time(170));
+    schedule
+        .apply_curfew(id("WAW"), Time(150), Time(170))
+        .unwrap();
+
+    let changes = &schedule.last_report().unwrap().changes;
+    assert_eq!(
+        vec![Unscheduled(AirportCurfew), Unscheduled(BrokenChain)],
+        changes
+            .iter()
+            .map(|c| c.after.status.clone())
+            .collect::<Vec<_>>()
+    );
+    assert!(changes.iter().all(|c| c.before.status == Scheduled));
+    assert_eq!(preview.changes, *changes);
+}
@@ -0,0 +1,70 @@
+use crate::aircraft::Charter;
+use crate::flight::FlightStatus::{Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::anneal::AnnealConfig;
+use crate::schedule::schedule::{Schedule, Strategy};
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+fn build(cost_per_block_hour: u64, to: u64) -> Schedule {
+    let mut schedule = scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            // both leave KRK at the same time, one aircraft can only fly one of them
+            ("FLIGHT_1", "KRK", "WAW", 100, 220, None),
+            ("FLIGHT_2", "KRK", "WAW", 100, 220, None),
+            ("FLIGHT_3", "WAW", "KRK", 300, 420, None),
+        ],
+    )
+    .build()
+    .unwrap();
+    schedule.charters = vec![Charter {
+        id: id("WET_1"),
+        base_id: id("KRK"),
+        from: Time(0),
+        to: Time(to),
+        cost_per_block_hour,
+        fleet_type: None,
+        etops: false,
+    }];
+    schedule
+}
+
+#[test]
+fn test_charter_covers_cancelled_flight() {
+    let mut schedule = build(3_000, 1440);
+    schedule.assign();
+    schedule.assign_charters();
+
+    assert!(schedule.flights.iter().all(|f| f.status == Scheduled));
+    let usage = schedule.charter_usage();
+    assert_eq!(1, usage.len());
+    assert_eq!(vec![key("FLIGHT_2")], usage[0].flights);
+    assert_eq!(120, usage[0].block_minutes);
+    assert_eq!(6_000, schedule.charter_cost());
+    assert_eq!(6_000, schedule.total_cost());
+}
+
+#[test]
+fn test_charter_skipped_when_too_expensive() {
+    let mut schedule = build(6_000, 1440);
+    schedule.assign();
+    schedule.assign_charters();
+
+    assert_eq!(Unscheduled(Waiting), schedule.flights[1].status);
+    assert!(schedule.charter_usage().is_empty());
+    assert!(!schedule.aircraft.contains_key(&id("WET_1")));
+}
+
+#[test]
+fn test_charter_respects_window() {
+    let mut schedule = build(3_000, 200);
+    schedule.recover(Strategy::Anneal(AnnealConfig {
+        max_iterations: Some(500),
+        ..AnnealConfig::default()
+    }));
+
+    assert_eq!(Unscheduled(Waiting), schedule.flights[1].status);
+    assert_eq!(0, schedule.charter_cost());
+}
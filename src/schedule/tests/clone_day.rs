@@ -0,0 +1,57 @@
+use crate::flight::FlightKey;
+use crate::flight::FlightStatus::{Delayed, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::clone_day::CloneDayError;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{key, scenario};
+use crate::time::Time;
+
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 300, 400, Some("PLANE_1")),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_clone_day_into_following_days() {
+    let mut schedule = build();
+    schedule.apply_delay(key("FLIGHT_2"), 30).unwrap();
+
+    assert_eq!(Ok(4), schedule.clone_day(1, 2));
+    assert_eq!(6, schedule.flights.len());
+
+    let copy = schedule.flight(&FlightKey::new("FLIGHT_2", 3)).unwrap();
+    assert_eq!(Time(300 + 2 * 1440), copy.departure_time);
+    assert_eq!(Time(400 + 2 * 1440), copy.arrival_time);
+    assert_eq!(Unscheduled(Waiting), copy.status);
+    assert_eq!(None, copy.aircraft_id);
+    assert_eq!(
+        Delayed { minutes: 30 },
+        schedule.flight(&key("FLIGHT_2")).unwrap().status
+    );
+
+    schedule.assign();
+    assert!(schedule.flights.iter().all(|f| f.aircraft_id.is_some()));
+}
+
+#[test]
+fn test_clone_day_keeps_existing_flights() {
+    let mut schedule = build();
+    assert_eq!(Ok(2), schedule.clone_day(1, 1));
+    assert_eq!(Ok(2), schedule.clone_day(1, 2));
+    assert_eq!(6, schedule.flights.len());
+}
+
+#[test]
+fn test_clone_day_errors() {
+    let mut schedule = build();
+    assert_eq!(Err(CloneDayError::NoFlights(2)), schedule.clone_day(2, 1));
+    assert_eq!(Err(CloneDayError::NoCopies), schedule.clone_day(1, 0));
+}
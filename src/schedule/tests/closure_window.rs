@@ -0,0 +1,107 @@
+use crate::airport::Airport;
+use crate::flight::FlightStatus;
+use crate::flight::FlightStatus::{Delayed, Unscheduled};
+use crate::flight::UnscheduledReason::AirportCurfew;
+use crate::schedule::schedule::{ClosureWindow, Schedule};
+use crate::schedule::tests::utils::{curfew, key, scenario};
+
+/// PLANE_1 flies FLIGHT_1 (KRK-WRO, 200-300) then FLIGHT_2 (WRO-WAW, 400-500), with
+/// `(airport, from, to)` curfews in place before any delay.
+fn build(window: ClosureWindow, curfews: &[(&str, u64, u64)]) -> Schedule {
+    let airport = |ap| Airport {
+        disruptions: curfews
+            .iter()
+            .filter(|(at, _, _)| *at == ap)
+            .map(|(_, from, to)| curfew(*from, *to))
+            .collect(),
+        ..Airport::new(ap, 30)
+    };
+    let mut schedule = scenario(
+        &[],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WRO", 200, 300, Some("PLANE_1")),
+            ("FLIGHT_2", "WRO", "WAW", 400, 500, Some("PLANE_1")),
+        ],
+    )
+    .airport(airport("KRK"))
+    .airport(airport("WRO"))
+    .airport(airport("WAW"))
+    .build()
+    .unwrap();
+    schedule.closure_window = window;
+    schedule
+}
+
+fn status(schedule: &Schedule, flight: &str) -> FlightStatus {
+    schedule.flight(&key(flight)).unwrap().status.clone()
+}
+
+#[test]
+fn test_new_departure_inside_curfew_is_refused_in_both_modes() {
+    // the curfew opens after the original departure but covers the new one
+    for window in [ClosureWindow::Block, ClosureWindow::Instants] {
+        let mut schedule = build(window, &[("KRK", 215, 230)]);
+        schedule.apply_delay(key("FLIGHT_1"), 20).unwrap();
+
+        assert_eq!(Unscheduled(AirportCurfew), status(&schedule, "FLIGHT_1"));
+    }
+}
+
+#[test]
+fn test_curfew_over_original_departure_only_is_cleared_in_both_modes() {
+    for window in [ClosureWindow::Block, ClosureWindow::Instants] {
+        let mut schedule = build(window, &[("KRK", 190, 210)]);
+        schedule.apply_delay(key("FLIGHT_1"), 20).unwrap();
+
+        assert_eq!(Delayed { minutes: 20 }, status(&schedule, "FLIGHT_1"));
+    }
+}
+
+#[test]
+fn test_origin_curfew_while_airborne() {
+    // new block 220-320; KRK closes at 230 once the flight has left
+    let mut block = build(ClosureWindow::Block, &[("KRK", 230, 260)]);
+    block.apply_delay(key("FLIGHT_1"), 20).unwrap();
+    let mut instants = build(ClosureWindow::Instants, &[("KRK", 230, 260)]);
+    instants.apply_delay(key("FLIGHT_1"), 20).unwrap();
+
+    assert_eq!(Unscheduled(AirportCurfew), status(&block, "FLIGHT_1"));
+    assert_eq!(Delayed { minutes: 20 }, status(&instants, "FLIGHT_1"));
+}
+
+#[test]
+fn test_instants_check_the_new_arrival() {
+    let mut schedule = build(ClosureWindow::Instants, &[("WRO", 310, 330)]);
+    schedule.apply_delay(key("FLIGHT_1"), 20).unwrap();
+
+    assert_eq!(Unscheduled(AirportCurfew), status(&schedule, "FLIGHT_1"));
+}
+
+#[test]
+fn test_instants_apply_to_propagated_flights() {
+    // FLIGHT_1 lands at 450, so FLIGHT_2 moves to 480-580 while WRO closes at 500
+    let mut block = build(ClosureWindow::Block, &[("WRO", 500, 520)]);
+    block.apply_delay(key("FLIGHT_1"), 150).unwrap();
+    let mut instants = build(ClosureWindow::Instants, &[("WRO", 500, 520)]);
+    instants.apply_delay(key("FLIGHT_1"), 150).unwrap();
+
+    assert_eq!(Unscheduled(AirportCurfew), status(&block, "FLIGHT_2"));
+    assert_eq!(Delayed { minutes: 80 }, status(&instants, "FLIGHT_2"));
+}
+
+#[test]
+fn test_closure_window_from_scenario() {
+    let raw = serde_json::from_str(
+        r#"{"aircraft": [], "airports": [], "flights": [], "closure_window": "instants"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        ClosureWindow::Instants,
+        Schedule::from_scenario(raw).closure_window
+    );
+    assert_eq!(
+        ClosureWindow::Block,
+        build(ClosureWindow::default(), &[]).closure_window
+    );
+}
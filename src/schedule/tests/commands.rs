@@ -0,0 +1,367 @@
+use crate::airport::Curfew;
+use crate::cli::commands::{
+    AtfmScope, BranchAction, Command, FrameExport, Setting, StatsView, StatusFilter,
+};
+use crate::config::TableStyle;
+use crate::export::Date;
+use crate::feed::FeedFormat;
+use crate::filter::FlightFilter;
+use crate::metrics::GroupBy;
+use crate::schedule::atfm::DelayDistribution;
+use crate::schedule::schedule::Objective;
+use crate::time::{Time, TimeFormat};
+use std::time::Duration;
+
+fn error(line: &str) -> String {
+    Command::parse(line).unwrap_err().to_string()
+}
+
+#[test]
+fn test_parse_ls() {
+    assert_eq!(
+        Command::Ls {
+            day: None,
+            status: None
+        },
+        Command::parse("ls").unwrap()
+    );
+    assert_eq!(
+        Command::Ls {
+            day: Some(2),
+            status: Some(StatusFilter::Delayed)
+        },
+        Command::parse("ls 2 d").unwrap()
+    );
+    assert_eq!(
+        Command::LsWhere(FlightFilter::parse("delay>60").unwrap()),
+        Command::parse("ls where delay>60").unwrap()
+    );
+    assert_eq!(
+        "Unknown status `x`\nUsage: ls [<day>] [u|s|d]",
+        error("ls x")
+    );
+    assert!(error("ls 0").starts_with("Days start at 1"));
+    assert!(error("ls where colour=red").starts_with("Cannot filter flights"));
+}
+
+#[test]
+fn test_parse_delay() {
+    assert_eq!(
+        Command::Delay {
+            flight: "FLIGHT_1/2".to_string(),
+            minutes: 30
+        },
+        Command::parse("delay FLIGHT_1/2 30").unwrap()
+    );
+    assert_eq!(
+        Command::DelayWhere {
+            filter: FlightFilter::parse("route=KRK-WAW and day=2").unwrap(),
+            minutes: 45
+        },
+        Command::parse("delay where route=KRK-WAW and day=2 by 45").unwrap()
+    );
+    assert_eq!(
+        "Invalid delay: expected minutes, `90m` or `1h30`, not `soon`\nUsage: delay <flight_id> <minutes>",
+        error("delay FLIGHT_1 soon")
+    );
+    assert_eq!(
+        "Usage: delay <flight_id> <minutes>",
+        error("delay FLIGHT_1")
+    );
+    assert_eq!(
+        "Usage: delay where <conditions> by <minutes>",
+        error("delay where day=2")
+    );
+}
+
+#[test]
+fn test_parse_curfew() {
+    let curfew = Curfew {
+        from: Time(1320),
+        to: Time(360),
+        arrival_tolerance: 15,
+    };
+    assert_eq!(
+        Command::Curfew {
+            airport: "KRK".to_string(),
+            curfew: curfew.clone(),
+            push: true
+        },
+        Command::parse("curfew KRK 1320 360 --push --arrival-tolerance 15").unwrap()
+    );
+    assert_eq!(
+        Command::PreviewCurfew {
+            airport: "KRK".to_string(),
+            curfew
+        },
+        Command::parse("curfew --preview KRK 1320 360 --arrival-tolerance 15").unwrap()
+    );
+    assert!(error("curfew KRK late 360").starts_with("Invalid start time: expected minutes"));
+    assert!(error("curfew --preview KRK").starts_with("Usage: curfew --preview"));
+    assert!(error("curfew KRK 06:00 06:00").starts_with("The curfew starts and ends at"));
+    assert!(
+        error("curfew --preview KRK DAY2 23:30 DAY2 05:30")
+            .starts_with("The curfew ends at DAY2 05:30 before it starts at DAY2 23:30")
+    );
+}
+
+#[test]
+fn test_parse_atfm() {
+    assert_eq!(
+        Command::Atfm {
+            scope: AtfmScope::Window {
+                airport: "WAW".to_string(),
+                from: Time(600),
+                to: Time(720)
+            },
+            delays: DelayDistribution::Rate(10)
+        },
+        Command::parse("atfm WAW 600 720 --rate 10").unwrap()
+    );
+    assert_eq!(
+        Command::Atfm {
+            scope: AtfmScope::Flights(vec!["FLIGHT_1".to_string(), "FLIGHT_2".to_string()]),
+            delays: DelayDistribution::Uniform {
+                min: 10,
+                max: 40,
+                seed: 7
+            }
+        },
+        Command::parse("atfm FLIGHT_1,FLIGHT_2 --uniform 10 40 --seed 7").unwrap()
+    );
+    assert!(error("atfm WAW 600 720 --fixed").starts_with("Usage: atfm"));
+    assert!(error("atfm WAW 600 720 --fixed x").starts_with("Invalid delay: expected minutes"));
+    assert!(
+        error("atfm WAW 720 600 --rate 10")
+            .starts_with("The window has to end after it starts at DAY1 12:00, not at DAY1 10:00")
+    );
+    assert!(error("delay-airport WAW 40 12:00 10:00").starts_with("The window has to end"));
+    assert_eq!(
+        Command::Capacity {
+            airport: "WAW".to_string(),
+            percent: 40,
+            from: Time(360),
+            to: Time(600)
+        },
+        Command::parse("capacity WAW 40% 06:00 10:00").unwrap()
+    );
+    assert!(error("capacity WAW 40 10:00 06:00").starts_with("The window has to end"));
+    assert!(error("capacity WAW 40 06:00").starts_with("Usage: capacity"));
+    assert_eq!(
+        Command::CloseRunway {
+            airport: "WAW".to_string(),
+            runway: "11/29".to_string(),
+            from: Time(360),
+            to: Time(600)
+        },
+        Command::parse("close-runway WAW 11/29 06:00 10:00").unwrap()
+    );
+    assert!(error("close-runway WAW 06:00 10:00").starts_with("Usage: close-runway"));
+}
+
+#[test]
+fn test_parse_times_and_durations() {
+    assert_eq!(
+        Command::At {
+            at: Time(1830),
+            command: "delay FL-101 1h30".to_string()
+        },
+        Command::parse("at DAY2  06:30 delay FL-101 1h30").unwrap()
+    );
+    assert_eq!(
+        Command::Curfew {
+            airport: "KRK".to_string(),
+            curfew: Curfew {
+                from: Time(1410),
+                to: Time(330),
+                arrival_tolerance: 15
+            },
+            push: false
+        },
+        Command::parse("curfew KRK 23:30 05:30 --arrival-tolerance 15m").unwrap()
+    );
+    assert_eq!(
+        Command::DelayAirport {
+            airport: "WAW".to_string(),
+            minutes: 40,
+            from: Some(Time(1800)),
+            to: Some(Time(3240))
+        },
+        Command::parse("delay-airport WAW 40m DAY2 06:00 06:00+2").unwrap()
+    );
+    assert_eq!(
+        Command::Retime {
+            flight: "FL-102".to_string(),
+            minutes: -80
+        },
+        Command::parse("retime FL-102 -1h20").unwrap()
+    );
+    assert!(error("clock 25:00").starts_with("Invalid time: expected minutes"));
+}
+
+#[test]
+fn test_parse_recover() {
+    let Command::Recover {
+        strategy,
+        objective,
+        anneal,
+    } = Command::parse("recover --strategy anneal --objective robust --time 5s --seed 3").unwrap()
+    else {
+        panic!("expected recover");
+    };
+    assert_eq!("anneal", strategy);
+    assert_eq!(Objective::Robust, objective);
+    assert_eq!(Duration::from_secs(5), anneal.time_limit);
+    assert_eq!(3, anneal.seed);
+    assert!(error("recover --objective cheapest").starts_with("Unknown objective `cheapest`"));
+    assert!(error("recover --strategy").starts_with("Usage: recover"));
+}
+
+#[test]
+fn test_parse_remaining_text() {
+    assert_eq!(
+        Command::At {
+            at: Time(600),
+            command: "delay FLIGHT_5 30".to_string()
+        },
+        Command::parse("at  600   delay FLIGHT_5 30").unwrap()
+    );
+    assert_eq!(
+        Command::Foreach {
+            filter: "delayed".to_string(),
+            command: "retime {flight} -10".to_string()
+        },
+        Command::parse("foreach delayed retime {flight} -10").unwrap()
+    );
+    assert_eq!("Usage: at <time> <command>", error("at 600"));
+}
+
+#[test]
+fn test_parse_subcommands() {
+    assert_eq!(
+        Command::Stats(StatsView::By(GroupBy::Airport)),
+        Command::parse("stats by=airport").unwrap()
+    );
+    assert_eq!(
+        Command::Branch(BranchAction::Switch("what-if".to_string())),
+        Command::parse("branch switch what-if").unwrap()
+    );
+    assert_eq!(
+        Command::Impact { delay: 30, top: 5 },
+        Command::parse("impact 5 --delay 30").unwrap()
+    );
+    assert!(error("stats by=crew").starts_with("Usage: stats"));
+    assert!(error("impact --delay 0").starts_with("The delay must be at least 1"));
+    assert!(error("report notes.txt").starts_with("Usage: report"));
+    assert_eq!("audit takes no arguments\nUsage: audit", error("audit now"));
+}
+
+#[test]
+fn test_parse_watch() {
+    assert_eq!(
+        Command::Watch(Some(Box::new(Command::Ls {
+            day: None,
+            status: Some(StatusFilter::Unscheduled)
+        }))),
+        Command::parse("watch ls u").unwrap()
+    );
+    assert_eq!(
+        Command::Watch(Some(Box::new(Command::Stats(StatsView::Delays)))),
+        Command::parse("watch stats delays").unwrap()
+    );
+    assert_eq!(Command::Watch(None), Command::parse("watch off").unwrap());
+    assert!(error("watch delay FLIGHT_1 30").starts_with("Only ls and stats can be watched"));
+    assert!(error("watch ls x").starts_with("Unknown status `x`"));
+    assert!(error("watch").starts_with("Usage: watch"));
+    assert!(!Command::parse("watch ls").unwrap().mutates());
+}
+
+#[test]
+fn test_parse_set() {
+    assert_eq!(Command::Set(None), Command::parse("set").unwrap());
+    assert_eq!(
+        Command::Set(Some(Setting::TimeFormat {
+            format: TimeFormat::Iso,
+            start: Some("2024-06-03".parse::<Date>().unwrap())
+        })),
+        Command::parse("set timefmt iso --start 2024-06-03").unwrap()
+    );
+    assert!(error("set timefmt unix").starts_with("unknown time format `unix`"));
+    assert!(error("set timefmt iso --start 2024-13-01").starts_with("invalid date"));
+    assert_eq!(
+        Command::Set(Some(Setting::TableStyle(TableStyle::Markdown))),
+        Command::parse("set style markdown").unwrap()
+    );
+    assert!(error("set style fancy").starts_with("unknown table style `fancy`"));
+    assert!(error("set colour red").starts_with("Usage: set"));
+}
+
+#[test]
+fn test_parse_plugin_command() {
+    assert_eq!(
+        Command::Plugin {
+            name: "reroute".to_string(),
+            args: vec!["KRK".to_string(), "WAW".to_string()]
+        },
+        Command::parse("reroute KRK WAW").unwrap()
+    );
+}
+
+#[test]
+fn test_mutates() {
+    assert!(Command::parse("delay FLIGHT_1 30").unwrap().mutates());
+    assert!(Command::parse("curfew KRK 0 360").unwrap().mutates());
+    assert!(
+        !Command::parse("curfew --preview KRK 0 360")
+            .unwrap()
+            .mutates()
+    );
+    assert!(!Command::parse("ls").unwrap().mutates());
+}
+
+#[test]
+fn test_parse_export_frame() {
+    assert_eq!(
+        Command::ExportFrame {
+            format: FrameExport::Parquet,
+            path: "run.parquet".to_string()
+        },
+        Command::parse("export parquet flights run.parquet").unwrap()
+    );
+    assert_eq!(
+        Command::ExportFrame {
+            format: FrameExport::Ipc,
+            path: "run.arrow".to_string()
+        },
+        Command::parse("export ipc flights run.arrow").unwrap()
+    );
+    assert_eq!(
+        "Usage: export parquet|ipc flights <file>",
+        error("export parquet stats run.parquet")
+    );
+}
+
+#[test]
+fn test_parse_feed() {
+    assert_eq!(
+        Command::Feed {
+            source: "observed.json".to_string(),
+            format: FeedFormat::Observations,
+            every: None,
+        },
+        Command::parse("feed observed.json").unwrap()
+    );
+    assert_eq!(
+        Command::Feed {
+            source: "https://opensky-network.org/api/flights/departure".to_string(),
+            format: FeedFormat::OpenSky,
+            every: Some(Duration::from_secs(300)),
+        },
+        Command::parse(
+            "feed https://opensky-network.org/api/flights/departure --format opensky --every 300s"
+        )
+        .unwrap()
+    );
+    assert!(error("feed a.json --every 0s").starts_with("The interval must be at least 1s"));
+    assert!(error("feed a.json --format adsb").starts_with("Unknown feed format `adsb`"));
+}
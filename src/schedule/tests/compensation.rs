@@ -0,0 +1,102 @@
+use crate::flight::FlightStatus::{Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{add_aircraft, add_airport, add_flight, id, key};
+use crate::time::Time;
+use std::collections::HashMap;
+
+#[test]
+fn test_compensation_delay_above_threshold() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+
+    add_aircraft(&mut aircraft, "PLANE_1", "KRK", vec![]);
+
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        "WAW",
+        "KRK",
+        400,
+        500,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+
+    let mut schedule = Schedule::new(aircraft, airports, flights);
+    schedule.flights[0].passengers = 100;
+    schedule.flights[1].passengers = 50;
+    schedule.flights[1].distance = Some(2000);
+
+    schedule.apply_delay(key("FLIGHT_1"), 120).unwrap();
+    assert_eq!(0, schedule.last_report().unwrap().compensation);
+
+    schedule.apply_delay(key("FLIGHT_1"), 300).unwrap();
+    // FLIGHT_1 is delayed 300m (short haul), FLIGHT_2 is pushed 250m (medium haul)
+    assert_eq!(
+        100 * 250 + 50 * 400,
+        schedule.last_report().unwrap().compensation
+    );
+    assert_eq!(
+        100 * 250 + 50 * 400,
+        schedule.compensation.exposure(&schedule.flights)
+    );
+}
+
+#[test]
+fn test_compensation_cancellation() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+
+    add_aircraft(&mut aircraft, "PLANE_1", "KRK", vec![]);
+
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        "WAW",
+        "KRK",
+        300,
+        400,
+        None,
+        Unscheduled(Waiting),
+    );
+
+    let mut schedule = Schedule::new(aircraft, airports, flights);
+    schedule.flights[0].passengers = 100;
+    schedule.flights[1].passengers = 80;
+
+    assert_eq!(0, schedule.compensation.exposure(&schedule.flights));
+
+    schedule
+        .apply_curfew(id("WAW"), Time(150), Time(250))
+        .unwrap();
+    assert_eq!(100 * 250, schedule.last_report().unwrap().compensation);
+}
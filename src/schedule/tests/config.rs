@@ -0,0 +1,85 @@
+use crate::config::{ColorMode, Config, ConfigError, TableStyle};
+use crate::cost::CostModel;
+use crate::flight::FlightStatus::{Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::MaxDelayExceeded;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{add_aircraft, add_airport, add_flight, key};
+use crate::time::TimeFormat;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[test]
+fn test_parse_config() {
+    let config = Config::parse(
+        r#"
+        # startup defaults
+        scenario = "data/hub.json"  # relative to the working directory
+        max_delay = 1_200
+        table_style = "markdown"
+        color = "never"
+        time_format = "iso"
+        start_date = "2024-06-03"
+
+        [cost]
+        cancellation = 8000
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(Some(PathBuf::from("data/hub.json")), config.scenario);
+    assert_eq!(Some(1200), config.max_delay);
+    assert_eq!(TableStyle::Markdown, config.table_style);
+    assert_eq!(ColorMode::Never, config.color);
+    assert_eq!(TimeFormat::Iso, config.time_format);
+    assert_eq!(Some("2024-06-03".to_string()), config.start_date);
+    // missing keys of a table keep their defaults
+    assert_eq!(
+        Some(CostModel {
+            cancellation: 8000,
+            ..CostModel::default()
+        }),
+        config.cost
+    );
+    assert_eq!(None, config.compensation);
+    assert_eq!(None, config.severity);
+}
+
+#[test]
+fn test_parse_config_errors() {
+    let message_of = |text: &str| match Config::parse(text) {
+        Err(e @ ConfigError::Invalid(_)) => e.to_string(),
+        other => panic!("expected an invalid config, got {:?}", other),
+    };
+    assert!(message_of("scenario = \"data.json").contains("line 1"));
+    assert!(message_of("\n[cost\ncancellation = 1").contains("line 2"));
+    assert!(message_of("max_delay = soon").contains("line 1"));
+    assert!(message_of("\nunknown = 1").contains("line 2"));
+    assert!(message_of("max_delay = \"soon\"").contains("line 1"));
+    assert_eq!(Config::default(), Config::parse("").unwrap());
+}
+
+#[test]
+fn test_max_delay_is_configurable() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+    add_aircraft(&mut aircraft, "PLANE_1", "KRK", vec![]);
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+
+    let mut schedule = Schedule::new(aircraft, airports, flights);
+    schedule.max_delay = 120;
+    schedule.apply_delay(key("FLIGHT_1"), 150).unwrap();
+    assert_eq!(Unscheduled(MaxDelayExceeded), schedule.flights[0].status);
+}
@@ -0,0 +1,142 @@
+use crate::flight::Flight;
+use crate::flight::FlightStatus::Unscheduled;
+use crate::flight::UnscheduledReason::{BrokenChain, ConstraintViolated};
+use crate::schedule::constraint::{Constraint, ConstraintContext, Rule, Violation};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+/// PLANE_1 must be back on the ground by `latest`.
+struct Curfewed {
+    latest: Time,
+}
+
+impl Constraint for Curfewed {
+    fn violated(&self, flight: &Flight, context: &ConstraintContext) -> Option<Violation> {
+        (context.aircraft.id == id("PLANE_1") && context.arrival > self.latest).then(|| Violation {
+            rule: "crew-curfew".to_string(),
+            detail: format!("{} lands after {}", flight.id, self.latest),
+        })
+    }
+}
+
+fn build(assigned: bool) -> Schedule {
+    let plane = assigned.then_some("PLANE_1");
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, plane),
+            ("FLIGHT_2", "WAW", "KRK", 300, 400, plane),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_assign_skips_aircraft_breaking_custom_rule() {
+    let mut schedule = build(false);
+    schedule.add_constraint(Curfewed { latest: Time(150) });
+    schedule.assign();
+
+    assert_eq!(Some(id("PLANE_2")), schedule.flights[0].aircraft_id);
+    assert_eq!(Some(id("PLANE_2")), schedule.flights[1].aircraft_id);
+}
+
+#[test]
+fn test_delay_reports_custom_violation() {
+    let mut schedule = build(true);
+    schedule.add_constraint(Curfewed { latest: Time(420) });
+    schedule.apply_delay(key("FLIGHT_1"), 100).unwrap();
+
+    assert_eq!(Unscheduled(ConstraintViolated), schedule.flights[1].status);
+    let report = schedule.last_report.unwrap();
+    assert_eq!(
+        vec![(key("FLIGHT_2"), ConstraintViolated)],
+        report.unscheduled
+    );
+    assert_eq!(
+        vec![(
+            key("FLIGHT_2"),
+            Violation {
+                rule: "crew-curfew".to_string(),
+                detail: "FLIGHT_2 lands after DAY1 07:00".to_string(),
+            }
+        )],
+        report.violations
+    );
+}
+
+#[test]
+fn test_delay_breaks_chain_after_violating_trigger() {
+    let mut schedule = build(true);
+    schedule.add_constraint(Curfewed { latest: Time(250) });
+    schedule.apply_delay(key("FLIGHT_1"), 100).unwrap();
+
+    assert_eq!(
+        vec![
+            (key("FLIGHT_1"), ConstraintViolated),
+            (key("FLIGHT_2"), BrokenChain)
+        ],
+        schedule.last_report.unwrap().unscheduled
+    );
+}
+
+#[test]
+fn test_rules_from_json() {
+    let rules: Vec<Rule> = serde_json::from_str(
+        r#"[
+            {"kind": "avoid_airport", "aircraft_id": "PLANE_1", "airport_id": "WAW"},
+            {"kind": "depart_by", "flight_id": "FLIGHT_2", "latest": 1300}
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(
+        vec![
+            Rule::AvoidAirport {
+                aircraft_id: id("PLANE_1"),
+                airport_id: id("WAW"),
+            },
+            Rule::DepartBy {
+                flight_id: id("FLIGHT_2"),
+                latest: Time(1300),
+            },
+        ],
+        rules
+    );
+}
+
+#[test]
+fn test_avoid_airport_rule() {
+    let mut schedule = build(false);
+    schedule.add_constraint(Rule::AvoidAirport {
+        aircraft_id: id("PLANE_1"),
+        airport_id: id("WAW"),
+    });
+    schedule.assign();
+
+    assert!(
+        schedule
+            .flights
+            .iter()
+            .all(|f| f.aircraft_id == Some(id("PLANE_2")))
+    );
+}
+
+#[test]
+fn test_depart_by_rule() {
+    let mut schedule = build(true);
+    schedule.add_constraint(Rule::DepartBy {
+        flight_id: id("FLIGHT_2"),
+        latest: Time(320),
+    });
+    schedule.apply_delay(key("FLIGHT_1"), 100).unwrap();
+
+    let report = schedule.last_report.unwrap();
+    assert_eq!(
+        vec![(key("FLIGHT_2"), ConstraintViolated)],
+        report.unscheduled
+    );
+    assert_eq!("depart_by", report.violations[0].1.rule);
+}
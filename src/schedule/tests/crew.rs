@@ -0,0 +1,191 @@
+use crate::crew::{Crew, ReserveCrew, RestBreach};
+use crate::flight::FlightKey;
+use crate::flight::FlightStatus::Scheduled;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{add_flight, id, key, scenario};
+use crate::time::Time;
+
+/// CREW_1 (KRK, 300 min duty) flies FLIGHT_1-3 on PLANE_1, landing FLIGHT_3 400 min
+/// after its first departure.
+fn build(reserve_base: &str, from: u64, to: u64) -> Schedule {
+    let mut schedule = scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 250, 350, Some("PLANE_1")),
+            ("FLIGHT_3", "KRK", "WAW", 400, 500, Some("PLANE_1")),
+        ],
+    )
+    .build()
+    .unwrap();
+    for flight in schedule.flights.iter_mut() {
+        flight.crew_id = Some(id("CREW_1"));
+    }
+    schedule.crews.push(Crew {
+        id: id("CREW_1"),
+        base_id: id("KRK"),
+        max_duty: 300,
+        min_rest: 600,
+        hotel_transfer: 60,
+    });
+    schedule.reserves.push(ReserveCrew {
+        id: id("RESERVE_1"),
+        base_id: id(reserve_base),
+        from: Time(from),
+        to: Time(to),
+        max_duty: 200,
+    });
+    schedule
+}
+
+#[test]
+fn test_duty_breaches() {
+    let schedule = build("KRK", 300, 600);
+
+    assert_eq!(vec![key("FLIGHT_3")], schedule.duty_breaches());
+}
+
+#[test]
+fn test_reserve_called_out() {
+    let mut schedule = build("KRK", 300, 600);
+    let cost_before = schedule.total_cost();
+    schedule.call_reserves();
+
+    assert_eq!(
+        Some(id("RESERVE_1")),
+        schedule.flight(&key("FLIGHT_3")).unwrap().crew_id
+    );
+    assert_eq!(
+        Some(id("CREW_1")),
+        schedule.flight(&key("FLIGHT_2")).unwrap().crew_id
+    );
+    assert!(schedule.duty_breaches().is_empty());
+
+    let callouts = schedule.callouts();
+    assert_eq!(1, callouts.len());
+    assert_eq!(vec![key("FLIGHT_3")], callouts[0].flights);
+    assert_eq!(schedule.cost.reserve_callout, schedule.callout_cost());
+    assert_eq!(
+        cost_before + schedule.cost.reserve_callout,
+        schedule.total_cost()
+    );
+}
+
+#[test]
+fn test_reserve_away_from_base_not_called() {
+    let mut schedule = build("WAW", 300, 600);
+    schedule.call_reserves();
+
+    assert_eq!(
+        Some(id("CREW_1")),
+        schedule.flight(&key("FLIGHT_3")).unwrap().crew_id
+    );
+    assert!(schedule.callouts().is_empty());
+}
+
+#[test]
+fn test_reserve_outside_availability_not_called() {
+    let mut schedule = build("KRK", 300, 450);
+    schedule.call_reserves();
+
+    assert!(schedule.callouts().is_empty());
+    assert_eq!(vec![key("FLIGHT_3")], schedule.duty_breaches());
+}
+
+#[test]
+fn test_recovery_records_callouts() {
+    let mut schedule = build("KRK", 300, 600);
+    let flights_before = schedule.flights.clone();
+    let cost_before = schedule.total_cost();
+    schedule.assign();
+    schedule.call_reserves();
+    schedule.record_recovery("greedy", &flights_before, cost_before);
+
+    let record = schedule.recoveries.last().unwrap();
+    assert_eq!(vec![id("RESERVE_1")], record.callouts);
+    assert_eq!(schedule.cost.reserve_callout, record.callout_cost);
+}
+
+/// Adds FLIGHT_4 on day 2 for CREW_1, departing `origin` at `departure`.
+fn add_next_day(schedule: &mut Schedule, origin: &str, departure: u64) {
+    let destination = if origin == "KRK" { "WAW" } else { "KRK" };
+    add_flight(
+        &mut schedule.flights,
+        "FLIGHT_4",
+        origin,
+        destination,
+        departure,
+        departure + 100,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    schedule.flights.last_mut().unwrap().crew_id = Some(id("CREW_1"));
+    schedule.reindex();
+}
+
+#[test]
+fn test_duty_counted_per_day() {
+    let mut schedule = build("KRK", 0, 0);
+    add_next_day(&mut schedule, "WAW", 1500);
+
+    assert_eq!(vec![key("FLIGHT_3")], schedule.duty_breaches());
+}
+
+#[test]
+fn test_hotel_rest_away_from_base() {
+    // CREW_1 ends day 1 at WAW at 500 and needs 1060 min there
+    let mut schedule = build("KRK", 0, 0);
+    schedule.crews[0].min_rest = 1000;
+    add_next_day(&mut schedule, "WAW", 1500);
+
+    assert_eq!(
+        vec![(
+            FlightKey::new("FLIGHT_4", 2),
+            id("CREW_1"),
+            RestBreach::Short {
+                required: 1060,
+                available: 1000
+            }
+        )],
+        schedule.rest_breaches()
+    );
+
+    schedule.crews[0].hotel_transfer = 0;
+    assert!(schedule.rest_breaches().is_empty());
+}
+
+#[test]
+fn test_next_duty_from_rest_airport() {
+    let mut schedule = build("KRK", 0, 0);
+    add_next_day(&mut schedule, "KRK", 1500);
+
+    assert_eq!(
+        vec![(
+            FlightKey::new("FLIGHT_4", 2),
+            id("CREW_1"),
+            RestBreach::Positioning {
+                rested_at: id("WAW"),
+                origin: id("KRK")
+            }
+        )],
+        schedule.rest_breaches()
+    );
+}
+
+#[test]
+fn test_reserve_covers_rest_breach() {
+    let mut schedule = build("WAW", 1400, 1700);
+    schedule.crews[0].min_rest = 1000;
+    add_next_day(&mut schedule, "WAW", 1500);
+    schedule.call_reserves();
+
+    assert_eq!(
+        Some(id("RESERVE_1")),
+        schedule
+            .flight(&FlightKey::new("FLIGHT_4", 2))
+            .unwrap()
+            .crew_id
+    );
+    assert!(schedule.rest_breaches().is_empty());
+}
@@ -1,12 +1,14 @@
-use crate::flight::FlightStatus::{Scheduled, Unscheduled};
-use crate::flight::UnscheduledReason::{AirportCurfew, BrokenChain};
+use crate::airport::Curfew;
+use crate::error::IrropsError;
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::{AirportCurfew, BrokenChain, MaxDelayExceeded, Waiting};
+use crate::flight::{Flight, FlightKey};
 use crate::schedule::schedule::Schedule;
-use crate::schedule::tests::utils::{add_aircraft, add_airport, add_flight, id};
+use crate::schedule::tests::utils::{add_aircraft, add_airport, add_flight, curfew, id, key};
 use crate::time::Time;
 use std::collections::HashMap;
 
-#[test]
-fn test_curfew_chain_reaction() {
+fn build() -> Schedule {
     let mut aircraft = HashMap::new();
     let mut airports = HashMap::new();
     let mut flights = Vec::new();
@@ -48,8 +50,15 @@ fn test_curfew_chain_reaction() {
         Scheduled,
     );
 
-    let mut schedule = Schedule::new(aircraft, airports, flights);
-    schedule.apply_curfew(id("WAW"), Time(450), Time(550));
+    Schedule::new(aircraft, airports, flights)
+}
+
+#[test]
+fn test_curfew_chain_reaction() {
+    let mut schedule = build();
+    schedule
+        .apply_curfew(id("WAW"), Time(450), Time(550))
+        .unwrap();
 
     assert_eq!(Some(id("PLANE_1")), schedule.flights[0].aircraft_id);
     assert_eq!(Time(200), schedule.flights[0].departure_time);
@@ -71,3 +80,383 @@ fn test_curfew_chain_reaction() {
     assert_eq!(Unscheduled(AirportCurfew), schedule.flights[1].status);
     assert_eq!(Unscheduled(BrokenChain), schedule.flights[2].status);
 }
+
+#[test]
+fn test_curfew_preview_matches_apply() {
+    let mut schedule = build();
+    let preview = schedule.preview_curfew(id("WAW"), Time(450), Time(550));
+
+    assert_eq!(
+        vec![
+            (key("FLIGHT_2"), AirportCurfew),
+            (key("FLIGHT_3"), BrokenChain)
+        ],
+        preview.unscheduled
+    );
+    assert_eq!(Some((key("FLIGHT_2"), AirportCurfew)), preview.first_break);
+    assert!(schedule.airports[&id("WAW")].disruptions.is_empty());
+    assert!(schedule.history.is_empty());
+    assert!(schedule.flights.iter().all(|f| f.status == Scheduled));
+
+    schedule
+        .apply_curfew(id("WAW"), Time(450), Time(550))
+        .unwrap();
+    let report = schedule.last_report().unwrap();
+    assert_eq!(preview.unscheduled, report.unscheduled);
+    assert_eq!(preview.compensation, report.compensation);
+}
+
+#[test]
+fn test_legs_by_airport() {
+    let mut schedule = build();
+    let keys = |legs: Vec<&Flight>| legs.iter().map(|f| f.key()).collect::<Vec<_>>();
+
+    assert_eq!(vec![key("FLIGHT_1")], keys(schedule.departures(&id("KRK"))));
+    assert_eq!(vec![key("FLIGHT_3")], keys(schedule.arrivals(&id("KRK"))));
+    assert_eq!(vec![key("FLIGHT_2")], keys(schedule.departures(&id("WRO"))));
+    assert!(schedule.departures(&id("GDN")).is_empty());
+
+    schedule.apply_delay(key("FLIGHT_2"), 60).unwrap();
+    assert_eq!(
+        vec![Time(460)],
+        schedule
+            .departures(&id("WRO"))
+            .iter()
+            .map(|f| f.departure_time)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_legs_by_airport_follow_retimes() {
+    let mut schedule = build();
+    add_aircraft(&mut schedule.aircraft, "PLANE_2", "WRO", vec![]);
+    add_flight(
+        &mut schedule.flights,
+        "FLIGHT_4",
+        "WRO",
+        "KRK",
+        420,
+        520,
+        Some("PLANE_2"),
+        Scheduled,
+    );
+    schedule.reindex();
+    let keys = |legs: Vec<&Flight>| legs.iter().map(|f| f.key()).collect::<Vec<_>>();
+
+    schedule.apply_delay(key("FLIGHT_2"), 60).unwrap();
+    assert_eq!(
+        vec![key("FLIGHT_4"), key("FLIGHT_2")],
+        keys(schedule.departures(&id("WRO")))
+    );
+    schedule.retime(key("FLIGHT_2"), -60).unwrap();
+    assert_eq!(
+        vec![key("FLIGHT_2"), key("FLIGHT_4")],
+        keys(schedule.departures(&id("WRO")))
+    );
+}
+
+#[test]
+fn test_curfew_without_impact_or_airport() {
+    let mut schedule = build();
+
+    let report = schedule
+        .apply_curfew(id("WAW"), Time(1000), Time(1100))
+        .unwrap();
+    assert!(report.affected.is_empty());
+    assert!(report.unscheduled.is_empty());
+    assert_eq!(1, schedule.history.len());
+
+    assert_eq!(
+        Some(IrropsError::UnknownAirport(id("GDN"))),
+        schedule.apply_curfew(id("GDN"), Time(450), Time(550)).err()
+    );
+    assert_eq!(
+        Some(IrropsError::EmptyCurfew(id("WAW"))),
+        schedule.apply_curfew(id("WAW"), Time(450), Time(450)).err()
+    );
+    assert_eq!(1, schedule.history.len());
+    assert_eq!(1, schedule.airports[&id("WAW")].disruptions.len());
+}
+
+#[test]
+fn test_assign_respects_curfew_inside_block() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![curfew(120, 150)]);
+
+    add_aircraft(&mut aircraft, "PLANE_1", "KRK", vec![]);
+
+    // neither departure nor arrival falls into the curfew, the block does
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        None,
+        Unscheduled(Waiting),
+    );
+
+    let mut schedule = Schedule::new(aircraft, airports, flights);
+    schedule.assign();
+    assert_eq!(None, schedule.flights[0].aircraft_id);
+    assert_eq!(Unscheduled(Waiting), schedule.flights[0].status);
+
+    schedule
+        .apply_curfew(id("WAW"), Time(120), Time(150))
+        .unwrap();
+    assert!(schedule.last_report().unwrap().unscheduled.is_empty());
+}
+
+#[test]
+fn test_wrapping_curfew_windows() {
+    let night = curfew(1410, 330);
+    assert!(night.is_wrapping());
+    assert_eq!("daily 23:30-05:30", night.to_string());
+    assert_eq!(
+        vec![
+            (Time(0), Time(330)),
+            (Time(1410), Time(1770)),
+            (Time(2850), Time(3210))
+        ],
+        night.windows((Time(0), Time(3000)))
+    );
+    assert!(night.windows((Time(400), Time(1400))).is_empty());
+}
+
+#[test]
+fn test_wrapping_curfew_applies_every_night() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 0, vec![]);
+    add_airport(&mut airports, "WAW", 0, vec![]);
+
+    add_aircraft(&mut aircraft, "PLANE_1", "KRK", vec![]);
+    add_aircraft(&mut aircraft, "PLANE_2", "KRK", vec![]);
+    add_aircraft(&mut aircraft, "PLANE_3", "KRK", vec![]);
+
+    for (flight_id, dep) in [("FLIGHT_1", 100), ("FLIGHT_2", 600), ("FLIGHT_3", 1500)] {
+        add_flight(
+            &mut flights,
+            flight_id,
+            "KRK",
+            "WAW",
+            dep,
+            dep + 60,
+            None,
+            Unscheduled(Waiting),
+        );
+    }
+
+    let mut schedule = Schedule::new(aircraft, airports, flights);
+    schedule.assign();
+    schedule
+        .apply_curfew(id("WAW"), Time(1410), Time(330))
+        .unwrap();
+
+    assert_eq!(
+        vec![
+            (key("FLIGHT_1"), AirportCurfew),
+            (FlightKey::new("FLIGHT_3", 2), AirportCurfew)
+        ],
+        schedule.last_report().unwrap().unscheduled
+    );
+    assert_eq!(Scheduled, schedule.flights[1].status);
+
+    schedule.assign();
+    assert_eq!(Unscheduled(AirportCurfew), schedule.flights[0].status);
+    assert_eq!(Unscheduled(AirportCurfew), schedule.flights[2].status);
+}
+
+#[test]
+fn test_curfew_exempt_flight_keeps_operating() {
+    let mut schedule = build();
+    schedule.flights[1].curfew_exempt = true;
+    let preview = schedule.preview_curfew(id("WAW"), Time(450), Time(550));
+    schedule
+        .apply_curfew(id("WAW"), Time(450), Time(550))
+        .unwrap();
+
+    assert!(schedule.flights.iter().all(|f| f.status == Scheduled));
+    let report = schedule.last_report().unwrap();
+    assert!(report.unscheduled.is_empty());
+    assert_eq!(vec![(key("FLIGHT_2"), AirportCurfew)], report.exempted);
+    assert_eq!(report.exempted, preview.exempted);
+    assert_eq!(
+        vec![(key("FLIGHT_2"), AirportCurfew)],
+        schedule.closure_waivers()
+    );
+}
+
+#[test]
+fn test_curfew_exempt_flight_delayed_into_curfew() {
+    let mut schedule = build();
+    schedule
+        .apply_curfew(id("WAW"), Time(560), Time(590))
+        .unwrap();
+    schedule.flights[1].curfew_exempt = true;
+    schedule.apply_delay(key("FLIGHT_2"), 100).unwrap();
+
+    assert_eq!(Scheduled, schedule.flights[0].status);
+    assert_eq!(Time(500), schedule.flights[1].departure_time);
+    assert_eq!(Time(600), schedule.flights[1].arrival_time);
+    assert_eq!(Delayed { minutes: 30 }, schedule.flights[2].status);
+    let report = schedule.last_report().unwrap();
+    assert!(report.unscheduled.is_empty());
+    assert_eq!(vec![(key("FLIGHT_2"), AirportCurfew)], report.exempted);
+}
+
+#[test]
+fn test_curfew_exempt_flight_assigned_inside_curfew() {
+    let mut schedule = build();
+    schedule
+        .apply_curfew(id("WRO"), Time(250), Time(350))
+        .unwrap();
+    assert_eq!(Unscheduled(AirportCurfew), schedule.flights[0].status);
+
+    schedule.flights[0].curfew_exempt = true;
+    schedule.assign();
+    assert_eq!(Some(id("PLANE_1")), schedule.flights[0].aircraft_id);
+    assert_eq!(Scheduled, schedule.flights[0].status);
+}
+
+#[test]
+fn test_arrival_tolerance_keeps_late_arrival() {
+    let mut schedule = build();
+    schedule
+        .apply_curfew_with(
+            id("WAW"),
+            Curfew {
+                arrival_tolerance: 60,
+                ..curfew(450, 550)
+            },
+        )
+        .unwrap();
+
+    assert!(schedule.flights.iter().all(|f| f.status == Scheduled));
+    assert!(schedule.last_report().unwrap().unscheduled.is_empty());
+}
+
+#[test]
+fn test_arrival_tolerance_does_not_cover_departures() {
+    let mut schedule = build();
+    let tolerant = Curfew {
+        arrival_tolerance: 60,
+        ..curfew(390, 500)
+    };
+    let preview = schedule.preview_curfew_with(id("WRO"), tolerant.clone());
+    schedule.apply_curfew_with(id("WRO"), tolerant).unwrap();
+
+    assert_eq!(Scheduled, schedule.flights[0].status);
+    assert_eq!(Unscheduled(AirportCurfew), schedule.flights[1].status);
+    assert_eq!(Unscheduled(BrokenChain), schedule.flights[2].status);
+    assert_eq!(
+        preview.unscheduled,
+        schedule.last_report().unwrap().unscheduled
+    );
+}
+
+#[test]
+fn test_arrival_tolerance_in_delay_propagation() {
+    let mut schedule = build();
+    schedule
+        .apply_curfew_with(
+            id("WAW"),
+            Curfew {
+                arrival_tolerance: 30,
+                ..curfew(520, 580)
+            },
+        )
+        .unwrap();
+
+    let mut delayed = schedule.clone();
+    delayed.apply_delay(key("FLIGHT_2"), 40).unwrap();
+    assert_eq!(Delayed { minutes: 40 }, delayed.flights[1].status);
+    assert_eq!(Time(540), delayed.flights[1].arrival_time);
+
+    schedule.apply_delay(key("FLIGHT_2"), 60).unwrap();
+    assert_eq!(Unscheduled(AirportCurfew), schedule.flights[1].status);
+}
+
+#[test]
+fn test_push_curfew_delays_departures_to_reopening() {
+    let mut schedule = build();
+    schedule.push_curfew(id("WRO"), curfew(380, 420)).unwrap();
+    let report = schedule.last_report().unwrap();
+
+    assert_eq!(vec![key("FLIGHT_2")], report.affected);
+    assert!(report.unscheduled.is_empty());
+    assert_eq!(20, report.delay_minutes);
+    assert_eq!(1, schedule.history.len());
+    assert_eq!(Time(420), schedule.flights[1].departure_time);
+    assert_eq!(Delayed { minutes: 20 }, schedule.flights[1].status);
+    assert_eq!(Scheduled, schedule.flights[2].status);
+}
+
+#[test]
+fn test_push_curfew_propagates_down_the_rotation() {
+    let mut schedule = build();
+    schedule.push_curfew(id("WRO"), curfew(380, 560)).unwrap();
+    let report = schedule.last_report().unwrap();
+
+    assert_eq!(vec![key("FLIGHT_2"), key("FLIGHT_3")], report.affected);
+    assert_eq!(160 + 90, report.delay_minutes);
+    assert_eq!(Delayed { minutes: 90 }, schedule.flights[2].status);
+}
+
+#[test]
+fn test_push_curfew_unschedules_when_push_is_infeasible() {
+    let mut schedule = build();
+    schedule.max_delay = 10;
+    schedule.push_curfew(id("WRO"), curfew(380, 420)).unwrap();
+    let report = schedule.last_report().unwrap();
+
+    assert!(report.affected.is_empty());
+    assert_eq!(
+        vec![
+            (key("FLIGHT_2"), MaxDelayExceeded),
+            (key("FLIGHT_3"), BrokenChain)
+        ],
+        report.unscheduled
+    );
+    assert_eq!(1, schedule.history.len());
+}
+
+#[test]
+fn test_push_curfew_still_unschedules_arrivals() {
+    let mut schedule = build();
+    schedule.push_curfew(id("WAW"), curfew(450, 550)).unwrap();
+    let report = schedule.last_report().unwrap();
+
+    assert!(report.affected.is_empty());
+    assert_eq!(
+        vec![
+            (key("FLIGHT_2"), AirportCurfew),
+            (key("FLIGHT_3"), BrokenChain)
+        ],
+        report.unscheduled
+    );
+}
+
+#[test]
+fn test_push_curfew_validates_like_apply_curfew() {
+    let mut schedule = build();
+
+    assert_eq!(
+        Some(IrropsError::UnknownAirport(id("GDN"))),
+        schedule.push_curfew(id("GDN"), curfew(380, 420)).err()
+    );
+    assert_eq!(
+        Some(IrropsError::EmptyCurfew(id("WRO"))),
+        schedule.push_curfew(id("WRO"), curfew(420, 420)).err()
+    );
+    assert!(schedule.history.is_empty());
+    assert!(schedule.last_report().is_none());
+}
@@ -0,0 +1,79 @@
+use crate::flight::Flight;
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::{AirportCurfew, BrokenChain};
+use crate::schedule::schedule::{CurfewSelection, Schedule};
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+/// PLANE_1 flies a KRK-WAW-KRK loop (FLIGHT_1, FLIGHT_2) with 20 passengers each, then
+/// FLIGHT_3 KRK-GDN with 150 passengers at priority 2, running 80 minutes late at
+/// 280-340, and FLIGHT_4 back from GDN at 420.
+fn build(selection: CurfewSelection, loop_passengers: u32) -> Schedule {
+    let flight = |flight_id, origin, destination, dep, arr, passengers| Flight {
+        passengers,
+        ..Flight::new(flight_id, origin, destination, Time(dep), Time(arr)).assigned("PLANE_1")
+    };
+    let mut schedule = scenario(&["KRK", "WAW", "GDN"], &[("PLANE_1", "KRK")], &[])
+        .flight(flight("FLIGHT_1", "KRK", "WAW", 100, 160, loop_passengers))
+        .flight(flight("FLIGHT_2", "WAW", "KRK", 190, 250, loop_passengers))
+        .flight(Flight {
+            status: Delayed { minutes: 80 },
+            priority: 2,
+            ..flight("FLIGHT_3", "KRK", "GDN", 280, 340, 150)
+        })
+        .flight(flight("FLIGHT_4", "GDN", "KRK", 420, 480, 100))
+        .build()
+        .unwrap();
+    schedule.curfew_selection = selection;
+    schedule
+}
+
+fn status(schedule: &Schedule, flight: &str) -> crate::flight::FlightStatus {
+    schedule.flight(&key(flight)).unwrap().status.clone()
+}
+
+#[test]
+fn test_blind_curfew_breaks_the_hit_rotation() {
+    let mut schedule = build(CurfewSelection::Blind, 20);
+    schedule
+        .apply_curfew(id("KRK"), Time(270), Time(400))
+        .unwrap();
+
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_1"));
+    assert_eq!(Unscheduled(AirportCurfew), status(&schedule, "FLIGHT_3"));
+    assert_eq!(Unscheduled(BrokenChain), status(&schedule, "FLIGHT_4"));
+}
+
+#[test]
+fn test_priority_curfew_cancels_low_value_loop() {
+    let mut schedule = build(CurfewSelection::Priority, 20);
+    let preview = schedule.preview_curfew(id("KRK"), Time(270), Time(400));
+    schedule
+        .apply_curfew(id("KRK"), Time(270), Time(400))
+        .unwrap();
+
+    assert_eq!(Unscheduled(AirportCurfew), status(&schedule, "FLIGHT_1"));
+    assert_eq!(Unscheduled(AirportCurfew), status(&schedule, "FLIGHT_2"));
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_3"));
+    assert_eq!(
+        Time(200),
+        schedule.flight(&key("FLIGHT_3")).unwrap().departure_time
+    );
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_4"));
+
+    let report = schedule.last_report().unwrap();
+    assert_eq!(vec![key("FLIGHT_3")], report.affected);
+    assert_eq!(preview.unscheduled, report.unscheduled);
+    assert_eq!(preview.affected, report.affected);
+}
+
+#[test]
+fn test_priority_curfew_keeps_heavier_loop() {
+    let mut schedule = build(CurfewSelection::Priority, 300);
+    schedule
+        .apply_curfew(id("KRK"), Time(270), Time(400))
+        .unwrap();
+
+    assert_eq!(Scheduled, status(&schedule, "FLIGHT_1"));
+    assert_eq!(Unscheduled(AirportCurfew), status(&schedule, "FLIGHT_3"));
+}
@@ -1,11 +1,12 @@
-use crate::flight::FlightId;
+use crate::error::IrropsError;
+use crate::flight::FlightKey;
 use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
 use crate::flight::UnscheduledReason::{
-    AircraftMaintenance, AirportCurfew, BrokenChain, MaxDelayExceeded, Waiting,
+    AircraftMaintenance, AirportCurfew, BrokenChain, MaxDelayExceeded, OutOfPosition, Waiting,
 };
 use crate::schedule::schedule::Schedule;
 use crate::schedule::tests::utils::{
-    add_aircraft, add_airport, add_flight, availability, curfew, id,
+    add_aircraft, add_airport, add_flight, availability, curfew, id, key,
 };
 use crate::time::Time;
 use std::collections::HashMap;
@@ -67,7 +68,7 @@ fn test_delay_full_absorption() {
 
     let mut schedule = Schedule::new(aircraft, airports, flights);
     schedule.assign();
-    schedule.apply_delay(id("FLIGHT_1"), 500);
+    schedule.apply_delay(key("FLIGHT_1"), 500).unwrap();
 
     assert_eq!(Time(1200) + 500, schedule.flights[0].departure_time);
     assert_eq!(Time(1500) + 500, schedule.flights[0].arrival_time);
@@ -133,15 +134,22 @@ fn test_delay_aircraft_first_flight_into_availability_disruption() {
 
     let mut schedule = Schedule::new(aircraft, airports, flights);
     schedule.assign();
-    schedule.apply_delay(id("FLIGHT_1"), 500);
+    schedule.apply_delay(key("FLIGHT_1"), 500).unwrap();
     let report = schedule
         .last_report
         .unwrap()
         .unscheduled
         .iter()
         .map(|(x, _)| x.clone())
-        .collect::<Vec<FlightId>>();
-    assert_eq!(vec![id("FLIGHT_1"), id("FLIGHT_2"), id("FLIGHT_3")], report);
+        .collect::<Vec<FlightKey>>();
+    assert_eq!(
+        vec![
+            key("FLIGHT_1"),
+            FlightKey::new("FLIGHT_2", 2),
+            FlightKey::new("FLIGHT_3", 2)
+        ],
+        report
+    );
 
     assert_eq!(Time(1700), schedule.flights[0].departure_time);
     assert_eq!(Time(2000), schedule.flights[0].arrival_time);
@@ -207,15 +215,18 @@ fn test_delay_aircraft_subsequent_flight_into_availability_disruption() {
 
     let mut schedule = Schedule::new(aircraft, airports, flights);
     schedule.assign();
-    schedule.apply_delay(id("FLIGHT_1"), 500);
+    schedule.apply_delay(key("FLIGHT_1"), 500).unwrap();
     let report = schedule
         .last_report
         .unwrap()
         .unscheduled
         .iter()
         .map(|(x, _)| x.clone())
-        .collect::<Vec<FlightId>>();
-    assert_eq!(vec![id("FLIGHT_2"), id("FLIGHT_3")], report);
+        .collect::<Vec<FlightKey>>();
+    assert_eq!(
+        vec![FlightKey::new("FLIGHT_2", 2), FlightKey::new("FLIGHT_3", 2)],
+        report
+    );
 
     assert_eq!(Time(1200) + 500, schedule.flights[0].departure_time);
     assert_eq!(Time(1500) + 500, schedule.flights[0].arrival_time);
@@ -279,15 +290,22 @@ fn test_delay_aircraft_first_flight_into_curfew() {
 
     let mut schedule = Schedule::new(aircraft, airports, flights);
     schedule.assign();
-    schedule.apply_delay(id("FLIGHT_1"), 150);
+    schedule.apply_delay(key("FLIGHT_1"), 150).unwrap();
     let report = schedule
         .last_report
         .unwrap()
         .unscheduled
         .iter()
         .map(|(x, _)| x.clone())
-        .collect::<Vec<FlightId>>();
-    assert_eq!(vec![id("FLIGHT_1"), id("FLIGHT_2"), id("FLIGHT_3")], report);
+        .collect::<Vec<FlightKey>>();
+    assert_eq!(
+        vec![
+            key("FLIGHT_1"),
+            FlightKey::new("FLIGHT_2", 2),
+            FlightKey::new("FLIGHT_3", 2)
+        ],
+        report
+    );
 
     assert_eq!(Time(1350), schedule.flights[0].departure_time);
     assert_eq!(Time(1650), schedule.flights[0].arrival_time);
@@ -351,15 +369,18 @@ fn test_delay_aircraft_subsequent_flight_into_curfew() {
 
     let mut schedule = Schedule::new(aircraft, airports, flights);
     schedule.assign();
-    schedule.apply_delay(id("FLIGHT_1"), 500);
+    schedule.apply_delay(key("FLIGHT_1"), 500).unwrap();
     let report = schedule
         .last_report
         .unwrap()
         .unscheduled
         .iter()
         .map(|(x, _)| x.clone())
-        .collect::<Vec<FlightId>>();
-    assert_eq!(vec![id("FLIGHT_2"), id("FLIGHT_3")], report);
+        .collect::<Vec<FlightKey>>();
+    assert_eq!(
+        vec![FlightKey::new("FLIGHT_2", 2), FlightKey::new("FLIGHT_3", 2)],
+        report
+    );
 
     assert_eq!(Time(1200) + 500, schedule.flights[0].departure_time);
     assert_eq!(Time(1500) + 500, schedule.flights[0].arrival_time);
@@ -420,15 +441,22 @@ fn test_delay_aircraft_first_flight_into_max_delay() {
 
     let mut schedule = Schedule::new(aircraft, airports, flights);
     schedule.assign();
-    schedule.apply_delay(id("FLIGHT_1"), 2050);
+    schedule.apply_delay(key("FLIGHT_1"), 2050).unwrap();
     let report = schedule
         .last_report
         .unwrap()
         .unscheduled
         .iter()
         .map(|(x, _)| x.clone())
-        .collect::<Vec<FlightId>>();
-    assert_eq!(vec![id("FLIGHT_1"), id("FLIGHT_2"), id("FLIGHT_3")], report);
+        .collect::<Vec<FlightKey>>();
+    assert_eq!(
+        vec![
+            key("FLIGHT_1"),
+            FlightKey::new("FLIGHT_2", 2),
+            FlightKey::new("FLIGHT_3", 2)
+        ],
+        report
+    );
 
     assert_eq!(Time(1200), schedule.flights[0].departure_time);
     assert_eq!(Time(1500), schedule.flights[0].arrival_time);
@@ -489,10 +517,10 @@ fn test_delay_aircraft_no_shift() {
 
     let mut schedule = Schedule::new(aircraft, airports, flights);
     schedule.assign();
-    schedule.apply_delay(id("FLIGHT_1"), 100);
+    schedule.apply_delay(key("FLIGHT_1"), 100).unwrap();
     let report = schedule.last_report.unwrap();
     assert!(report.unscheduled.is_empty());
-    assert_eq!(vec![id("FLIGHT_1")], report.affected);
+    assert_eq!(vec![key("FLIGHT_1")], report.affected);
 
     assert_eq!(Time(1300), schedule.flights[0].departure_time);
     assert_eq!(Time(1600), schedule.flights[0].arrival_time);
@@ -507,6 +535,42 @@ fn test_delay_aircraft_no_shift() {
     assert_eq!(Scheduled, schedule.flights[2].status);
 }
 
+#[test]
+fn test_delay_unknown_flight() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+    add_aircraft(&mut aircraft, "PLANE_1", "KRK", vec![]);
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        1200,
+        1500,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+
+    let mut schedule = Schedule::new(aircraft, airports, flights);
+    schedule.assign();
+    assert_eq!(
+        Some(IrropsError::UnknownFlight(key("FLIGHT_9"))),
+        schedule.apply_delay(key("FLIGHT_9"), 100).err()
+    );
+    assert_eq!(
+        Some(IrropsError::ZeroDelay(key("FLIGHT_1"))),
+        schedule.apply_delay(key("FLIGHT_1"), 0).err()
+    );
+    assert!(schedule.last_report.is_none());
+    assert!(schedule.history.is_empty());
+    assert_eq!(Time(1200), schedule.flights[0].departure_time);
+    assert_eq!(Scheduled, schedule.flights[0].status);
+}
+
 #[test]
 fn test_delay_aircraft_first_flight_by_overlap() {
     let mut aircraft = HashMap::new();
@@ -553,11 +617,15 @@ fn test_delay_aircraft_first_flight_by_overlap() {
 
     let mut schedule = Schedule::new(aircraft, airports, flights);
     schedule.assign();
-    schedule.apply_delay(id("FLIGHT_1"), 500);
+    schedule.apply_delay(key("FLIGHT_1"), 500).unwrap();
     let report = schedule.last_report.unwrap();
     assert!(report.unscheduled.is_empty());
     assert_eq!(
-        vec![id("FLIGHT_1"), id("FLIGHT_2"), id("FLIGHT_3")],
+        vec![
+            key("FLIGHT_1"),
+            FlightKey::new("FLIGHT_2", 2),
+            FlightKey::new("FLIGHT_3", 2)
+        ],
         report.affected
     );
 
@@ -620,11 +688,15 @@ fn test_delay_aircraft_first_flight_by_leapfrog() {
 
     let mut schedule = Schedule::new(aircraft, airports, flights);
     schedule.assign();
-    schedule.apply_delay(id("FLIGHT_1"), 1000);
+    schedule.apply_delay(key("FLIGHT_1"), 1000).unwrap();
     let report = schedule.last_report.unwrap();
     assert!(report.unscheduled.is_empty());
     assert_eq!(
-        vec![id("FLIGHT_1"), id("FLIGHT_2"), id("FLIGHT_3")],
+        vec![
+            key("FLIGHT_1"),
+            FlightKey::new("FLIGHT_2", 2),
+            FlightKey::new("FLIGHT_3", 2)
+        ],
         report.affected
     );
 
@@ -681,15 +753,15 @@ fn test_delay_into_spatial_disruption() {
     );
 
     let mut schedule = Schedule::new(aircraft, airports, flights);
-    schedule.apply_delay(id("FLIGHT_1"), 50);
+    schedule.apply_delay(key("FLIGHT_1"), 50).unwrap();
     let report = schedule.last_report.unwrap();
     let broken = report
         .unscheduled
         .iter()
         .map(|(x, _)| x.clone())
-        .collect::<Vec<FlightId>>();
-    assert_eq!(vec![id("FLIGHT_2")], broken);
-    assert_eq!(vec![id("FLIGHT_1")], report.affected);
+        .collect::<Vec<FlightKey>>();
+    assert_eq!(vec![FlightKey::new("FLIGHT_2", 2)], broken);
+    assert_eq!(vec![key("FLIGHT_1")], report.affected);
 
     assert_eq!(Time(1250), schedule.flights[0].departure_time);
     assert_eq!(Time(1550), schedule.flights[0].arrival_time);
@@ -697,7 +769,13 @@ fn test_delay_into_spatial_disruption() {
 
     assert_eq!(Time(1800), schedule.flights[1].departure_time);
     assert_eq!(Time(2000), schedule.flights[1].arrival_time);
-    assert_eq!(Unscheduled(AircraftMaintenance), schedule.flights[1].status);
+    assert_eq!(
+        Unscheduled(OutOfPosition {
+            expected: id("KRK"),
+            actual: id("WRO")
+        }),
+        schedule.flights[1].status
+    );
 }
 
 #[test]
@@ -740,10 +818,10 @@ fn test_delay_into_valid_base_maintenance() {
     );
 
     let mut schedule = Schedule::new(aircraft, airports, flights);
-    schedule.apply_delay(id("FLIGHT_1"), 50);
+    schedule.apply_delay(key("FLIGHT_1"), 50).unwrap();
     let report = schedule.last_report.unwrap();
     assert!(report.unscheduled.is_empty());
-    assert_eq!(vec![id("FLIGHT_1")], report.affected);
+    assert_eq!(vec![key("FLIGHT_1")], report.affected);
 
     assert_eq!(Time(1250), schedule.flights[0].departure_time);
     assert_eq!(Time(1550), schedule.flights[0].arrival_time);
@@ -753,3 +831,141 @@ fn test_delay_into_valid_base_maintenance() {
     assert_eq!(Time(2000), schedule.flights[1].arrival_time);
     assert_eq!(Scheduled, schedule.flights[1].status);
 }
+
+fn mid_chain(second_origin: &str) -> Schedule {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+    add_airport(&mut airports, "GDN", 30, vec![]);
+
+    add_aircraft(&mut aircraft, "PLANE_1", "KRK", vec![]);
+
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    // only 10 minutes on the ground at WAW, MTT is 30
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        second_origin,
+        "GDN",
+        210,
+        300,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_3",
+        "GDN",
+        "KRK",
+        400,
+        500,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+
+    Schedule::new(aircraft, airports, flights)
+}
+
+#[test]
+fn test_delay_repropagates_from_upstream_conflict() {
+    let mut schedule = mid_chain("WAW");
+    schedule.apply_delay(key("FLIGHT_3"), 10).unwrap();
+
+    assert_eq!(Scheduled, schedule.flights[0].status);
+    assert_eq!(Delayed { minutes: 20 }, schedule.flights[1].status);
+    assert_eq!(Time(230), schedule.flights[1].departure_time);
+    assert_eq!(Delayed { minutes: 10 }, schedule.flights[2].status);
+    assert_eq!(Time(410), schedule.flights[2].departure_time);
+
+    let report = schedule.last_report().unwrap();
+    assert_eq!(vec![key("FLIGHT_3"), key("FLIGHT_2")], report.affected);
+    assert!(report.unscheduled.is_empty());
+}
+
+#[test]
+fn test_delay_checks_aircraft_position() {
+    let mut schedule = mid_chain("KRK");
+    schedule.apply_delay(key("FLIGHT_2"), 10).unwrap();
+
+    let report = schedule.last_report().unwrap();
+    assert!(report.affected.is_empty());
+    assert_eq!(
+        vec![
+            (key("FLIGHT_2"), BrokenChain),
+            (key("FLIGHT_3"), BrokenChain)
+        ],
+        report.unscheduled
+    );
+    assert_eq!(Scheduled, schedule.flights[0].status);
+}
+
+#[test]
+fn test_delay_past_end_of_time_exceeds_max_delay() {
+    let mut schedule = mid_chain("WAW");
+    schedule.apply_delay(key("FLIGHT_3"), 10).unwrap();
+    schedule.apply_delay(key("FLIGHT_3"), u64::MAX).unwrap();
+    assert_eq!(Unscheduled(MaxDelayExceeded), schedule.flights[2].status);
+
+    // without a limit the shift still has to fit in time
+    let mut schedule = mid_chain("WAW");
+    schedule.max_delay = u64::MAX;
+    schedule.apply_delay(key("FLIGHT_3"), u64::MAX).unwrap();
+    assert_eq!(Unscheduled(MaxDelayExceeded), schedule.flights[2].status);
+    assert_eq!(Time(400), schedule.flights[2].departure_time);
+}
+
+/// `mid_chain` with FLIGHT_3 already 15 minutes late and a fourth leg, 100 minutes late,
+/// turning tight behind it.
+fn delayed_chain() -> Schedule {
+    let schedule = mid_chain("WAW");
+    let mut flights = schedule.flights.to_vec();
+    flights[2].status = Delayed { minutes: 15 };
+    flights[2].departure_time = Time(330);
+    flights[2].arrival_time = Time(430);
+    add_flight(
+        &mut flights,
+        "FLIGHT_4",
+        "KRK",
+        "GDN",
+        460,
+        560,
+        Some("PLANE_1"),
+        Delayed { minutes: 100 },
+    );
+    Schedule::new(schedule.aircraft, schedule.airports, flights)
+}
+
+#[test]
+fn test_delay_keeps_earlier_delays_down_the_rotation() {
+    let mut schedule = delayed_chain();
+    schedule.apply_delay(key("FLIGHT_3"), 10).unwrap();
+
+    // FLIGHT_2 is turned in time first, which holds FLIGHT_3 another 10 minutes
+    assert_eq!(Time(230), schedule.flights[1].departure_time);
+    assert_eq!(Time(350), schedule.flights[2].departure_time);
+    assert_eq!(Delayed { minutes: 35 }, schedule.flights[2].status);
+    assert_eq!(Time(480), schedule.flights[3].departure_time);
+    assert_eq!(Delayed { minutes: 120 }, schedule.flights[3].status);
+}
+
+#[test]
+fn test_delay_counts_earlier_delays_against_max_delay() {
+    let mut schedule = delayed_chain();
+    schedule.max_delay = 110;
+    schedule.apply_delay(key("FLIGHT_3"), 10).unwrap();
+
+    assert_eq!(Delayed { minutes: 35 }, schedule.flights[2].status);
+    assert_eq!(Unscheduled(MaxDelayExceeded), schedule.flights[3].status);
+}
@@ -0,0 +1,70 @@
+use crate::airport::Airport;
+use crate::delay_code::DelayCodes;
+use crate::export::history_csv;
+use crate::flight::UnscheduledReason::{AirportCurfew, BrokenChain};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{curfew, id, key, scenario};
+use crate::time::Time;
+
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 230, 330, Some("PLANE_1")),
+            ("FLIGHT_3", "KRK", "GDN", 400, 500, Some("PLANE_1")),
+            ("FLIGHT_4", "GDN", "KRK", 530, 630, Some("PLANE_1")),
+        ],
+    )
+    .airport(Airport {
+        disruptions: vec![curfew(600, 700)],
+        ..Airport::new("GDN", 30)
+    })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_codes_per_flight() {
+    let mut schedule = build();
+    schedule.apply_delay(key("FLIGHT_1"), 60).unwrap();
+
+    let codes = schedule
+        .flights
+        .iter()
+        .map(|f| schedule.delay_codes.flight(f, &schedule.history))
+        .collect::<Vec<_>>();
+    // the injected delay has no known cause, the one it pushes down the rotation does
+    assert_eq!(vec![None, Some(93), Some(93), Some(87)], codes);
+    assert_eq!(
+        vec![(key("FLIGHT_4"), AirportCurfew)],
+        schedule.last_report.unwrap().unscheduled
+    );
+}
+
+#[test]
+fn test_codes_in_history_csv() {
+    let mut schedule = build();
+    schedule
+        .apply_curfew(id("GDN"), Time(450), Time(520))
+        .unwrap();
+
+    let mut out = Vec::new();
+    history_csv(&mut out, &schedule).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+    assert_eq!(
+        Some("1,curfew,GDN,450,520,,0,2,FLIGHT_3/1,AirportCurfew,87,0,200"),
+        csv.lines().nth(1)
+    );
+}
+
+#[test]
+fn test_codes_from_config() {
+    let codes: DelayCodes =
+        serde_json::from_str(r#"{"reasons": {"BrokenChain": 91}, "injected": 99}"#).unwrap();
+    assert_eq!(Some(91), codes.reason(&BrokenChain));
+    assert_eq!(None, codes.reason(&AirportCurfew));
+    assert_eq!(Some(93), codes.rotational);
+    assert_eq!(Some(99), codes.injected);
+}
@@ -0,0 +1,67 @@
+use crate::schedule::events::EventError;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::scenario;
+use crate::time::Time;
+
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1"))],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_events_fire_in_time_order() {
+    let mut schedule = build();
+    assert_eq!(Ok(1), schedule.queue_event(Time(300), "delay FLIGHT_1 30"));
+    assert_eq!(
+        Ok(2),
+        schedule.queue_event(Time(100), " curfew KRK 400 500 ")
+    );
+    assert_eq!(Ok(3), schedule.queue_event(Time(300), "recover"));
+
+    let order = schedule.events().iter().map(|e| e.id).collect::<Vec<_>>();
+    assert_eq!(vec![2, 1, 3], order);
+
+    let due = schedule.take_due_events(Time(300));
+    assert_eq!(
+        vec!["curfew KRK 400 500", "delay FLIGHT_1 30", "recover"],
+        due.iter().map(|e| e.command.as_str()).collect::<Vec<_>>()
+    );
+    assert!(schedule.events().is_empty());
+}
+
+#[test]
+fn test_events_keep_later_ones_queued() {
+    let mut schedule = build();
+    schedule.queue_event(Time(100), "recover").unwrap();
+    schedule.queue_event(Time(500), "recover").unwrap();
+
+    assert_eq!(1, schedule.take_due_events(Time(499)).len());
+    assert_eq!(Time(500), schedule.events()[0].at);
+}
+
+#[test]
+fn test_events_cancel_and_reject() {
+    let mut schedule = build();
+    schedule.clock = Time(200);
+
+    assert_eq!(
+        Err(EventError::Past(Time(200))),
+        schedule.queue_event(Time(100), "recover")
+    );
+    assert_eq!(
+        Err(EventError::EmptyCommand),
+        schedule.queue_event(Time(300), "  ")
+    );
+    let id = schedule.queue_event(Time(300), "recover").unwrap();
+    assert_eq!(
+        Err(EventError::Unknown(id + 1)),
+        schedule.cancel_event(id + 1)
+    );
+    assert_eq!(Time(300), schedule.cancel_event(id).unwrap().at);
+    assert!(schedule.events().is_empty());
+}
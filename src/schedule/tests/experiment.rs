@@ -0,0 +1,78 @@
+use crate::schedule::experiment::{ExperimentError, ExperimentStep};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{add_aircraft, id, scenario};
+
+/// Three overlapping KRK-WAW departures and one aircraft at KRK to fly them.
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 160, None),
+            ("FLIGHT_2", "KRK", "WAW", 110, 170, None),
+            ("FLIGHT_3", "KRK", "WAW", 120, 180, None),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_experiment_marginal_spares() {
+    let schedule = build();
+    let experiment = schedule
+        .experiment_add_aircraft(id("A320"), id("KRK"), 3)
+        .unwrap();
+
+    assert_eq!(
+        vec![0, 1, 2, 3],
+        experiment.steps.iter().map(|s| s.added).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![2, 1, 0, 0],
+        experiment
+            .steps
+            .iter()
+            .map(|s| s.unscheduled)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(2, experiment.saved());
+    assert!((experiment.saved_per_aircraft() - 2.0 / 3.0).abs() < 1e-9);
+    // the schedule itself is untouched
+    assert_eq!(1, schedule.aircraft.len());
+    assert!(schedule.flights.iter().all(|f| f.status.is_unscheduled()));
+}
+
+#[test]
+fn test_experiment_spares_at_wrong_base() {
+    let experiment = build()
+        .experiment_add_aircraft(id("A320"), id("WAW"), 1)
+        .unwrap();
+    assert_eq!(
+        ExperimentStep {
+            added: 1,
+            unscheduled: 2
+        },
+        experiment.steps[1]
+    );
+    assert_eq!(0, experiment.saved());
+}
+
+#[test]
+fn test_experiment_skips_taken_ids_and_rejects() {
+    let mut schedule = build();
+    add_aircraft(&mut schedule.aircraft, "SPARE_1", "WAW", vec![]);
+    let experiment = schedule
+        .experiment_add_aircraft(id("A320"), id("KRK"), 1)
+        .unwrap();
+    assert_eq!(vec![id("SPARE_2")], experiment.aircraft);
+
+    assert_eq!(
+        Err(ExperimentError::UnknownAirport(id("GDN"))),
+        schedule.experiment_add_aircraft(id("A320"), id("GDN"), 1)
+    );
+    assert_eq!(
+        Err(ExperimentError::NoAircraft),
+        schedule.experiment_add_aircraft(id("A320"), id("KRK"), 0)
+    );
+}
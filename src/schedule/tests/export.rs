@@ -0,0 +1,183 @@
+use crate::export::{Date, flight_columns, flights_csv, history_csv, ics};
+use crate::flight::FlightStatus::{Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{
+    add_aircraft, add_airport, add_flight, availability, id, key, scenario,
+};
+use std::collections::HashMap;
+
+#[test]
+fn test_export_flights_and_history_csv() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+
+    add_aircraft(&mut aircraft, "PLANE_1", "KRK", vec![]);
+
+    add_flight(
+        &mut flights,
+        "FLIGHT,1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+
+    let mut schedule = Schedule::new(aircraft, airports, flights);
+    schedule.apply_delay(key("FLIGHT,1"), 30).unwrap();
+
+    let mut out = Vec::new();
+    flights_csv(&mut out, &schedule).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(2, lines.len());
+    assert_eq!(
+        "\"FLIGHT,1\",1,PLANE_1,KRK,WAW,130,230,Delayed,,30,,0,30",
+        lines[1]
+    );
+
+    let mut out = Vec::new();
+    history_csv(&mut out, &schedule).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+    assert_eq!(
+        Some("1,delay,\"FLIGHT,1/1\",,,30,1,0,,,,0,30"),
+        csv.lines().nth(1)
+    );
+}
+
+fn delayed_pair() -> Schedule {
+    let mut schedule = scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 230, 330, Some("PLANE_1")),
+        ],
+    )
+    .build()
+    .unwrap();
+    schedule.apply_delay(key("FLIGHT_1"), 60).unwrap();
+    schedule
+}
+
+#[test]
+fn test_flight_columns() {
+    let columns = flight_columns(&delayed_pair());
+    assert_eq!(vec!["FLIGHT_1", "FLIGHT_2"], columns.id);
+    assert_eq!(vec!["Delayed", "Delayed"], columns.status);
+    assert_eq!(vec![60, 60], columns.delay_minutes);
+    assert_eq!(vec![None, Some(93)], columns.delay_code);
+    assert_eq!(vec![160, 290], columns.departure_time);
+    assert_eq!(vec![Some(60), Some(60)], columns.plan_delta);
+}
+
+#[cfg(feature = "dataframe")]
+#[test]
+fn test_flights_parquet_round_trip() {
+    use polars::prelude::*;
+
+    let schedule = delayed_pair();
+    let frame = schedule.to_dataframe().unwrap();
+    assert_eq!((2, 13), frame.shape());
+
+    let mut parquet = vec![];
+    crate::export::flights_parquet(&mut parquet, &schedule).unwrap();
+    let read = ParquetReader::new(std::io::Cursor::new(parquet))
+        .finish()
+        .unwrap();
+    assert!(read.equals_missing(&frame));
+
+    let mut ipc = vec![];
+    crate::export::flights_ipc(&mut ipc, &schedule).unwrap();
+    let read = IpcReader::new(std::io::Cursor::new(ipc)).finish().unwrap();
+    assert!(read.equals_missing(&frame));
+}
+
+#[test]
+fn test_export_ics() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+
+    add_aircraft(
+        &mut aircraft,
+        "PLANE_1",
+        "KRK",
+        vec![availability(1400, 1500, Some(id("WAW")))],
+    );
+
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        "WAW",
+        "KRK",
+        1500,
+        1600,
+        None,
+        Unscheduled(Waiting),
+    );
+
+    let mut schedule = Schedule::new(aircraft, airports, flights);
+    schedule.apply_delay(key("FLIGHT_1"), 30).unwrap();
+
+    let mut out = Vec::new();
+    ics(&mut out, &schedule, "2024-02-28".parse().unwrap()).unwrap();
+    let calendar = String::from_utf8(out).unwrap();
+    assert!(calendar.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+
+    let events = calendar
+        .split("BEGIN:VEVENT\r\n")
+        .skip(1)
+        .map(|e| e.lines().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(3, events.len());
+    // maintenance first, then the rotation, then unassigned flights
+    assert!(events[0].contains(&"SUMMARY:PLANE_1 maintenance"));
+    assert!(events[0].contains(&"DTSTART:20240228T232000"));
+    assert!(events[0].contains(&"DTEND:20240229T010000"));
+    assert!(events[0].contains(&"LOCATION:WAW"));
+    assert!(events[1].contains(&"SUMMARY:FLIGHT_1/1 KRK-WAW"));
+    assert!(events[1].contains(&"DTSTART:20240228T021000"));
+    assert!(events[1].contains(&"DESCRIPTION:Delayed +30 min"));
+    assert!(events[1].contains(&"CATEGORIES:PLANE_1"));
+    assert!(events[2].contains(&"SUMMARY:FLIGHT_2/2 WAW-KRK"));
+    assert!(events[2].contains(&"DTSTART:20240229T010000"));
+    assert!(events[2].contains(&"CATEGORIES:UNASSIGNED"));
+    assert!(events[2].contains(&"STATUS:CANCELLED"));
+}
+
+#[test]
+fn test_parse_ics_start_date() {
+    assert_eq!(
+        Date {
+            year: 2023,
+            month: 12,
+            day: 31
+        },
+        "2023-12-31".parse().unwrap()
+    );
+    assert!("2024-02-29".parse::<Date>().is_ok());
+    assert!("2023-02-29".parse::<Date>().is_err());
+    assert!("2024-13-01".parse::<Date>().is_err());
+    assert!("yesterday".parse::<Date>().is_err());
+}
@@ -0,0 +1,154 @@
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::feasibility::ChainViolation;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{add_aircraft, add_airport, add_flight, availability, id, key};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[test]
+fn test_chain_violations_of_preassigned_rotation() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+    add_airport(&mut airports, "GDN", 30, vec![]);
+    add_aircraft(
+        &mut aircraft,
+        "PLANE_1",
+        "KRK",
+        vec![availability(500, 600, None)],
+    );
+
+    for (flight_id, origin, destination, dep, arr, plane, status) in [
+        (
+            "FLIGHT_1",
+            "KRK",
+            "WAW",
+            100,
+            160,
+            Some("PLANE_1"),
+            Scheduled,
+        ),
+        (
+            "FLIGHT_2",
+            "WAW",
+            "KRK",
+            180,
+            240,
+            Some("PLANE_1"),
+            Scheduled,
+        ),
+        (
+            "FLIGHT_3",
+            "GDN",
+            "KRK",
+            300,
+            360,
+            Some("PLANE_1"),
+            Scheduled,
+        ),
+        (
+            "FLIGHT_4",
+            "KRK",
+            "WAW",
+            550,
+            610,
+            Some("PLANE_1"),
+            Delayed { minutes: 0 },
+        ),
+        ("FLIGHT_5", "KRK", "WAW", 700, 760, None, Scheduled),
+        (
+            "FLIGHT_6",
+            "KRK",
+            "WAW",
+            800,
+            860,
+            Some("PLANE_9"),
+            Scheduled,
+        ),
+        (
+            "FLIGHT_7",
+            "KRK",
+            "WAW",
+            900,
+            960,
+            None,
+            Unscheduled(Waiting),
+        ),
+    ] {
+        add_flight(
+            &mut flights,
+            flight_id,
+            origin,
+            destination,
+            dep,
+            arr,
+            plane,
+            status,
+        );
+    }
+    let schedule = Schedule::new(aircraft, airports, flights);
+
+    assert_eq!(
+        vec![
+            ChainViolation::ZeroDelay(key("FLIGHT_4")),
+            ChainViolation::Assignment(key("FLIGHT_5")),
+            ChainViolation::TurnTime {
+                flight: key("FLIGHT_2"),
+                required: 30,
+                available: 20,
+            },
+            ChainViolation::Continuity {
+                flight: key("FLIGHT_3"),
+                expected: id("KRK"),
+                actual: id("GDN"),
+            },
+            ChainViolation::Maintenance(key("FLIGHT_4"), id("PLANE_1")),
+            ChainViolation::UnknownAircraft(key("FLIGHT_6"), id("PLANE_9")),
+        ],
+        schedule.chain_violations()
+    );
+}
+
+#[test]
+fn test_infeasible_scenario_loads_with_warnings() {
+    let path = std::env::temp_dir().join(format!("irrops-feasibility-{}.json", std::process::id()));
+    let leg = |id: &str, origin: &str, destination: &str, dep: u64| {
+        json!({
+            "id": id, "aircraft_id": "A1", "origin_id": origin, "destination_id": destination,
+            "departure_time": dep, "arrival_time": dep + 60, "status": "Scheduled"
+        })
+    };
+    let scenario = json!({
+        "aircraft": [{"id": "A1", "initial_location_id": "WAW", "disruptions": []}],
+        "airports": [
+            {"id": "WAW", "mtt": 30, "disruptions": []},
+            {"id": "KRK", "mtt": 30, "disruptions": []}
+        ],
+        "flights": [
+            leg("F1", "WAW", "KRK", 100),
+            leg("F2", "KRK", "WAW", 170),
+            leg("F3", "WAW", "KRK", 300)
+        ]
+    });
+    std::fs::write(&path, scenario.to_string()).unwrap();
+    let mut schedule = Schedule::load_from_file(path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        vec!["flight F2/1 departs 10 min after its aircraft lands, 30 min turn required"],
+        crate::schedule::validate::load_warnings(&schedule)
+            .iter()
+            .map(|issue| issue.message.as_str())
+            .collect::<Vec<_>>()
+    );
+    // the loaded violation does not trip the invariant checks afterwards
+    schedule.apply_delay(key("F3"), 10).unwrap();
+    assert_eq!(
+        Delayed { minutes: 10 },
+        schedule.flight(&key("F3")).unwrap().status
+    );
+}
@@ -0,0 +1,156 @@
+use crate::export::Date;
+use crate::feed::{
+    FeedError, FeedFormat, Ingested, Observation, opensky_observations, poll, poll_as,
+};
+use crate::flight::FlightKey;
+use crate::flight::FlightStatus::{Delayed, Scheduled};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{key, scenario};
+use crate::time::Time;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "WAW")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 1300, 1400, Some("PLANE_2")),
+            ("FLIGHT_2", "KRK", "WAW", 2040, 2140, Some("PLANE_2")),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+fn observed(flight: &str, departure: u64) -> Observation {
+    Observation {
+        flight: flight.to_string(),
+        departure: Time(departure),
+    }
+}
+
+#[test]
+fn test_ingest_applies_observed_delays() {
+    let mut schedule = build();
+    let ingested = schedule.ingest(&[
+        observed("FLIGHT_1 ", 130),
+        observed("FLIGHT_2", 2100),
+        observed("FLIGHT_9", 300),
+    ]);
+    assert_eq!(
+        Ingested {
+            delayed: vec![(key("FLIGHT_1"), 30), (FlightKey::new("FLIGHT_2", 2), 60)],
+            unmatched: vec!["FLIGHT_9".to_string()],
+        },
+        ingested
+    );
+    assert_eq!(Delayed { minutes: 30 }, schedule.flights[0].status);
+    assert_eq!(Scheduled, schedule.flights[1].status);
+    assert_eq!(Delayed { minutes: 60 }, schedule.flights[2].status);
+    assert_eq!(2, schedule.history.len());
+}
+
+#[test]
+fn test_ingest_is_idempotent() {
+    let mut schedule = build();
+    let observations = [observed("FLIGHT_1", 130), observed("FLIGHT_2/1", 1290)];
+    schedule.ingest(&observations);
+    assert_eq!(Ingested::default(), schedule.ingest(&observations));
+    assert_eq!(Time(130), schedule.flights[0].departure_time);
+    assert_eq!(Scheduled, schedule.flights[1].status);
+    assert_eq!(1, schedule.history.len());
+}
+
+#[test]
+fn test_ingest_matches_delay_past_midnight() {
+    let mut schedule = build();
+    schedule.ingest(&[observed("FLIGHT_2", 1450)]);
+    assert_eq!(Delayed { minutes: 150 }, schedule.flights[1].status);
+}
+
+/// Serves one request with `response` and returns its request line.
+fn serve_once(response: &'static str) -> (u16, std::thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(&stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            if header.trim().is_empty() {
+                break;
+            }
+        }
+        stream.write_all(response.as_bytes()).unwrap();
+        request_line
+    });
+    (port, server)
+}
+
+#[test]
+fn test_poll_http_feed() {
+    let (port, server) = serve_once(
+        "HTTP/1.1 200 OK\r\nContent-Length: 42\r\n\r\n[{\"flight\": \"FLIGHT_1\", \"departure\": 130}]",
+    );
+    let observations = poll(&format!("http://127.0.0.1:{}/departures", port)).unwrap();
+    assert_eq!("GET /departures HTTP/1.1\r\n", server.join().unwrap());
+    assert_eq!(vec![observed("FLIGHT_1", 130)], observations);
+}
+
+#[test]
+fn test_poll_chunked_http_feed() {
+    let (port, server) = serve_once(
+        "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+         14\r\n[{\"flight\": \"FLIGHT_\r\n16\r\n1\", \"departure\": 130}]\r\n0\r\n\r\n",
+    );
+    let observations = poll(&format!("http://127.0.0.1:{}/departures", port)).unwrap();
+    server.join().unwrap();
+    assert_eq!(vec![observed("FLIGHT_1", 130)], observations);
+}
+
+#[test]
+fn test_poll_reports_error_status() {
+    let (port, server) =
+        serve_once("HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n");
+    let result = poll(&format!("http://127.0.0.1:{}/departures", port));
+    server.join().unwrap();
+    assert!(matches!(result, Err(FeedError::Status(503))));
+}
+
+#[test]
+fn test_opensky_observations() {
+    // 2024-06-03 00:00 UTC is 1717372800
+    let json = r#"[
+        {"icao24": "48ae21", "callsign": "FLIGHT_1 ", "firstSeen": 1717380600, "estDepartureAirport": "EPKK"},
+        {"icao24": "48ae22", "callsign": null, "firstSeen": 1717380600},
+        {"icao24": "48ae23", "callsign": "EARLY", "firstSeen": 1717372000}
+    ]"#;
+    let day_one = "2024-06-03".parse::<Date>().unwrap();
+    assert_eq!(
+        vec![observed("FLIGHT_1", 130)],
+        opensky_observations(json, day_one).unwrap()
+    );
+}
+
+#[test]
+fn test_poll_opensky_feed() {
+    let (port, server) = serve_once(
+        "HTTP/1.1 200 OK\r\nContent-Length: 51\r\n\r\n[{\"callsign\": \"FLIGHT_1\", \"firstSeen\": 1717380600}]",
+    );
+    let day_one = "2024-06-03".parse::<Date>().unwrap();
+    let url = format!(
+        "http://127.0.0.1:{}/api/flights/departure?airport=EPKK",
+        port
+    );
+    let observations = poll_as(&url, FeedFormat::OpenSky, day_one).unwrap();
+    assert_eq!(
+        "GET /api/flights/departure?airport=EPKK HTTP/1.1\r\n",
+        server.join().unwrap()
+    );
+    assert_eq!(vec![observed("FLIGHT_1", 130)], observations);
+}
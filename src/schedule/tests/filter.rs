@@ -0,0 +1,83 @@
+use crate::filter::{FilterError, FlightFilter};
+use crate::flight::Flight;
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::tests::utils::add_flight;
+
+fn flights() -> Vec<Flight> {
+    let mut flights = Vec::new();
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        "KRK",
+        "WAW",
+        1440 + 100,
+        1440 + 200,
+        Some("PLANE_1"),
+        Delayed { minutes: 90 },
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_3",
+        "WAW",
+        "KRK",
+        1440 + 300,
+        1440 + 400,
+        None,
+        Unscheduled(Waiting),
+    );
+    for f in flights.iter_mut() {
+        f.day = f.departure_time.day();
+    }
+    flights
+}
+
+fn select(expression: &str) -> Vec<String> {
+    let filter = FlightFilter::parse(expression).unwrap();
+    flights()
+        .iter()
+        .filter(|f| filter.matches(f))
+        .map(|f| f.id.to_string())
+        .collect()
+}
+
+#[test]
+fn test_filter_conditions_joined_by_and() {
+    assert_eq!(vec!["FLIGHT_2"], select("route=KRK-WAW and day=2"));
+    assert_eq!(vec!["FLIGHT_2"], select("delay>60"));
+    assert_eq!(vec!["FLIGHT_1", "FLIGHT_3"], select("delay <= 60"));
+    assert_eq!(vec!["FLIGHT_3"], select("status=u"));
+    assert_eq!(vec!["FLIGHT_3"], select("aircraft != PLANE_1"));
+    assert_eq!(vec!["FLIGHT_2"], select("day >= 2 AND origin=krk"));
+}
+
+#[test]
+fn test_filter_rejects_bad_expressions() {
+    assert_eq!(Err(FilterError::Empty), FlightFilter::parse("  "));
+    assert_eq!(
+        Err(FilterError::UnknownField("gate".to_string())),
+        FlightFilter::parse("gate=A1")
+    );
+    assert_eq!(
+        Err(FilterError::Condition("route".to_string())),
+        FlightFilter::parse("route")
+    );
+    assert_eq!(
+        Err(FilterError::InvalidValue("route>KRK-WAW".to_string())),
+        FlightFilter::parse("route>KRK-WAW")
+    );
+    assert_eq!(
+        Err(FilterError::InvalidValue("delay>soon".to_string())),
+        FlightFilter::parse("delay>soon")
+    );
+}
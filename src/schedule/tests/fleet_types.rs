@@ -0,0 +1,198 @@
+use crate::aircraft::Aircraft;
+use crate::aircraft::Charter;
+use crate::airport::Airport;
+use crate::flight::FlightStatus::Unscheduled;
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::anneal::AnnealConfig;
+use crate::schedule::schedule::{Schedule, Strategy};
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+use std::collections::HashMap;
+
+/// WAW does not accept the B744 that PLANE_1 is.
+fn build(assigned: Option<&str>) -> Schedule {
+    scenario(
+        &["KRK"],
+        &[],
+        &[("FLIGHT_1", "KRK", "WAW", 100, 200, assigned)],
+    )
+    .airport(Airport {
+        restricted_types: vec![id("B744")],
+        ..Airport::new("WAW", 30)
+    })
+    .aircraft(Aircraft {
+        fleet_type: Some(id("B744")),
+        ..Aircraft::new("PLANE_1", "KRK")
+    })
+    .aircraft(Aircraft {
+        fleet_type: Some(id("A320")),
+        ..Aircraft::new("PLANE_2", "KRK")
+    })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_restricted_type_is_skipped() {
+    let anneal = AnnealConfig {
+        max_iterations: Some(500),
+        ..AnnealConfig::default()
+    };
+    for strategy in [
+        Strategy::Greedy(Default::default()),
+        Strategy::Flow,
+        Strategy::Anneal(anneal),
+    ] {
+        let mut schedule = build(None);
+        schedule.recover(strategy);
+        assert_eq!(Some(id("PLANE_2")), schedule.flights[0].aircraft_id);
+    }
+}
+
+#[test]
+fn test_restricted_charter_is_not_hired() {
+    let mut schedule = build(None);
+    schedule.aircraft.clear();
+    schedule.charters = vec![Charter {
+        id: id("WET_1"),
+        base_id: id("KRK"),
+        from: Time(0),
+        to: Time(1000),
+        cost_per_block_hour: 0,
+        fleet_type: Some(id("B744")),
+        etops: false,
+    }];
+    schedule.assign_charters();
+    assert_eq!(Unscheduled(Waiting), schedule.flights[0].status);
+
+    schedule.charters[0].fleet_type = Some(id("A320"));
+    schedule.assign_charters();
+    assert_eq!(Some(id("WET_1")), schedule.flights[0].aircraft_id);
+}
+
+#[test]
+fn test_type_restriction_violations() {
+    assert!(
+        build(Some("PLANE_2"))
+            .type_restriction_violations()
+            .is_empty()
+    );
+    assert_eq!(
+        vec![(key("FLIGHT_1"), id("B744"))],
+        build(Some("PLANE_1")).type_restriction_violations()
+    );
+}
+
+/// An 800 km KRK-WAW sector, beyond the 600 km of the E175 that PLANE_1 is.
+fn build_ranges(assigned: Option<&str>) -> Schedule {
+    let mut schedule = build(assigned);
+    schedule
+        .airports
+        .get_mut(&id("WAW"))
+        .unwrap()
+        .restricted_types = vec![];
+    schedule
+        .aircraft
+        .get_mut(&id("PLANE_1"))
+        .unwrap()
+        .fleet_type = Some(id("E175"));
+    schedule.ranges = HashMap::from([(id("E175"), 600), (id("A320"), 3000)]);
+    schedule.flights[0].distance = Some(800);
+    schedule
+}
+
+#[test]
+fn test_out_of_range_type_is_skipped() {
+    let anneal = AnnealConfig {
+        max_iterations: Some(500),
+        ..AnnealConfig::default()
+    };
+    for strategy in [
+        Strategy::Greedy(Default::default()),
+        Strategy::Flow,
+        Strategy::Anneal(anneal),
+    ] {
+        let mut schedule = build_ranges(None);
+        schedule.recover(strategy);
+        assert_eq!(Some(id("PLANE_2")), schedule.flights[0].aircraft_id);
+    }
+
+    let candidates = build_ranges(None).candidates(&key("FLIGHT_1"));
+    assert_eq!(Some(600), candidates[0].out_of_range);
+    assert!(!candidates[0].is_feasible());
+    assert!(candidates[1].is_feasible());
+}
+
+#[test]
+fn test_range_violations() {
+    assert!(build_ranges(Some("PLANE_2")).range_violations().is_empty());
+    assert_eq!(
+        vec![(key("FLIGHT_1"), id("E175"), 600)],
+        build_ranges(Some("PLANE_1")).range_violations()
+    );
+    // without a distance any type fits
+    let mut schedule = build_ranges(Some("PLANE_1"));
+    schedule.flights[0].distance = None;
+    assert!(schedule.range_violations().is_empty());
+}
+
+/// FLIGHT_1 as an ETOPS sector that only PLANE_2 is certified for.
+fn build_etops(assigned: Option<&str>) -> Schedule {
+    let mut schedule = build(assigned);
+    schedule
+        .airports
+        .get_mut(&id("WAW"))
+        .unwrap()
+        .restricted_types = vec![];
+    schedule.aircraft.get_mut(&id("PLANE_2")).unwrap().etops = true;
+    schedule.flights[0].etops = true;
+    schedule
+}
+
+#[test]
+fn test_etops_sector_needs_certified_tail() {
+    let anneal = AnnealConfig {
+        max_iterations: Some(500),
+        ..AnnealConfig::default()
+    };
+    for strategy in [
+        Strategy::Greedy(Default::default()),
+        Strategy::Flow,
+        Strategy::Anneal(anneal),
+    ] {
+        let mut schedule = build_etops(None);
+        schedule.recover(strategy);
+        assert_eq!(Some(id("PLANE_2")), schedule.flights[0].aircraft_id);
+    }
+
+    let candidates = build_etops(None).candidates(&key("FLIGHT_1"));
+    assert!(candidates[0].lacks_etops);
+    assert!(!candidates[0].is_feasible());
+    assert!(candidates[1].is_feasible());
+}
+
+#[test]
+fn test_etops_charter_and_violations() {
+    let mut schedule = build_etops(None);
+    schedule.aircraft.clear();
+    schedule.charters = vec![Charter {
+        id: id("WET_1"),
+        base_id: id("KRK"),
+        from: Time(0),
+        to: Time(1000),
+        cost_per_block_hour: 0,
+        fleet_type: None,
+        etops: false,
+    }];
+    schedule.assign_charters();
+    assert_eq!(Unscheduled(Waiting), schedule.flights[0].status);
+    schedule.charters[0].etops = true;
+    schedule.assign_charters();
+    assert_eq!(Some(id("WET_1")), schedule.flights[0].aircraft_id);
+
+    assert!(build_etops(Some("PLANE_2")).etops_violations().is_empty());
+    assert_eq!(
+        vec![(key("FLIGHT_1"), id("PLANE_1"))],
+        build_etops(Some("PLANE_1")).etops_violations()
+    );
+}
@@ -0,0 +1,138 @@
+use crate::flight::FlightStatus::{Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::schedule::{Schedule, Strategy};
+use crate::schedule::tests::utils::{add_aircraft, add_airport, add_flight, availability, id};
+use std::collections::HashMap;
+
+#[test]
+fn test_flow_holds_aircraft_for_longer_rotation() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+    add_airport(&mut airports, "GDN", 30, vec![]);
+
+    add_aircraft(&mut aircraft, "PLANE_1", "KRK", vec![]);
+
+    // first departure leads to a dead end at WAW
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        None,
+        Unscheduled(Waiting),
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        "KRK",
+        "GDN",
+        150,
+        250,
+        None,
+        Unscheduled(Waiting),
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_3",
+        "GDN",
+        "KRK",
+        300,
+        400,
+        None,
+        Unscheduled(Waiting),
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_4",
+        "KRK",
+        "GDN",
+        450,
+        550,
+        None,
+        Unscheduled(Waiting),
+    );
+
+    let mut greedy = Schedule::new(aircraft.clone(), airports.clone(), flights.clone());
+    greedy.assign();
+    let greedy_count = greedy
+        .flights
+        .iter()
+        .filter(|f| f.status == Scheduled)
+        .count();
+    assert_eq!(1, greedy_count);
+
+    let mut flow = Schedule::new(aircraft, airports, flights);
+    flow.recover(Strategy::Flow);
+    let flow_count = flow
+        .flights
+        .iter()
+        .filter(|f| f.status == Scheduled)
+        .count();
+    assert_eq!(3, flow_count);
+    assert_eq!(Unscheduled(Waiting), flow.flights[0].status);
+    assert_eq!(Some(id("PLANE_1")), flow.flights[1].aircraft_id);
+    assert_eq!(Some(id("PLANE_1")), flow.flights[3].aircraft_id);
+}
+
+#[test]
+fn test_flow_respects_aircraft_maintenance() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+
+    add_aircraft(
+        &mut aircraft,
+        "PLANE_1",
+        "KRK",
+        vec![availability(280, 450, None)],
+    );
+    add_aircraft(&mut aircraft, "PLANE_2", "KRK", vec![]);
+    add_aircraft(&mut aircraft, "PLANE_3", "KRK", vec![]);
+
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        None,
+        Unscheduled(Waiting),
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        "WAW",
+        "KRK",
+        300,
+        400,
+        None,
+        Unscheduled(Waiting),
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_3",
+        "KRK",
+        "WAW",
+        120,
+        220,
+        None,
+        Unscheduled(Waiting),
+    );
+
+    let mut schedule = Schedule::new(aircraft, airports, flights);
+    schedule.recover(Strategy::Flow);
+
+    assert!(schedule.flights.iter().all(|f| f.status == Scheduled));
+    let tail = schedule.flights[2].aircraft_id.clone().unwrap();
+    assert_ne!(id("PLANE_1"), tail);
+}
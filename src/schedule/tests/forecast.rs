@@ -0,0 +1,80 @@
+use crate::aircraft::Aircraft;
+use crate::aircraft::Availability;
+use crate::airport::Airport;
+use crate::flight::UnscheduledReason::{AircraftMaintenance, AirportCurfew};
+use crate::schedule::forecast::ForecastLeg;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{availability, curfew, id, key, scenario};
+use crate::time::Time;
+
+/// PLANE_1 flies KRK-WAW-KRK-GDN with turns 20 and 10 minutes short of the 30 minute
+/// MTT; GDN closes at 320.
+fn build(maintenance: Vec<Availability>) -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 160, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 170, 230, Some("PLANE_1")),
+            ("FLIGHT_3", "KRK", "GDN", 250, 310, Some("PLANE_1")),
+        ],
+    )
+    .airport(Airport {
+        disruptions: vec![curfew(320, 400)],
+        ..Airport::new("GDN", 30)
+    })
+    .aircraft(Aircraft {
+        disruptions: maintenance,
+        ..Aircraft::new("PLANE_1", "KRK")
+    })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_forecast_knock_on_into_curfew() {
+    let schedule = build(vec![]);
+    let forecast = schedule.forecast(&id("PLANE_1")).unwrap();
+
+    assert_eq!(
+        vec![
+            ForecastLeg {
+                flight: key("FLIGHT_1"),
+                departure_time: Time(100),
+                arrival_time: Time(160),
+                knock_on: 0,
+            },
+            ForecastLeg {
+                flight: key("FLIGHT_2"),
+                departure_time: Time(190),
+                arrival_time: Time(250),
+                knock_on: 20,
+            },
+        ],
+        forecast.legs
+    );
+    assert_eq!(Some((key("FLIGHT_3"), AirportCurfew)), forecast.break_at);
+    // nothing is applied
+    assert_eq!(
+        Time(170),
+        schedule.flight(&key("FLIGHT_2")).unwrap().departure_time
+    );
+}
+
+#[test]
+fn test_forecast_from_clock_into_maintenance() {
+    let mut schedule = build(vec![availability(240, 260, None)]);
+    schedule.clock = Time(120);
+    let forecast = schedule.forecast(&id("PLANE_1")).unwrap();
+
+    assert!(forecast.legs.is_empty());
+    assert_eq!(
+        Some((key("FLIGHT_2"), AircraftMaintenance)),
+        forecast.break_at
+    );
+}
+
+#[test]
+fn test_forecast_unknown_aircraft() {
+    assert!(build(vec![]).forecast(&id("PLANE_9")).is_none());
+}
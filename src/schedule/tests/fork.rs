@@ -0,0 +1,97 @@
+use crate::flight::FlightStatus::{Delayed, Scheduled};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::shared::Shared;
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+fn build() -> Schedule {
+    let mut schedule = scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 300, 400, Some("PLANE_1")),
+        ],
+    )
+    .build()
+    .unwrap();
+    schedule.assign();
+    schedule
+}
+
+#[test]
+fn test_fork_shares_flights_until_changed() {
+    let schedule = build();
+    let mut fork = schedule.fork();
+
+    assert!(Shared::ptr_eq(&schedule.flights, &fork.flights));
+    assert!(Shared::ptr_eq(&schedule.slots, &fork.slots));
+
+    fork.apply_delay(key("FLIGHT_1"), 150).unwrap();
+    assert!(!Shared::ptr_eq(&schedule.flights, &fork.flights));
+    assert!(Shared::ptr_eq(&schedule.slots, &fork.slots));
+
+    assert_eq!(Time(250), fork.flights[0].departure_time);
+    assert_eq!(Delayed { minutes: 150 }, fork.flights[0].status);
+    assert_eq!(Time(100), schedule.flights[0].departure_time);
+    assert_eq!(Scheduled, schedule.flights[0].status);
+    assert!(schedule.history.is_empty());
+}
+
+#[test]
+fn test_changing_the_original_leaves_the_fork() {
+    let mut schedule = build();
+    let fork = schedule.fork();
+
+    schedule.apply_delay(key("FLIGHT_2"), 30).unwrap();
+    assert_eq!(Time(330), schedule.flights[1].departure_time);
+    assert_eq!(Time(300), fork.flights[1].departure_time);
+    assert_eq!(
+        Time(300),
+        fork.flight(&key("FLIGHT_2")).unwrap().departure_time
+    );
+}
+
+#[test]
+fn test_fork_shares_baseline_and_history() {
+    let mut schedule = build();
+    schedule.apply_delay(key("FLIGHT_1"), 30).unwrap();
+    // the baseline is taken without copying the flights
+    schedule.capture_baseline();
+    assert!(Shared::ptr_eq(
+        schedule.baseline.as_ref().unwrap(),
+        &schedule.flights
+    ));
+
+    let mut fork = schedule.fork();
+    assert!(Shared::ptr_eq(
+        schedule.baseline.as_ref().unwrap(),
+        fork.baseline.as_ref().unwrap()
+    ));
+    assert!(Shared::ptr_eq(&schedule.history, &fork.history));
+
+    fork.apply_delay(key("FLIGHT_2"), 30).unwrap();
+    assert!(!Shared::ptr_eq(&schedule.history, &fork.history));
+    assert!(Shared::ptr_eq(
+        schedule.baseline.as_ref().unwrap(),
+        fork.baseline.as_ref().unwrap()
+    ));
+    assert_eq!(1, schedule.history.len());
+    assert_eq!(2, fork.history.len());
+}
+
+#[test]
+fn test_disruptions_change_unshared_flights_in_place() {
+    let mut schedule = build();
+    // the baseline would share them
+    schedule.baseline = None;
+    let flights = schedule.flights.as_ptr();
+
+    schedule.apply_delay(key("FLIGHT_1"), 30).unwrap();
+    schedule
+        .apply_curfew(id("WAW"), Time(280), Time(320))
+        .unwrap();
+    assert_eq!(flights, schedule.flights.as_ptr());
+    assert!(schedule.flights[1].status.is_unscheduled());
+    assert_eq!(2, schedule.history.len());
+}
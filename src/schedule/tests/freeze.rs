@@ -0,0 +1,113 @@
+use crate::flight::FlightStatus::{Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::anneal::AnnealConfig;
+use crate::schedule::schedule::{Schedule, Strategy};
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, None),
+            ("FLIGHT_2", "KRK", "WAW", 300, 400, None),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_assign_skips_frozen_flights() {
+    let mut schedule = build();
+    schedule.clock = Time(50);
+    schedule.freeze_horizon = 60;
+    schedule.assign();
+
+    assert_eq!(Time(110), schedule.frozen_until());
+    assert_eq!(Unscheduled(Waiting), schedule.flights[0].status);
+    assert_eq!(Scheduled, schedule.flights[1].status);
+    assert_eq!(vec![key("FLIGHT_1")], schedule.frozen_unscheduled());
+}
+
+#[test]
+fn test_recovery_records_frozen_flights() {
+    let mut schedule = build();
+    schedule.clock = Time(50);
+    schedule.freeze_horizon = 60;
+    let before = schedule.flights.clone();
+    let cost = schedule.total_cost();
+    schedule.recover(Strategy::Flow);
+    schedule.record_recovery("flow", &before, cost);
+
+    let recovery = schedule.recoveries.last().unwrap();
+    assert_eq!(vec![key("FLIGHT_1")], recovery.frozen);
+    assert_eq!(Time(110), recovery.frozen_until);
+    assert_eq!(
+        vec![key("FLIGHT_2")],
+        recovery
+            .outcome
+            .fixed
+            .iter()
+            .map(|(f, _)| f.clone())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_flow_skips_frozen_flights() {
+    let mut schedule = build();
+    schedule.freeze_horizon = 150;
+    schedule.recover(Strategy::Flow);
+
+    assert_eq!(None, schedule.flights[0].aircraft_id);
+    assert_eq!(Some(id("PLANE_1")), schedule.flights[1].aircraft_id);
+}
+
+#[test]
+fn test_anneal_keeps_frozen_assignment() {
+    let config = AnnealConfig {
+        max_iterations: Some(2_000),
+        ..AnnealConfig::default()
+    };
+    let build = || {
+        let mut schedule = build();
+        schedule.assign();
+        // the later flight is worth more, trading the first one for it pays off
+        schedule.flights[1].status = Unscheduled(Waiting);
+        schedule.flights[1].departure_time = Time(150);
+        schedule.flights[1].arrival_time = Time(250);
+        schedule.flights[1].passengers = 200;
+        schedule
+    };
+
+    let mut schedule = build();
+    schedule.recover(Strategy::Anneal(config));
+    assert_eq!(None, schedule.flights[0].aircraft_id);
+    assert_eq!(Some(id("PLANE_1")), schedule.flights[1].aircraft_id);
+
+    let mut schedule = build();
+    schedule.freeze_horizon = 120;
+    schedule.recover(Strategy::Anneal(config));
+    assert_eq!(Some(id("PLANE_1")), schedule.flights[0].aircraft_id);
+    assert_eq!(Scheduled, schedule.flights[0].status);
+    assert_eq!(None, schedule.flights[1].aircraft_id);
+}
+
+#[cfg(feature = "solver-milp")]
+#[test]
+fn test_milp_keeps_frozen_assignment() {
+    let mut schedule = build();
+    schedule.assign();
+    schedule.flights[1].status = Unscheduled(Waiting);
+    schedule.flights[1].departure_time = Time(150);
+    schedule.flights[1].arrival_time = Time(250);
+    schedule.flights[1].passengers = 200;
+    schedule.freeze_horizon = 120;
+    schedule.assign_milp().unwrap();
+
+    assert_eq!(Some(id("PLANE_1")), schedule.flights[0].aircraft_id);
+    assert_eq!(Time(100), schedule.flights[0].departure_time);
+    assert_eq!(None, schedule.flights[1].aircraft_id);
+}
@@ -0,0 +1,120 @@
+use crate::flight::Frequency;
+use crate::schedule::schedule::{LoadError, Schedule};
+use crate::time::Time;
+use serde_json::{Value, json};
+
+fn load_json(name: &str, scenario: Value) -> Result<Schedule, LoadError> {
+    let path = std::env::temp_dir().join(format!("irrops-{}-{}.json", name, std::process::id()));
+    std::fs::write(&path, scenario.to_string()).unwrap();
+    let result = Schedule::load_from_file(path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+    result
+}
+
+fn scenario(frequency: Value) -> Value {
+    json!({
+        "aircraft": [{"id": "PLANE_1", "initial_location_id": "KRK", "disruptions": []}],
+        "airports": [
+            {"id": "KRK", "mtt": 30, "disruptions": []},
+            {"id": "WAW", "mtt": 30, "disruptions": []}
+        ],
+        "flights": [
+            {
+                "id": "FLIGHT_1", "aircraft_id": null, "origin_id": "KRK", "destination_id": "WAW",
+                "departure_time": 420, "arrival_time": 480, "status": {"Unscheduled": "Waiting"},
+                "frequency": frequency
+            },
+            {
+                "id": "FLIGHT_2", "aircraft_id": null, "origin_id": "WAW", "destination_id": "KRK",
+                "departure_time": 600, "arrival_time": 660, "status": {"Unscheduled": "Waiting"}
+            }
+        ]
+    })
+}
+
+#[test]
+fn test_operating_days() {
+    let frequency = Frequency {
+        days_of_week: "1.3.5..".to_string(),
+        from_day: 3,
+        to_day: 14,
+    };
+    assert_eq!(Ok(vec![3, 5, 8, 10, 12]), frequency.operating_days());
+
+    let weekend = Frequency {
+        days_of_week: "67".to_string(),
+        from_day: 1,
+        to_day: 7,
+    };
+    assert_eq!(Ok(vec![6, 7]), weekend.operating_days());
+}
+
+#[test]
+fn test_frequency_expands_into_daily_flights() {
+    let schedule = load_json(
+        "frequency",
+        scenario(json!({"days_of_week": "1234567", "from_day": 1, "to_day": 3})),
+    )
+    .unwrap();
+
+    let expanded = schedule
+        .flights
+        .iter()
+        .filter(|f| &*f.id == "FLIGHT_1")
+        .map(|f| (f.day, f.departure_time, f.arrival_time))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            (1, Time(420), Time(480)),
+            (2, Time(1860), Time(1920)),
+            (3, Time(3300), Time(3360))
+        ],
+        expanded
+    );
+    assert_eq!(4, schedule.flights.len());
+}
+
+#[test]
+fn test_invalid_frequency_is_rejected() {
+    let result = load_json(
+        "bad-frequency",
+        scenario(json!({"days_of_week": "1.8", "from_day": 1, "to_day": 7})),
+    );
+    match result {
+        Err(LoadError::Json(e)) => assert!(e.to_string().contains("invalid weekday `8`")),
+        _ => panic!("expected a parse error"),
+    }
+}
+
+#[test]
+fn test_frequency_range_is_bounded() {
+    for (from_day, to_day) in [(1, 367), (u64::MAX - 1, u64::MAX)] {
+        let result = load_json(
+            "long-frequency",
+            scenario(json!({"days_of_week": "1234567", "from_day": from_day, "to_day": to_day})),
+        );
+        match result {
+            Err(LoadError::Json(e)) => assert!(e.to_string().contains("FLIGHT_1")),
+            _ => panic!("expected a parse error for {}..{}", from_day, to_day),
+        }
+    }
+
+    let schedule = load_json(
+        "year-frequency",
+        scenario(json!({"days_of_week": "1234567", "from_day": 1, "to_day": 366})),
+    )
+    .unwrap();
+    assert_eq!(367, schedule.flights.len());
+}
+
+#[test]
+fn test_load_from_slice() {
+    let text = scenario(json!({"days_of_week": "1", "from_day": 1, "to_day": 7})).to_string();
+    let schedule = Schedule::load_from_slice(text.as_bytes()).unwrap();
+    assert_eq!(2, schedule.flights.len());
+
+    assert!(matches!(
+        Schedule::load_from_slice(&text.as_bytes()[..text.len() / 2]),
+        Err(LoadError::Json(_))
+    ));
+}
@@ -0,0 +1,81 @@
+use crate::aircraft::Aircraft;
+use crate::airport::Airport;
+use crate::flight::Flight;
+use crate::golden::{self, Case};
+use crate::schedule::builder::ScheduleBuilder;
+use crate::schedule::tests::utils::key;
+use crate::time::Time;
+use std::fs;
+
+#[test]
+fn test_cases_pair_scenarios_with_scripts() {
+    let dir = std::env::temp_dir().join(format!("irrops-golden-cases-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for file in [
+        "b.json",
+        "b.script",
+        "a.json",
+        "a.script",
+        "a.golden.json",
+        "no_script.json",
+        "orphan.script",
+    ] {
+        fs::write(dir.join(file), "").unwrap();
+    }
+    let cases = golden::cases(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(
+        vec![
+            Case {
+                name: "a".to_string(),
+                scenario: dir.join("a.json"),
+                script: dir.join("a.script"),
+                golden: dir.join("a.golden.json"),
+            },
+            Case {
+                name: "b".to_string(),
+                scenario: dir.join("b.json"),
+                script: dir.join("b.script"),
+                golden: dir.join("b.golden.json"),
+            },
+        ],
+        cases
+    );
+}
+
+#[test]
+fn test_diff_outcomes() {
+    let mut schedule = ScheduleBuilder::new()
+        .airport(Airport::new("KRK", 30))
+        .airport(Airport::new("WAW", 30))
+        .aircraft(Aircraft::new("PLANE_1", "KRK"))
+        .flight(Flight::new("FLIGHT_1", "KRK", "WAW", Time(100), Time(160)).assigned("PLANE_1"))
+        .flight(Flight::new("FLIGHT_2", "WAW", "KRK", Time(200), Time(260)).assigned("PLANE_1"))
+        .build()
+        .unwrap();
+    let expected = golden::outcomes(&schedule);
+
+    // round trip through the golden file format
+    let path = std::env::temp_dir().join(format!("irrops-golden-{}.json", std::process::id()));
+    golden::write(&path, &expected).unwrap();
+    let stored = golden::read(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(golden::diff(&expected, &stored).is_empty());
+
+    schedule.apply_delay(key("FLIGHT_1"), 90).unwrap();
+    let mut actual = golden::outcomes(&schedule);
+    let flight_2 = actual.remove("FLIGHT_2/1").unwrap();
+    actual.insert("FLIGHT_3/1".to_string(), flight_2);
+
+    assert_eq!(
+        vec![
+            "FLIGHT_1/1 status: Scheduled -> Delayed { minutes: 90 }",
+            "FLIGHT_1/1 departure: DAY1 01:40 -> DAY1 03:10",
+            "FLIGHT_1/1 arrival: DAY1 02:40 -> DAY1 04:10",
+            "FLIGHT_2/1: missing",
+            "FLIGHT_3/1: unexpected",
+        ],
+        golden::diff(&expected, &actual)
+    );
+}
@@ -0,0 +1,102 @@
+use crate::airport::Airport;
+use crate::flight::FlightStatus::{Delayed, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::schedule::{Schedule, Strategy};
+use crate::schedule::tests::utils::{add_flight, id, key, scenario};
+use crate::time::Time;
+
+/// WAW turns aircraft in 30 min with one handling team. PLANE_2 lands FLIGHT_2 at 150;
+/// PLANE_1 lands FLIGHT_1 at 160 and turns for FLIGHT_3 at 200.
+fn build(handling: Option<u32>) -> Schedule {
+    scenario(
+        &["KRK", "GDN"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "GDN")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 160, Some("PLANE_1")),
+            ("FLIGHT_2", "GDN", "WAW", 90, 150, Some("PLANE_2")),
+            ("FLIGHT_3", "WAW", "KRK", 200, 260, Some("PLANE_1")),
+        ],
+    )
+    .airport(Airport {
+        handling,
+        ..Airport::new("WAW", 30)
+    })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_turn_waits_for_handler() {
+    let schedule = build(Some(1));
+
+    // PLANE_2 is serviced until 180, so PLANE_1's turn runs 180-210
+    assert_eq!(
+        Time(210),
+        schedule.turn_ready_time(&id("WAW"), Time(160), Some(&id("PLANE_1")))
+    );
+    assert_eq!(
+        Time(190),
+        build(Some(2)).turn_ready_time(&id("WAW"), Time(160), Some(&id("PLANE_1")))
+    );
+    assert_eq!(
+        Time(190),
+        build(None).turn_ready_time(&id("WAW"), Time(160), Some(&id("PLANE_1")))
+    );
+}
+
+#[test]
+fn test_free_handler_keeps_turn_time() {
+    let mut schedule = build(Some(1));
+    schedule.apply_delay(key("FLIGHT_1"), 20).unwrap();
+
+    // lands at 180 as PLANE_2's turn ends, ready at 210 as without a handling limit
+    assert_eq!(
+        Delayed { minutes: 10 },
+        schedule.flight(&key("FLIGHT_3")).unwrap().status
+    );
+}
+
+#[test]
+fn test_congested_turn_delays_more() {
+    let mut schedule = build(Some(1));
+    schedule.apply_delay(key("FLIGHT_1"), 5).unwrap();
+
+    // lands at 165 while PLANE_2 is still serviced until 180: ready at 210
+    assert_eq!(
+        Time(210),
+        schedule.flight(&key("FLIGHT_3")).unwrap().departure_time
+    );
+
+    let mut unlimited = build(None);
+    unlimited.apply_delay(key("FLIGHT_1"), 5).unwrap();
+    assert_eq!(
+        Time(200),
+        unlimited.flight(&key("FLIGHT_3")).unwrap().departure_time
+    );
+}
+
+#[test]
+fn test_flow_waits_for_handler() {
+    // FLIGHT_3 is open, as is FLIGHT_4 out of WAW a little later
+    let schedule = build(Some(1));
+    let mut flights = schedule.flights.to_vec();
+    flights[2].aircraft_id = None;
+    flights[2].status = Unscheduled(Waiting);
+    add_flight(
+        &mut flights,
+        "FLIGHT_4",
+        "WAW",
+        "GDN",
+        220,
+        280,
+        None,
+        Unscheduled(Waiting),
+    );
+    let mut schedule = Schedule::new(schedule.aircraft, schedule.airports, flights);
+    schedule.recover(Strategy::Flow);
+
+    // PLANE_1 is only turned at 210, too late for FLIGHT_3, but PLANE_2 is ready at 180
+    let aircraft = |k: &str| schedule.flight(&key(k)).unwrap().aircraft_id.clone();
+    assert_eq!(Some(id("PLANE_2")), aircraft("FLIGHT_3"));
+    assert_eq!(Some(id("PLANE_1")), aircraft("FLIGHT_4"));
+}
@@ -0,0 +1,65 @@
+use crate::cli::commands::Command;
+use crate::cli::help::{COMMANDS, detail};
+
+#[test]
+fn test_examples_parse() {
+    for help in COMMANDS {
+        for example in help.examples {
+            match Command::parse(example) {
+                Ok(Command::Plugin { name, .. }) => panic!("{} parsed as plugin {}", example, name),
+                Ok(_) => {}
+                Err(e) => panic!("{}: {}", example, e),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_every_command_has_help() {
+    for name in [
+        "ls",
+        "audit",
+        "baseline",
+        "compare",
+        "branch",
+        "clock",
+        "events",
+        "play",
+        "pause",
+        "stop",
+        "freeze",
+        "explain",
+        "history",
+        "impact",
+        "recover",
+        "rollover",
+        "slack",
+        "stats",
+        "strandings",
+        "utilization",
+        "webhook",
+        "help",
+        "exit",
+    ] {
+        assert!(detail(name).is_some(), "no help for {}", name);
+    }
+}
+
+#[test]
+fn test_detail_lists_every_form() {
+    let delay = detail("delay").unwrap();
+    assert!(delay.contains("Usage: delay <flight_id> <minutes>"));
+    assert!(delay.contains("Usage: delay where <conditions> by <minutes>"));
+    assert!(delay.contains("FL-101/2"));
+    assert!(delay.contains("See also: delay-airport, explain, foreach, ls, recover, retime"));
+
+    let clock = detail("clock").unwrap();
+    assert!(clock.contains("minutes since DAY1 00:00"));
+}
+
+#[test]
+fn test_detail_of_aliases_and_unknown_names() {
+    assert_eq!(detail("help"), detail("?"));
+    assert_eq!(detail("exit"), detail("quit"));
+    assert_eq!(None, detail("reroute"));
+}
@@ -0,0 +1,85 @@
+use crate::flight::FlightKey;
+use crate::flight::FlightStatus::{Delayed, Scheduled};
+use crate::schedule::schedule::{FlightLookupError, Schedule};
+use crate::schedule::tests::utils::{id, scenario};
+
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            // the same rotation on two consecutive days
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 300, 400, Some("PLANE_1")),
+            ("FLIGHT_1", "KRK", "WAW", 1540, 1640, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 1740, 1840, Some("PLANE_1")),
+            ("FLIGHT_3", "KRK", "WAW", 1900, 2000, None),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_repeated_flight_numbers_stay_apart() {
+    let mut schedule = build();
+    assert_eq!(5, schedule.flights.len());
+
+    schedule
+        .apply_delay(FlightKey::new("FLIGHT_1", 2), 30)
+        .unwrap();
+
+    let report = schedule.last_report().unwrap();
+    assert_eq!(vec![FlightKey::new("FLIGHT_1", 2)], report.affected);
+    assert_eq!(
+        Scheduled,
+        schedule
+            .flight(&FlightKey::new("FLIGHT_1", 1))
+            .unwrap()
+            .status
+    );
+    assert_eq!(
+        Delayed { minutes: 30 },
+        schedule
+            .flight(&FlightKey::new("FLIGHT_1", 2))
+            .unwrap()
+            .status
+    );
+}
+
+#[test]
+fn test_day_kept_when_delayed_past_midnight() {
+    let mut schedule = build();
+    schedule
+        .apply_delay(FlightKey::new("FLIGHT_2", 1), 1200)
+        .unwrap();
+
+    let flight = schedule.flight(&FlightKey::new("FLIGHT_2", 1)).unwrap();
+    assert_eq!(2, flight.departure_time.day());
+    assert_eq!(1, flight.day);
+}
+
+#[test]
+fn test_find_flight() {
+    let schedule = build();
+    assert_eq!(
+        Ok(FlightKey::new("FLIGHT_1", 2)),
+        schedule.find_flight("FLIGHT_1/2")
+    );
+    assert_eq!(
+        Ok(FlightKey::new("FLIGHT_3", 2)),
+        schedule.find_flight("FLIGHT_3")
+    );
+    assert_eq!(
+        Err(FlightLookupError::Ambiguous(id("FLIGHT_1"), vec![1, 2])),
+        schedule.find_flight("FLIGHT_1")
+    );
+    assert_eq!(
+        Err(FlightLookupError::Unknown("FLIGHT_3/1".to_string())),
+        schedule.find_flight("FLIGHT_3/1")
+    );
+    assert_eq!(
+        Err(FlightLookupError::Unknown("FLIGHT_9".to_string())),
+        schedule.find_flight("FLIGHT_9")
+    );
+}
@@ -0,0 +1,72 @@
+use crate::flight::FlightStatus::Scheduled;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{add_aircraft, add_airport, add_flight, curfew, key};
+use crate::time::Time;
+use std::collections::HashMap;
+
+#[test]
+fn test_impact_ranking_leaves_schedule_untouched() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![curfew(330, 400)]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+    add_airport(&mut airports, "GDN", 30, vec![]);
+
+    add_aircraft(&mut aircraft, "PLANE_1", "KRK", vec![]);
+    add_aircraft(&mut aircraft, "PLANE_2", "GDN", vec![]);
+
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        "WAW",
+        "KRK",
+        240,
+        300,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_3",
+        "GDN",
+        "KRK",
+        100,
+        200,
+        Some("PLANE_2"),
+        Scheduled,
+    );
+
+    let schedule = Schedule::new(aircraft, airports, flights);
+    let impact = schedule.impact(60);
+
+    assert_eq!(
+        vec![key("FLIGHT_1"), key("FLIGHT_2"), key("FLIGHT_3")],
+        impact
+            .iter()
+            .map(|i| i.flight_id.clone())
+            .collect::<Vec<_>>()
+    );
+    // FLIGHT_1 pushes FLIGHT_2 into the KRK curfew
+    assert_eq!(1, impact[0].unscheduled);
+    assert_eq!(60, impact[0].delay_minutes);
+    // FLIGHT_2 itself lands at KRK during the curfew
+    assert_eq!(1, impact[1].unscheduled);
+    assert_eq!(0, impact[1].delay_minutes);
+    assert_eq!(0, impact[2].unscheduled);
+    assert_eq!(60, impact[2].delay_minutes);
+
+    assert!(schedule.history.is_empty());
+    assert_eq!(Time(240), schedule.flights[2].departure_time);
+}
@@ -0,0 +1,61 @@
+use crate::schedule::schedule::{LoadError, Schedule};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use std::io::Write;
+use std::path::PathBuf;
+
+fn scenario() -> String {
+    format!("{}/data/default.json", env!("CARGO_MANIFEST_DIR"))
+}
+
+fn compressed(ext: &str, bytes: Vec<u8>) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("irrops-load-{}.json.{}", std::process::id(), ext));
+    std::fs::write(&path, bytes).unwrap();
+    path
+}
+
+fn assert_loads_like_plain(path: PathBuf) {
+    let plain = Schedule::load_from_file(&scenario()).unwrap();
+    let packed = Schedule::load_from_file(path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+    let packed = packed.unwrap();
+    let key = |s: &Schedule| {
+        s.flights
+            .iter()
+            .map(|f| (f.id.clone(), f.departure_time, f.arrival_time))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(key(&plain), key(&packed));
+    assert_eq!(plain.aircraft.len(), packed.aircraft.len());
+}
+
+#[test]
+fn test_load_gzip_matches_plain() {
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    encoder
+        .write_all(&std::fs::read(scenario()).unwrap())
+        .unwrap();
+    assert_loads_like_plain(compressed("gz", encoder.finish().unwrap()));
+}
+
+#[test]
+fn test_load_zstd_matches_plain() {
+    let json = std::fs::read(scenario()).unwrap();
+    assert_loads_like_plain(compressed("zst", zstd::encode_all(&json[..], 0).unwrap()));
+}
+
+#[test]
+fn test_load_corrupt_gzip_fails() {
+    let path = std::env::temp_dir().join(format!("irrops-corrupt-{}.json.gz", std::process::id()));
+    std::fs::write(&path, b"not gzip at all").unwrap();
+    let result = Schedule::load_from_file(path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_load_missing_file() {
+    let result = Schedule::load_from_file("/nonexistent/scenario.json.zst");
+    assert!(matches!(result, Err(LoadError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound));
+}
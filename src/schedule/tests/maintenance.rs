@@ -0,0 +1,111 @@
+use crate::aircraft::{Aircraft, MaintenanceCheck};
+use crate::airport::Airport;
+use crate::flight::FlightStatus::{Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::{BrokenChain, MaintenanceDue, Waiting};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+/// PLANE_1 starts at KRK and flies KRK-WAW-GDN-WAW, checked at one of `bases`.
+fn build(check: MaintenanceCheck, assigned: bool) -> Schedule {
+    let plane = assigned.then_some("PLANE_1");
+    scenario(
+        &[],
+        &[],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, plane),
+            ("FLIGHT_2", "WAW", "GDN", 220, 320, plane),
+            ("FLIGHT_3", "GDN", "WAW", 500, 600, plane),
+        ],
+    )
+    .airport(Airport::new("KRK", 0))
+    .airport(Airport::new("WAW", 0))
+    .airport(Airport::new("GDN", 0))
+    .aircraft(Aircraft {
+        maintenance: Some(check),
+        ..Aircraft::new("PLANE_1", "KRK")
+    })
+    .build()
+    .unwrap()
+}
+
+fn check(max_cycles: u32, due_by: Option<u64>, bases: &[&str]) -> MaintenanceCheck {
+    MaintenanceCheck {
+        flown_minutes: 0,
+        flown_cycles: 0,
+        max_minutes: 10_000,
+        max_cycles,
+        due_by: due_by.map(Time),
+        bases: bases.iter().map(|b| id(b)).collect(),
+    }
+}
+
+#[test]
+fn test_assign_stops_at_cycle_limit() {
+    let mut schedule = build(check(2, None, &["KRK"]), false);
+    schedule.assign();
+
+    assert_eq!(Scheduled, schedule.flights[0].status);
+    assert_eq!(Scheduled, schedule.flights[1].status);
+    assert_eq!(Unscheduled(Waiting), schedule.flights[2].status);
+    assert_eq!(None, schedule.flights[2].aircraft_id);
+}
+
+#[test]
+fn test_assign_resets_counters_at_base() {
+    let mut schedule = build(check(2, None, &["GDN"]), false);
+    schedule.assign();
+
+    assert!(
+        schedule
+            .flights
+            .iter()
+            .all(|f| f.aircraft_id == Some(id("PLANE_1")))
+    );
+}
+
+#[test]
+fn test_assign_respects_due_by() {
+    let mut schedule = build(check(10, Some(300), &["GDN"]), false);
+    schedule.assign();
+    assert_eq!(Scheduled, schedule.flights[0].status);
+    assert_eq!(Unscheduled(Waiting), schedule.flights[1].status);
+
+    // reaching the base first lifts the deadline
+    let mut schedule = build(check(10, Some(300), &["WAW"]), false);
+    schedule.assign();
+    assert!(schedule.flights.iter().all(|f| f.status == Scheduled));
+}
+
+#[test]
+fn test_delay_busts_maintenance_deadline() {
+    let mut schedule = build(check(10, Some(380), &["GDN"]), true);
+    schedule.apply_delay(key("FLIGHT_1"), 100).unwrap();
+
+    assert_eq!(Time(300), schedule.flights[1].departure_time);
+    assert_eq!(Unscheduled(MaintenanceDue), schedule.flights[1].status);
+    assert_eq!(Unscheduled(BrokenChain), schedule.flights[2].status);
+    assert_eq!(
+        Some((key("FLIGHT_2"), MaintenanceDue)),
+        schedule.last_report.unwrap().first_break
+    );
+}
+
+#[test]
+fn test_maintenance_defaults_when_missing() {
+    let aircraft: Aircraft = serde_json::from_str(
+        r#"{"id": "PLANE_1", "initial_location_id": "KRK", "disruptions": []}"#,
+    )
+    .unwrap();
+    assert!(aircraft.maintenance.is_none());
+
+    let aircraft: Aircraft = serde_json::from_str(
+        r#"{"id": "PLANE_1", "initial_location_id": "KRK", "disruptions": [],
+            "maintenance": {"flown_cycles": 3, "max_minutes": 600, "max_cycles": 4, "bases": ["KRK"]}}"#,
+    )
+    .unwrap();
+    let maintenance = aircraft.maintenance.unwrap();
+    assert_eq!(3, maintenance.flown_cycles);
+    assert_eq!(0, maintenance.flown_minutes);
+    assert_eq!(None, maintenance.due_by);
+}
@@ -0,0 +1,77 @@
+use crate::airport::Airport;
+use crate::schedule::merge::{MergeConflict, MergeError, MergeSummary};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{curfew, id, key, scenario};
+
+fn base() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1"))],
+    )
+    .build()
+    .unwrap()
+}
+
+fn program(aircraft_id: &str, flight_id: &str, waw_curfew: bool) -> Schedule {
+    let curfews = if waw_curfew {
+        vec![curfew(0, 60)]
+    } else {
+        vec![]
+    };
+    scenario(
+        &["GDN"],
+        &[(aircraft_id, "WAW")],
+        &[(flight_id, "WAW", "GDN", 50, 150, None)],
+    )
+    .airport(Airport {
+        disruptions: curfews,
+        ..Airport::new("WAW", 30)
+    })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_merge_adds_other_scenario() {
+    let mut schedule = base();
+    let summary = schedule
+        .merge(program("CHARTER_1", "FLIGHT_9", false))
+        .unwrap();
+
+    assert_eq!(
+        MergeSummary {
+            aircraft: 1,
+            airports: 1,
+            flights: 1,
+            charters: 0,
+        },
+        summary
+    );
+    assert_eq!(3, schedule.airports.len());
+    assert_eq!("FLIGHT_9", &*schedule.flights[0].id);
+    assert!(schedule.flight(&key("FLIGHT_1")).is_some());
+
+    schedule.assign();
+    assert_eq!(
+        Some(id("CHARTER_1")),
+        schedule.flight(&key("FLIGHT_9")).unwrap().aircraft_id
+    );
+}
+
+#[test]
+fn test_merge_conflicts_leave_schedule_untouched() {
+    let mut schedule = base();
+    let result = schedule.merge(program("PLANE_1", "FLIGHT_1", true));
+
+    assert_eq!(
+        Err(MergeError(vec![
+            MergeConflict::Aircraft(id("PLANE_1")),
+            MergeConflict::Airport(id("WAW")),
+            MergeConflict::Flight(key("FLIGHT_1")),
+        ])),
+        result
+    );
+    assert_eq!(1, schedule.flights.len());
+    assert_eq!(2, schedule.airports.len());
+}
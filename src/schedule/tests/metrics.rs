@@ -0,0 +1,264 @@
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::{BrokenChain, Waiting};
+use crate::metrics::{
+    DelayDistribution, GroupBy, GroupKey, Utilization, by_day, compare_recovery,
+    delay_distribution, group, listing_day, summarize, utilization,
+};
+use crate::schedule::tests::utils::{add_flight, id, key};
+use crate::time::Time;
+
+#[test]
+fn test_metrics_group_by() {
+    let mut flights = Vec::new();
+
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        "WAW",
+        "KRK",
+        1500,
+        1600,
+        Some("PLANE_1"),
+        Delayed { minutes: 40 },
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_3",
+        "KRK",
+        "GDN",
+        1700,
+        1800,
+        None,
+        Unscheduled(BrokenChain),
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_4",
+        "KRK",
+        "GDN",
+        1700,
+        1800,
+        None,
+        Unscheduled(Waiting),
+    );
+
+    let total = summarize(&flights);
+    assert_eq!(4, total.total);
+    assert_eq!(1, total.scheduled);
+    assert_eq!(1, total.delayed);
+    assert_eq!(2, total.unscheduled());
+    assert_eq!(1, total.broken_chain);
+    assert_eq!(40, total.delay_minutes);
+
+    let by_day = group(&flights, GroupBy::Day);
+    assert_eq!(1, by_day[&GroupKey::Day(1)].total);
+    assert_eq!(3, by_day[&GroupKey::Day(2)].total);
+    assert_eq!(40, by_day[&GroupKey::Day(2)].delay_minutes);
+
+    let by_airport = group(&flights, GroupBy::Airport);
+    assert_eq!(3, by_airport[&GroupKey::Id(id("KRK"))].total);
+    assert_eq!(1, by_airport[&GroupKey::Id(id("WAW"))].delayed);
+
+    let by_aircraft = group(&flights, GroupBy::Aircraft);
+    assert_eq!(2, by_aircraft[&GroupKey::Id(id("PLANE_1"))].total);
+    assert_eq!(2, by_aircraft[&GroupKey::Unassigned].unscheduled());
+}
+
+#[test]
+fn test_metrics_by_day() {
+    let mut flights = Vec::new();
+    for (id, departure) in [("FLIGHT_1", 1500), ("FLIGHT_2", 100), ("FLIGHT_3", 1700)] {
+        add_flight(
+            &mut flights,
+            id,
+            "KRK",
+            "WAW",
+            departure,
+            departure + 60,
+            None,
+            Scheduled,
+        );
+    }
+    // delayed past midnight, the flight is listed on the day it now departs
+    flights[1].departure_time = Time(1450);
+
+    let days = by_day(&flights);
+    assert_eq!(vec![&2], days.keys().collect::<Vec<_>>());
+    assert_eq!(
+        vec!["FLIGHT_1", "FLIGHT_2", "FLIGHT_3"],
+        days[&2].iter().map(|f| &*f.id).collect::<Vec<_>>()
+    );
+    assert_eq!(2, listing_day(&flights[1]));
+    assert_eq!(1, flights[1].day);
+}
+
+#[test]
+fn test_metrics_utilization() {
+    let mut flights = Vec::new();
+
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        "WAW",
+        "KRK",
+        300,
+        345,
+        Some("PLANE_1"),
+        Delayed { minutes: 15 },
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_3",
+        "KRK",
+        "GDN",
+        400,
+        500,
+        None,
+        Unscheduled(Waiting),
+    );
+
+    assert_eq!(
+        vec![
+            Utilization {
+                aircraft_id: id("PLANE_1"),
+                block_minutes: 145,
+                cycles: 2,
+            },
+            Utilization {
+                aircraft_id: id("PLANE_2"),
+                block_minutes: 0,
+                cycles: 0,
+            },
+        ],
+        utilization(&[id("PLANE_2"), id("PLANE_1")], &flights)
+    );
+}
+
+#[test]
+fn test_metrics_compare_recovery() {
+    let mut before = Vec::new();
+
+    add_flight(
+        &mut before,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    add_flight(
+        &mut before,
+        "FLIGHT_2",
+        "WAW",
+        "KRK",
+        300,
+        400,
+        None,
+        Unscheduled(Waiting),
+    );
+    add_flight(
+        &mut before,
+        "FLIGHT_3",
+        "KRK",
+        "GDN",
+        500,
+        600,
+        None,
+        Unscheduled(BrokenChain),
+    );
+    let mut after = before.clone();
+    after[1].aircraft_id = Some(id("PLANE_2"));
+    after[1].status = Delayed { minutes: 10 };
+
+    let outcome = compare_recovery(&before, &after);
+    assert_eq!(vec![(key("FLIGHT_2"), id("PLANE_2"))], outcome.fixed);
+    assert_eq!(vec![(key("FLIGHT_3"), BrokenChain)], outcome.broken);
+    assert_eq!(2, outcome.before.unscheduled());
+    assert_eq!(1, outcome.after.unscheduled());
+    assert_eq!(10, outcome.after.delay_minutes);
+}
+
+#[test]
+fn test_metrics_delay_distribution() {
+    let mut flights = Vec::new();
+    for (i, status) in [
+        Scheduled,
+        Scheduled,
+        Delayed { minutes: 12 },
+        Delayed { minutes: 40 },
+        Delayed { minutes: 95 },
+        Unscheduled(Waiting),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        add_flight(
+            &mut flights,
+            &format!("FLIGHT_{}", i + 1),
+            "KRK",
+            "WAW",
+            100,
+            200,
+            None,
+            status,
+        );
+    }
+
+    let dist = delay_distribution(&flights);
+
+    assert_eq!(5, dist.flights);
+    assert_eq!(10, dist.bucket_width);
+    assert_eq!(vec![2, 1, 0, 0, 1, 0, 0, 0, 0, 1], dist.buckets);
+    assert_eq!((12, 95, 95, 95), (dist.p50, dist.p90, dist.p99, dist.max));
+}
+
+#[test]
+fn test_metrics_delay_distribution_widens_buckets() {
+    let mut flights = Vec::new();
+    for i in 0..100u64 {
+        add_flight(
+            &mut flights,
+            &format!("FLIGHT_{}", i + 1),
+            "KRK",
+            "WAW",
+            100,
+            200,
+            None,
+            Delayed { minutes: i * 5 },
+        );
+    }
+
+    let dist = delay_distribution(&flights);
+
+    assert_eq!(60, dist.bucket_width);
+    assert_eq!(9, dist.buckets.len());
+    assert_eq!(100, dist.buckets.iter().sum::<usize>());
+    assert_eq!((245, 445, 490), (dist.p50, dist.p90, dist.p99));
+}
+
+#[test]
+fn test_metrics_delay_distribution_empty() {
+    assert_eq!(DelayDistribution::default(), delay_distribution(&[]));
+}
@@ -0,0 +1,60 @@
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::Waiting;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{add_aircraft, add_airport, add_flight, id};
+use crate::time::Time;
+use std::collections::HashMap;
+
+#[test]
+fn test_milp_cancels_dead_end_and_retimes() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+    add_airport(&mut airports, "GDN", 30, vec![]);
+
+    add_aircraft(&mut aircraft, "PLANE_1", "KRK", vec![]);
+
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        None,
+        Unscheduled(Waiting),
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        "KRK",
+        "GDN",
+        150,
+        250,
+        None,
+        Unscheduled(Waiting),
+    );
+    // needs a 10 minute retime after FLIGHT_2's turn at GDN
+    add_flight(
+        &mut flights,
+        "FLIGHT_3",
+        "GDN",
+        "KRK",
+        270,
+        370,
+        None,
+        Unscheduled(Waiting),
+    );
+
+    let mut schedule = Schedule::new(aircraft, airports, flights);
+    schedule.assign_milp().unwrap();
+
+    assert_eq!(Unscheduled(Waiting), schedule.flights[0].status);
+    assert_eq!(Scheduled, schedule.flights[1].status);
+    assert_eq!(Some(id("PLANE_1")), schedule.flights[2].aircraft_id);
+    assert_eq!(Delayed { minutes: 10 }, schedule.flights[2].status);
+    assert_eq!(Time(280), schedule.flights[2].departure_time);
+}
@@ -1,5 +1,77 @@
+mod airport_delay;
+mod alert;
+mod anneal;
 mod assign;
+mod atfm;
+mod audit;
+mod banks;
+mod baseline;
+mod block_bands;
+mod branch;
+mod breaks;
+mod builder;
+mod candidates;
+mod capacity;
+mod chain_repair;
+mod changes;
+mod charter;
+mod clone_day;
+mod closure_window;
+mod commands;
+mod compensation;
+mod config;
+mod constraint;
+mod crew;
 mod curfew;
+mod curfew_selection;
 mod delay;
+mod delay_code;
+mod events;
+mod experiment;
+mod export;
+mod feasibility;
+mod feed;
+mod filter;
+mod fleet_types;
+mod flow;
+mod forecast;
+mod fork;
+mod freeze;
+mod frequency;
+mod golden;
+mod handling;
+mod help;
+mod identity;
+mod impact;
+mod load;
+mod maintenance;
+mod merge;
+mod metrics;
+#[cfg(feature = "solver-milp")]
+mod milp;
+mod monte_carlo;
+mod operating_hours;
+mod out_of_position;
+mod play;
+mod plugin;
 mod proptests;
+mod report;
+mod retime;
+mod rollover;
+mod rotation;
+mod script;
+mod search;
+mod session;
+mod severity;
+mod slack;
+mod slots;
+mod spill;
+mod stands;
+mod strandings;
+mod time;
+mod turn_time;
+mod turn_times;
 mod utils;
+mod validate;
+mod webhook;
+mod windows;
@@ -0,0 +1,133 @@
+use crate::flight::Flight;
+use crate::flight::FlightStatus::Scheduled;
+use crate::schedule::monte_carlo::{MonteCarloConfig, MonteCarloError};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::scenario;
+use crate::time::Time;
+
+/// Two aircraft shuttling KRK-WAW four times each, with 30 minutes to spare per turn.
+fn build() -> Schedule {
+    let legs = (0..2u64).flat_map(|n| (0..4u64).map(move |leg| (n, leg)));
+    let builder = scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "KRK")],
+        &[],
+    );
+    let mut schedule = legs
+        .fold(builder, |builder, (n, leg)| {
+            let (origin, destination) = if leg % 2 == 0 {
+                ("KRK", "WAW")
+            } else {
+                ("WAW", "KRK")
+            };
+            let dep = 100 + n * 10 + leg * 120;
+            let flight_id = format!("FLIGHT_{}_{}", n + 1, leg + 1);
+            builder.flight(
+                Flight::new(&flight_id, origin, destination, Time(dep), Time(dep + 60))
+                    .assigned(&format!("PLANE_{}", n + 1)),
+            )
+        })
+        .build()
+        .unwrap();
+    schedule.assign();
+    schedule
+}
+
+#[test]
+fn test_monte_carlo_same_seed_same_result_on_any_thread_count() {
+    let schedule = build();
+    let config = MonteCarloConfig {
+        runs: 50,
+        probability: 30,
+        seed: 7,
+        ..MonteCarloConfig::default()
+    };
+    let run_on = |threads: usize| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap()
+            .install(|| schedule.monte_carlo(config.clone()).unwrap())
+    };
+
+    let single = run_on(1);
+    assert_eq!(single, run_on(4));
+    assert_eq!(50, single.runs.len());
+    assert!(single.runs.iter().any(|r| r.delayed > 0));
+    assert!(single.runs.iter().any(|r| r.summary.delay_minutes > 0));
+    // runs draw their own delays
+    assert!(single.runs.windows(2).any(|w| w[0].summary != w[1].summary));
+
+    let other = schedule
+        .monte_carlo(MonteCarloConfig {
+            seed: 8,
+            ..config.clone()
+        })
+        .unwrap();
+    assert_ne!(single.runs, other.runs);
+    // the schedule itself is untouched
+    assert!(schedule.flights.iter().all(|f| f.status == Scheduled));
+}
+
+#[test]
+fn test_monte_carlo_aggregates() {
+    // every flight takes exactly 40 minutes: 10 over the turn buffer, which propagates
+    let result = build()
+        .monte_carlo(MonteCarloConfig {
+            runs: 5,
+            probability: 100,
+            min: 40,
+            max: 40,
+            ..MonteCarloConfig::default()
+        })
+        .unwrap();
+
+    assert!(result.runs.iter().all(|r| r.delayed == 8));
+    assert!(result.runs.windows(2).all(|w| w[0].summary == w[1].summary));
+    let minutes = result.runs[0].summary.delay_minutes;
+    assert!(minutes >= 8 * 40);
+    assert_eq!(minutes as f64, result.mean_delay_minutes());
+    assert_eq!(minutes, result.delay_minutes_percentile(50));
+    assert_eq!(minutes, result.delay_minutes_percentile(100));
+    assert_eq!(0.0, result.mean_unscheduled());
+}
+
+#[test]
+fn test_monte_carlo_rejects() {
+    let schedule = build();
+    assert_eq!(
+        Err(MonteCarloError::NoRuns),
+        schedule
+            .monte_carlo(MonteCarloConfig {
+                runs: 0,
+                ..MonteCarloConfig::default()
+            })
+            .map(|_| ())
+    );
+    assert_eq!(
+        Err(MonteCarloError::InvalidProbability(120)),
+        schedule
+            .monte_carlo(MonteCarloConfig {
+                probability: 120,
+                ..MonteCarloConfig::default()
+            })
+            .map(|_| ())
+    );
+}
+
+#[test]
+fn test_monte_carlo_full_delay_range() {
+    // nearly every draw is far past the max delay and unschedules its flight
+    let result = build()
+        .monte_carlo(MonteCarloConfig {
+            runs: 3,
+            probability: 100,
+            min: 0,
+            max: u64::MAX,
+            ..MonteCarloConfig::default()
+        })
+        .unwrap();
+
+    assert_eq!(3, result.runs.len());
+    assert!(result.mean_unscheduled() > 0.0);
+}
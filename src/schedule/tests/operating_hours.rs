@@ -0,0 +1,82 @@
+use crate::airport::{Airport, OperatingHours};
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::{OutsideOperatingHours, Waiting};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{key, scenario};
+
+/// `assigned` maps a flight to its pre-assigned aircraft, if any.
+fn build(assigned: fn(&str) -> Option<&'static str>) -> Schedule {
+    let flights = [("FLIGHT_1", 100), ("FLIGHT_2", 1200), ("FLIGHT_3", 1300)]
+        .map(|(flight_id, dep)| (flight_id, "KRK", "WAW", dep, dep + 60, assigned(flight_id)));
+    scenario(
+        &[],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "KRK"), ("PLANE_3", "KRK")],
+        &flights,
+    )
+    .airport(Airport::new("KRK", 0))
+    // WAW is open 06:00-22:00
+    .airport(Airport {
+        operating_hours: Some(OperatingHours {
+            open: 360,
+            close: 1320,
+        }),
+        ..Airport::new("WAW", 0)
+    })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_assign_respects_operating_hours() {
+    let mut schedule = build(|_| None);
+    schedule.assign();
+
+    assert_eq!(Unscheduled(Waiting), schedule.flights[0].status);
+    assert_eq!(Scheduled, schedule.flights[1].status);
+    assert_eq!(Unscheduled(Waiting), schedule.flights[2].status);
+}
+
+#[test]
+fn test_delay_past_closing_time() {
+    let mut schedule = build(|flight_id| match flight_id {
+        "FLIGHT_2" => Some("PLANE_1"),
+        _ => None,
+    });
+    schedule.apply_delay(key("FLIGHT_2"), 30).unwrap();
+    assert_eq!(Delayed { minutes: 30 }, schedule.flights[1].status);
+
+    let mut schedule = build(|flight_id| match flight_id {
+        "FLIGHT_2" => Some("PLANE_1"),
+        _ => None,
+    });
+    schedule.apply_delay(key("FLIGHT_2"), 90).unwrap();
+    assert_eq!(
+        Some((key("FLIGHT_2"), OutsideOperatingHours)),
+        schedule.last_report().unwrap().first_break
+    );
+}
+
+#[test]
+fn test_closure_violations_on_load() {
+    let schedule = build(|flight_id| match flight_id {
+        "FLIGHT_1" => Some("PLANE_1"),
+        _ => None,
+    });
+    assert_eq!(
+        vec![(key("FLIGHT_1"), OutsideOperatingHours)],
+        schedule.closure_violations()
+    );
+}
+
+#[test]
+fn test_operating_hours_default_to_open() {
+    let airport: Airport =
+        serde_json::from_str(r#"{"id": "KRK", "mtt": 30, "disruptions": []}"#).unwrap();
+    assert_eq!(None, airport.operating_hours);
+
+    let airport: Airport = serde_json::from_str(
+        r#"{"id": "KRK", "mtt": 30, "disruptions": [], "operating_hours": {"open": 360, "close": 1320}}"#,
+    )
+    .unwrap();
+    assert_eq!("06:00-22:00", airport.operating_hours.unwrap().to_string());
+}
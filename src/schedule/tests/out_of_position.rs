@@ -0,0 +1,52 @@
+use crate::aircraft::Aircraft;
+use crate::delay_code::DelayCodes;
+use crate::flight::FlightStatus::Unscheduled;
+use crate::flight::UnscheduledReason::{AircraftMaintenance, OutOfPosition};
+use crate::metrics::summarize;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{availability, id, key, scenario};
+
+/// PLANE_1 waits at KRK but has to be in WAW for a check at 100-150, before FLIGHT_1
+/// (KRK-GDN, 200-300) departs.
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW", "GDN"],
+        &[],
+        &[("FLIGHT_1", "KRK", "GDN", 200, 300, None)],
+    )
+    .aircraft(Aircraft {
+        disruptions: vec![availability(100, 150, Some(id("WAW")))],
+        ..Aircraft::new("PLANE_1", "KRK")
+    })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_assign_reports_out_of_position() {
+    let mut schedule = build();
+    schedule.assign();
+
+    assert_eq!(
+        Unscheduled(OutOfPosition {
+            expected: id("WAW"),
+            actual: id("KRK")
+        }),
+        schedule.flight(&key("FLIGHT_1")).unwrap().status
+    );
+    let summary = summarize(&schedule.flights);
+    assert_eq!(1, summary.out_of_position);
+    assert_eq!(0, summary.aircraft_maintenance);
+}
+
+#[test]
+fn test_out_of_position_delay_code() {
+    let codes = DelayCodes::default();
+    let reason = OutOfPosition {
+        expected: id("WAW"),
+        actual: id("KRK"),
+    };
+
+    assert_eq!(Some(93), codes.reason(&reason));
+    assert_eq!(Some(41), codes.reason(&AircraftMaintenance));
+}
@@ -0,0 +1,49 @@
+use crate::play::{self, Movement, Pace};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+use std::time::Duration;
+
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 160, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 190, 250, Some("PLANE_1")),
+            ("FLIGHT_3", "KRK", "WAW", 120, 180, None),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_play_pace_scales_wall_clock() {
+    let pace = Pace::new(60, Time(100));
+    assert_eq!(Time(100), pace.clock_after(Duration::from_millis(900)));
+    assert_eq!(Time(101), pace.clock_after(Duration::from_secs(1)));
+    assert_eq!(Time(130), pace.clock_after(Duration::from_secs(30)));
+
+    let fast = Pace::new(600, Time(0));
+    assert_eq!(Time(50), fast.clock_after(Duration::from_secs(5)));
+}
+
+#[test]
+fn test_play_movements_between_ticks() {
+    let schedule = build();
+
+    assert_eq!(
+        vec![
+            Movement::Departed(key("FLIGHT_1"), id("KRK"), Time(100)),
+            Movement::Landed(key("FLIGHT_1"), id("WAW"), Time(160)),
+            Movement::Departed(key("FLIGHT_2"), id("WAW"), Time(190)),
+        ],
+        play::movements(&schedule, Time(0), Time(190))
+    );
+    assert_eq!(
+        vec![Movement::Landed(key("FLIGHT_2"), id("KRK"), Time(250))],
+        play::movements(&schedule, Time(190), Time(300))
+    );
+    assert!(play::movements(&schedule, Time(100), Time(150)).is_empty());
+}
@@ -0,0 +1,99 @@
+use crate::flight::FlightStatus::Scheduled;
+use crate::schedule::plugin::{PluginError, RecoveryStrategy, ReplCommand};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{id, scenario};
+
+/// Puts every waiting flight on the highest aircraft id, just to be told apart from
+/// the built-in first-fit.
+struct LastFit;
+
+impl RecoveryStrategy for LastFit {
+    fn name(&self) -> &str {
+        "last-fit"
+    }
+
+    fn description(&self) -> &str {
+        "Assign waiting flights to the highest aircraft id"
+    }
+
+    fn recover(&self, schedule: &mut Schedule) {
+        let last = schedule.aircraft.keys().max().cloned();
+        for f in schedule
+            .flights
+            .iter_mut()
+            .filter(|f| f.status.is_unscheduled())
+        {
+            f.aircraft_id = last.clone();
+            f.status = Scheduled;
+        }
+    }
+}
+
+struct Count;
+
+impl ReplCommand for Count {
+    fn name(&self) -> &str {
+        "count"
+    }
+
+    fn help(&self) -> &str {
+        "count [<airport>] - Count flights, optionally departing from <airport>"
+    }
+
+    fn run(&self, schedule: &mut Schedule, args: &[&str]) -> String {
+        let count = schedule
+            .flights
+            .iter()
+            .filter(|f| args.first().is_none_or(|ap| *f.origin_id == **ap))
+            .count();
+        format!("{} flights", count)
+    }
+}
+
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "KRK")],
+        &[("FLIGHT_1", "KRK", "WAW", 100, 200, None)],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_registered_strategy_recovers() {
+    let mut schedule = build();
+    schedule.register_strategy(LastFit);
+    assert_eq!(
+        vec!["last-fit"],
+        schedule.strategies().map(|s| s.name()).collect::<Vec<_>>()
+    );
+
+    assert_eq!(Ok(()), schedule.recover_with("last-fit"));
+    assert_eq!(Some(id("PLANE_2")), schedule.flights[0].aircraft_id);
+    assert_eq!(
+        Err(PluginError::UnknownStrategy("milp".to_string())),
+        schedule.recover_with("milp")
+    );
+}
+
+#[test]
+fn test_registered_command_runs() {
+    let mut schedule = build();
+    schedule.register_command(Count);
+    schedule.register_command(Count);
+    assert_eq!(1, schedule.commands().count());
+
+    assert_eq!(
+        Ok("1 flights".to_string()),
+        schedule.run_command("count", &[])
+    );
+    assert_eq!(
+        Ok("0 flights".to_string()),
+        schedule.run_command("count", &["WAW"])
+    );
+    assert_eq!(
+        Err(PluginError::UnknownCommand("tails".to_string())),
+        schedule.run_command("tails", &[])
+    );
+}
@@ -0,0 +1,107 @@
+use crate::report::{Format, post_mortem};
+use crate::schedule::schedule::{Objective, Schedule, Strategy};
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+/// PLANE_1 flies FLIGHT_1 KRK-WAW and FLIGHT_2 back; PLANE_2 waits at WAW.
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "WAW")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 300, 400, Some("PLANE_1")),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+fn session() -> Schedule {
+    let mut schedule = build();
+    schedule.max_delay = 300;
+    schedule.apply_delay(key("FLIGHT_1"), 500).unwrap();
+    let before = schedule.flights.clone();
+    let cost = schedule.total_cost();
+    schedule.recover(Strategy::Greedy(Objective::FirstFit));
+    schedule.record_recovery("greedy", &before, cost);
+    schedule
+        .apply_curfew(id("KRK"), Time(350), Time(450))
+        .unwrap();
+    schedule
+}
+
+fn render(schedule: &Schedule, format: Format) -> String {
+    let mut out = Vec::new();
+    post_mortem(&mut out, schedule, format).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_post_mortem_markdown() {
+    let document = render(&session(), Format::Markdown);
+    let headings = document
+        .lines()
+        .filter(|l| l.starts_with('#'))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            "# IRROPs post-mortem",
+            "## Scenario",
+            "## Session",
+            "### 1. Delay FLIGHT_1/1 by 500 min",
+            "### Recovery (greedy)",
+            "### 2. Curfew at KRK from DAY1 05:50 to DAY1 07:30",
+            "## Final KPIs",
+        ],
+        headings
+    );
+    assert!(document.contains(
+        "- FLIGHT_1/1 unscheduled (MaxDelayExceeded)\n- FLIGHT_2/1 unscheduled (BrokenChain)\n"
+    ));
+    assert!(
+        document.contains("- FLIGHT_1/1 assigned to PLANE_1\n- FLIGHT_2/1 assigned to PLANE_1\n")
+    );
+    assert!(document.contains("- FLIGHT_2/1 unscheduled (AirportCurfew)\n"));
+    assert!(document.contains("| Unscheduled | 1 | 50.0 |\n"));
+}
+
+#[test]
+fn test_post_mortem_lists_frozen_flights() {
+    let mut schedule = build();
+    schedule.max_delay = 300;
+    schedule.apply_delay(key("FLIGHT_1"), 500).unwrap();
+    schedule.freeze_horizon = 150;
+    let before = schedule.flights.clone();
+    let cost = schedule.total_cost();
+    schedule.recover(Strategy::Greedy(Objective::FirstFit));
+    schedule.record_recovery("greedy", &before, cost);
+
+    let document = render(&schedule, Format::Markdown);
+    assert!(
+        document
+            .contains("Left untouched inside the freeze window before DAY1 02:30: FLIGHT_1/1.\n")
+    );
+}
+
+#[test]
+fn test_post_mortem_html() {
+    let document = render(&session(), Format::Html);
+
+    assert!(document.starts_with("<!DOCTYPE html>"));
+    assert!(document.contains("<h3>Recovery (greedy)</h3>"));
+    assert!(document.contains("<li>FLIGHT_2/1 assigned to PLANE_1</li>"));
+    assert!(document.contains("<tr><td>Flights</td><td>2</td><td>100.0</td></tr>"));
+    assert!(document.trim_end().ends_with("</html>"));
+}
+
+#[test]
+fn test_post_mortem_empty_session() {
+    let document = render(&build(), Format::Markdown);
+
+    assert!(document.contains("No disruptions or recoveries."));
+    assert_eq!(Some(Format::Html), Format::from_path("out/session.HTML"));
+    assert_eq!(Some(Format::Markdown), Format::from_path("session.md"));
+    assert_eq!(None, Format::from_path("session.pdf"));
+}
@@ -0,0 +1,74 @@
+use crate::airport::Airport;
+use crate::flight::FlightStatus::{Delayed, Scheduled};
+use crate::flight::UnscheduledReason::AirportCurfew;
+use crate::schedule::retime::RetimeError;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{curfew, key, scenario};
+use crate::time::Time;
+
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "GDN", 400, 500, Some("PLANE_1")),
+        ],
+    )
+    .airport(Airport {
+        disruptions: vec![curfew(340, 360)],
+        ..Airport::new("GDN", 30)
+    })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_retime_earlier() {
+    let mut schedule = build();
+    assert_eq!(Ok(()), schedule.retime(key("FLIGHT_2"), -30));
+    assert_eq!(Time(370), schedule.flights[1].departure_time);
+    assert_eq!(Time(470), schedule.flights[1].arrival_time);
+    assert_eq!(Scheduled, schedule.flights[1].status);
+}
+
+#[test]
+fn test_retime_reduces_delay() {
+    let mut schedule = build();
+    assert_eq!(Ok(()), schedule.retime(key("FLIGHT_2"), 90));
+    assert_eq!(Delayed { minutes: 90 }, schedule.flights[1].status);
+
+    assert_eq!(Ok(()), schedule.retime(key("FLIGHT_2"), -60));
+    assert_eq!(Time(430), schedule.flights[1].departure_time);
+    assert_eq!(Delayed { minutes: 30 }, schedule.flights[1].status);
+}
+
+#[test]
+fn test_retime_rejects_infeasible_moves() {
+    let mut schedule = build();
+    assert_eq!(
+        Err(RetimeError::NotReady(Time(230))),
+        schedule.retime(key("FLIGHT_2"), -180)
+    );
+    assert_eq!(
+        Err(RetimeError::AirportClosed(AirportCurfew)),
+        schedule.retime(key("FLIGHT_2"), -100)
+    );
+    assert_eq!(
+        Err(RetimeError::TooEarly),
+        schedule.retime(key("FLIGHT_1"), -200)
+    );
+    assert_eq!(
+        Err(RetimeError::UnknownFlight(key("FLIGHT_9"))),
+        schedule.retime(key("FLIGHT_9"), -10)
+    );
+
+    schedule.clock = Time(350);
+    assert_eq!(
+        Err(RetimeError::Frozen(Time(350))),
+        schedule.retime(key("FLIGHT_2"), -60)
+    );
+
+    assert_eq!(Time(400), schedule.flights[1].departure_time);
+    assert_eq!(Scheduled, schedule.flights[1].status);
+}
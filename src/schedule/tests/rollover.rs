@@ -0,0 +1,129 @@
+use crate::aircraft::Aircraft;
+use crate::flight::FlightKey;
+use crate::schedule::rollover::{RolloverError, RolloverSummary};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{availability, id, scenario};
+use crate::schedule::validate;
+use crate::time::Time;
+
+/// PLANE_1 flies KRK-WAW on day 1, WAW-KRK overnight from day 1 into day 2, and
+/// KRK-WAW on days 2 and 3; PLANE_2 has maintenance on day 1 and on day 3.
+fn build(horizon: Option<u64>) -> Schedule {
+    let mut schedule = scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 600, 700, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 1400, 1500, Some("PLANE_1")),
+            (
+                "FLIGHT_3",
+                "KRK",
+                "WAW",
+                1440 + 600,
+                1440 + 700,
+                Some("PLANE_1"),
+            ),
+            (
+                "FLIGHT_4",
+                "WAW",
+                "KRK",
+                2 * 1440 + 600,
+                2 * 1440 + 700,
+                None,
+            ),
+        ],
+    )
+    .aircraft(Aircraft {
+        disruptions: vec![
+            availability(100, 200, None),
+            availability(2 * 1440 + 100, 2 * 1440 + 200, None),
+        ],
+        ..Aircraft::new("PLANE_2", "WAW")
+    })
+    .build()
+    .unwrap();
+    schedule.horizon = horizon;
+    schedule.apply_horizon();
+    schedule.reindex();
+    schedule
+}
+
+#[test]
+fn test_horizon_defers_later_days() {
+    let schedule = build(Some(2));
+
+    assert_eq!(3, schedule.flights.len());
+    assert_eq!(1, schedule.deferred.flights.len());
+    assert_eq!(1, schedule.deferred.maintenance.len());
+    assert_eq!(1, schedule.aircraft[&id("PLANE_2")].disruptions.len());
+    assert!(!schedule.slots.contains_key(&FlightKey::new("FLIGHT_4", 3)));
+    assert!(
+        validate::load_warnings(&schedule)
+            .iter()
+            .any(|i| i.message.contains("1 flight and 1 maintenance window"))
+    );
+}
+
+#[test]
+fn test_rollover_drops_completed_day_and_rebases() {
+    let mut schedule = build(Some(2));
+    schedule.clock = Time(1440 + 60);
+
+    assert_eq!(
+        Ok(RolloverSummary {
+            dropped: 2,
+            loaded: 1
+        }),
+        schedule.rollover(1)
+    );
+    assert_eq!(Time(60), schedule.clock);
+
+    let flight = schedule.flight(&FlightKey::new("FLIGHT_3", 1)).unwrap();
+    assert_eq!(Time(600), flight.departure_time);
+    assert_eq!(
+        Some(&(Time(600), Time(700))),
+        schedule.slots.get(&FlightKey::new("FLIGHT_3", 1))
+    );
+    let loaded = schedule.flight(&FlightKey::new("FLIGHT_4", 2)).unwrap();
+    assert_eq!(Time(1440 + 600), loaded.departure_time);
+    assert!(schedule.deferred.is_empty());
+
+    // PLANE_1 lands the overnight leg at KRK 60 minutes into the new day
+    let plane = &schedule.aircraft[&id("PLANE_1")];
+    assert_eq!(id("KRK"), plane.initial_location_id);
+    assert_eq!(Time(0), plane.disruptions[0].from);
+    assert_eq!(Time(60), plane.disruptions[0].to);
+
+    let windows = &schedule.aircraft[&id("PLANE_2")].disruptions;
+    assert_eq!(1, windows.len());
+    assert_eq!(Time(1440 + 100), windows[0].from);
+}
+
+#[test]
+fn test_rollover_needs_completed_days() {
+    let mut schedule = build(None);
+    schedule.clock = Time(1000);
+
+    assert_eq!(Err(RolloverError::NotCompleted(1)), schedule.rollover(1));
+    assert_eq!(Err(RolloverError::NoDays), schedule.rollover(0));
+
+    schedule.clock = Time(1440 + 60);
+    assert_eq!(Err(RolloverError::NotCompleted(2)), schedule.rollover(2));
+    assert_eq!(4, schedule.flights.len());
+}
+
+#[test]
+fn test_horizon_days_from_scenario() {
+    let raw = serde_json::from_str(
+        r#"{"aircraft": [], "airports": [], "horizon_days": 1, "flights": [
+            {"id": "FL-1", "aircraft_id": null, "origin_id": "KRK", "destination_id": "WAW",
+             "departure_time": 1500, "arrival_time": 1600, "status": {"Unscheduled": "Waiting"}}
+        ]}"#,
+    )
+    .unwrap();
+    let schedule = Schedule::from_scenario(raw);
+
+    assert_eq!(Some(1), schedule.horizon);
+    assert!(schedule.flights.is_empty());
+    assert_eq!(1, schedule.deferred.flights.len());
+}
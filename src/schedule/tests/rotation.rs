@@ -0,0 +1,65 @@
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+/// PLANE_1 flies KRK-WAW-KRK, with the legs listed out of departure order; FLIGHT_3 from
+/// WAW waits for an aircraft.
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "WAW")],
+        &[
+            ("FLIGHT_2", "WAW", "KRK", 300, 400, Some("PLANE_1")),
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_3", "WAW", "KRK", 500, 600, None),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+fn rotation(schedule: &Schedule, aircraft_id: &str) -> Vec<String> {
+    schedule
+        .rotation(&id(aircraft_id))
+        .into_iter()
+        .map(|f| f.key().to_string())
+        .collect()
+}
+
+#[test]
+fn test_rotation_in_departure_order() {
+    let schedule = build();
+
+    assert_eq!(
+        vec!["FLIGHT_1/1", "FLIGHT_2/1"],
+        rotation(&schedule, "PLANE_1")
+    );
+    assert!(rotation(&schedule, "PLANE_2").is_empty());
+    assert!(rotation(&schedule, "PLANE_9").is_empty());
+}
+
+#[test]
+fn test_rotation_follows_assign_and_unschedule() {
+    let mut schedule = build();
+
+    schedule.assign();
+    assert_eq!(vec!["FLIGHT_3/1"], rotation(&schedule, "PLANE_2"));
+
+    schedule
+        .apply_curfew(id("KRK"), Time(380), Time(420))
+        .unwrap();
+    assert_eq!(vec!["FLIGHT_1/1"], rotation(&schedule, "PLANE_1"));
+    assert_eq!(vec!["FLIGHT_3/1"], rotation(&schedule, "PLANE_2"));
+}
+
+#[test]
+fn test_rotation_follows_delays() {
+    let mut schedule = build();
+
+    schedule.apply_delay(key("FLIGHT_1"), 150).unwrap();
+    let legs = schedule.rotation(&id("PLANE_1"));
+    assert_eq!(
+        vec![Time(250), Time(380)],
+        legs.iter().map(|f| f.departure_time).collect::<Vec<_>>()
+    );
+}
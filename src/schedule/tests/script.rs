@@ -0,0 +1,152 @@
+use crate::flight::Flight;
+use crate::flight::FlightKey;
+use crate::flight::FlightStatus::{Delayed, Scheduled};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{key, scenario};
+use crate::script::{self, ForeachError, ScriptError};
+use crate::time::Time;
+
+fn build() -> Schedule {
+    let flight = |flight_id, origin, destination, dep, arr| {
+        Flight::new(flight_id, origin, destination, Time(dep), Time(arr)).assigned("PLANE_1")
+    };
+    scenario(&["KRK", "WAW"], &[("PLANE_1", "KRK")], &[])
+        .flight(flight("FLIGHT_1", "KRK", "WAW", 100, 200))
+        .flight(Flight {
+            status: Delayed { minutes: 20 },
+            ..flight("FLIGHT_2", "WAW", "KRK", 300, 400)
+        })
+        .flight(flight("FLIGHT_1", "KRK", "WAW", 1540, 1640))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_foreach_expands_matching_flights() {
+    let schedule = build();
+    assert_eq!(
+        Ok(vec![
+            "delay FLIGHT_1/1 15".to_string(),
+            "delay FLIGHT_1/2 15".to_string()
+        ]),
+        script::foreach(&schedule, "scheduled", "delay {flight} 15")
+    );
+    assert_eq!(
+        Ok(vec!["retime FLIGHT_2/1 -20".to_string()]),
+        script::foreach(&schedule, "d", "retime {flight} -20")
+    );
+    assert_eq!(
+        3,
+        script::foreach(&schedule, "all", "explain {flight}")
+            .unwrap()
+            .len()
+    );
+}
+
+#[test]
+fn test_foreach_rejects_bad_input() {
+    let schedule = build();
+    assert_eq!(
+        Err(ForeachError::UnknownFilter("late".to_string())),
+        script::foreach(&schedule, "late", "delay {flight} 15")
+    );
+    assert_eq!(
+        Err(ForeachError::MissingPlaceholder),
+        script::foreach(&schedule, "all", "recover")
+    );
+}
+
+#[test]
+fn test_read_skips_comments_and_blank_lines() {
+    let path = std::env::temp_dir().join(format!("irrops-script-{}.txt", std::process::id()));
+    std::fs::write(
+        &path,
+        "# stress the morning wave\n\nforeach scheduled delay {flight} 30\n  recover --strategy flow\n",
+    )
+    .unwrap();
+    let lines = script::read(path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        vec![
+            "foreach scheduled delay {flight} 30".to_string(),
+            "recover --strategy flow".to_string()
+        ],
+        lines
+    );
+}
+
+fn write_script(name: &str, text: &str) -> String {
+    let path = std::env::temp_dir().join(format!("irrops-{}-{}", std::process::id(), name));
+    std::fs::write(&path, text).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_expand_splices_nested_scripts() {
+    let inner = write_script("inner.script", "delay FLIGHT_1/1 15\n");
+    let outer = write_script(
+        "outer.script",
+        &format!("# morning\nscript run {}\nrecover\n", inner),
+    );
+    assert_eq!(
+        vec!["delay FLIGHT_1/1 15".to_string(), "recover".to_string()],
+        script::expand(&outer).unwrap()
+    );
+}
+
+#[test]
+fn test_expand_refuses_scripts_running_themselves() {
+    let path = std::env::temp_dir().join(format!("irrops-{}-loop.script", std::process::id()));
+    let path = path.to_str().unwrap().to_string();
+    std::fs::write(&path, format!("delay FLIGHT_1/1 15\nscript run {}\n", path)).unwrap();
+    match script::expand(&path) {
+        Err(ScriptError::Cycle(paths)) => assert_eq!(vec![path.clone(), path], paths),
+        other => panic!("expected a cycle, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_rhai_branches_on_flight_state() {
+    let mut schedule = build();
+    let path = write_script(
+        "branch.rhai",
+        r#"
+        let hit = [];
+        for key in flights() {
+            let f = flight(key);
+            if f.status == "scheduled" && f.departure < 1440 {
+                delay(key, 30);
+                hit.push(key);
+            }
+        }
+        repl("candidates " + hit[0]);
+        "#,
+    );
+    let queued = script::run_rhai(&mut schedule, &path).unwrap();
+    assert_eq!(vec!["candidates FLIGHT_1/1".to_string()], queued);
+    let delayed = schedule.flight(&key("FLIGHT_1")).unwrap();
+    assert_eq!(Delayed { minutes: 30 }, delayed.status);
+    assert_eq!(
+        Scheduled,
+        schedule
+            .flight(&FlightKey::new("FLIGHT_1", 2))
+            .unwrap()
+            .status
+    );
+}
+
+#[test]
+fn test_failing_rhai_script_leaves_schedule_alone() {
+    let mut schedule = build();
+    let path = write_script(
+        "throw.rhai",
+        r#"delay("FLIGHT_1/1", 30); delay("NOPE", 10);"#,
+    );
+    assert!(matches!(
+        script::run_rhai(&mut schedule, &path),
+        Err(ScriptError::Eval(_))
+    ));
+    assert_eq!(Scheduled, schedule.flight(&key("FLIGHT_1")).unwrap().status);
+    let path = write_script("queue.rhai", r#"repl("script run again.rhai");"#);
+    assert!(script::run_rhai(&mut schedule, &path).is_err());
+}
@@ -0,0 +1,117 @@
+use crate::aircraft::Aircraft;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::search::{Hit, did_you_mean};
+use crate::schedule::tests::utils::{id, scenario};
+
+/// FL-101 (KRK-WAW) operates on days 1 and 2 on ALPHA, FL-202 (WAW-GDN) waits for an
+/// aircraft; BRAVO is an idle A320.
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW", "GDN"],
+        &[("ALPHA", "KRK")],
+        &[
+            ("FL-101", "KRK", "WAW", 100, 160, Some("ALPHA")),
+            ("FL-101", "KRK", "WAW", 1540, 1600, Some("ALPHA")),
+            ("FL-202", "WAW", "GDN", 300, 360, None),
+        ],
+    )
+    .aircraft(Aircraft {
+        fleet_type: Some(id("A320")),
+        ..Aircraft::new("BRAVO", "KRK")
+    })
+    .build()
+    .unwrap()
+}
+
+fn names(schedule: &Schedule, text: &str) -> Vec<String> {
+    schedule
+        .search(text)
+        .into_iter()
+        .map(|h| h.hit.name().to_string())
+        .collect()
+}
+
+#[test]
+fn test_search_ignores_case_and_punctuation() {
+    let schedule = build();
+    let hits = schedule.search("fl101");
+
+    assert_eq!(
+        Hit::Flight {
+            number: id("FL-101"),
+            route: "KRK-WAW".to_string(),
+            days: vec![1, 2]
+        },
+        hits[0].hit
+    );
+    assert_eq!(0, hits[0].score);
+    assert_eq!(
+        Hit::Route {
+            route: "KRK-WAW".to_string(),
+            flights: 2
+        },
+        schedule.search("krk waw")[0].hit
+    );
+    assert_eq!(
+        Hit::Aircraft {
+            id: id("BRAVO"),
+            fleet_type: Some(id("A320")),
+            flights: 0
+        },
+        schedule.search("bravo")[0].hit
+    );
+}
+
+#[test]
+fn test_search_ranks_closer_matches_first() {
+    let schedule = build();
+
+    // prefix before substring
+    assert_eq!(vec!["WAW-GDN", "KRK-WAW"], names(&schedule, "waw"));
+    // characters in order
+    assert_eq!(vec!["FL-202"], names(&schedule, "f22"));
+    // a swapped pair and a wrong letter are one typo each
+    assert_eq!(vec!["ALPHA"], names(&schedule, "ALHPA"));
+    assert_eq!(vec!["BRAVO"], names(&schedule, "brovo"));
+}
+
+#[test]
+fn test_search_without_match() {
+    let schedule = build();
+
+    assert!(schedule.search("zulu").is_empty());
+    assert!(schedule.search(" - ").is_empty());
+    // too short to allow for a typo
+    assert!(schedule.search("xk").is_empty());
+}
+
+#[test]
+fn test_suggest_close_ids() {
+    let schedule = build();
+
+    assert_eq!(vec!["FL-101", "FL-202"], schedule.suggest_flights("FL-10"));
+    assert_eq!(vec!["FL-202/2"], schedule.suggest_flights("fl202/2"));
+    assert_eq!(vec![id("KRK")], schedule.suggest_airports("krx"));
+    assert_eq!(vec![id("BRAVO")], schedule.suggest_aircraft("BRVAO"));
+    // nothing to suggest for an id that exists or resembles nothing
+    assert!(schedule.suggest_airports("KRK").is_empty());
+    assert!(schedule.suggest_aircraft("ZULU").is_empty());
+}
+
+#[test]
+fn test_did_you_mean() {
+    let message = || "unknown flight FL-10".to_string();
+
+    assert_eq!(
+        "unknown flight FL-10",
+        did_you_mean(message(), &[] as &[&str])
+    );
+    assert_eq!(
+        "unknown flight FL-10; did you mean FL-101?",
+        did_you_mean(message(), &["FL-101"])
+    );
+    assert_eq!(
+        "unknown flight FL-10; did you mean FL-101, FL-102 or FL-110?",
+        did_you_mean(message(), &["FL-101", "FL-102", "FL-110"])
+    );
+}
@@ -0,0 +1,50 @@
+use crate::config::TableStyle;
+use crate::session::SessionState;
+use crate::time::TimeFormat;
+use std::io;
+use std::path::PathBuf;
+
+fn dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("irrops-session-{}-{}", name, std::process::id()))
+}
+
+#[test]
+fn test_session_state_round_trips() {
+    let dir = dir("round-trip");
+    let state = SessionState {
+        scenario: Some(PathBuf::from("/data/hub.json")),
+        table_style: Some(TableStyle::Psql),
+        time_format: Some(TimeFormat::Iso),
+        start_date: Some("2024-06-03".to_string()),
+    };
+
+    state.save(&dir).unwrap();
+    assert_eq!(state, SessionState::load(&dir).unwrap());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_session_state_before_first_session() {
+    assert_eq!(
+        SessionState::default(),
+        SessionState::load(&dir("missing")).unwrap()
+    );
+}
+
+#[test]
+fn test_session_state_keeps_unset_settings_out() {
+    let dir = dir("partial");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("state.json"), r#"{"time_format": "minutes"}"#).unwrap();
+
+    let state = SessionState::load(&dir).unwrap();
+    assert_eq!(Some(TimeFormat::Minutes), state.time_format);
+    assert_eq!(None, state.table_style);
+
+    std::fs::write(dir.join("state.json"), "{").unwrap();
+    assert_eq!(
+        io::ErrorKind::InvalidData,
+        SessionState::load(&dir).unwrap_err().kind()
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,68 @@
+use crate::flight::Flight;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::severity::SeverityModel;
+use crate::time::Time;
+
+/// PLANE_1 flies FLIGHT_1 (100 pax) into WAW and FLIGHT_2 (50 pax) back with a 20 minute
+/// buffer after the turn.
+fn build() -> Schedule {
+    let flight = |flight_id, origin, destination, dep, arr, passengers| Flight {
+        passengers,
+        ..Flight::new(flight_id, origin, destination, Time(dep), Time(arr)).assigned("PLANE_1")
+    };
+    scenario(&["KRK", "WAW"], &[("PLANE_1", "KRK")], &[])
+        .flight(flight("FLIGHT_1", "KRK", "WAW", 100, 200, 100))
+        .flight(flight("FLIGHT_2", "WAW", "KRK", 250, 350, 50))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_delay_severity() {
+    let mut schedule = build();
+    schedule.apply_delay(key("FLIGHT_1"), 60).unwrap();
+    let report = schedule.last_report().unwrap();
+
+    // 60 minutes on FLIGHT_1, 40 more once FLIGHT_2 uses up its buffer
+    assert_eq!(100, report.delay_minutes);
+    assert_eq!(150, report.passengers);
+    assert_eq!(0, report.compensation);
+    assert_eq!(250, report.severity);
+}
+
+#[test]
+fn test_curfew_severity_counts_unscheduled() {
+    let mut schedule = build();
+    schedule
+        .apply_curfew(id("KRK"), Time(300), Time(400))
+        .unwrap();
+    let report = schedule.last_report().unwrap();
+
+    assert_eq!(1, report.unscheduled.len());
+    assert_eq!(0, report.delay_minutes);
+    assert_eq!(50, report.passengers);
+    assert_eq!(100 + 50 + report.compensation / 100, report.severity);
+}
+
+#[test]
+fn test_severity_weights() {
+    let mut schedule = build();
+    schedule.severity = SeverityModel {
+        unscheduled: 0,
+        delay_minute: 10,
+        passenger: 0,
+        compensation: 0,
+    };
+    schedule.apply_delay(key("FLIGHT_2"), 15).unwrap();
+
+    assert_eq!(150, schedule.last_report().unwrap().severity);
+    assert_eq!(
+        vec![150],
+        schedule
+            .history
+            .iter()
+            .map(|r| r.severity)
+            .collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,64 @@
+use crate::airport::Airport;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{curfew, key, scenario};
+
+fn build() -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "GDN", 300, 400, Some("PLANE_1")),
+            ("FLIGHT_3", "GDN", "KRK", 500, 600, Some("PLANE_1")),
+        ],
+    )
+    .airport(Airport {
+        disruptions: vec![curfew(900, 1000)],
+        ..Airport::new("GDN", 30)
+    })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_slack_buffers() {
+    let schedule = build();
+    let slack = schedule.slack();
+
+    assert_eq!(1, slack.len());
+    assert_eq!(3, slack[0].legs);
+    assert_eq!(
+        vec![70, 70],
+        slack[0]
+            .connections
+            .iter()
+            .map(|c| c.buffer)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(Some(70), slack[0].min_buffer);
+    // FLIGHT_3's block must end before GDN closes at 900: 300 of its own, plus 2x70 upstream
+    assert_eq!(440, slack[0].absorbable);
+    assert_eq!(Some(key("FLIGHT_3")), slack[0].limited_by);
+}
+
+#[test]
+fn test_slack_matches_apply_delay() {
+    let mut schedule = build();
+    let absorbable = schedule.slack()[0].absorbable;
+    schedule.apply_delay(key("FLIGHT_1"), absorbable).unwrap();
+    assert!(schedule.last_report().unwrap().unscheduled.is_empty());
+
+    let mut schedule = build();
+    schedule
+        .apply_delay(key("FLIGHT_1"), absorbable + 1)
+        .unwrap();
+    assert_eq!(
+        Some(key("FLIGHT_3")),
+        schedule
+            .last_report()
+            .unwrap()
+            .first_break
+            .clone()
+            .map(|b| b.0)
+    );
+}
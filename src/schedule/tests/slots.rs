@@ -0,0 +1,96 @@
+use crate::airport::Airport;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+/// WAW is coordinated with a 15 min slot tolerance. PLANE_1 flies FLIGHT_1 (KRK-WAW,
+/// 100-160) and FLIGHT_2 (WAW-KRK, 250-310); PLANE_2 flies FLIGHT_3 (KRK-WAW, 170-230).
+fn build() -> Schedule {
+    scenario(
+        &["KRK"],
+        &[("PLANE_1", "KRK"), ("PLANE_2", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 160, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", 250, 310, Some("PLANE_1")),
+            ("FLIGHT_3", "KRK", "WAW", 170, 230, Some("PLANE_2")),
+        ],
+    )
+    .airport(Airport {
+        slot_tolerance: Some(15),
+        ..Airport::new("WAW", 30)
+    })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_delay_within_tolerance() {
+    let mut schedule = build();
+    schedule.apply_delay(key("FLIGHT_1"), 15).unwrap();
+
+    assert!(schedule.slot_violations().is_empty());
+    assert!(schedule.last_report.unwrap().slot_violations.is_empty());
+}
+
+#[test]
+fn test_delay_outside_tolerance_reported() {
+    let mut schedule = build();
+    schedule.apply_delay(key("FLIGHT_1"), 100).unwrap();
+    let report = schedule.last_report.as_ref().unwrap();
+
+    // FLIGHT_1 lands at 260, FLIGHT_2 is pushed to 290 by the 30 min turn
+    assert_eq!(
+        vec![
+            (key("FLIGHT_1"), Time(160), Time(260)),
+            (key("FLIGHT_2"), Time(250), Time(290)),
+        ],
+        report
+            .slot_violations
+            .iter()
+            .map(|v| (v.flight.clone(), v.slot, v.actual))
+            .collect::<Vec<_>>()
+    );
+    assert!(
+        report
+            .slot_violations
+            .iter()
+            .all(|v| v.airport_id == id("WAW"))
+    );
+    assert!(report.slot_violations.iter().all(|v| !v.new_slot));
+}
+
+#[test]
+fn test_new_slot_from_hourly_capacity() {
+    let mut schedule = build();
+    schedule
+        .airports
+        .get_mut(&id("WAW"))
+        .unwrap()
+        .slots_per_hour = Some(2);
+    schedule.apply_delay(key("FLIGHT_3"), 40).unwrap();
+    let violations = schedule.slot_violations();
+
+    // FLIGHT_3 now lands in hour 4, where WAW has slotted FLIGHT_2's departure
+    assert_eq!(1, violations.len());
+    assert_eq!(Time(270), violations[0].actual);
+    assert!(violations[0].new_slot);
+
+    schedule
+        .airports
+        .get_mut(&id("WAW"))
+        .unwrap()
+        .slots_per_hour = Some(1);
+    assert!(!schedule.slot_violations()[0].new_slot);
+}
+
+#[test]
+fn test_plan_delta_follows_moves_both_ways() {
+    let mut schedule = build();
+    schedule.apply_delay(key("FLIGHT_1"), 20).unwrap();
+    schedule.retime(key("FLIGHT_3"), -30).unwrap();
+
+    let delta = |flight| schedule.plan_delta(schedule.flight(&key(flight)).unwrap());
+    assert_eq!(Some(20), delta("FLIGHT_1"));
+    assert_eq!(Some(0), delta("FLIGHT_2"));
+    assert_eq!(Some(-30), delta("FLIGHT_3"));
+}
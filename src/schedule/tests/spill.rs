@@ -0,0 +1,78 @@
+use crate::aircraft::Aircraft;
+use crate::flight::Flight;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+use std::collections::HashMap;
+
+/// FLIGHT_1 (120 pax) flies on the 76 seat E175 PLANE_2; PLANE_1 is a 180 seat A320 and
+/// PLANE_3 has no fleet type.
+fn build() -> Schedule {
+    let plane = |aircraft_id, fleet_type| Aircraft {
+        fleet_type: Some(id(fleet_type)),
+        ..Aircraft::new(aircraft_id, "KRK")
+    };
+    let mut schedule = scenario(&["KRK", "WAW"], &[], &[])
+        .aircraft(plane("PLANE_1", "A320"))
+        .aircraft(plane("PLANE_2", "E175"))
+        .aircraft(Aircraft::new("PLANE_3", "KRK"))
+        .flight(Flight {
+            passengers: 120,
+            ..Flight::new("FLIGHT_1", "KRK", "WAW", Time(100), Time(200)).assigned("PLANE_2")
+        })
+        .flight(Flight {
+            passengers: 120,
+            ..Flight::new("FLIGHT_2", "KRK", "WAW", Time(300), Time(400))
+        })
+        .build()
+        .unwrap();
+    schedule.seats = HashMap::from([(id("A320"), 180), (id("E175"), 76)]);
+    schedule
+}
+
+#[test]
+fn test_spill_per_aircraft() {
+    let schedule = build();
+    let flight = schedule.flight(&key("FLIGHT_1")).unwrap();
+
+    assert_eq!(0, schedule.spill_on(flight, &id("PLANE_1")));
+    assert_eq!(44, schedule.spill_on(flight, &id("PLANE_2")));
+    assert_eq!(0, schedule.spill_on(flight, &id("PLANE_3")));
+    assert_eq!(None, schedule.seats(&id("PLANE_3")));
+}
+
+#[test]
+fn test_spill_in_cost() {
+    let mut schedule = build();
+    let cost_before = schedule.total_cost();
+
+    assert_eq!(vec![(key("FLIGHT_1"), 44)], schedule.spills());
+    assert_eq!(44 * 250, schedule.spill_cost());
+
+    schedule.cost.spilled_passenger = 100;
+    assert_eq!(cost_before - 44 * 150, schedule.total_cost());
+}
+
+#[test]
+fn test_candidates_show_spill() {
+    let schedule = build();
+    let candidates = schedule.candidates(&key("FLIGHT_2"));
+
+    assert_eq!(
+        vec![0, 44, 0],
+        candidates.iter().map(|c| c.spill).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_seats_from_scenario() {
+    let raw = serde_json::from_str(
+        r#"{"aircraft": [], "airports": [], "flights": [], "seats": {"A320": 180}}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        HashMap::from([(id("A320"), 180)]),
+        Schedule::from_scenario(raw).seats
+    );
+}
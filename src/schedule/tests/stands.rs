@@ -0,0 +1,174 @@
+use crate::aircraft::Aircraft;
+use crate::airport::Airport;
+use crate::flight::Flight;
+use crate::flight::FlightStatus::{Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::StandCapacity;
+use crate::schedule::anneal::AnnealConfig;
+use crate::schedule::builder::ScheduleBuilder;
+use crate::schedule::schedule::{Schedule, Strategy};
+use crate::schedule::tests::utils::{add_flight, id, key};
+use crate::time::Time;
+
+/// WAW has a single stand, taken by PLANE_3 until it leaves on FLIGHT_2, if at all.
+fn build(assigned: bool, leaves_at: Option<u64>) -> Schedule {
+    let mut builder = ScheduleBuilder::new()
+        .airport(Airport::new("KRK", 0))
+        .airport(Airport {
+            stands: Some(1),
+            ..Airport::new("WAW", 0)
+        })
+        .airport(Airport::new("GDN", 0))
+        .aircraft(Aircraft::new("PLANE_1", "KRK"))
+        .aircraft(Aircraft::new("PLANE_3", "WAW"));
+    let flight = Flight::new("FLIGHT_1", "KRK", "WAW", Time(100), Time(200));
+    builder = builder.flight(match assigned {
+        true => flight.assigned("PLANE_1"),
+        false => flight,
+    });
+    if let Some(dep) = leaves_at {
+        builder = builder.flight(
+            Flight::new("FLIGHT_2", "WAW", "GDN", Time(dep), Time(dep + 100)).assigned("PLANE_3"),
+        );
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_assign_refuses_full_destination() {
+    let mut schedule = build(false, None);
+    schedule.assign();
+
+    assert_eq!(Unscheduled(StandCapacity), schedule.flights[0].status);
+    assert_eq!(None, schedule.flights[0].aircraft_id);
+}
+
+#[test]
+fn test_assign_uses_freed_stand() {
+    let mut schedule = build(false, Some(50));
+    schedule.assign();
+
+    let flight = schedule
+        .flights
+        .iter()
+        .find(|f| f.id == id("FLIGHT_1"))
+        .unwrap();
+    assert_eq!(Scheduled, flight.status);
+    assert_eq!(Some(id("PLANE_1")), flight.aircraft_id);
+}
+
+#[test]
+fn test_recovery_refuses_full_destination() {
+    let anneal = AnnealConfig {
+        max_iterations: Some(500),
+        ..AnnealConfig::default()
+    };
+    for strategy in [Strategy::Flow, Strategy::Anneal(anneal)] {
+        let mut schedule = build(false, None);
+        schedule.recover(strategy);
+        assert_eq!(None, schedule.flights[0].aircraft_id);
+        assert!(schedule.stand_overflows().is_empty());
+    }
+}
+
+#[test]
+fn test_assign_counts_aircraft_between_legs() {
+    // PLANE_3 passes through WAW from 150 to 250, while FLIGHT_1 would land at 200
+    let mut schedule = build(false, None);
+    let mut flights = schedule.flights.to_vec();
+    schedule
+        .aircraft
+        .get_mut(&id("PLANE_3"))
+        .unwrap()
+        .initial_location_id = id("GDN");
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        "GDN",
+        "WAW",
+        50,
+        150,
+        Some("PLANE_3"),
+        Scheduled,
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_3",
+        "WAW",
+        "GDN",
+        250,
+        350,
+        Some("PLANE_3"),
+        Scheduled,
+    );
+    let mut schedule = Schedule::new(schedule.aircraft, schedule.airports, flights);
+    schedule.assign();
+
+    let flight = schedule.flight(&key("FLIGHT_1")).unwrap();
+    assert_eq!(Unscheduled(StandCapacity), flight.status);
+}
+
+#[test]
+fn test_assign_allows_arrival_leaving_before_the_stand_is_taken() {
+    // PLANE_3 only lands at WAW at 500, and PLANE_1 is due out of WAW again at 300
+    let mut schedule = build(false, None);
+    let mut flights = schedule.flights.to_vec();
+    schedule
+        .aircraft
+        .get_mut(&id("PLANE_3"))
+        .unwrap()
+        .initial_location_id = id("GDN");
+    add_flight(
+        &mut flights,
+        "FLIGHT_2",
+        "GDN",
+        "WAW",
+        400,
+        500,
+        Some("PLANE_3"),
+        Scheduled,
+    );
+    add_flight(
+        &mut flights,
+        "FLIGHT_3",
+        "WAW",
+        "KRK",
+        300,
+        350,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    let mut schedule = Schedule::new(schedule.aircraft, schedule.airports, flights);
+    schedule.assign();
+
+    let flight = schedule.flight(&key("FLIGHT_1")).unwrap();
+    assert_eq!(Some(id("PLANE_1")), flight.aircraft_id);
+    assert!(schedule.stand_overflows().is_empty());
+}
+
+#[test]
+fn test_ground_occupancy() {
+    let schedule = build(true, Some(250));
+    assert_eq!(
+        vec![(Time(0), 1), (Time(200), 2), (Time(250), 1)],
+        schedule.ground_occupancy(&id("WAW"))
+    );
+
+    let overflows = schedule.stand_overflows();
+    assert_eq!(1, overflows.len());
+    assert_eq!(id("WAW"), overflows[0].airport_id);
+    assert_eq!(Time(200), overflows[0].from);
+    assert_eq!(Some(Time(250)), overflows[0].to);
+    assert_eq!(2, overflows[0].peak);
+}
+
+#[test]
+fn test_stand_overflow_until_end() {
+    let schedule = build(true, None);
+    let overflows = schedule.stand_overflows();
+    assert_eq!(1, overflows.len());
+    assert_eq!(None, overflows[0].to);
+
+    // a departure in the same minute frees the stand for the arrival
+    let schedule = build(true, Some(200));
+    assert!(schedule.stand_overflows().is_empty());
+}
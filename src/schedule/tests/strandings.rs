@@ -0,0 +1,72 @@
+use crate::aircraft::Aircraft;
+use crate::flight::Flight;
+use crate::flight::FlightStatus::{Delayed, Unscheduled};
+use crate::flight::UnscheduledReason::MaxDelayExceeded;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{id, key, scenario};
+use crate::time::Time;
+
+/// FLIGHT_1 (KRK-WAW, 120 pax) is cancelled; FLIGHT_2 flies the same route later that
+/// day with 100 pax on a 150-seat A320, FLIGHT_3 flew it in the morning.
+fn build() -> Schedule {
+    let flight = |flight_id, dep, passengers| Flight {
+        passengers,
+        ..Flight::new(flight_id, "KRK", "WAW", Time(dep), Time(dep + 60))
+    };
+    let mut schedule = scenario(&["KRK", "WAW"], &[("PLANE_1", "KRK")], &[])
+        .aircraft(Aircraft {
+            fleet_type: Some(id("A320")),
+            ..Aircraft::new("PLANE_2", "KRK")
+        })
+        .flight(flight("FLIGHT_3", 100, 50).assigned("PLANE_1"))
+        .flight(Flight {
+            status: Unscheduled(MaxDelayExceeded),
+            ..flight("FLIGHT_1", 600, 120)
+        })
+        .flight(flight("FLIGHT_2", 900, 100).assigned("PLANE_2"))
+        .build()
+        .unwrap();
+    schedule.seats.insert(id("A320"), 150);
+    schedule
+}
+
+#[test]
+fn test_strandings_after_spare_seats() {
+    let schedule = build();
+    let strandings = schedule.strandings();
+
+    assert_eq!(1, strandings.len());
+    assert_eq!(id("KRK"), strandings[0].airport_id);
+    assert_eq!(1, strandings[0].day);
+    assert_eq!(70, strandings[0].passengers);
+    assert_eq!(vec![key("FLIGHT_1")], strandings[0].flights);
+}
+
+#[test]
+fn test_strandings_unknown_seats_absorb_everyone() {
+    let mut schedule = build();
+    schedule.seats.clear();
+
+    assert!(schedule.strandings().is_empty());
+}
+
+#[test]
+fn test_strandings_delay_past_midnight() {
+    let mut schedule = build();
+    let flight = schedule
+        .flights
+        .iter_mut()
+        .find(|f| f.key() == key("FLIGHT_2"))
+        .unwrap();
+    flight.departure_time += 600;
+    flight.arrival_time += 600;
+    flight.status = Delayed { minutes: 600 };
+
+    let strandings = schedule.strandings();
+    assert_eq!(1, strandings.len());
+    assert_eq!(220, strandings[0].passengers);
+    assert_eq!(
+        vec![key("FLIGHT_1"), key("FLIGHT_2")],
+        strandings[0].flights
+    );
+}
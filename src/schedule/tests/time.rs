@@ -0,0 +1,112 @@
+use crate::export::Date;
+use crate::time::{Time, TimeFormat, parse_duration};
+use proptest::prelude::*;
+
+#[test]
+fn test_parse_time() {
+    assert_eq!(Ok(Time(90)), "90".parse::<Time>());
+    assert_eq!(Ok(Time(1830)), "DAY2 06:30".parse::<Time>());
+    assert_eq!(Ok(Time(1830)), "day2 06:30".parse::<Time>());
+    assert_eq!(Ok(Time(1830)), "06:30+1".parse::<Time>());
+    assert_eq!(Ok(Time(390)), "06:30".parse::<Time>());
+    assert_eq!(Ok(Time(390)), "6:30".parse::<Time>());
+
+    for invalid in [
+        "",
+        "DAY0 06:30",
+        "DAY2",
+        "24:00",
+        "06:60",
+        "06:3",
+        "06:30+",
+        "late",
+    ] {
+        assert!(invalid.parse::<Time>().is_err(), "{} parsed", invalid);
+    }
+    assert_eq!(
+        Err(
+            "expected minutes, `DAY2 06:30`, `06:30+1` or `2024-01-02T06:30`, not `late`"
+                .to_string()
+        ),
+        "late".parse::<Time>()
+    );
+}
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(Ok(90), parse_duration("90"));
+    assert_eq!(Ok(90), parse_duration("90m"));
+    assert_eq!(Ok(90), parse_duration("1h30"));
+    assert_eq!(Ok(90), parse_duration("1h30m"));
+    assert_eq!(Ok(120), parse_duration("2h"));
+
+    for invalid in ["", "m", "h", "1h90", "-5", "1.5h", "soon"] {
+        assert!(parse_duration(invalid).is_err(), "{} parsed", invalid);
+    }
+}
+
+#[test]
+fn test_display_formats() {
+    let day_one = "2024-02-28".parse::<Date>().unwrap();
+    let time = Time(1830);
+    assert_eq!("1830", time.display_with(TimeFormat::Minutes, day_one));
+    assert_eq!(
+        "DAY2 06:30",
+        time.display_with(TimeFormat::DayHhmm, day_one)
+    );
+    assert_eq!(
+        "2024-02-29T06:30",
+        time.display_with(TimeFormat::Iso, day_one)
+    );
+    assert_eq!(
+        "2024-03-01T00:00",
+        Time(2880).display_with(TimeFormat::Iso, day_one)
+    );
+}
+
+#[test]
+fn test_parse_iso_time() {
+    let day_one = "2024-02-28".parse::<Date>().unwrap();
+    assert_eq!(
+        Ok(Time(1830)),
+        Time::parse_with("2024-02-29T06:30", day_one)
+    );
+    assert_eq!(Ok(Time(390)), Time::parse_with("2024-02-28T06:30", day_one));
+    for invalid in [
+        "2024-02-27T06:30",
+        "2024-02-30T06:30",
+        "2024-02-29T25:00",
+        "2024-02-29T",
+    ] {
+        assert!(
+            Time::parse_with(invalid, day_one).is_err(),
+            "{} parsed",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_parse_time_format() {
+    assert_eq!(Ok(TimeFormat::Minutes), "minutes".parse());
+    assert_eq!(Ok(TimeFormat::DayHhmm), "dayhhmm".parse());
+    assert_eq!(Ok(TimeFormat::Iso), "iso".parse());
+    assert!("unix".parse::<TimeFormat>().is_err());
+    assert_eq!(TimeFormat::DayHhmm, TimeFormat::default());
+}
+
+proptest! {
+    #[test]
+    fn test_time_display_round_trips(minutes in 0u64..10_000_000) {
+        prop_assert_eq!(Ok(Time(minutes)), Time(minutes).to_string().parse::<Time>());
+    }
+
+    #[test]
+    fn test_every_format_round_trips(minutes in 0u64..10_000_000) {
+        let day_one = Date::default();
+        for format in [TimeFormat::Minutes, TimeFormat::DayHhmm, TimeFormat::Iso] {
+            let text = Time(minutes).display_with(format, day_one);
+            prop_assert_eq!(Ok(Time(minutes)), Time::parse_with(&text, day_one));
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::delay_code::DelayCodes;
+use crate::flight::FlightStatus::{Scheduled, Unscheduled};
+use crate::flight::UnscheduledReason::{TurnTimeViolation, Waiting};
+use crate::metrics::summarize;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{key, scenario};
+
+/// PLANE_1 lands FLIGHT_1 (KRK-WAW) at 200; FLIGHT_2 (WAW-KRK) leaves at `departure`
+/// with a 30 minute turn time at WAW.
+fn build(departure: u64) -> Schedule {
+    scenario(
+        &["KRK", "WAW"],
+        &[("PLANE_1", "KRK")],
+        &[
+            ("FLIGHT_1", "KRK", "WAW", 100, 200, Some("PLANE_1")),
+            ("FLIGHT_2", "WAW", "KRK", departure, departure + 100, None),
+        ],
+    )
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_assign_reports_short_turn() {
+    let mut schedule = build(210);
+    schedule.assign();
+
+    assert_eq!(
+        Unscheduled(TurnTimeViolation {
+            required: 30,
+            available: 10
+        }),
+        schedule.flight(&key("FLIGHT_2")).unwrap().status
+    );
+    assert_eq!(1, summarize(&schedule.flights).turn_time_violation);
+    assert_eq!(
+        Some(93),
+        DelayCodes::default().reason(&TurnTimeViolation {
+            required: 30,
+            available: 10
+        })
+    );
+}
+
+#[test]
+fn test_assign_keeps_reason_when_aircraft_lands_later() {
+    // the aircraft is still airborne at 150: not a turn time problem
+    let mut schedule = build(150);
+    schedule.assign();
+
+    assert_eq!(
+        Unscheduled(Waiting),
+        schedule.flight(&key("FLIGHT_2")).unwrap().status
+    );
+}
+
+#[test]
+fn test_assign_with_enough_turn_time() {
+    let mut schedule = build(230);
+    schedule.assign();
+
+    assert_eq!(Scheduled, schedule.flight(&key("FLIGHT_2")).unwrap().status);
+}
@@ -0,0 +1,37 @@
+use crate::flight::FlightStatus::Scheduled;
+use crate::schedule::tests::utils::{add_airport, add_flight};
+use crate::schedule::turn_times::TurnTimes;
+use crate::time::Time;
+use std::collections::HashMap;
+
+#[test]
+fn test_turn_times_by_destination() {
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 45, vec![]);
+    for (origin, destination) in [
+        ("KRK", "WAW"),
+        ("WAW", "KRK"),
+        ("KRK", "XXX"),
+        ("XXX", "WAW"),
+    ] {
+        add_flight(
+            &mut flights,
+            "FLIGHT",
+            origin,
+            destination,
+            100,
+            200,
+            None,
+            Scheduled,
+        );
+    }
+    let turns = TurnTimes::new(&airports, &flights);
+
+    assert_eq!(Time(245), turns.ready_after(0, Time(200)));
+    assert_eq!(Time(230), turns.ready_after(1, Time(200)));
+    // an airport missing from the scenario needs no turn
+    assert_eq!(Time(200), turns.ready_after(2, Time(200)));
+    assert_eq!(Time(545), turns.ready_after(3, Time(500)));
+}
@@ -1,12 +1,8 @@
 use crate::aircraft::{Aircraft, AircraftId, Availability};
 use crate::airport::{Airport, AirportId, Curfew};
-use crate::flight::FlightStatus::Unscheduled;
-use crate::flight::UnscheduledReason::Waiting;
-use crate::flight::{Flight, FlightStatus};
+use crate::flight::{Flight, FlightKey, FlightStatus};
+use crate::schedule::builder::ScheduleBuilder;
 use crate::time::Time;
-use proptest::prelude::Strategy;
-use proptest::prop_oneof;
-use proptest::strategy::Just;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -14,6 +10,11 @@ pub fn id(s: &str) -> Arc<str> {
     Arc::from(s)
 }
 
+/// Key of a flight operating on day 1, where most tests take place.
+pub fn key(flight_id: &str) -> FlightKey {
+    FlightKey::new(flight_id, 1)
+}
+
 pub fn add_aircraft(
     aircraft: &mut HashMap<AircraftId, Aircraft>,
     aircraft_id: &str,
@@ -21,11 +22,10 @@ pub fn add_aircraft(
     disruptions: Vec<Availability>,
 ) {
     aircraft.insert(
-        id(aircraft_id).clone(),
+        id(aircraft_id),
         Aircraft {
-            id: id(aircraft_id).clone(),
-            initial_location_id: id(initial_location_id).clone(),
             disruptions,
+            ..Aircraft::new(aircraft_id, initial_location_id)
         },
     );
 }
@@ -37,15 +37,15 @@ pub fn add_airport(
     disruptions: Vec<Curfew>,
 ) {
     airports.insert(
-        id(airport_id).clone(),
+        id(airport_id),
         Airport {
-            id: id(airport_id).clone(),
-            mtt,
             disruptions,
+            ..Airport::new(airport_id, mtt)
         },
     );
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn add_flight(
     flights: &mut Vec<Flight>,
     flight_id: &str,
@@ -57,13 +57,15 @@ pub fn add_flight(
     status: FlightStatus,
 ) {
     flights.push(Flight {
-        id: id(flight_id),
-        origin_id: id(origin_id),
-        destination_id: id(destination_id),
-        departure_time: Time(departure_time),
-        arrival_time: Time(arrival_time),
-        aircraft_id: aircraft_id.map(|x| id(x)),
+        aircraft_id: aircraft_id.map(id),
         status,
+        ..Flight::new(
+            flight_id,
+            origin_id,
+            destination_id,
+            Time(departure_time),
+            Time(arrival_time),
+        )
     });
 }
 
@@ -79,32 +81,40 @@ pub fn curfew(from: u64, to: u64) -> Curfew {
     Curfew {
         from: Time(from),
         to: Time(to),
+        arrival_tolerance: 0,
     }
 }
 
-pub fn arb_id(prefix: &'static str) -> impl Strategy<Value = Arc<str>> {
-    prop_oneof![
-        Just(Arc::from(format!("{}_1", prefix))),
-        Just(Arc::from(format!("{}_2", prefix))),
-        Just(Arc::from(format!("{}_3", prefix))),
-    ]
-}
+/// A flight as (id, origin, destination, departure, arrival, aircraft).
+pub type Leg<'a> = (&'a str, &'a str, &'a str, u64, u64, Option<&'a str>);
 
-pub fn arb_flight() -> impl Strategy<Value = Flight> {
-    (
-        arb_id("FL"),
-        arb_id("AP"),
-        arb_id("AP"),
-        0..2500u64,
-        10..1000u64,
+/// A builder holding `airports` with a 30 minute turn time, `aircraft` as (id, location)
+/// and `flights`, each `Scheduled` on its aircraft or waiting for one.
+pub fn scenario(airports: &[&str], aircraft: &[(&str, &str)], flights: &[Leg]) -> ScheduleBuilder {
+    let builder = airports
+        .iter()
+        .fold(ScheduleBuilder::new(), |builder, airport_id| {
+            builder.airport(Airport::new(airport_id, 30))
+        });
+    let builder = aircraft
+        .iter()
+        .fold(builder, |builder, (aircraft_id, location_id)| {
+            builder.aircraft(Aircraft::new(aircraft_id, location_id))
+        });
+    flights.iter().fold(
+        builder,
+        |builder, (flight_id, origin_id, destination_id, departure, arrival, aircraft_id)| {
+            let flight = Flight::new(
+                flight_id,
+                origin_id,
+                destination_id,
+                Time(*departure),
+                Time(*arrival),
+            );
+            builder.flight(match aircraft_id {
+                Some(aircraft_id) => flight.assigned(aircraft_id),
+                None => flight,
+            })
+        },
     )
-        .prop_map(|(fid, org, dst, dep, dur)| Flight {
-            id: id(fid.as_ref()),
-            origin_id: id(org.as_ref()),
-            destination_id: id(dst.as_ref()),
-            departure_time: Time(dep),
-            arrival_time: Time(dep) + dur,
-            aircraft_id: None,
-            status: Unscheduled(Waiting),
-        })
 }
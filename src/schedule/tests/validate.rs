@@ -0,0 +1,167 @@
+use crate::flight::UnscheduledReason;
+use crate::schedule::schedule::LoadError;
+use crate::schedule::validate::{Severity, scenario_schema, validate_file};
+use serde_json::{Value, json};
+
+fn validate_json(name: &str, scenario: Value) -> Result<Vec<(Severity, String)>, LoadError> {
+    let path = std::env::temp_dir().join(format!("irrops-{}-{}.json", name, std::process::id()));
+    std::fs::write(&path, scenario.to_string()).unwrap();
+    let result = validate_file(path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+    result.map(|issues| {
+        issues
+            .into_iter()
+            .map(|issue| (issue.severity, issue.message))
+            .collect()
+    })
+}
+
+fn flight(id: &str, origin: &str, destination: &str, dep: u64, arr: u64) -> Value {
+    json!({
+        "id": id, "aircraft_id": "", "origin_id": origin, "destination_id": destination,
+        "departure_time": dep, "arrival_time": arr, "status": {"Unscheduled": "Waiting"}
+    })
+}
+
+#[test]
+fn test_validate_reports_semantic_errors() {
+    let issues = validate_json(
+        "semantic",
+        json!({
+            "aircraft": [
+                {"id": "A1", "initial_location_id": "WAW", "disruptions": [
+                    {"from": 100, "to": 200, "location_id": "WAW"},
+                    {"from": 150, "to": 250, "location_id": null}
+                ]},
+                {"id": "A1", "initial_location_id": "XXX", "disruptions": []}
+            ],
+            "airports": [{"id": "WAW", "mtt": 30, "disruptions": [{"from": 50, "to": 10}],
+                "runways": [
+                    {"id": "11/29", "movements_per_hour": 20},
+                    {"id": "11/29", "movements_per_hour": 0}
+                ],
+                "reductions": [{"from": 300, "to": 300, "rate": 0}]}],
+            "flights": [flight("F1", "WAW", "KRK", 100, 90)],
+            "rules": [{"kind": "depart_by", "flight_id": "F9", "latest": 100}]
+        }),
+    )
+    .unwrap();
+
+    let errors = issues
+        .iter()
+        .filter(|(severity, _)| *severity == Severity::Error)
+        .map(|(_, message)| message.as_str())
+        .collect::<Vec<_>>();
+    assert!(errors.contains(&"duplicate aircraft id A1"));
+    assert!(errors.contains(&"aircraft A1 refers to unknown airport XXX"));
+    assert!(errors.contains(&"flight F1 refers to unknown airport KRK"));
+    assert!(errors.contains(&"depart_by rule refers to unknown flight F9"));
+    assert!(
+        errors
+            .iter()
+            .any(|m| m.starts_with("curfew at WAW ends at"))
+    );
+    assert!(errors.iter().any(|m| m.starts_with("flight F1 ends at")));
+    assert!(
+        errors
+            .iter()
+            .any(|m| m.starts_with("disruptions of aircraft A1 at")
+                && m.ends_with("overlap at different airports"))
+    );
+    assert!(errors.contains(&"duplicate runway 11/29 at WAW"));
+    assert!(errors.contains(&"runway 11/29 at WAW handles no movements"));
+    assert!(
+        errors
+            .iter()
+            .any(|m| m.starts_with("capacity reduction at WAW ends at"))
+    );
+    assert!(errors.contains(&"capacity reduction at WAW handles no movements"));
+    assert_eq!(errors.len(), 11);
+}
+
+#[test]
+fn test_validate_reports_load_warnings_after_errors() {
+    let issues = validate_json(
+        "warnings",
+        json!({
+            "aircraft": [{"id": "A1", "initial_location_id": "WAW", "disruptions": [],
+                "fleet_type": "E175"}],
+            "airports": [
+                {"id": "WAW", "mtt": 30, "disruptions": []},
+                {"id": "KRK", "mtt": 30, "disruptions": [{"from": 0, "to": 300}]}
+            ],
+            "ranges": {"E175": 200},
+            "flights": [
+                flight("F1", "WAW", "KRK", 100, 200),
+                {
+                    "id": "F2", "aircraft_id": "A1", "origin_id": "WAW", "destination_id": "KRK",
+                    "departure_time": 100, "arrival_time": 200, "status": "Scheduled",
+                    "distance": 250, "etops": true
+                },
+                flight("F3", "WAW", "WAW", 100, 200)
+            ]
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        issues,
+        vec![
+            (
+                Severity::Error,
+                "flight F3 departs from and arrives at WAW".to_string()
+            ),
+            (
+                Severity::Warning,
+                "flight F2/1 operates while an airport is closed (AirportCurfew)".to_string()
+            ),
+            (
+                Severity::Warning,
+                "flight F2/1 is longer than the 200 km range of the E175 flying it".to_string()
+            ),
+            (
+                Severity::Warning,
+                "ETOPS flight F2/1 is flown by A1, which is not ETOPS-certified".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_structural_error() {
+    let result = validate_json("structure", json!({"aircraft": [], "airports": []}));
+    assert!(matches!(result, Err(LoadError::Json(_))));
+}
+
+#[test]
+fn test_schema_lists_every_unscheduled_reason() {
+    let schema: Value = serde_json::from_str(&scenario_schema()).unwrap();
+    let listed = &schema["$defs"]["UnscheduledReason"]["oneOf"][0]["enum"];
+    for reason in [
+        UnscheduledReason::Waiting,
+        UnscheduledReason::MaxDelayExceeded,
+        UnscheduledReason::AirportCurfew,
+        UnscheduledReason::OutsideOperatingHours,
+        UnscheduledReason::AircraftMaintenance,
+        UnscheduledReason::MaintenanceDue,
+        UnscheduledReason::StandCapacity,
+        UnscheduledReason::ConstraintViolated,
+        UnscheduledReason::BrokenChain,
+    ] {
+        let name = serde_json::to_value(reason).unwrap();
+        assert!(
+            listed.as_array().unwrap().contains(&name),
+            "{} missing from the schema",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_schema_file_is_up_to_date() {
+    let path = format!("{}/schema/scenario.schema.json", env!("CARGO_MANIFEST_DIR"));
+    assert!(
+        std::fs::read_to_string(path).unwrap() == scenario_schema(),
+        "schema/scenario.schema.json is stale; regenerate it with `cargo run -- schema`"
+    );
+}
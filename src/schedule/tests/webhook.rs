@@ -0,0 +1,117 @@
+use crate::config::Config;
+use crate::flight::FlightStatus::Scheduled;
+use crate::http::UrlError;
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{add_aircraft, add_airport, add_flight, key};
+use crate::webhook::{Webhook, report_payload};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+
+#[test]
+fn test_parse_webhook_url() {
+    assert!(Webhook::parse("http://localhost:8080/hooks/irrops").is_ok());
+    assert!(Webhook::parse("http://ops.example").is_ok());
+    assert!(Webhook::parse("https://ops.example/hooks").is_ok());
+    assert_eq!(
+        Err(UrlError::UnsupportedScheme("ftp://ops.example".to_string())),
+        Webhook::parse("ftp://ops.example")
+    );
+    assert_eq!(
+        Err(UrlError::Invalid("ops.example/hooks".to_string())),
+        Webhook::parse("ops.example/hooks")
+    );
+    assert_eq!(
+        Err(UrlError::Invalid("http://:80/x".to_string())),
+        Webhook::parse("http://:80/x")
+    );
+}
+
+#[test]
+fn test_post_report_payload() {
+    let mut aircraft = HashMap::new();
+    let mut airports = HashMap::new();
+    let mut flights = Vec::new();
+
+    add_airport(&mut airports, "KRK", 30, vec![]);
+    add_airport(&mut airports, "WAW", 30, vec![]);
+    add_aircraft(&mut aircraft, "PLANE_1", "KRK", vec![]);
+    add_flight(
+        &mut flights,
+        "FLIGHT_1",
+        "KRK",
+        "WAW",
+        100,
+        200,
+        Some("PLANE_1"),
+        Scheduled,
+    );
+    let mut schedule = Schedule::new(aircraft, airports, flights);
+    schedule.apply_delay(key("FLIGHT_1"), 45).unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            if header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':')
+                && name.eq_ignore_ascii_case("content-length")
+            {
+                length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+            .unwrap();
+        (
+            request_line,
+            serde_json::from_slice::<Value>(&body).unwrap(),
+        )
+    });
+
+    let hook = Webhook::parse(&format!("http://127.0.0.1:{}/irrops", port)).unwrap();
+    let status = hook
+        .post(&report_payload(schedule.last_report().unwrap()))
+        .unwrap();
+    let (request_line, body) = server.join().unwrap();
+
+    assert_eq!(204, status);
+    assert_eq!("POST /irrops HTTP/1.1", request_line.trim());
+    assert_eq!(
+        json!({
+            "event": "disruption",
+            "trigger": {"kind": "delay", "flight": "FLIGHT_1/1", "delay_by": 45},
+            "affected": ["FLIGHT_1/1"],
+            "unscheduled": [],
+            "compensation": 0,
+            "exempted": [],
+            "slot_violations": [],
+        }),
+        body
+    );
+}
+
+#[test]
+fn test_webhooks_from_config() {
+    let config =
+        Config::parse(r#"webhooks = ["http://localhost:9000/a", "http://ops:80/b"]  # relays"#)
+            .unwrap();
+    assert_eq!(
+        vec!["http://localhost:9000/a", "http://ops:80/b"],
+        config.webhooks
+    );
+    assert!(Config::parse("webhooks = [\"http://a\",").is_err());
+}
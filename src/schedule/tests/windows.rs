@@ -0,0 +1,105 @@
+use crate::aircraft::{Availability, normalize_windows};
+use crate::airport::{Curfew, normalize_curfews};
+use crate::schedule::schedule::Schedule;
+use crate::schedule::tests::utils::{availability, id};
+use crate::time::Time;
+
+fn spans(windows: &[Availability]) -> Vec<(u64, u64)> {
+    windows.iter().map(|w| (w.from.0, w.to.0)).collect()
+}
+
+fn curfew(from: u64, to: u64, arrival_tolerance: u64) -> Curfew {
+    Curfew {
+        from: Time(from),
+        to: Time(to),
+        arrival_tolerance,
+    }
+}
+
+#[test]
+fn test_normalize_windows_merges_overlaps_at_one_location() {
+    let mut windows = vec![
+        availability(500, 600, Some(id("KRK"))),
+        availability(100, 200, None),
+        availability(150, 300, None),
+        availability(300, 350, None),
+        availability(550, 700, Some(id("KRK"))),
+        availability(650, 800, Some(id("WAW"))),
+    ];
+    normalize_windows(&mut windows);
+
+    assert_eq!(vec![(100, 350), (500, 700), (650, 800)], spans(&windows));
+    assert_eq!(Some(id("KRK")), windows[1].location_id);
+}
+
+#[test]
+fn test_maintenance_checks_search_normalized_windows() {
+    let mut windows = vec![
+        availability(900, 1000, Some(id("WAW"))),
+        availability(100, 200, None),
+        availability(400, 500, Some(id("KRK"))),
+    ];
+    normalize_windows(&mut windows);
+
+    assert!(!Schedule::violates_aircraft_maintenance(
+        &windows,
+        Time(0),
+        Time(100)
+    ));
+    assert!(Schedule::violates_aircraft_maintenance(
+        &windows,
+        Time(190),
+        Time(250)
+    ));
+    assert!(!Schedule::violates_aircraft_maintenance(
+        &windows,
+        Time(200),
+        Time(400)
+    ));
+    assert!(Schedule::violates_aircraft_maintenance(
+        &windows,
+        Time(300),
+        Time(1200)
+    ));
+    assert!(!Schedule::violates_aircraft_maintenance(
+        &windows,
+        Time(1000),
+        Time(1100)
+    ));
+
+    let at_waw = (id("WAW"), Time(300));
+    assert_eq!(
+        Some(id("KRK")),
+        Schedule::required_position(&windows, Time(600), Some(&at_waw))
+    );
+    assert_eq!(
+        None,
+        Schedule::required_position(&windows, Time(450), Some(&at_waw))
+    );
+    let at_krk = (id("KRK"), Time(300));
+    assert_eq!(
+        Some(id("WAW")),
+        Schedule::required_position(&windows, Time(1100), Some(&at_krk))
+    );
+}
+
+#[test]
+fn test_normalize_curfews() {
+    let mut curfews = vec![
+        curfew(1380, 330, 0),
+        curfew(700, 800, 0),
+        curfew(600, 700, 0),
+        curfew(650, 750, 15),
+        curfew(1380, 330, 0),
+    ];
+    normalize_curfews(&mut curfews);
+
+    assert_eq!(
+        vec![
+            curfew(600, 800, 0),
+            curfew(650, 750, 15),
+            curfew(1380, 330, 0)
+        ],
+        curfews
+    );
+}
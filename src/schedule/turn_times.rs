@@ -0,0 +1,46 @@
+use crate::airport::{Airport, AirportId};
+use crate::flight::Flight;
+use crate::time::Time;
+use std::collections::HashMap;
+
+/// Minimum turn times of the schedule's flights with their airports interned into a dense
+/// table, built once in `reindex` so assign and delay propagation index into it instead of
+/// hashing airport ids.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TurnTimes {
+    mtt: Vec<u64>,
+    /// Index into `mtt` of where each flight lands, by position in `flights`.
+    destinations: Vec<usize>,
+}
+
+impl TurnTimes {
+    pub(crate) fn new<'a>(
+        airports: &HashMap<AirportId, Airport>,
+        flights: impl IntoIterator<Item = &'a Flight>,
+    ) -> TurnTimes {
+        // an airport missing from the scenario turns aircraft around at once
+        let mut mtt = vec![0];
+        let mut interned = HashMap::<&AirportId, usize>::new();
+        let destinations = flights
+            .into_iter()
+            .map(|f| {
+                *interned.entry(&f.destination_id).or_insert_with(|| {
+                    match airports.get(&f.destination_id) {
+                        Some(airport) => {
+                            mtt.push(airport.mtt);
+                            mtt.len() - 1
+                        }
+                        None => 0,
+                    }
+                })
+            })
+            .collect();
+        TurnTimes { mtt, destinations }
+    }
+
+    /// When the aircraft landing from the flight at position `i` at `arrival` is ready for
+    /// its next leg.
+    pub(crate) fn ready_after(&self, i: usize, arrival: Time) -> Time {
+        arrival + self.mtt[self.destinations[i]]
+    }
+}
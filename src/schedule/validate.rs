@@ -0,0 +1,374 @@
+use crate::crew::RestBreach;
+use crate::flight::FlightKey;
+use crate::schedule::constraint::Rule;
+use crate::schedule::schedule::{LoadError, Scenario, Schedule};
+use crate::time::Time;
+use std::collections::HashSet;
+use std::fmt;
+
+/// JSON Schema (draft 2020-12) of the scenario file format, generated from the types it
+/// deserializes into; `schema/scenario.schema.json` is a copy of it.
+pub fn scenario_schema() -> String {
+    let mut schema = schemars::schema_for!(Scenario);
+    schema.insert("title".into(), "irrops scenario".into());
+    schema.insert(
+        "description".into(),
+        "Aircraft, airports and flights loaded by `irrops --scenario`. Times are minutes from \
+         the start of day 1."
+            .into(),
+    );
+    serde_json::to_string_pretty(&schema).expect("schemas serialize") + "\n"
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Recovery would misread the scenario, e.g. a flight from an airport that does not exist.
+    Error,
+    /// The scenario is consistent but starts out breaking an operational limit.
+    Warning,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Issue {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Error => write!(f, "error: {}", self.message),
+            Severity::Warning => write!(f, "warning: {}", self.message),
+        }
+    }
+}
+
+/// Checks a scenario file without starting a session. The structure has to deserialize,
+/// which is reported as `Err`; ids, references and time windows are then checked, followed
+/// by the warnings the REPL prints at load. Errors come first in the result.
+pub fn validate_file(path: &str) -> Result<Vec<Issue>, LoadError> {
+    let scenario: Scenario = Schedule::read_scenario(path)?;
+    let mut issues = validate(&scenario);
+    issues.extend(load_warnings(&Schedule::from_scenario(scenario)));
+    issues.sort_by_key(|issue| issue.severity);
+    Ok(issues)
+}
+
+struct Checker<'a> {
+    airports: HashSet<&'a str>,
+    aircraft: HashSet<&'a str>,
+    flights: HashSet<&'a str>,
+    issues: Vec<Issue>,
+}
+
+impl<'a> Checker<'a> {
+    fn error(&mut self, message: String) {
+        self.issues.push(Issue {
+            severity: Severity::Error,
+            message,
+        });
+    }
+
+    fn unique(&mut self, kind: &str, ids: impl Iterator<Item = &'a str>) -> HashSet<&'a str> {
+        let mut seen = HashSet::new();
+        for id in ids {
+            if !seen.insert(id) {
+                self.error(format!("duplicate {} id {}", kind, id));
+            }
+        }
+        seen
+    }
+
+    fn airport(&mut self, owner: &str, id: &str) {
+        if !self.airports.contains(id) {
+            self.error(format!("{} refers to unknown airport {}", owner, id));
+        }
+    }
+
+    fn window(&mut self, owner: &str, from: Time, to: Time) {
+        if from >= to {
+            self.error(format!(
+                "{} ends at {} before it starts at {}",
+                owner, to, from
+            ));
+        }
+    }
+}
+
+/// Errors in the ids, references and time windows of a scenario.
+pub(crate) fn validate(scenario: &Scenario) -> Vec<Issue> {
+    let mut c = Checker {
+        airports: HashSet::new(),
+        aircraft: HashSet::new(),
+        flights: HashSet::new(),
+        issues: vec![],
+    };
+    c.airports = c.unique("airport", scenario.airports.iter().map(|a| &*a.id));
+    c.aircraft = c.unique(
+        "aircraft",
+        scenario
+            .aircraft
+            .iter()
+            .map(|a| &*a.id)
+            .chain(scenario.charters.iter().map(|ch| &*ch.id)),
+    );
+    // flight numbers repeat across days, so only number and day together must be unique
+    c.flights = scenario.flights.iter().map(|f| &*f.id).collect();
+    let mut keys = HashSet::new();
+    for f in &scenario.flights {
+        let day = match f.day {
+            0 => f.departure_time.day(),
+            day => day,
+        };
+        if !keys.insert(FlightKey::new(&f.id, day)) {
+            c.error(format!("duplicate flight {}/{}", f.id, day));
+        }
+    }
+
+    for ap in &scenario.airports {
+        for curfew in &ap.disruptions {
+            c.window(&format!("curfew at {}", ap.id), curfew.from, curfew.to);
+        }
+        for reduction in &ap.reductions {
+            let owner = format!("capacity reduction at {}", ap.id);
+            c.window(&owner, reduction.from, reduction.to);
+            if reduction.rate == 0 {
+                c.error(format!("{} handles no movements", owner));
+            }
+        }
+        if let Some(hours) = ap.operating_hours
+            && (hours.open >= 1440 || hours.close >= 1440)
+        {
+            c.error(format!(
+                "operating hours at {} must be minutes within a day",
+                ap.id
+            ));
+        }
+        for band in &ap.block_bands {
+            if band.hours.open >= band.hours.close || band.hours.close >= 1440 {
+                c.error(format!(
+                    "block band {} at {} must be a window within a day",
+                    band.hours, ap.id
+                ));
+            }
+        }
+        let mut runways = HashSet::new();
+        for runway in &ap.runways {
+            if !runways.insert(&runway.id) {
+                c.error(format!("duplicate runway {} at {}", runway.id, ap.id));
+            }
+            if runway.movements_per_hour == 0 {
+                c.error(format!(
+                    "runway {} at {} handles no movements",
+                    runway.id, ap.id
+                ));
+            }
+        }
+    }
+
+    for ac in &scenario.aircraft {
+        let owner = format!("aircraft {}", ac.id);
+        c.airport(&owner, &ac.initial_location_id);
+        for d in &ac.disruptions {
+            c.window(&format!("disruption of {}", owner), d.from, d.to);
+            if let Some(location_id) = &d.location_id {
+                c.airport(&owner, location_id);
+            }
+        }
+        for (i, a) in ac.disruptions.iter().enumerate() {
+            for b in &ac.disruptions[i + 1..] {
+                if a.location_id != b.location_id
+                    && Time::is_overlapping(&(a.from, a.to), &(b.from, b.to))
+                {
+                    c.error(format!(
+                        "disruptions of {} at {}-{} and {}-{} overlap at different airports",
+                        owner, a.from, a.to, b.from, b.to
+                    ));
+                }
+            }
+        }
+        if let Some(check) = &ac.maintenance {
+            if check.bases.is_empty() {
+                c.error(format!("maintenance check of {} has no bases", owner));
+            }
+            for base in &check.bases {
+                c.airport(&owner, base);
+            }
+        }
+    }
+
+    for ch in &scenario.charters {
+        let owner = format!("charter {}", ch.id);
+        c.airport(&owner, &ch.base_id);
+        c.window(&owner, ch.from, ch.to);
+    }
+
+    let crews = c.unique(
+        "crew",
+        scenario
+            .crews
+            .iter()
+            .map(|crew| &*crew.id)
+            .chain(scenario.reserves.iter().map(|r| &*r.id)),
+    );
+    for crew in &scenario.crews {
+        c.airport(&format!("crew {}", crew.id), &crew.base_id);
+    }
+    for r in &scenario.reserves {
+        let owner = format!("reserve crew {}", r.id);
+        c.airport(&owner, &r.base_id);
+        c.window(&owner, r.from, r.to);
+    }
+
+    for f in &scenario.flights {
+        let owner = format!("flight {}", f.id);
+        c.airport(&owner, &f.origin_id);
+        c.airport(&owner, &f.destination_id);
+        if f.origin_id == f.destination_id {
+            c.error(format!(
+                "{} departs from and arrives at {}",
+                owner, f.origin_id
+            ));
+        }
+        c.window(&owner, f.departure_time, f.arrival_time);
+        if let Some(aircraft_id) = f.aircraft_id.as_deref()
+            && !aircraft_id.is_empty()
+            && !c.aircraft.contains(aircraft_id)
+        {
+            c.error(format!(
+                "{} refers to unknown aircraft {}",
+                owner, aircraft_id
+            ));
+        }
+        if let Some(crew_id) = f.crew_id.as_deref()
+            && !crews.contains(crew_id)
+        {
+            c.error(format!("{} refers to unknown crew {}", owner, crew_id));
+        }
+    }
+
+    for rule in &scenario.rules {
+        match rule {
+            Rule::AvoidAirport {
+                aircraft_id,
+                airport_id,
+            } => {
+                if !c.aircraft.contains(&**aircraft_id) {
+                    c.error(format!(
+                        "avoid_airport rule refers to unknown aircraft {}",
+                        aircraft_id
+                    ));
+                }
+                c.airport("avoid_airport rule", airport_id);
+            }
+            Rule::DepartBy { flight_id, .. } => {
+                if !c.flights.contains(&**flight_id) {
+                    c.error(format!(
+                        "depart_by rule refers to unknown flight {}",
+                        flight_id
+                    ));
+                }
+            }
+        }
+    }
+    c.issues
+}
+
+/// Operational limits a loaded schedule already breaks, as printed when a session starts.
+pub fn load_warnings(schedule: &Schedule) -> Vec<Issue> {
+    let closures = schedule
+        .closure_violations()
+        .into_iter()
+        .map(|(flight_id, reason)| {
+            format!(
+                "flight {} operates while an airport is closed ({:?})",
+                flight_id, reason
+            )
+        });
+    let types =
+        schedule
+            .type_restriction_violations()
+            .into_iter()
+            .map(|(flight_id, fleet_type)| {
+                format!(
+                    "flight {} is flown by a {} that one of its airports does not accept",
+                    flight_id, fleet_type
+                )
+            });
+    let ranges = schedule
+        .range_violations()
+        .into_iter()
+        .map(|(flight_id, fleet_type, range)| {
+            format!(
+                "flight {} is longer than the {} km range of the {} flying it",
+                flight_id, range, fleet_type
+            )
+        });
+    let etops = schedule
+        .etops_violations()
+        .into_iter()
+        .map(|(flight_id, aircraft_id)| {
+            format!(
+                "ETOPS flight {} is flown by {}, which is not ETOPS-certified",
+                flight_id, aircraft_id
+            )
+        });
+    let stands = schedule.stand_overflows().into_iter().map(|o| {
+        format!(
+            "{} aircraft on the ground at {} from {} to {}, with {} stand{}",
+            o.peak,
+            o.airport_id,
+            o.from,
+            o.to.map_or("end".to_string(), |t| t.to_string()),
+            o.stands,
+            if o.stands == 1 { "" } else { "s" }
+        )
+    });
+    let rest =
+        schedule
+            .rest_breaches()
+            .into_iter()
+            .map(|(flight_id, crew_id, breach)| match breach {
+                RestBreach::Short {
+                    required,
+                    available,
+                } => format!(
+                    "crew {} starts flight {} after {} min rest, {} min required",
+                    crew_id, flight_id, available, required
+                ),
+                RestBreach::Positioning { rested_at, origin } => format!(
+                    "crew {} rests at {} but starts flight {} at {}",
+                    crew_id, rested_at, flight_id, origin
+                ),
+            });
+    let chains = schedule
+        .chain_violations()
+        .into_iter()
+        .map(|violation| violation.to_string());
+    let deferred = schedule
+        .horizon
+        .filter(|_| !schedule.deferred.is_empty())
+        .map(|days| {
+            format!(
+                "{} flight{} and {} maintenance window{} beyond the {}-day horizon deferred until rollover",
+                schedule.deferred.flights.len(),
+                if schedule.deferred.flights.len() == 1 { "" } else { "s" },
+                schedule.deferred.maintenance.len(),
+                if schedule.deferred.maintenance.len() == 1 { "" } else { "s" },
+                days
+            )
+        });
+    chains
+        .chain(closures)
+        .chain(types)
+        .chain(ranges)
+        .chain(etops)
+        .chain(stands)
+        .chain(rest)
+        .chain(deferred)
+        .map(|message| Issue {
+            severity: Severity::Warning,
+            message,
+        })
+        .collect()
+}
@@ -0,0 +1,243 @@
+use crate::cli::commands::Command;
+use crate::flight::FlightStatus::{Delayed, Scheduled, Unscheduled};
+use crate::schedule::schedule::{Schedule, Strategy};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::{fmt, fs, io};
+
+/// Reads a script: one REPL command per line, with blank lines and `#` comments skipped.
+pub fn read(path: &str) -> io::Result<Vec<String>> {
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+#[derive(Debug)]
+pub enum ScriptError {
+    Read(String, io::Error),
+    /// The script runs itself through the `script run` lines listed, outermost first.
+    Cycle(Vec<String>),
+    /// The Rhai script did not compile or threw; the schedule is left as it was.
+    Eval(String),
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptError::Read(path, e) => write!(f, "cannot read {}: {}", path, e),
+            ScriptError::Cycle(paths) => write!(f, "script runs itself: {}", paths.join(" -> ")),
+            ScriptError::Eval(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+/// Whether `path` is run by the Rhai engine rather than replayed line by line.
+pub fn is_rhai(path: &str) -> bool {
+    Path::new(path).extension().is_some_and(|ext| ext == "rhai")
+}
+
+/// Reads a script like `read`, with the lines of every line script it runs through
+/// `script run` spliced in place, so a script that ends up running itself is refused
+/// before any of it runs. Rhai scripts are left as `script run` lines.
+pub fn expand(path: &str) -> Result<Vec<String>, ScriptError> {
+    let mut lines = vec![];
+    expand_into(path, &mut vec![], &mut lines)?;
+    Ok(lines)
+}
+
+fn expand_into(
+    path: &str,
+    stack: &mut Vec<(PathBuf, String)>,
+    lines: &mut Vec<String>,
+) -> Result<(), ScriptError> {
+    let read_error = |e| ScriptError::Read(path.to_string(), e);
+    let canonical = fs::canonicalize(path).map_err(read_error)?;
+    if stack.iter().any(|(seen, _)| *seen == canonical) {
+        let mut paths = stack.iter().map(|(_, p)| p.clone()).collect::<Vec<_>>();
+        paths.push(path.to_string());
+        return Err(ScriptError::Cycle(paths));
+    }
+    stack.push((canonical, path.to_string()));
+    for line in read(path).map_err(read_error)? {
+        match Command::parse(&line) {
+            Ok(Command::ScriptRun { path }) if !is_rhai(&path) => expand_into(&path, stack, lines)?,
+            _ => lines.push(line),
+        }
+    }
+    stack.pop();
+    Ok(())
+}
+
+/// Runs a Rhai script against the schedule. Besides the Rhai language itself, scripts
+/// get:
+///
+/// - `clock()`, `cost()` and `flights()`, the keys of all flights in schedule order;
+/// - `flight(key)`, a map of `key`, `id`, `day`, `origin`, `destination`, `departure`,
+///   `arrival`, `aircraft` (`()` when unassigned), `status` (`scheduled`, `delayed` or
+///   `unscheduled`), `delay` and `reason`;
+/// - `delay(key, minutes)`, which returns the keys of the flights it unscheduled, and
+///   `recover(strategy)`, with the built-in or plugin strategy's default settings;
+/// - `repl(line)`, which queues a REPL command to run once the script has finished.
+///
+/// Returns the queued commands. A script that fails leaves the schedule as it was.
+pub fn run_rhai(schedule: &mut Schedule, path: &str) -> Result<Vec<String>, ScriptError> {
+    let source = fs::read_to_string(path).map_err(|e| ScriptError::Read(path.to_string(), e))?;
+    let shared = Rc::new(RefCell::new(schedule.fork()));
+    let queued = Rc::new(RefCell::new(vec![]));
+    let result = engine(&shared, &queued).run(&source);
+    result.map_err(|e| ScriptError::Eval(e.to_string()))?;
+    let script = Rc::try_unwrap(shared).ok();
+    *schedule = script.expect("the engine is gone").into_inner();
+    Ok(queued.take())
+}
+
+fn int(n: u64) -> i64 {
+    i64::try_from(n).unwrap_or(i64::MAX)
+}
+
+fn engine(schedule: &Rc<RefCell<Schedule>>, queued: &Rc<RefCell<Vec<String>>>) -> Engine {
+    let mut engine = Engine::new();
+    let s = schedule.clone();
+    engine.register_fn("clock", move || int(s.borrow().clock.0));
+    let s = schedule.clone();
+    engine.register_fn("cost", move || int(s.borrow().total_cost()));
+    let s = schedule.clone();
+    engine.register_fn("flights", move || {
+        s.borrow()
+            .flights
+            .iter()
+            .map(|f| Dynamic::from(f.key().to_string()))
+            .collect::<Array>()
+    });
+    let s = schedule.clone();
+    engine.register_fn(
+        "flight",
+        move |key: &str| -> Result<Map, Box<EvalAltResult>> {
+            let s = s.borrow();
+            let key = s.find_flight(key).map_err(|e| e.to_string())?;
+            let f = s.flight(&key).expect("found flights are indexed");
+            let (status, delay, reason) = match &f.status {
+                Scheduled => ("scheduled", 0, Dynamic::UNIT),
+                Delayed { minutes } => ("delayed", *minutes, Dynamic::UNIT),
+                Unscheduled(reason) => ("unscheduled", 0, format!("{:?}", reason).into()),
+            };
+            let mut map = Map::new();
+            map.insert("key".into(), key.to_string().into());
+            map.insert("id".into(), f.id.to_string().into());
+            map.insert("day".into(), int(f.day).into());
+            map.insert("origin".into(), f.origin_id.to_string().into());
+            map.insert("destination".into(), f.destination_id.to_string().into());
+            map.insert("departure".into(), int(f.departure_time.0).into());
+            map.insert("arrival".into(), int(f.arrival_time.0).into());
+            let aircraft = f.aircraft_id.as_ref().map(|a| a.to_string().into());
+            map.insert("aircraft".into(), aircraft.unwrap_or(Dynamic::UNIT));
+            map.insert("status".into(), status.into());
+            map.insert("delay".into(), int(delay).into());
+            map.insert("reason".into(), reason);
+            Ok(map)
+        },
+    );
+    let s = schedule.clone();
+    engine.register_fn(
+        "delay",
+        move |key: &str, minutes: i64| -> Result<Array, Box<EvalAltResult>> {
+            let mut s = s.borrow_mut();
+            let key = s.find_flight(key).map_err(|e| e.to_string())?;
+            let minutes = u64::try_from(minutes).map_err(|_| "delay must not be negative")?;
+            let report = s.apply_delay(key, minutes).map_err(|e| e.to_string())?;
+            Ok(report
+                .unscheduled
+                .iter()
+                .map(|(key, _)| Dynamic::from(key.to_string()))
+                .collect())
+        },
+    );
+    let s = schedule.clone();
+    engine.register_fn(
+        "recover",
+        move |name: &str| -> Result<(), Box<EvalAltResult>> {
+            let mut s = s.borrow_mut();
+            let cost_before = s.total_cost();
+            let flights_before = s.flights.to_vec();
+            match name {
+                "greedy" => s.recover(Strategy::Greedy(Default::default())),
+                "flow" => s.recover(Strategy::Flow),
+                "anneal" => s.recover(Strategy::Anneal(Default::default())),
+                plugin => s.recover_with(plugin).map_err(|e| e.to_string())?,
+            }
+            s.record_recovery(name, &flights_before, cost_before);
+            Ok(())
+        },
+    );
+    let q = queued.clone();
+    engine.register_fn(
+        "repl",
+        move |line: &str| -> Result<(), Box<EvalAltResult>> {
+            match Command::parse(line) {
+                Ok(Command::ScriptRun { .. }) => Err("scripts cannot queue scripts".into()),
+                Ok(_) => {
+                    q.borrow_mut().push(line.to_string());
+                    Ok(())
+                }
+                Err(e) => Err(e.to_string().into()),
+            }
+        },
+    );
+    engine
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ForeachError {
+    /// Not one of `all`, `scheduled`, `delayed` or `unscheduled`.
+    UnknownFilter(String),
+    MissingPlaceholder,
+}
+
+impl fmt::Display for ForeachError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForeachError::UnknownFilter(filter) => write!(
+                f,
+                "unknown filter {}; use all, scheduled, delayed or unscheduled",
+                filter
+            ),
+            ForeachError::MissingPlaceholder => write!(f, "the command needs a {{flight}}"),
+        }
+    }
+}
+
+impl std::error::Error for ForeachError {}
+
+/// Expands `foreach <filter> <command>` into one command per flight matching the filter,
+/// in schedule order, with `{flight}` replaced by its `number/day` key. The flights are
+/// picked from the schedule as it is now, so commands run later see the effects of
+/// earlier ones but do not change the set of flights visited.
+pub fn foreach(
+    schedule: &Schedule,
+    filter: &str,
+    command: &str,
+) -> Result<Vec<String>, ForeachError> {
+    let matches = match filter {
+        "all" => |_: &_| true,
+        "s" | "scheduled" => |status: &_| matches!(status, Scheduled),
+        "d" | "delayed" => |status: &_| matches!(status, Delayed { .. }),
+        "u" | "unscheduled" => |status: &_| matches!(status, Unscheduled(_)),
+        _ => return Err(ForeachError::UnknownFilter(filter.to_string())),
+    };
+    if !command.contains("{flight}") {
+        return Err(ForeachError::MissingPlaceholder);
+    }
+    Ok(schedule
+        .flights
+        .iter()
+        .filter(|f| matches(&f.status))
+        .map(|f| command.replace("{flight}", &f.key().to_string()))
+        .collect())
+}
@@ -0,0 +1,52 @@
+use crate::config::TableStyle;
+use crate::time::TimeFormat;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// What one REPL session hands on to the next: the scenario it ran and the settings changed
+/// with `set`. Settings left alone stay `None`, so the config file keeps deciding them.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    pub scenario: Option<PathBuf>,
+    pub table_style: Option<TableStyle>,
+    pub time_format: Option<TimeFormat>,
+    /// `YYYY-MM-DD` that DAY1 falls on in ISO timestamps.
+    pub start_date: Option<String>,
+}
+
+impl SessionState {
+    /// `$XDG_STATE_HOME/irrops`, falling back to `~/.local/state/irrops`.
+    pub fn default_dir() -> Option<PathBuf> {
+        std::env::var_os("XDG_STATE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|home| Path::new(&home).join(".local").join("state"))
+            })
+            .map(|dir| dir.join("irrops"))
+    }
+
+    /// Command history kept next to the state.
+    pub fn history_path(dir: &Path) -> PathBuf {
+        dir.join("history.txt")
+    }
+
+    /// Reads `state.json` in `dir`; before the first session there is none and the
+    /// defaults apply.
+    pub fn load(dir: &Path) -> io::Result<SessionState> {
+        match std::fs::read_to_string(dir.join("state.json")) {
+            Ok(text) => serde_json::from_str(&text)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(SessionState::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Writes `state.json` in `dir`, creating the directory if needed.
+    pub fn save(&self, dir: &Path) -> io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(dir.join("state.json"), json)
+    }
+}
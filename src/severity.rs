@@ -0,0 +1,34 @@
+use crate::schedule::schedule::DisruptionReport;
+use serde::{Deserialize, Serialize};
+
+/// Points a disruption scores per unit of damage, so the big events of a long session
+/// stand out in `history` and `explain`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeverityModel {
+    pub unscheduled: u64,
+    pub delay_minute: u64,
+    pub passenger: u64,
+    /// Points per EUR 100 of added compensation exposure.
+    pub compensation: u64,
+}
+
+impl Default for SeverityModel {
+    fn default() -> Self {
+        SeverityModel {
+            unscheduled: 100,
+            delay_minute: 1,
+            passenger: 1,
+            compensation: 1,
+        }
+    }
+}
+
+impl SeverityModel {
+    pub fn score(&self, report: &DisruptionReport) -> u64 {
+        report.unscheduled.len() as u64 * self.unscheduled
+            + report.delay_minutes * self.delay_minute
+            + report.passengers * self.passenger
+            + report.compensation / 100 * self.compensation
+    }
+}
@@ -0,0 +1,67 @@
+use crate::aircraft::Aircraft;
+use crate::airport::{Airport, AirportId};
+use crate::flight::Flight;
+use crate::schedule::schedule::Schedule;
+use crate::time::Time;
+use proptest::collection::vec;
+use proptest::prelude::Strategy;
+use proptest::prop_oneof;
+use proptest::strategy::Just;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// One of `<prefix>_1`, `<prefix>_2` and `<prefix>_3`, so generated ids collide often.
+pub fn arb_id(prefix: &'static str) -> impl Strategy<Value = Arc<str>> {
+    prop_oneof![
+        Just(Arc::from(format!("{}_1", prefix))),
+        Just(Arc::from(format!("{}_2", prefix))),
+        Just(Arc::from(format!("{}_3", prefix))),
+    ]
+}
+
+/// An unassigned flight between `AP_` airports within the first two days, 10 to 1000
+/// minutes long.
+pub fn arb_flight() -> impl Strategy<Value = Flight> {
+    (
+        arb_id("FL"),
+        arb_id("AP"),
+        arb_id("AP"),
+        0..2500u64,
+        10..1000u64,
+    )
+        .prop_map(|(fid, org, dst, dep, dur)| {
+            Flight::new(&fid, &org, &dst, Time(dep), Time(dep) + dur)
+        })
+}
+
+/// An `AC_` aircraft starting at one of the `AP_` airports.
+pub fn arb_aircraft() -> impl Strategy<Value = Aircraft> {
+    (arb_id("AC"), arb_id("AP")).prop_map(|(id, location)| Aircraft::new(&id, &location))
+}
+
+/// One to four aircraft; ids may repeat, in which case the last one is kept.
+pub fn arb_fleet() -> impl Strategy<Value = Vec<Aircraft>> {
+    vec(arb_aircraft(), 1..5)
+}
+
+pub fn arb_flights() -> impl Strategy<Value = Vec<Flight>> {
+    vec(arb_flight(), 1..30)
+}
+
+/// `AP_1`, `AP_2` and `AP_3`, with minimum turn times of 20, 45 and 60 minutes.
+pub fn airports() -> HashMap<AirportId, Airport> {
+    [("AP_1", 20), ("AP_2", 45), ("AP_3", 60)]
+        .into_iter()
+        .map(|(id, mtt)| (Arc::from(id), Airport::new(id, mtt)))
+        .collect()
+}
+
+/// A schedule of generated aircraft and flights between the `airports`, nothing assigned
+/// yet.
+pub fn schedule(aircraft: Vec<Aircraft>, flights: Vec<Flight>) -> Schedule {
+    Schedule::new(
+        aircraft.into_iter().map(|a| (a.id.clone(), a)).collect(),
+        airports(),
+        flights,
+    )
+}
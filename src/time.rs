@@ -1,23 +1,202 @@
+use crate::export::Date;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::ops::{Add, AddAssign, Div, Sub};
+use std::str::FromStr;
+use std::sync::RwLock;
 
-#[derive(Debug, Clone, Copy, Ord, Eq, PartialEq, Serialize, Deserialize, PartialOrd)]
+#[derive(
+    Debug, Clone, Copy, Ord, Eq, PartialEq, Serialize, Deserialize, JsonSchema, PartialOrd,
+)]
 pub struct Time(pub u64);
 
 impl Time {
+    /// Operating day, counted from 1 to match the `DAYn` display.
+    pub fn day(&self) -> u64 {
+        self.0 / 1440 + 1
+    }
+
     pub(crate) fn is_overlapping(time: &(Time, Time), window: &(Time, Time)) -> bool {
         time.0 < window.1 && time.1 > window.0
     }
+
+    /// `self + minutes`, or `None` past the end of time.
+    pub fn checked_add(self, minutes: u64) -> Option<Time> {
+        self.0.checked_add(minutes).map(Time)
+    }
+}
+
+/// How times are written by `Display`, in tables, reports and CSV exports.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeFormat {
+    /// Minutes since DAY1 00:00, e.g. `1830`.
+    Minutes,
+    /// `DAY2 06:30`.
+    #[default]
+    DayHhmm,
+    /// ISO 8601 local date-time, e.g. `2024-01-02T06:30`, with DAY1 on a chosen date.
+    Iso,
+}
+
+impl FromStr for TimeFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "minutes" => Ok(TimeFormat::Minutes),
+            "dayhhmm" => Ok(TimeFormat::DayHhmm),
+            "iso" => Ok(TimeFormat::Iso),
+            _ => Err(format!(
+                "unknown time format `{}`, expected minutes, dayhhmm or iso",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TimeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            TimeFormat::Minutes => "minutes",
+            TimeFormat::DayHhmm => "dayhhmm",
+            TimeFormat::Iso => "iso",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// The format every `Display` of a time uses, and the date DAY1 falls on for ISO
+/// timestamps; one per process, like the colour override.
+static DISPLAY: RwLock<(TimeFormat, Date)> = RwLock::new((
+    TimeFormat::DayHhmm,
+    Date {
+        year: 2024,
+        month: 1,
+        day: 1,
+    },
+));
+
+/// Switches how times are displayed from now on.
+pub fn set_display(format: TimeFormat, day_one: Date) {
+    *DISPLAY.write().unwrap_or_else(|e| e.into_inner()) = (format, day_one);
+}
+
+/// The current time format and the date of DAY1.
+pub fn display() -> (TimeFormat, Date) {
+    *DISPLAY.read().unwrap_or_else(|e| e.into_inner())
+}
+
+impl Time {
+    /// Writes the time in `format`, with DAY1 on `day_one` for ISO timestamps.
+    pub fn display_with(&self, format: TimeFormat, day_one: Date) -> String {
+        match format {
+            TimeFormat::Minutes => self.0.to_string(),
+            TimeFormat::DayHhmm => {
+                let minutes = self.0 % 1440;
+                format!("DAY{} {:02}:{:02}", self.day(), minutes / 60, minutes % 60)
+            }
+            TimeFormat::Iso => {
+                let date = Date::from_days(day_one.days() + (self.0 / 1440) as i64);
+                let minutes = self.0 % 1440;
+                format!("{}T{:02}:{:02}", date, minutes / 60, minutes % 60)
+            }
+        }
+    }
+
+    /// Parses any time `FromStr` accepts, reading ISO timestamps with DAY1 on `day_one`.
+    pub fn parse_with(s: &str, day_one: Date) -> Result<Time, String> {
+        let invalid = || {
+            format!(
+                "expected minutes, `DAY2 06:30`, `06:30+1` or `2024-01-02T06:30`, not `{}`",
+                s
+            )
+        };
+        let s = s.trim();
+        if let Ok(minutes) = s.parse() {
+            return Ok(Time(minutes));
+        }
+        if let Some((date, clock)) = s.split_once('T') {
+            let days = date.parse::<Date>().map_err(|_| invalid())?.days() - day_one.days();
+            let minutes = time_of_day(clock).ok_or_else(invalid)?;
+            return u64::try_from(days)
+                .ok()
+                .and_then(|days| days.checked_mul(1440))
+                .and_then(|offset| offset.checked_add(minutes))
+                .map(Time)
+                .ok_or_else(invalid);
+        }
+        let (days, clock) = match s.get(..3) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("day") => {
+                let (day, clock) = s[3..].split_once(char::is_whitespace).ok_or_else(invalid)?;
+                let day = day.parse::<u64>().map_err(|_| invalid())?;
+                (day.checked_sub(1).ok_or_else(invalid)?, clock.trim_start())
+            }
+            _ => match s.split_once('+') {
+                Some((clock, days)) => (days.parse().map_err(|_| invalid())?, clock),
+                None => (0, s),
+            },
+        };
+        let minutes = time_of_day(clock).ok_or_else(invalid)?;
+        days.checked_mul(1440)
+            .and_then(|offset| offset.checked_add(minutes))
+            .map(Time)
+            .ok_or_else(invalid)
+    }
+}
+
+/// Written in the format chosen with `set_display`, `DAY2 06:30` unless changed.
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (format, day_one) = display();
+        write!(f, "{}", self.display_with(format, day_one))
+    }
+}
+
+/// Reads what `Display` writes in any format: raw minutes since DAY1 00:00 (`1830`),
+/// `DAY2 06:30` and ISO timestamps, as well as a time of day followed by the days after
+/// DAY1 (`06:30+1`, or `06:30` on DAY1).
+impl FromStr for Time {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Time::parse_with(s, display().1)
+    }
+}
+
+/// Minutes after midnight of `HH:MM`, up to 23:59.
+fn time_of_day(s: &str) -> Option<u64> {
+    let (hours, minutes) = s.split_once(':')?;
+    if minutes.len() != 2 {
+        return None;
+    }
+    let (hours, minutes) = (hours.parse::<u64>().ok()?, minutes.parse::<u64>().ok()?);
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
 }
 
-impl std::fmt::Display for Time {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let days = self.0 / 1440;
-        let remaining = self.0 % 1440;
-        let hours = remaining / 60;
-        let mins = remaining % 60;
-        write!(f, "DAY{} {:02}:{:02}", days + 1, hours, mins)
+/// Parses a length of time in minutes: raw minutes (`90`), minutes with a unit (`90m`) or
+/// hours and minutes (`1h30`, `1h30m`, `2h`).
+pub fn parse_duration(s: &str) -> Result<u64, String> {
+    let invalid = || format!("expected minutes, `90m` or `1h30`, not `{}`", s);
+    let s = s.trim();
+    if let Ok(minutes) = s.strip_suffix('m').unwrap_or(s).parse() {
+        return Ok(minutes);
     }
+    let (hours, minutes) = s.split_once('h').ok_or_else(invalid)?;
+    let hours = hours.parse::<u64>().map_err(|_| invalid())?;
+    let minutes = match minutes.strip_suffix('m').unwrap_or(minutes) {
+        "" => 0,
+        m => m
+            .parse::<u64>()
+            .ok()
+            .filter(|m| *m < 60)
+            .ok_or_else(invalid)?,
+    };
+    hours
+        .checked_mul(60)
+        .and_then(|h| h.checked_add(minutes))
+        .ok_or_else(invalid)
 }
 
 impl Add<u64> for Time {
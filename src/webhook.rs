@@ -0,0 +1,133 @@
+use crate::http::{Url, UrlError};
+use crate::metrics::Summary;
+use crate::schedule::schedule::{DisruptionReport, DisruptionType};
+use serde_json::{Value, json};
+use std::io;
+use tracing::warn;
+
+/// An endpoint that receives a JSON `POST` for every disruption and recovery.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Webhook {
+    url: Url,
+}
+
+impl Webhook {
+    pub fn parse(url: &str) -> Result<Webhook, UrlError> {
+        Url::parse(url).map(|url| Webhook { url })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url.text
+    }
+
+    /// Posts `payload` and returns the response status code.
+    pub fn post(&self, payload: &Value) -> io::Result<u16> {
+        self.url.post_json(&payload.to_string())
+    }
+}
+
+/// Sends `payload` to every hook in the background, so a slow endpoint never holds up
+/// the session; failures are only logged.
+pub fn notify(hooks: &[Webhook], payload: Value) {
+    for hook in hooks {
+        let hook = hook.clone();
+        let payload = payload.clone();
+        std::thread::spawn(move || match hook.post(&payload) {
+            Ok(status) if (200..300).contains(&status) => {}
+            Ok(status) => warn!(target: "irrops::webhook", "{} answered {}", hook.url(), status),
+            Err(e) => warn!(target: "irrops::webhook", "{}: {}", hook.url(), e),
+        });
+    }
+}
+
+pub fn report_payload(report: &DisruptionReport) -> Value {
+    let trigger = match &report.kind {
+        DisruptionType::Delay { flight, delay_by } => json!({
+            "kind": "delay",
+            "flight": flight.to_string(),
+            "delay_by": delay_by,
+        }),
+        DisruptionType::Curfew {
+            airport,
+            from,
+            to,
+            arrival_tolerance,
+        } => json!({
+            "kind": "curfew",
+            "airport": airport.to_string(),
+            "from": from.0,
+            "to": to.0,
+            "arrival_tolerance": arrival_tolerance,
+        }),
+        DisruptionType::Atfm { target, delays } => json!({
+            "kind": "atfm",
+            "target": target.to_string(),
+            "delays": delays.to_string(),
+        }),
+        DisruptionType::AirportDelay {
+            airport,
+            delay_by,
+            from,
+            to,
+        } => json!({
+            "kind": "delay-airport",
+            "airport": airport.to_string(),
+            "delay_by": delay_by,
+            "from": from.0,
+            "to": to.map(|t| t.0),
+        }),
+        DisruptionType::CapacityReduction {
+            airport,
+            runway,
+            from,
+            to,
+            percent,
+        } => json!({
+            "kind": "capacity",
+            "airport": airport.to_string(),
+            "runway": runway.as_deref(),
+            "from": from.0,
+            "to": to.0,
+            "percent": percent,
+        }),
+    };
+    json!({
+        "event": "disruption",
+        "trigger": trigger,
+        "affected": report.affected.iter().map(|f| f.to_string()).collect::<Vec<_>>(),
+        "unscheduled": report
+            .unscheduled
+            .iter()
+            .map(|(f, reason)| json!({"flight": f.to_string(), "reason": format!("{:?}", reason)}))
+            .collect::<Vec<_>>(),
+        "compensation": report.compensation,
+        "exempted": report
+            .exempted
+            .iter()
+            .map(|(f, reason)| json!({"flight": f.to_string(), "waived": format!("{:?}", reason)}))
+            .collect::<Vec<_>>(),
+        "slot_violations": report
+            .slot_violations
+            .iter()
+            .map(|v| json!({"flight": v.flight.to_string(), "airport": v.airport_id.to_string(), "slot": v.slot.0, "actual": v.actual.0}))
+            .collect::<Vec<_>>(),
+    })
+}
+
+pub fn recovery_payload(
+    strategy: &str,
+    cost_before: u64,
+    cost_after: u64,
+    summary: &Summary,
+) -> Value {
+    json!({
+        "event": "recovery",
+        "strategy": strategy,
+        "cost_before": cost_before,
+        "cost_after": cost_after,
+        "scheduled": summary.scheduled,
+        "delayed": summary.delayed,
+        "unscheduled": summary.unscheduled(),
+        "delay_minutes": summary.delay_minutes,
+    })
+}